@@ -0,0 +1,48 @@
+//! Board operations that run on every move or frame.
+
+use criterion::{BatchSize, Criterion, criterion_group, criterion_main};
+use iced_15_puzzle::{Difficulty, Puzzle};
+
+use std::hint::black_box;
+
+/// A reproducible uniform scramble of a `size` by `size` board.
+fn scrambled(size: usize, seed: u64) -> Puzzle {
+    let mut puzzle = Puzzle::new(size, size);
+    puzzle.set_difficulty(Difficulty::Hard);
+    puzzle.shuffle_with_seed(seed);
+    puzzle
+}
+
+fn board(c: &mut Criterion) {
+    for size in [3, 4, 6] {
+        let puzzle = scrambled(size, 1);
+        let (row, col) = puzzle.movable_tiles()[0];
+
+        c.bench_function(&format!("move_tile {size}x{size}"), |b| {
+            b.iter_batched(
+                || puzzle.clone(),
+                |mut puzzle| puzzle.move_tile(black_box(row), black_box(col)),
+                BatchSize::SmallInput,
+            )
+        });
+        c.bench_function(&format!("is_solved {size}x{size}"), |b| {
+            b.iter(|| black_box(&puzzle).is_solved())
+        });
+        c.bench_function(&format!("manhattan_distance {size}x{size}"), |b| {
+            b.iter(|| black_box(&puzzle).manhattan_distance())
+        });
+
+        let mut fresh = Puzzle::new(size, size);
+        fresh.set_difficulty(Difficulty::Hard);
+        let mut seed = 0;
+        c.bench_function(&format!("uniform scramble {size}x{size}"), |b| {
+            b.iter(|| {
+                seed += 1;
+                fresh.shuffle_with_seed(black_box(seed));
+            })
+        });
+    }
+}
+
+criterion_group!(benches, board);
+criterion_main!(benches);
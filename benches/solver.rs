@@ -0,0 +1,196 @@
+//! Optimal solving of fixed positions, so changes to the heuristic or the
+//! pruning show up as changes in wall time.
+//!
+//! The corpus is solved with each heuristic in turn: the Manhattan distance,
+//! with linear conflicts added, the walking distance and the 4x4 pattern
+//! database, the last two alongside hard 4x4 positions too far for the
+//! Manhattan distance, to set them against each other there. A batch of boards is also solved on more and more
+//! threads, to show how [`solver::solve_many`] scales.
+
+use criterion::{Criterion, criterion_group, criterion_main};
+use iced_15_puzzle::{
+    Direction, Puzzle,
+    heuristic::{LinearConflict, Manhattan},
+    pattern_database::{PARTITION_4X4, PatternDatabase},
+    solver::{self, Cancel},
+    walking_distance::WalkingDistance,
+};
+use rand::{SeedableRng, rngs::StdRng};
+
+use std::hint::black_box;
+use std::thread;
+
+/// A position in the corpus: a random walk of `steps` moves from the solved
+/// `size` by `size` board, drawn from `seed`, and the length of its shortest
+/// solution.
+struct Position {
+    size: usize,
+    seed: u64,
+    steps: usize,
+    optimal: usize,
+}
+
+const CORPUS: [Position; 5] = [
+    Position {
+        size: 3,
+        seed: 4,
+        steps: 40,
+        optimal: 20,
+    },
+    Position {
+        size: 3,
+        seed: 5,
+        steps: 200,
+        optimal: 26,
+    },
+    Position {
+        size: 4,
+        seed: 4,
+        steps: 40,
+        optimal: 30,
+    },
+    Position {
+        size: 4,
+        seed: 0,
+        steps: 80,
+        optimal: 40,
+    },
+    Position {
+        size: 4,
+        seed: 13,
+        steps: 150,
+        optimal: 50,
+    },
+];
+
+/// 4x4 positions too far from solved for the Manhattan distance, with or
+/// without linear conflicts.
+const HARD_CORPUS: [Position; 2] = [
+    Position {
+        size: 4,
+        seed: 19,
+        steps: 400,
+        optimal: 58,
+    },
+    Position {
+        size: 4,
+        seed: 14,
+        steps: 400,
+        optimal: 60,
+    },
+];
+
+impl Position {
+    fn puzzle(&self) -> Puzzle {
+        let mut puzzle = Puzzle::new(self.size, self.size);
+        puzzle.scramble_walk(self.steps, &mut StdRng::seed_from_u64(self.seed));
+        puzzle
+    }
+}
+
+/// Times `solve` on every position of `corpus`.
+fn bench_corpus<'a>(
+    c: &mut Criterion,
+    name: &str,
+    corpus: impl IntoIterator<Item = &'a Position>,
+    solve: impl Fn(&Puzzle) -> Option<Vec<Direction>>,
+) {
+    let mut group = c.benchmark_group(name);
+    // The longest solves take a good fraction of a second each
+    group.sample_size(10);
+
+    for position in corpus {
+        let puzzle = position.puzzle();
+        // A corpus that no longer matches would time some other search
+        let solution = solve(&puzzle).expect("corpus positions are solvable");
+        assert_eq!(
+            solution.len(),
+            position.optimal,
+            "seed {} no longer gives a {}-move position",
+            position.seed,
+            position.optimal
+        );
+
+        let name = format!(
+            "{}x{} in {} moves",
+            position.size, position.size, position.optimal
+        );
+        group.bench_function(name, |b| b.iter(|| solve(black_box(&puzzle))));
+    }
+
+    group.finish();
+}
+
+fn solve(c: &mut Criterion) {
+    bench_corpus(c, "solve", &CORPUS, |puzzle| {
+        solver::solve_with(puzzle, None, &Manhattan).solution
+    });
+}
+
+fn solve_with_linear_conflicts(c: &mut Criterion) {
+    bench_corpus(c, "solve with linear conflicts", &CORPUS, |puzzle| {
+        solver::solve_with(puzzle, None, &LinearConflict).solution
+    });
+}
+
+fn solve_with_walking_distance(c: &mut Criterion) {
+    bench_corpus(
+        c,
+        "solve with walking distance",
+        CORPUS.iter().chain(&HARD_CORPUS),
+        |puzzle| {
+            let walking_distance = WalkingDistance::for_puzzle(puzzle)?;
+            solver::solve_with(puzzle, None, &*walking_distance).solution
+        },
+    );
+}
+
+fn solve_with_pattern_database(c: &mut Criterion) {
+    let database = PatternDatabase::generate(4, 4, &PARTITION_4X4, |_| {});
+    bench_corpus(
+        c,
+        "solve with pattern database",
+        CORPUS.iter().chain(&HARD_CORPUS),
+        |puzzle| {
+            if database.covers(puzzle) {
+                solver::solve_with(puzzle, None, &database).solution
+            } else {
+                solver::solve_with(puzzle, None, &Manhattan).solution
+            }
+        },
+    );
+}
+
+fn solve_many(c: &mut Criterion) {
+    let batch: Vec<Puzzle> = (0..64)
+        .map(|seed| {
+            let mut puzzle = Puzzle::with_seed(3, 3, seed);
+            puzzle.shuffle();
+            puzzle
+        })
+        .collect();
+    let available = thread::available_parallelism().map_or(1, |threads| threads.get());
+
+    let mut group = c.benchmark_group("solve many");
+    group.sample_size(10);
+    for threads in [1, 2, 4, 8]
+        .into_iter()
+        .filter(|&threads| threads <= available)
+    {
+        let name = format!("64 3x3 boards, {threads} at a time");
+        group.bench_function(name, |b| {
+            b.iter(|| solver::solve_many_on(threads, black_box(&batch), None, &Cancel::new()))
+        });
+    }
+    group.finish();
+}
+
+criterion_group!(
+    benches,
+    solve,
+    solve_with_linear_conflicts,
+    solve_with_walking_distance,
+    solve_with_pattern_database,
+    solve_many
+);
+criterion_main!(benches);
@@ -0,0 +1,180 @@
+//! Milestones unlocked by solves, kept in an [`Unlocked`] once reached.
+//!
+//! Every achievement is an entry in [`ACHIEVEMENTS`] with a test on the
+//! [`SolveSummary`] of a finished solve, so adding one needs no code beyond
+//! its entry and its text.
+
+use iced_15_puzzle::Unlocked;
+
+use std::time::Duration;
+
+use crate::i18n::Text;
+
+/// What a finished solve looked like.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SolveSummary {
+    pub rows: usize,
+    pub cols: usize,
+    pub moves: u32,
+    pub time: Duration,
+    pub hints: u32,
+    pub undos: u32,
+    pub time_attack: bool,
+    /// Solved blindfolded without a peek.
+    pub blind: bool,
+    /// Solves of every size so far, this one included.
+    pub total_solves: u32,
+    /// Consecutive days with a daily solved, up to today.
+    pub daily_streak: u32,
+}
+
+/// A milestone and the test a solve must pass to reach it.
+pub struct Achievement {
+    /// Stays the same across releases, as unlocks are saved under it.
+    pub id: &'static str,
+    pub name: Text,
+    pub description: Text,
+    predicate: fn(&SolveSummary) -> bool,
+}
+
+pub const ACHIEVEMENTS: [Achievement; 10] = [
+    Achievement {
+        id: "first-solve",
+        name: Text::FirstSolve,
+        description: Text::FirstSolveDescription,
+        predicate: |_| true,
+    },
+    Achievement {
+        id: "no-undo",
+        name: Text::NoUndo,
+        description: Text::NoUndoDescription,
+        predicate: |solve| solve.undos == 0,
+    },
+    Achievement {
+        id: "no-hints-4x4",
+        name: Text::NoHints,
+        description: Text::NoHintsDescription,
+        predicate: |solve| solve.rows * solve.cols >= 16 && solve.hints == 0,
+    },
+    Achievement {
+        id: "sub-100-moves-4x4",
+        name: Text::Sub100Moves,
+        description: Text::Sub100MovesDescription,
+        predicate: |solve| solve.rows == 4 && solve.cols == 4 && solve.moves < 100,
+    },
+    Achievement {
+        id: "sub-minute",
+        name: Text::SubMinute,
+        description: Text::SubMinuteDescription,
+        predicate: |solve| solve.rows * solve.cols >= 16 && solve.time < Duration::from_secs(60),
+    },
+    Achievement {
+        id: "solve-5x5",
+        name: Text::Solve5x5,
+        description: Text::Solve5x5Description,
+        predicate: |solve| solve.rows >= 5 && solve.cols >= 5,
+    },
+    Achievement {
+        id: "beat-the-clock",
+        name: Text::BeatTheClock,
+        description: Text::BeatTheClockDescription,
+        predicate: |solve| solve.time_attack,
+    },
+    Achievement {
+        id: "blind-solve",
+        name: Text::BlindSolve,
+        description: Text::BlindSolveDescription,
+        predicate: |solve| solve.blind,
+    },
+    Achievement {
+        id: "hundred-solves",
+        name: Text::HundredSolves,
+        description: Text::HundredSolvesDescription,
+        predicate: |solve| solve.total_solves >= 100,
+    },
+    Achievement {
+        id: "daily-streak-7",
+        name: Text::WeekOfDailies,
+        description: Text::WeekOfDailiesDescription,
+        predicate: |solve| solve.daily_streak >= 7,
+    },
+];
+
+/// Unlocks every achievement `solve` reaches for the first time, at `now` in
+/// seconds since 1970, and returns them.
+pub fn evaluate(
+    unlocked: &mut Unlocked,
+    solve: &SolveSummary,
+    now: u64,
+) -> Vec<&'static Achievement> {
+    ACHIEVEMENTS
+        .iter()
+        .filter(|achievement| {
+            (achievement.predicate)(solve) && unlocked.unlock(achievement.id, now)
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use iced_15_puzzle::Day;
+
+    const NOW: u64 = 1_700_000_000;
+
+    fn solve(rows: usize, cols: usize, moves: u32, seconds: u64) -> SolveSummary {
+        SolveSummary {
+            rows,
+            cols,
+            moves,
+            time: Duration::from_secs(seconds),
+            hints: 0,
+            undos: 1,
+            time_attack: false,
+            blind: false,
+            total_solves: 1,
+            daily_streak: 0,
+        }
+    }
+
+    fn ids(achievements: Vec<&Achievement>) -> Vec<&'static str> {
+        achievements
+            .into_iter()
+            .map(|achievement| achievement.id)
+            .collect()
+    }
+
+    #[test]
+    fn achievements_unlock_once_on_the_solve_that_reaches_them() {
+        let mut unlocked = Unlocked::default();
+
+        assert_eq!(
+            ids(evaluate(&mut unlocked, &solve(3, 3, 40, 90), NOW)),
+            ["first-solve"]
+        );
+        assert_eq!(
+            ids(evaluate(&mut unlocked, &solve(4, 4, 95, 50), NOW + 60)),
+            ["no-hints-4x4", "sub-100-moves-4x4", "sub-minute"]
+        );
+        // Reaching them again changes nothing
+        assert!(evaluate(&mut unlocked, &solve(4, 4, 95, 50), NOW + 120).is_empty());
+        assert_eq!(
+            unlocked.day(ACHIEVEMENTS[0].id),
+            Some(Day::from_days_since_epoch(19_675))
+        );
+        assert_eq!(unlocked.day(ACHIEVEMENTS[5].id), None);
+    }
+
+    #[test]
+    fn ids_are_unique() {
+        for (index, achievement) in ACHIEVEMENTS.iter().enumerate() {
+            assert!(
+                ACHIEVEMENTS[..index]
+                    .iter()
+                    .all(|earlier| earlier.id != achievement.id),
+                "{} appears twice",
+                achievement.id
+            );
+        }
+    }
+}
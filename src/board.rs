@@ -0,0 +1,657 @@
+//! The tiles of the game board, worked out once from the puzzle and what is
+//! animating on it, and drawn by either renderer: a stack of stock widgets,
+//! or [`BoardCanvas`], a single canvas that draws every tile itself and
+//! turns clicks back into the cells pressed.
+
+use crate::palette::Palette;
+
+use iced::widget::canvas::{self, Frame, Geometry, Path, Stroke};
+use iced::widget::{button, container, image};
+use iced::{
+    Background, Color, Font, Pixels, Point, Rectangle, Renderer, Size, Theme, alignment, border,
+    mouse, time::Duration, touch,
+};
+use iced_15_puzzle::ColorScheme;
+
+// Tiles in their goal cell are this many times rounder than the rest, and
+// the blank's well twice as round
+const PLACED_ROUNDNESS: f32 = 5.0;
+const WELL_ROUNDNESS: f32 = 2.0;
+
+/// Where the tiles of a board go, in pixels from its top left corner.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct BoardLayout {
+    pub rows: usize,
+    pub cols: usize,
+    pub tile_size: f32,
+    pub spacing: f32,
+}
+
+impl BoardLayout {
+    /// The distance from one tile to the next.
+    pub fn pitch(self) -> f32 {
+        self.tile_size + self.spacing
+    }
+
+    pub fn size(self) -> Size {
+        let pitch = self.pitch();
+        Size::new(
+            (pitch * self.cols as f32 - self.spacing).round(),
+            (pitch * self.rows as f32 - self.spacing).round(),
+        )
+    }
+
+    /// The square of a tile drawn at `(row, col)`, counted in cells, which
+    /// is part of the way between two cells while the tile moves.
+    ///
+    /// Its edges are on whole pixels, so that tiles with no gap between
+    /// them meet without a seam, at the cost of some being a pixel wider
+    /// than others.
+    pub fn bounds(self, (row, col): (f32, f32)) -> Rectangle {
+        let pitch = self.pitch();
+        let (left, top) = (col * pitch, row * pitch);
+        let (x, y) = (left.round(), top.round());
+
+        Rectangle::new(
+            Point::new(x, y),
+            Size::new(
+                (left + self.tile_size).round() - x,
+                (top + self.tile_size).round() - y,
+            ),
+        )
+    }
+}
+
+/// How round the corners on the board are.
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct Corners {
+    pub tile: f32,
+    /// Of tiles in their goal cell, rounder so that telling them apart
+    /// never rests on colour alone.
+    pub placed: f32,
+    /// Of the blank's well, and of the outline over a tile's goal cell.
+    pub well: f32,
+}
+
+impl Corners {
+    /// The corners for tiles `tile_size` across, with a corner radius of
+    /// `radius`. None is rounder than a circle, whose widest label still
+    /// fits inside it.
+    pub fn new(radius: f32, tile_size: f32) -> Self {
+        let round = |roundness: f32| (radius * roundness).min(tile_size / 2.0);
+
+        Self {
+            tile: round(1.0),
+            placed: round(PLACED_ROUNDNESS),
+            well: round(WELL_ROUNDNESS),
+        }
+    }
+}
+
+/// One cell of the board as it is drawn this frame.
+#[derive(Debug, Clone, PartialEq)]
+pub struct TileSprite {
+    /// The cell the tile is in.
+    pub cell: (usize, usize),
+    /// Where it is drawn, counted in cells from the top left corner, which
+    /// is off its cell while it slides, shakes or is dragged.
+    pub at: (f32, f32),
+    /// The label on it, empty while blindfolded or when the picture hides
+    /// the numbers; `None` for the blank.
+    pub label: Option<String>,
+    /// The slice of the picture it shows, in picture mode.
+    pub picture: Option<Rectangle<u32>>,
+    pub look: TileLook,
+}
+
+impl TileSprite {
+    pub fn is_blank(&self) -> bool {
+        self.label.is_none()
+    }
+}
+
+/// What sets a tile apart from the others, besides its label.
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct TileLook {
+    pub hinted: bool,
+    /// Whether tiles are tinted by whether they are in their goal cell.
+    pub goal_colors: bool,
+    pub in_place: bool,
+    /// Whether it is beside the blank.
+    pub movable: bool,
+    pub focused: bool,
+    pub last_moved: bool,
+    /// Whether it was pressed just now but could not move.
+    pub rejected: bool,
+    /// Time into the victory wave, and how far the tile is from where it
+    /// starts.
+    pub wave: Option<(Duration, usize)>,
+    pub corners: Corners,
+}
+
+impl TileLook {
+    /// The tile as a button in `status`, however it is drawn.
+    pub fn style(self, palette: &Palette, theme: &Theme, status: button::Status) -> button::Style {
+        let mut style = if self.hinted {
+            palette.hint.style(status)
+        } else if self.goal_colors {
+            tile_style(palette, status, self.in_place, self.last_moved)
+        } else {
+            palette.tile.style(status)
+        };
+        let placed = self.goal_colors && !self.hinted && self.in_place;
+        style.border = style.border.rounded(if placed {
+            self.corners.placed
+        } else {
+            self.corners.tile
+        });
+        if self.movable {
+            style = movable_style(theme, status, style);
+        }
+        if self.focused {
+            style = focused_style(theme, style);
+        }
+        if self.rejected {
+            style = rejected_style(theme, style);
+        }
+        if let Some((elapsed, distance)) = self.wave {
+            style.background = Some(Background::Color(palette.wave_color(elapsed, distance)));
+        }
+        style
+    }
+}
+
+/// The tile under `point`, measured from the board's top left corner,
+/// wherever it is drawn. The blank is not a tile to press.
+pub fn tile_at(tiles: &[TileSprite], layout: BoardLayout, point: Point) -> Option<(usize, usize)> {
+    // Later tiles are drawn over earlier ones
+    tiles
+        .iter()
+        .rev()
+        .find(|tile| !tile.is_blank() && layout.bounds(tile.at).contains(point))
+        .map(|tile| tile.cell)
+}
+
+/// The blank is a recessed well rather than a faded button, so it stays
+/// distinct from the tiles in both light and dark themes.
+pub fn blank_style(theme: &Theme, radius: f32) -> container::Style {
+    let palette = theme.extended_palette();
+
+    // A darker rim makes the well look sunk into the board
+    container::Style::default()
+        .background(palette.background.strong.color)
+        .border(
+            border::rounded(radius)
+                .color(palette.background.strongest.color)
+                .width(2),
+        )
+}
+
+/// Tile colours by position: tinted in their goal cell, neutral elsewhere,
+/// and accented right after moving.
+fn tile_style(
+    palette: &Palette,
+    status: button::Status,
+    in_place: bool,
+    last_moved: bool,
+) -> button::Style {
+    let swatch = if last_moved {
+        palette.last_moved
+    } else if in_place {
+        palette.in_place
+    } else {
+        palette.misplaced
+    };
+    let style = swatch.style(status);
+
+    // Placed tiles are ringed as well as rounder, so telling them apart
+    // never rests on colour alone
+    if in_place {
+        button::Style {
+            border: style.border.color(swatch.text.scale_alpha(0.6)).width(2),
+            ..style
+        }
+    } else {
+        style
+    }
+}
+
+/// A tile with keyboard focus: a thick border in the text colour, which
+/// stands out against every other outline on the board.
+fn focused_style(theme: &Theme, style: button::Style) -> button::Style {
+    let ring = theme.extended_palette().background.base.text;
+
+    button::Style {
+        border: style.border.color(ring).width(4),
+        ..style
+    }
+}
+
+/// Outlines a tile that can slide into the blank, more boldly while the
+/// pointer is over it.
+fn movable_style(theme: &Theme, status: button::Status, style: button::Style) -> button::Style {
+    let text = theme.extended_palette().background.base.text;
+    let border = match status {
+        button::Status::Active => text.scale_alpha(0.25),
+        button::Status::Hovered | button::Status::Pressed => text.scale_alpha(0.6),
+        // Nothing moves while the board is out of play
+        button::Status::Disabled => return style,
+    };
+
+    button::Style {
+        border: style.border.color(border).width(2),
+        ..style
+    }
+}
+
+/// A tile pressed that could not move: a red border for a moment.
+fn rejected_style(theme: &Theme, style: button::Style) -> button::Style {
+    button::Style {
+        border: style
+            .border
+            .color(theme.extended_palette().danger.base.color)
+            .width(3),
+        ..style
+    }
+}
+
+/// The outline over the cell a tile belongs in, in the colour of a tile at
+/// home, or for a tile already there a fainter one in the text colour.
+/// `pulse` scales it from nothing to full strength.
+pub fn goal_flash_style(
+    palette: &Palette,
+    theme: &Theme,
+    home: bool,
+    pulse: f32,
+    radius: f32,
+) -> container::Style {
+    let (color, width) = if home {
+        (
+            theme
+                .extended_palette()
+                .background
+                .base
+                .text
+                .scale_alpha(0.4),
+            2,
+        )
+    } else {
+        (palette.in_place.background, 4)
+    };
+
+    container::Style::default().border(
+        border::rounded(radius)
+            .color(color.scale_alpha(pulse))
+            .width(width),
+    )
+}
+
+/// The whole board drawn on one canvas, from the same tiles the widget
+/// renderer stacks up, sending `on_press` for a tile clicked.
+pub struct BoardCanvas<Message> {
+    pub layout: BoardLayout,
+    pub tiles: Vec<TileSprite>,
+    pub scheme: ColorScheme,
+    pub label_size: f32,
+    pub font: Font,
+    pub corners: Corners,
+    /// The picture the tiles' slices are cut from, a square this many
+    /// pixels across.
+    pub picture: (image::Handle, u32),
+    /// The cell a right-clicked tile belongs in, whether that tile is home
+    /// already, and how strongly it is outlined.
+    pub goal_flash: Option<((usize, usize), bool, f32)>,
+    /// `None` while the board is out of play.
+    pub on_press: Option<fn(usize, usize) -> Message>,
+}
+
+/// The tile the pointer is over, redrawn as it moves, and the one pressed
+/// and not yet let go of.
+#[derive(Debug, Default)]
+pub struct Pointer {
+    hovered: Option<(usize, usize)>,
+    pressed: Option<(usize, usize)>,
+}
+
+impl<Message> BoardCanvas<Message> {
+    fn tile_under(&self, bounds: Rectangle, cursor: mouse::Cursor) -> Option<(usize, usize)> {
+        cursor
+            .position_in(bounds)
+            .and_then(|point| tile_at(&self.tiles, self.layout, point))
+    }
+
+    fn draw_tile(&self, frame: &mut Frame, tile: &TileSprite, label: &str, style: button::Style) {
+        let bounds = self.layout.bounds(tile.at);
+        draw_box(frame, bounds, style.background, style.border);
+
+        if let Some(crop) = tile.picture {
+            // The whole picture, scaled so the tile's slice covers the tile
+            let (picture, size) = &self.picture;
+            let scale = (
+                bounds.width / crop.width as f32,
+                bounds.height / crop.height as f32,
+            );
+            let whole = Rectangle::new(
+                Point::new(
+                    bounds.x - crop.x as f32 * scale.0,
+                    bounds.y - crop.y as f32 * scale.1,
+                ),
+                Size::new(*size as f32 * scale.0, *size as f32 * scale.1),
+            );
+            frame.with_clip(bounds, |frame| {
+                frame.draw_image(whole, canvas::Image::new(picture.clone()));
+            });
+        }
+
+        if !label.is_empty() {
+            frame.fill_text(canvas::Text {
+                content: label.to_owned(),
+                position: bounds.center(),
+                color: style.text_color,
+                size: Pixels(self.label_size),
+                font: self.font,
+                align_x: alignment::Horizontal::Center.into(),
+                align_y: alignment::Vertical::Center,
+                ..canvas::Text::default()
+            });
+        }
+    }
+}
+
+impl<Message> canvas::Program<Message> for BoardCanvas<Message> {
+    type State = Pointer;
+
+    fn update(
+        &self,
+        state: &mut Pointer,
+        event: &canvas::Event,
+        bounds: Rectangle,
+        cursor: mouse::Cursor,
+    ) -> Option<canvas::Action<Message>> {
+        let on_press = self.on_press?;
+        let under = self.tile_under(bounds, cursor);
+
+        // A tile is clicked as a button is, by letting go over the tile
+        // pressed; a swipe hides the cursor to let go of it without one
+        match event {
+            canvas::Event::Mouse(mouse::Event::ButtonPressed(mouse::Button::Left))
+            | canvas::Event::Touch(touch::Event::FingerPressed { .. }) => {
+                state.pressed = under;
+                under.map(|_| canvas::Action::request_redraw().and_capture())
+            }
+            canvas::Event::Mouse(mouse::Event::ButtonReleased(mouse::Button::Left))
+            | canvas::Event::Touch(touch::Event::FingerLifted { .. }) => {
+                let pressed = state.pressed.take()?;
+                Some(if under == Some(pressed) {
+                    canvas::Action::publish(on_press(pressed.0, pressed.1)).and_capture()
+                } else {
+                    canvas::Action::request_redraw()
+                })
+            }
+            _ if under != state.hovered => {
+                state.hovered = under;
+                Some(canvas::Action::request_redraw())
+            }
+            _ => None,
+        }
+    }
+
+    fn draw(
+        &self,
+        state: &Pointer,
+        renderer: &Renderer,
+        theme: &Theme,
+        bounds: Rectangle,
+        cursor: mouse::Cursor,
+    ) -> Vec<Geometry> {
+        let mut frame = Frame::new(renderer, bounds.size());
+        let palette = Palette::new(self.scheme, theme);
+        let under = self.tile_under(bounds, cursor);
+
+        // Keep the blank beneath tiles sliding over it
+        let blank = blank_style(theme, self.corners.well);
+        for tile in self.tiles.iter().filter(|tile| tile.is_blank()) {
+            draw_box(
+                &mut frame,
+                self.layout.bounds(tile.at),
+                blank.background,
+                blank.border,
+            );
+        }
+
+        for tile in &self.tiles {
+            let Some(label) = &tile.label else {
+                continue;
+            };
+            let status = if self.on_press.is_none() {
+                button::Status::Disabled
+            } else if under != Some(tile.cell) {
+                button::Status::Active
+            } else if state.pressed == Some(tile.cell) {
+                button::Status::Pressed
+            } else {
+                button::Status::Hovered
+            };
+
+            self.draw_tile(
+                &mut frame,
+                tile,
+                label,
+                tile.look.style(&palette, theme, status),
+            );
+        }
+
+        if let Some(((row, col), home, pulse)) = self.goal_flash {
+            let style = goal_flash_style(&palette, theme, home, pulse, self.corners.well);
+            draw_box(
+                &mut frame,
+                self.layout.bounds((row as f32, col as f32)),
+                None,
+                style.border,
+            );
+        }
+
+        vec![frame.into_geometry()]
+    }
+
+    fn mouse_interaction(
+        &self,
+        _state: &Pointer,
+        bounds: Rectangle,
+        cursor: mouse::Cursor,
+    ) -> mouse::Interaction {
+        match self.on_press {
+            Some(_) if self.tile_under(bounds, cursor).is_some() => mouse::Interaction::Pointer,
+            _ => mouse::Interaction::default(),
+        }
+    }
+}
+
+/// Fills `bounds` and draws `border` just inside its edge, as a container
+/// or button of that style is drawn, with corners no rounder than a circle.
+fn draw_box(
+    frame: &mut Frame,
+    bounds: Rectangle,
+    background: Option<Background>,
+    mut border: border::Border,
+) {
+    let widest = bounds.width.min(bounds.height) / 2.0;
+    let radius = border.radius;
+    border.radius = border::Radius {
+        top_left: radius.top_left.min(widest),
+        top_right: radius.top_right.min(widest),
+        bottom_right: radius.bottom_right.min(widest),
+        bottom_left: radius.bottom_left.min(widest),
+    };
+
+    if let Some(Background::Color(color)) = background {
+        frame.fill(
+            &Path::rounded_rectangle(bounds.position(), bounds.size(), border.radius),
+            color,
+        );
+    }
+
+    if border.width > 0.0 && border.color != Color::TRANSPARENT {
+        // A stroke straddles its path, so it runs half a width inside
+        let inset = border.width / 2.0;
+        let radius = border.radius;
+        let inner = |corner: f32| (corner - inset).max(0.0);
+        frame.stroke(
+            &Path::rounded_rectangle(
+                Point::new(bounds.x + inset, bounds.y + inset),
+                Size::new(bounds.width - border.width, bounds.height - border.width),
+                border::Radius {
+                    top_left: inner(radius.top_left),
+                    top_right: inner(radius.top_right),
+                    bottom_right: inner(radius.bottom_right),
+                    bottom_left: inner(radius.bottom_left),
+                },
+            ),
+            Stroke::default()
+                .with_color(border.color)
+                .with_width(border.width),
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // A 3x4 board of 100 pixel tiles, 10 apart
+    const LAYOUT: BoardLayout = BoardLayout {
+        rows: 3,
+        cols: 4,
+        tile_size: 100.0,
+        spacing: 10.0,
+    };
+
+    fn sprite(cell: (usize, usize), at: (f32, f32)) -> TileSprite {
+        TileSprite {
+            cell,
+            at,
+            label: Some(String::new()),
+            picture: None,
+            look: TileLook::default(),
+        }
+    }
+
+    /// Every tile of `LAYOUT` at rest, with the blank in the last cell.
+    fn resting() -> Vec<TileSprite> {
+        let mut tiles: Vec<TileSprite> = (0..LAYOUT.rows)
+            .flat_map(|row| (0..LAYOUT.cols).map(move |col| (row, col)))
+            .map(|(row, col)| sprite((row, col), (row as f32, col as f32)))
+            .collect();
+        tiles.last_mut().unwrap().label = None;
+        tiles
+    }
+
+    #[test]
+    fn the_board_is_its_tiles_and_the_gaps_between() {
+        assert_eq!(LAYOUT.size(), Size::new(430.0, 320.0));
+        assert_eq!(
+            LAYOUT.bounds((2.0, 1.5)),
+            Rectangle::new(Point::new(165.0, 220.0), Size::new(100.0, 100.0))
+        );
+    }
+
+    #[test]
+    fn tiles_without_a_gap_meet_on_whole_pixels() {
+        let layout = BoardLayout {
+            rows: 7,
+            cols: 7,
+            tile_size: 400.0 / 7.0,
+            spacing: 0.0,
+        };
+
+        for step in 0..7 {
+            let tile = layout.bounds((step as f32, step as f32));
+            let next = layout.bounds((step as f32 + 1.0, step as f32 + 1.0));
+            assert_eq!((tile.x.fract(), tile.width.fract()), (0.0, 0.0));
+            assert_eq!(
+                (tile.x + tile.width, tile.y + tile.height),
+                (next.x, next.y)
+            );
+        }
+        assert_eq!(
+            layout.bounds((6.0, 6.0)).x + layout.bounds((6.0, 6.0)).width,
+            400.0
+        );
+        assert_eq!(layout.size(), Size::new(400.0, 400.0));
+    }
+
+    #[test]
+    fn corners_round_no_further_than_a_circle() {
+        assert_eq!(
+            Corners::new(2.0, 100.0),
+            Corners {
+                tile: 2.0,
+                placed: 10.0,
+                well: 4.0
+            }
+        );
+        assert_eq!(Corners::new(0.0, 100.0), Corners::default());
+        assert_eq!(
+            Corners::new(40.0, 30.0),
+            Corners {
+                tile: 15.0,
+                placed: 15.0,
+                well: 15.0
+            }
+        );
+    }
+
+    #[test]
+    fn points_on_a_tile_find_its_cell_and_gaps_and_the_blank_none() {
+        let cases = [
+            (Point::new(0.0, 0.0), Some((0, 0))),
+            (Point::new(99.9, 99.9), Some((0, 0))),
+            (Point::new(100.0, 50.0), None),
+            (Point::new(109.9, 50.0), None),
+            (Point::new(110.0, 50.0), Some((0, 1))),
+            (Point::new(50.0, 105.0), None),
+            (Point::new(319.9, 319.9), Some((2, 2))),
+            (Point::new(429.9, 99.9), Some((0, 3))),
+            (Point::new(430.0, 50.0), None),
+            // The blank's cell
+            (Point::new(345.0, 265.0), None),
+            (Point::new(50.0, 320.0), None),
+            (Point::new(-0.1, 50.0), None),
+            (Point::new(50.0, -0.1), None),
+        ];
+
+        for (point, cell) in cases {
+            assert_eq!(tile_at(&resting(), LAYOUT, point), cell, "{point:?}");
+        }
+    }
+
+    #[test]
+    fn a_moving_tile_is_pressed_where_it_is_drawn() {
+        let mut tiles = resting();
+        // The tile from (2, 2) sliding halfway into the blank's cell
+        tiles[10].at = (2.0, 2.5);
+
+        assert_eq!(tile_at(&tiles, LAYOUT, Point::new(230.0, 250.0)), None);
+        assert_eq!(
+            tile_at(&tiles, LAYOUT, Point::new(280.0, 250.0)),
+            Some((2, 2))
+        );
+        assert_eq!(
+            tile_at(&tiles, LAYOUT, Point::new(370.0, 250.0)),
+            Some((2, 2))
+        );
+        assert_eq!(tile_at(&tiles, LAYOUT, Point::new(380.0, 250.0)), None);
+
+        // Shaken over the tile beside it, the one drawn later is on top
+        tiles[10].at = (2.0, 2.0);
+        tiles[9].at = (2.0, 1.5);
+        assert_eq!(
+            tile_at(&tiles, LAYOUT, Point::new(225.0, 250.0)),
+            Some((2, 2))
+        );
+        assert_eq!(
+            tile_at(&tiles, LAYOUT, Point::new(215.0, 250.0)),
+            Some((2, 1))
+        );
+    }
+}
@@ -0,0 +1,26 @@
+use serde::{Deserialize, Serialize};
+
+use std::fmt;
+
+/// What draws the board, while the canvas takes over from the widgets.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum BoardRenderer {
+    /// A button for each tile, stacked where it is on the board.
+    #[default]
+    Widgets,
+    /// A single canvas drawing every tile and working out which was clicked.
+    Canvas,
+}
+
+impl BoardRenderer {
+    pub const ALL: [BoardRenderer; 2] = [BoardRenderer::Widgets, BoardRenderer::Canvas];
+}
+
+impl fmt::Display for BoardRenderer {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            BoardRenderer::Widgets => "Widgets",
+            BoardRenderer::Canvas => "Canvas",
+        })
+    }
+}
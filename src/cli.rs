@@ -0,0 +1,531 @@
+//! The `solve` subcommand, which solves a board in the terminal without
+//! opening a window.
+
+use iced_15_puzzle::{
+    DEFAULT_GRID_SIZE, Puzzle,
+    heuristic::{LinearConflict, Manhattan, MisplacedTiles},
+    pattern_database::PatternDatabase,
+    solver::{self, Cancel, SearchReport},
+    walking_distance::WalkingDistance,
+};
+
+use std::time::{Duration, Instant};
+
+use crate::parse_size;
+
+pub const SOLVE_USAGE: &str = "\
+Usage: iced_15_puzzle solve [--heuristic <NAME>] <BOARD>
+       iced_15_puzzle solve [--heuristic <NAME>] --random [--seed <SEED>] [--size <N>]
+       iced_15_puzzle solve --random --count <COUNT> [--seed <SEED>] [--size <N>]
+
+Prints a shortest solution of a board in U/D/L/R notation, the way each tile
+slides, with its length, the time the search took and how many boards it
+expanded. A batch of scrambles is solved across all the machine's threads,
+guided the way the game's solver is.
+
+Arguments:
+  <BOARD>        Tile numbers in row-major order, 0 or _ for the blank,
+                 e.g. \"5 1 2 4 9 6 3 8 13 10 7 12 14 11 15 0\"
+
+Options:
+  --random       Solve a fresh scramble instead, e.g. for benchmarking
+  --seed <SEED>  Seed for a reproducible random scramble
+  --size <N>     Size of the random scramble as N or COLSxROWS (default: 4)
+  --count <COUNT>
+                 Solve this many random scrambles at once (default: 1)
+  --heuristic <NAME>
+                 What guides the search: misplaced, manhattan (the default),
+                 linear (Manhattan plus linear conflicts), wd (the walking
+                 distance, up to 4x4) or pdb (the 4x4 pattern database,
+                 built and saved on first use)
+  -h, --help     Print this help
+
+Exits with status 2 if the board cannot be solved.";
+
+/// Exit status for a board that has no solution.
+pub const UNSOLVABLE: i32 = 2;
+/// Exit status for arguments that could not be understood.
+pub const USAGE_ERROR: i32 = 1;
+
+/// What to solve, and how.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SolveArgs {
+    pub board: BoardArg,
+    pub heuristic: HeuristicArg,
+}
+
+/// The board to solve.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum BoardArg {
+    Given(String),
+    Random {
+        rows: usize,
+        cols: usize,
+        seed: Option<u64>,
+        count: usize,
+    },
+}
+
+/// The heuristic the search is guided by.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum HeuristicArg {
+    Misplaced,
+    #[default]
+    Manhattan,
+    Linear,
+    WalkingDistance,
+    PatternDatabase,
+}
+
+impl HeuristicArg {
+    fn parse(value: &str) -> Result<Self, String> {
+        match value {
+            "misplaced" => Ok(Self::Misplaced),
+            "manhattan" => Ok(Self::Manhattan),
+            "linear" => Ok(Self::Linear),
+            "wd" => Ok(Self::WalkingDistance),
+            "pdb" => Ok(Self::PatternDatabase),
+            _ => Err(format!(
+                "unknown heuristic '{value}', expected misplaced, manhattan, linear, wd or pdb"
+            )),
+        }
+    }
+}
+
+impl SolveArgs {
+    /// Parses the arguments following `solve`.
+    ///
+    /// Returns `Ok(None)` when help was requested.
+    pub fn parse(args: impl IntoIterator<Item = String>) -> Result<Option<Self>, String> {
+        let mut board = None;
+        let mut random = false;
+        let mut seed = None;
+        let mut size = None;
+        let mut count = None;
+        let mut heuristic = None;
+        let mut args = args.into_iter();
+
+        while let Some(arg) = args.next() {
+            match arg.as_str() {
+                "--random" => random = true,
+                "--seed" => {
+                    let value = args.next().ok_or("--seed requires a value")?;
+                    let parsed = value
+                        .parse()
+                        .map_err(|_| format!("invalid seed '{value}'"))?;
+                    seed = Some(parsed);
+                }
+                "--size" => {
+                    let value = args.next().ok_or("--size requires a value")?;
+                    size = Some(parse_size(&value)?);
+                }
+                "--count" => {
+                    let value = args.next().ok_or("--count requires a value")?;
+                    let parsed = value
+                        .parse()
+                        .ok()
+                        .filter(|&count| count > 0)
+                        .ok_or_else(|| format!("invalid count '{value}'"))?;
+                    count = Some(parsed);
+                }
+                "--heuristic" => {
+                    let value = args.next().ok_or("--heuristic requires a value")?;
+                    heuristic = Some(HeuristicArg::parse(&value)?);
+                }
+                "-h" | "--help" => return Ok(None),
+                _ if arg.starts_with("--") => return Err(format!("unexpected argument '{arg}'")),
+                _ if board.is_none() => board = Some(arg),
+                _ => return Err(format!("unexpected argument '{arg}'")),
+            }
+        }
+
+        let board = match (board, random) {
+            (Some(board), false) if seed.is_none() && size.is_none() && count.is_none() => {
+                BoardArg::Given(board)
+            }
+            (Some(_), false) => {
+                return Err(String::from(
+                    "--seed, --size and --count only go with --random",
+                ));
+            }
+            (Some(_), true) => {
+                return Err(String::from("give either a board or --random, not both"));
+            }
+            (None, true) => {
+                let (cols, rows) = size.unwrap_or((DEFAULT_GRID_SIZE, DEFAULT_GRID_SIZE));
+                let count = count.unwrap_or(1);
+                if count > 1 && heuristic.is_some() {
+                    return Err(String::from("--heuristic only goes with a single board"));
+                }
+                BoardArg::Random {
+                    rows,
+                    cols,
+                    seed,
+                    count,
+                }
+            }
+            (None, false) => return Err(String::from("missing the board to solve")),
+        };
+        Ok(Some(SolveArgs {
+            board,
+            heuristic: heuristic.unwrap_or_default(),
+        }))
+    }
+}
+
+impl BoardArg {
+    /// The first board this argument describes.
+    fn puzzle(&self) -> Result<Puzzle, String> {
+        self.puzzles().map(|mut puzzles| puzzles.swap_remove(0))
+    }
+
+    /// Every board this argument describes, one unless it is a batch of
+    /// scrambles.
+    fn puzzles(&self) -> Result<Vec<Puzzle>, String> {
+        match *self {
+            BoardArg::Given(ref board) => Puzzle::parse_permutation(board)
+                .map(|puzzle| vec![puzzle])
+                .map_err(|error| format!("invalid board: {error}")),
+            BoardArg::Random {
+                rows,
+                cols,
+                seed,
+                count,
+            } => {
+                let mut puzzle = match seed {
+                    Some(seed) => Puzzle::with_seed(rows, cols, seed),
+                    None => Puzzle::new(rows, cols),
+                };
+                Ok((0..count)
+                    .map(|_| {
+                        puzzle.shuffle();
+                        puzzle.clone()
+                    })
+                    .collect())
+            }
+        }
+    }
+}
+
+/// A solved board, ready to print.
+#[derive(Debug)]
+struct Report {
+    board: Puzzle,
+    solution: String,
+    elapsed: Duration,
+    nodes: u64,
+}
+
+/// A batch of scrambles solved together, ready to print.
+#[derive(Debug)]
+struct BatchReport {
+    boards: Vec<Puzzle>,
+    solutions: Vec<String>,
+    elapsed: Duration,
+}
+
+/// Why nothing was solved.
+#[derive(Debug, PartialEq, Eq)]
+enum Failure {
+    Usage(String),
+    Unsolvable,
+}
+
+fn solve(args: &SolveArgs) -> Result<Report, Failure> {
+    let board = args.board.puzzle().map_err(Failure::Usage)?;
+    if !board.is_solvable() {
+        return Err(Failure::Unsolvable);
+    }
+
+    // With no deadline the search only gives up on boards with no solution
+    let (started, SearchReport { solution, nodes }) = match args.heuristic {
+        HeuristicArg::Misplaced => timed(|| solver::solve_with(&board, None, &MisplacedTiles)),
+        HeuristicArg::Manhattan => timed(|| solver::solve_with(&board, None, &Manhattan)),
+        HeuristicArg::Linear => timed(|| solver::solve_with(&board, None, &LinearConflict)),
+        HeuristicArg::WalkingDistance => {
+            let walking_distance = WalkingDistance::for_puzzle(&board).ok_or_else(|| {
+                Failure::Usage(String::from("the walking distance only goes up to 4x4"))
+            })?;
+            timed(|| solver::solve_with(&board, None, &*walking_distance))
+        }
+        HeuristicArg::PatternDatabase => {
+            let database = pattern_database(&board).map_err(Failure::Usage)?;
+            timed(|| solver::solve_with(&board, None, &database))
+        }
+    };
+    let moves = solution.ok_or(Failure::Unsolvable)?;
+
+    Ok(Report {
+        elapsed: started,
+        nodes,
+        solution: moves
+            .into_iter()
+            .map(|direction| direction.notation())
+            .collect(),
+        board,
+    })
+}
+
+/// Solves every board `args` describes with [`solver::solve_many`], spread
+/// over the machine's threads.
+fn solve_batch(args: &SolveArgs) -> Result<BatchReport, Failure> {
+    let boards = args.board.puzzles().map_err(Failure::Usage)?;
+
+    let started = Instant::now();
+    let solutions = solver::solve_many(&boards, None, &Cancel::new());
+    let elapsed = started.elapsed();
+
+    let solutions = solutions
+        .into_iter()
+        .map(|solution| {
+            let moves = solution.ok_or(Failure::Unsolvable)?;
+            Ok(moves
+                .into_iter()
+                .map(|direction| direction.notation())
+                .collect())
+        })
+        .collect::<Result<_, _>>()?;
+    Ok(BatchReport {
+        boards,
+        solutions,
+        elapsed,
+    })
+}
+
+/// Runs `search`, also returning how long it took.
+fn timed(search: impl FnOnce() -> SearchReport) -> (Duration, SearchReport) {
+    let started = Instant::now();
+    let report = search();
+    (started.elapsed(), report)
+}
+
+/// The pattern database for `board`, which has to be 4x4, loaded or built
+/// the same way the game does it.
+#[cfg(not(target_arch = "wasm32"))]
+fn pattern_database(board: &Puzzle) -> Result<PatternDatabase, String> {
+    if !PatternDatabase::fits(4, 4, board) {
+        return Err(String::from("the pattern database only covers 4x4 boards"));
+    }
+
+    let mut shown = None;
+    let data = iced_15_puzzle::storage::Storage::data();
+    let database = crate::load_pattern_database(data.as_ref(), |done| {
+        let percent = (done * 100.0) as u32;
+        if shown.replace(percent) != Some(percent) {
+            eprint!("\rBuilding the pattern database... {percent}%");
+        }
+    });
+    if shown.is_some() {
+        eprintln!();
+    }
+    database.ok_or_else(|| String::from("no data directory to keep the pattern database in"))
+}
+
+/// Browsers have no terminal to run the subcommand in.
+#[cfg(target_arch = "wasm32")]
+fn pattern_database(_board: &Puzzle) -> Result<PatternDatabase, String> {
+    Err(String::from("the pattern database is not available here"))
+}
+
+/// Runs `solve` with the arguments following it and returns the exit status.
+pub fn run(args: impl IntoIterator<Item = String>) -> i32 {
+    let args = match SolveArgs::parse(args) {
+        Ok(Some(args)) => args,
+        Ok(None) => {
+            println!("{SOLVE_USAGE}");
+            return 0;
+        }
+        Err(error) => {
+            eprintln!("error: {error}\n\n{SOLVE_USAGE}");
+            return USAGE_ERROR;
+        }
+    };
+
+    if let BoardArg::Random { count, .. } = args.board
+        && count > 1
+    {
+        return match solve_batch(&args) {
+            Ok(report) => {
+                for (index, (board, solution)) in
+                    report.boards.iter().zip(&report.solutions).enumerate()
+                {
+                    print!("Board {}:\n{}", index + 1, board.board_text());
+                    println!("Solution: {solution}");
+                    println!("Length: {} moves", solution.len());
+                }
+                println!("Boards: {}", report.boards.len());
+                println!("Time: {:.3} s", report.elapsed.as_secs_f64());
+                0
+            }
+            Err(Failure::Usage(error)) => {
+                eprintln!("error: {error}\n\n{SOLVE_USAGE}");
+                USAGE_ERROR
+            }
+            Err(Failure::Unsolvable) => {
+                eprintln!("error: a board in the batch cannot be solved");
+                UNSOLVABLE
+            }
+        };
+    }
+
+    match solve(&args) {
+        Ok(report) => {
+            if matches!(args.board, BoardArg::Random { .. }) {
+                print!("Board:\n{}", report.board.board_text());
+            }
+            println!("Solution: {}", report.solution);
+            println!("Length: {} moves", report.solution.len());
+            println!("Time: {:.3} s", report.elapsed.as_secs_f64());
+            println!("Nodes expanded: {}", report.nodes);
+            0
+        }
+        Err(Failure::Usage(error)) => {
+            eprintln!("error: {error}\n\n{SOLVE_USAGE}");
+            USAGE_ERROR
+        }
+        Err(Failure::Unsolvable) => {
+            eprintln!("error: this board cannot be solved: no sequence of moves reaches the goal");
+            UNSOLVABLE
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse(args: &[&str]) -> Result<Option<SolveArgs>, String> {
+        SolveArgs::parse(args.iter().map(|arg| arg.to_string()))
+    }
+
+    fn given(board: &str) -> SolveArgs {
+        SolveArgs {
+            board: BoardArg::Given(String::from(board)),
+            heuristic: HeuristicArg::Manhattan,
+        }
+    }
+
+    fn random(seed: u64, heuristic: HeuristicArg) -> SolveArgs {
+        SolveArgs {
+            board: BoardArg::Random {
+                rows: 3,
+                cols: 3,
+                seed: Some(seed),
+                count: 1,
+            },
+            heuristic,
+        }
+    }
+
+    #[test]
+    fn solve_takes_a_board_or_a_random_scramble() {
+        assert_eq!(parse(&["1 2 3 0"]), Ok(Some(given("1 2 3 0"))));
+        assert_eq!(
+            parse(&["--random", "--seed", "9", "--size", "3"]),
+            Ok(Some(random(9, HeuristicArg::Manhattan)))
+        );
+        assert_eq!(
+            parse(&[
+                "--heuristic",
+                "linear",
+                "--random",
+                "--seed",
+                "9",
+                "--size",
+                "3"
+            ]),
+            Ok(Some(random(9, HeuristicArg::Linear)))
+        );
+        assert_eq!(parse(&["--help"]), Ok(None));
+
+        assert!(parse(&[]).is_err());
+        assert!(parse(&["1 2 3 0", "--random"]).is_err());
+        assert!(parse(&["1 2 3 0", "--seed", "9"]).is_err());
+        assert!(parse(&["1 2 3 0", "4 5 6 7"]).is_err());
+        assert!(parse(&["--random", "--seed", "nine"]).is_err());
+        assert!(parse(&["1 2 3 0", "--heuristic", "hamming"]).is_err());
+        assert!(parse(&["1 2 3 0", "--heuristic"]).is_err());
+        assert!(parse(&["1 2 3 0", "--count", "2"]).is_err());
+        assert!(parse(&["--random", "--count", "0"]).is_err());
+        assert!(parse(&["--random", "--count", "2", "--heuristic", "wd"]).is_err());
+    }
+
+    #[test]
+    fn a_batch_solves_each_scramble_as_it_would_alone() {
+        let mut args = random(21, HeuristicArg::Manhattan);
+        args.board = BoardArg::Random {
+            rows: 3,
+            cols: 3,
+            seed: Some(21),
+            count: 5,
+        };
+        assert_eq!(
+            parse(&["--random", "--seed", "21", "--size", "3", "--count", "5"]),
+            Ok(Some(args.clone()))
+        );
+
+        let report = solve_batch(&args).unwrap();
+        assert_eq!(report.boards.len(), 5);
+        // The first board of the batch is the one the seed gives alone
+        assert_eq!(
+            report.solutions[0].len(),
+            solve(&random(21, HeuristicArg::Manhattan))
+                .unwrap()
+                .solution
+                .len()
+        );
+        for (board, solution) in report.boards.iter().zip(&report.solutions) {
+            let mut board = board.clone();
+            board.apply_notation(solution).unwrap();
+            assert!(board.is_solved());
+        }
+    }
+
+    #[test]
+    fn solutions_replay_to_the_goal() {
+        let report = solve(&given("1 2 3 / 4 5 6 / 7 0 8")).unwrap();
+        assert_eq!(report.solution, "L");
+
+        let report = solve(&random(21, HeuristicArg::Manhattan)).unwrap();
+        let mut board = report.board.clone();
+        board.apply_notation(&report.solution).unwrap();
+        assert!(board.is_solved());
+    }
+
+    #[test]
+    fn heuristics_agree_on_the_length_but_not_the_work() {
+        let reports = [
+            HeuristicArg::Misplaced,
+            HeuristicArg::Linear,
+            HeuristicArg::WalkingDistance,
+        ]
+        .map(|heuristic| solve(&random(21, heuristic)).unwrap());
+
+        assert!(
+            reports
+                .iter()
+                .all(|report| report.solution.len() == reports[0].solution.len())
+        );
+        assert!(reports[0].nodes > reports[1].nodes);
+        assert!(reports[0].nodes > reports[2].nodes);
+    }
+
+    #[test]
+    fn bad_boards_are_told_apart_from_unsolvable_ones() {
+        assert_eq!(solve(&given("2 1 3 0")).err(), Some(Failure::Unsolvable));
+        assert!(matches!(solve(&given("1 2 3")), Err(Failure::Usage(_))));
+
+        let mut args = given("1 2 3 / 4 5 6 / 7 0 8");
+        args.heuristic = HeuristicArg::PatternDatabase;
+        assert!(matches!(solve(&args), Err(Failure::Usage(_))));
+        let mut args = given(
+            &(1..25)
+                .chain([0])
+                .map(|n| n.to_string())
+                .collect::<Vec<_>>()
+                .join(" "),
+        );
+        args.heuristic = HeuristicArg::WalkingDistance;
+        assert!(matches!(solve(&args), Err(Failure::Usage(_))));
+    }
+}
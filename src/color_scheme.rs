@@ -0,0 +1,33 @@
+use serde::{Deserialize, Serialize};
+
+use std::fmt;
+
+/// Which colours the tiles are drawn in, on top of the light or dark theme.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum ColorScheme {
+    /// Colours from the theme.
+    #[default]
+    Standard,
+    /// Stark tiles and bright highlights.
+    HighContrast,
+    /// Blues and oranges that stay apart for red-green colour blindness.
+    Deuteranopia,
+}
+
+impl ColorScheme {
+    pub const ALL: [ColorScheme; 3] = [
+        ColorScheme::Standard,
+        ColorScheme::HighContrast,
+        ColorScheme::Deuteranopia,
+    ];
+}
+
+impl fmt::Display for ColorScheme {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            ColorScheme::Standard => "Standard colours",
+            ColorScheme::HighContrast => "High contrast",
+            ColorScheme::Deuteranopia => "Deuteranopia-safe",
+        })
+    }
+}
@@ -0,0 +1,117 @@
+use serde::{Deserialize, Serialize};
+
+use std::fmt;
+use web_time::{SystemTime, UNIX_EPOCH};
+
+const SECONDS_PER_DAY: u64 = 24 * 60 * 60;
+
+/// A calendar day in UTC, as the number of days since 1970-01-01.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+pub struct Day(u32);
+
+impl Day {
+    pub fn from_days_since_epoch(days: u32) -> Self {
+        Self(days)
+    }
+
+    /// The UTC day `time` falls on; times before 1970 count as its first day.
+    pub fn from_system_time(time: SystemTime) -> Self {
+        let seconds = time
+            .duration_since(UNIX_EPOCH)
+            .map_or(0, |since| since.as_secs());
+
+        Self((seconds / SECONDS_PER_DAY) as u32)
+    }
+
+    pub fn today() -> Self {
+        Self::from_system_time(SystemTime::now())
+    }
+
+    pub fn previous(self) -> Self {
+        Self(self.0.saturating_sub(1))
+    }
+
+    /// The `(year, month, day)` of the proleptic Gregorian calendar.
+    pub fn ymd(self) -> (u32, u32, u32) {
+        // Howard Hinnant's civil_from_days, shifted so years start in March
+        // and the leap day falls last
+        let days = self.0 + 719_468;
+        let era = days / 146_097;
+        let day_of_era = days % 146_097;
+        let year_of_era =
+            (day_of_era - day_of_era / 1460 + day_of_era / 36_524 - day_of_era / 146_096) / 365;
+        let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+        let shifted_month = (5 * day_of_year + 2) / 153;
+        let day = day_of_year - (153 * shifted_month + 2) / 5 + 1;
+        let month = if shifted_month < 10 {
+            shifted_month + 3
+        } else {
+            shifted_month - 9
+        };
+        let year = year_of_era + era * 400 + u32::from(month <= 2);
+
+        (year, month, day)
+    }
+}
+
+/// Formats as an ISO 8601 date, e.g. `2024-06-01`.
+impl fmt::Display for Day {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let (year, month, day) = self.ymd();
+        write!(f, "{year:04}-{month:02}-{day:02}")
+    }
+}
+
+/// The scramble seed shared by everyone playing the daily puzzle on a board
+/// of `rows` by `cols` on `day`.
+///
+/// This is an FNV-1a hash of the date and size, which unlike the standard
+/// library's hasher is guaranteed to stay the same across builds.
+pub fn daily_seed(day: Day, rows: usize, cols: usize) -> u64 {
+    format!("{day} {cols}x{rows}")
+        .bytes()
+        .fold(0xcbf2_9ce4_8422_2325, |hash, byte| {
+            (hash ^ u64::from(byte)).wrapping_mul(0x0100_0000_01b3)
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use std::time::Duration;
+
+    #[test]
+    fn days_format_as_utc_dates() {
+        assert_eq!(Day::from_days_since_epoch(0).to_string(), "1970-01-01");
+        assert_eq!(Day::from_days_since_epoch(59).to_string(), "1970-03-01");
+        assert_eq!(Day::from_days_since_epoch(11_016).to_string(), "2000-02-29");
+        assert_eq!(Day::from_days_since_epoch(19_875).to_string(), "2024-06-01");
+        assert_eq!(Day::from_days_since_epoch(20_818).to_string(), "2026-12-31");
+    }
+
+    #[test]
+    fn days_change_at_midnight_utc() {
+        let midnight = UNIX_EPOCH + Duration::from_secs(19_875 * SECONDS_PER_DAY);
+
+        assert_eq!(
+            Day::from_system_time(midnight - Duration::from_secs(1)).to_string(),
+            "2024-05-31"
+        );
+        assert_eq!(Day::from_system_time(midnight).to_string(), "2024-06-01");
+        assert_eq!(
+            Day::from_system_time(midnight).previous(),
+            Day::from_system_time(midnight - Duration::from_secs(1))
+        );
+    }
+
+    #[test]
+    fn daily_seeds_depend_on_the_date_and_size_only() {
+        let day = Day::from_days_since_epoch(19_875);
+
+        assert_eq!(daily_seed(day, 4, 4), daily_seed(day, 4, 4));
+        assert_ne!(daily_seed(day, 4, 4), daily_seed(day.previous(), 4, 4));
+        assert_ne!(daily_seed(day, 4, 4), daily_seed(day, 3, 3));
+        assert_ne!(daily_seed(day, 3, 4), daily_seed(day, 4, 3));
+    }
+}
@@ -0,0 +1,39 @@
+use serde::{Deserialize, Serialize};
+
+use std::fmt;
+
+/// How thoroughly [`Puzzle::shuffle`](crate::Puzzle::shuffle) scrambles the board.
+#[derive(
+    Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize,
+)]
+pub enum Difficulty {
+    /// A short random walk of 10 to 20 moves from the solved position.
+    Easy,
+    /// A random walk of 50 moves from the solved position.
+    Medium,
+    /// A uniformly random solvable position.
+    #[default]
+    Hard,
+    /// A uniformly random solvable position that is far from solved.
+    Expert,
+}
+
+impl Difficulty {
+    pub const ALL: [Difficulty; 4] = [
+        Difficulty::Easy,
+        Difficulty::Medium,
+        Difficulty::Hard,
+        Difficulty::Expert,
+    ];
+}
+
+impl fmt::Display for Difficulty {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Difficulty::Easy => "Easy",
+            Difficulty::Medium => "Medium",
+            Difficulty::Hard => "Hard",
+            Difficulty::Expert => "Expert",
+        })
+    }
+}
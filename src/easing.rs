@@ -0,0 +1,112 @@
+use serde::{Deserialize, Serialize};
+
+use std::fmt;
+
+/// How a sliding tile speeds up and slows down on its way.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum Easing {
+    /// The same speed all the way.
+    Linear,
+    /// Fast off the mark, settling gently into place.
+    #[default]
+    EaseOut,
+    /// Gathering speed, then slowing down again.
+    EaseInOut,
+    /// Running slightly past the cell and springing back.
+    Overshoot,
+}
+
+impl Easing {
+    pub const ALL: [Easing; 4] = [
+        Easing::Linear,
+        Easing::EaseOut,
+        Easing::EaseInOut,
+        Easing::Overshoot,
+    ];
+
+    /// How far along a slide `t` of the way through its duration is, from 0
+    /// at the start to 1 at the end. `t` is clamped to that range.
+    pub fn ease(self, t: f32) -> f32 {
+        let t = t.clamp(0.0, 1.0);
+
+        match self {
+            Easing::Linear => linear(t),
+            Easing::EaseOut => ease_out(t),
+            Easing::EaseInOut => ease_in_out(t),
+            Easing::Overshoot => overshoot(t),
+        }
+    }
+}
+
+fn linear(t: f32) -> f32 {
+    t
+}
+
+fn ease_out(t: f32) -> f32 {
+    1.0 - (1.0 - t).powi(2)
+}
+
+fn ease_in_out(t: f32) -> f32 {
+    if t < 0.5 {
+        2.0 * t * t
+    } else {
+        1.0 - (2.0 - 2.0 * t).powi(2) / 2.0
+    }
+}
+
+/// Ease out past the end by up to a tenth of the way, for a little spring.
+fn overshoot(t: f32) -> f32 {
+    const BACK: f32 = 1.70158;
+
+    1.0 + (BACK + 1.0) * (t - 1.0).powi(3) + BACK * (t - 1.0).powi(2)
+}
+
+impl fmt::Display for Easing {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Easing::Linear => "Steady",
+            Easing::EaseOut => "Ease out",
+            Easing::EaseInOut => "Ease in and out",
+            Easing::Overshoot => "Springy",
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// `easing` at 101 evenly spaced points from the start to the end.
+    fn samples(easing: Easing) -> Vec<f32> {
+        (0..=100)
+            .map(|step| easing.ease(step as f32 / 100.0))
+            .collect()
+    }
+
+    #[test]
+    fn every_easing_starts_at_rest_and_ends_in_place() {
+        for easing in Easing::ALL {
+            assert!(easing.ease(0.0).abs() < 1e-6, "{easing}");
+            assert!((easing.ease(1.0) - 1.0).abs() < 1e-6, "{easing}");
+            // Times outside the slide are held at its ends
+            assert_eq!(easing.ease(-1.0), easing.ease(0.0), "{easing}");
+            assert_eq!(easing.ease(2.0), easing.ease(1.0), "{easing}");
+        }
+    }
+
+    #[test]
+    fn only_the_overshoot_ever_turns_back() {
+        for easing in [Easing::Linear, Easing::EaseOut, Easing::EaseInOut] {
+            let samples = samples(easing);
+            assert!(
+                samples.windows(2).all(|pair| pair[0] <= pair[1]),
+                "{easing}"
+            );
+            assert!(samples.iter().all(|&eased| eased <= 1.0), "{easing}");
+        }
+
+        let overshoot = samples(Easing::Overshoot);
+        let furthest = overshoot.iter().copied().fold(0.0, f32::max);
+        assert!(furthest > 1.05 && furthest < 1.15);
+    }
+}
@@ -0,0 +1,99 @@
+use crate::solver::OptimalLength;
+
+use serde::{Deserialize, Serialize};
+
+// Star thresholds as the most moves a solve may take per optimal move, in
+// tenths: three stars within 1.3 times the optimal length, two within twice
+const THREE_STARS: u64 = 13;
+const TWO_STARS: u64 = 20;
+
+/// How close a solve came to the fewest moves possible.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Efficiency {
+    pub optimal: OptimalLength,
+    pub moves: u32,
+}
+
+impl Efficiency {
+    pub fn new(optimal: OptimalLength, moves: u32) -> Self {
+        Self { optimal, moves }
+    }
+
+    /// Whether the optimal length is known, rather than only a lower bound on
+    /// it.
+    pub fn is_exact(self) -> bool {
+        matches!(self.optimal, OptimalLength::Exact(_))
+    }
+
+    /// The optimal length over the moves made, at most 1. Only a lower bound
+    /// unless [`is_exact`](Self::is_exact).
+    ///
+    /// Counting a line slide as one move can beat the optimal length in tile
+    /// moves, which still counts as fully efficient.
+    pub fn ratio(self) -> f64 {
+        if self.moves == 0 {
+            return 1.0;
+        }
+        (f64::from(self.optimal_moves()) / f64::from(self.moves)).min(1.0)
+    }
+
+    /// The ratio as a whole percentage.
+    pub fn percent(self) -> u32 {
+        (self.ratio() * 100.0).round() as u32
+    }
+
+    /// 1 to 3 stars, or `None` when the optimal length is only bounded, as
+    /// a rating from an uncertain bound could be too generous.
+    pub fn stars(self) -> Option<u8> {
+        let OptimalLength::Exact(optimal) = self.optimal else {
+            return None;
+        };
+        let (moves, optimal) = (u64::from(self.moves) * 10, u64::from(optimal));
+
+        Some(if moves <= optimal * THREE_STARS {
+            3
+        } else if moves <= optimal * TWO_STARS {
+            2
+        } else {
+            1
+        })
+    }
+
+    fn optimal_moves(self) -> u32 {
+        match self.optimal {
+            OptimalLength::Exact(length) | OptimalLength::AtLeast(length) => length,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn exact(optimal: u32, moves: u32) -> Efficiency {
+        Efficiency::new(OptimalLength::Exact(optimal), moves)
+    }
+
+    #[test]
+    fn star_thresholds_are_pinned() {
+        assert_eq!(exact(50, 50).stars(), Some(3));
+        assert_eq!(exact(50, 65).stars(), Some(3));
+        assert_eq!(exact(50, 66).stars(), Some(2));
+        assert_eq!(exact(50, 100).stars(), Some(2));
+        assert_eq!(exact(50, 101).stars(), Some(1));
+        assert_eq!(exact(10, 13).stars(), Some(3));
+        assert_eq!(exact(10, 14).stars(), Some(2));
+    }
+
+    #[test]
+    fn bounds_give_a_percentage_but_no_stars() {
+        let bounded = Efficiency::new(OptimalLength::AtLeast(48), 96);
+        assert!(!bounded.is_exact());
+        assert_eq!(bounded.percent(), 50);
+        assert_eq!(bounded.stars(), None);
+
+        assert_eq!(exact(52, 143).percent(), 36);
+        // Line slides counted as one move never score above 100%
+        assert_eq!(exact(30, 20).percent(), 100);
+    }
+}
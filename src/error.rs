@@ -0,0 +1,154 @@
+use std::fmt;
+
+/// Why a board description could not be turned into a [`Puzzle`](crate::Puzzle).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum BoardError {
+    /// Rows or columns are outside the supported range.
+    InvalidDimensions { rows: usize, cols: usize },
+    /// The number of cells does not match the dimensions.
+    WrongCellCount { expected: usize, found: usize },
+    /// A value is outside `0..rows * cols`.
+    ValueOutOfRange(usize),
+    /// A value appears more than once.
+    DuplicateValue(usize),
+    /// The recorded undo or redo history does not lead to this position.
+    InvalidHistory,
+    /// A permutation whose length is not the area of a supported square board.
+    NotSquare(usize),
+    /// A token in a board description that is not a tile number.
+    InvalidNumber(String),
+}
+
+impl fmt::Display for BoardError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            BoardError::InvalidDimensions { rows, cols } => {
+                write!(f, "unsupported board size {cols}x{rows}")
+            }
+            BoardError::WrongCellCount { expected, found } => {
+                write!(f, "expected {expected} cells, found {found}")
+            }
+            BoardError::ValueOutOfRange(value) => write!(f, "tile {value} is out of range"),
+            BoardError::DuplicateValue(value) => write!(f, "tile {value} appears more than once"),
+            BoardError::InvalidHistory => f.write_str("move history does not match the board"),
+            BoardError::NotSquare(cells) => write!(f, "{cells} tiles do not form a square board"),
+            BoardError::InvalidNumber(token) => write!(f, "'{token}' is not a tile number"),
+        }
+    }
+}
+
+impl std::error::Error for BoardError {}
+
+/// Why a move string could not be replayed with
+/// [`Puzzle::apply_notation`](crate::Puzzle::apply_notation).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum NotationError {
+    /// A character other than `U`, `D`, `L`, `R` or whitespace.
+    InvalidCharacter { position: usize, character: char },
+    /// A move that is not possible from the position reached so far.
+    IllegalMove { position: usize, character: char },
+}
+
+impl fmt::Display for NotationError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            NotationError::InvalidCharacter {
+                position,
+                character,
+            } => write!(f, "'{character}' at position {position} is not a move"),
+            NotationError::IllegalMove {
+                position,
+                character,
+            } => write!(
+                f,
+                "move '{character}' at position {position} is not possible"
+            ),
+        }
+    }
+}
+
+impl std::error::Error for NotationError {}
+
+/// Why a stored document could not be brought up to date with
+/// [`storage::migrate`](crate::storage::migrate) and read.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum MigrateError {
+    /// The document is not JSON at all.
+    Syntax(String),
+    /// A step could not make sense of the document it was given, written in
+    /// `version`.
+    Unexpected { version: u32, found: String },
+    /// Brought up to date, the document still does not fit its structure.
+    Invalid(String),
+}
+
+impl fmt::Display for MigrateError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            MigrateError::Syntax(error) => write!(f, "not valid JSON: {error}"),
+            MigrateError::Unexpected { version, found } => {
+                write!(f, "unexpected {found} in a version {version} document")
+            }
+            MigrateError::Invalid(error) => write!(f, "invalid document: {error}"),
+        }
+    }
+}
+
+impl std::error::Error for MigrateError {}
+
+/// Why a scramble code could not be read with
+/// [`decode_board`](crate::decode_board).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CodeError {
+    /// Too short to hold a board and its check character.
+    TooShort,
+    /// Longer than the code of any supported board.
+    TooLong,
+    /// A character that is not part of the code alphabet.
+    InvalidCharacter(char),
+    /// The check character does not match the rest, so the code was mistyped.
+    ChecksumMismatch,
+    /// A well-formed code that describes no board.
+    NoSuchBoard,
+}
+
+impl fmt::Display for CodeError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            CodeError::TooShort => f.write_str("the code is too short"),
+            CodeError::TooLong => f.write_str("the code is too long"),
+            CodeError::InvalidCharacter(character) => {
+                write!(f, "'{character}' is not part of a code")
+            }
+            CodeError::ChecksumMismatch => f.write_str("the code has a typo"),
+            CodeError::NoSuchBoard => f.write_str("the code is not for any board"),
+        }
+    }
+}
+
+impl std::error::Error for CodeError {}
+
+/// Why a profile could not be made with
+/// [`Profiles::create`](crate::Profiles::create).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ProfileError {
+    /// The name is empty once trimmed.
+    EmptyName,
+    /// The name is longer than [`MAX_PROFILE_NAME`](crate::MAX_PROFILE_NAME)
+    /// characters.
+    NameTooLong,
+    /// Another profile goes by the name, ignoring case.
+    NameTaken,
+}
+
+impl fmt::Display for ProfileError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ProfileError::EmptyName => f.write_str("the name is empty"),
+            ProfileError::NameTooLong => f.write_str("the name is too long"),
+            ProfileError::NameTaken => f.write_str("another profile has that name"),
+        }
+    }
+}
+
+impl std::error::Error for ProfileError {}
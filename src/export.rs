@@ -0,0 +1,260 @@
+//! Pictures of the board to share, drawn straight from the [`Puzzle`] so
+//! they look the same whatever the theme or window, and the files they and
+//! the statistics are saved to.
+
+use iced_15_puzzle::{LabelMode, Puzzle, label_for};
+use image::{ImageFormat, Rgba, RgbaImage};
+
+use std::io::Cursor;
+
+// Layout of the picture, in pixels
+const TILE: u32 = 72;
+const GAP: u32 = 6;
+const MARGIN: u32 = 12;
+// Glyphs are 5 by 7 dots, each dot this many pixels square
+const TILE_DOT: u32 = 4;
+const CAPTION_DOT: u32 = 2;
+
+const BACKGROUND: Rgba<u8> = Rgba([0xe8, 0xe8, 0xe8, 0xff]);
+const TILE_COLOR: Rgba<u8> = Rgba([0x2f, 0x5d, 0xa8, 0xff]);
+const BLANK_COLOR: Rgba<u8> = Rgba([0xc4, 0xc4, 0xc4, 0xff]);
+const LABEL_COLOR: Rgba<u8> = Rgba([0xff, 0xff, 0xff, 0xff]);
+const CAPTION_COLOR: Rgba<u8> = Rgba([0x20, 0x20, 0x20, 0xff]);
+
+/// Draws `puzzle` as tiles labelled in `labels`, and `caption` beneath if
+/// given. Letters in the caption are drawn in capitals.
+pub fn render(puzzle: &Puzzle, labels: LabelMode, caption: Option<&str>) -> RgbaImage {
+    let labels = labels.for_cells(puzzle.tiles().len());
+    let (rows, cols) = (puzzle.rows() as u32, puzzle.cols() as u32);
+    let board_width = cols * TILE + (cols - 1) * GAP;
+    let board_height = rows * TILE + (rows - 1) * GAP;
+    let caption_height = caption.map_or(0, |_| MARGIN + 7 * CAPTION_DOT);
+
+    let mut picture = RgbaImage::from_pixel(
+        board_width + 2 * MARGIN,
+        board_height + 2 * MARGIN + caption_height,
+        BACKGROUND,
+    );
+
+    for row in 0..rows {
+        for col in 0..cols {
+            let (x, y) = (MARGIN + col * (TILE + GAP), MARGIN + row * (TILE + GAP));
+            let tile = puzzle.tile(row as usize, col as usize);
+            let Some(value) = tile.value() else {
+                fill(&mut picture, x, y, TILE, TILE, BLANK_COLOR);
+                continue;
+            };
+
+            fill(&mut picture, x, y, TILE, TILE, TILE_COLOR);
+            let label = label_for(value, labels);
+            let (width, height) = text_size(&label, TILE_DOT);
+            draw_text(
+                &mut picture,
+                &label,
+                x + (TILE - width) / 2,
+                y + (TILE - height) / 2,
+                TILE_DOT,
+                LABEL_COLOR,
+            );
+        }
+    }
+
+    if let Some(caption) = caption {
+        // Too long a caption is cut short at the edge of the picture
+        let (width, _) = text_size(caption, CAPTION_DOT);
+        let x = (picture.width().saturating_sub(width) / 2).max(MARGIN);
+        let y = board_height + 2 * MARGIN;
+        draw_text(&mut picture, caption, x, y, CAPTION_DOT, CAPTION_COLOR);
+    }
+
+    picture
+}
+
+/// Encodes `picture` as a PNG file.
+pub fn encode_png(picture: &RgbaImage) -> Result<Vec<u8>, String> {
+    let mut bytes = Cursor::new(Vec::new());
+    picture
+        .write_to(&mut bytes, ImageFormat::Png)
+        .map_err(|error| format!("could not encode the image: {error}"))?;
+    Ok(bytes.into_inner())
+}
+
+/// Asks where to save `png` and writes it there, returning where it went, or
+/// `None` if the player cancelled. In the browser the picture is downloaded
+/// instead.
+pub async fn save_png(png: Vec<u8>) -> Result<Option<String>, String> {
+    save(png, "PNG image", "png", "puzzle.png").await
+}
+
+/// Like [`save_png`], for the solves as CSV.
+pub async fn save_csv(csv: String) -> Result<Option<String>, String> {
+    save(csv.into_bytes(), "CSV", "csv", "solves.csv").await
+}
+
+async fn save(
+    contents: Vec<u8>,
+    filter: &str,
+    extension: &str,
+    file_name: &str,
+) -> Result<Option<String>, String> {
+    let Some(file) = rfd::AsyncFileDialog::new()
+        .add_filter(filter, &[extension])
+        .set_file_name(file_name)
+        .save_file()
+        .await
+    else {
+        return Ok(None);
+    };
+
+    #[cfg(not(target_arch = "wasm32"))]
+    let written = tokio::fs::write(file.path(), contents).await;
+    #[cfg(target_arch = "wasm32")]
+    let written = file.write(&contents).await;
+
+    #[cfg(not(target_arch = "wasm32"))]
+    let name = file.path().display().to_string();
+    #[cfg(target_arch = "wasm32")]
+    let name = file.file_name();
+
+    written.map_err(|error| format!("could not write {name}: {error}"))?;
+    Ok(Some(name))
+}
+
+fn fill(picture: &mut RgbaImage, x: u32, y: u32, width: u32, height: u32, color: Rgba<u8>) {
+    let right = (x + width).min(picture.width());
+    let bottom = (y + height).min(picture.height());
+
+    for py in y..bottom {
+        for px in x..right {
+            picture.put_pixel(px, py, color);
+        }
+    }
+}
+
+/// The width and height `text` takes up at `dot` pixels a dot, with one
+/// dot between glyphs.
+fn text_size(text: &str, dot: u32) -> (u32, u32) {
+    let glyphs = text.chars().count() as u32;
+    ((glyphs * 6).saturating_sub(1) * dot, 7 * dot)
+}
+
+fn draw_text(picture: &mut RgbaImage, text: &str, x: u32, y: u32, dot: u32, color: Rgba<u8>) {
+    for (index, character) in text.chars().enumerate() {
+        let left = x + index as u32 * 6 * dot;
+
+        for (row, bits) in glyph(character).into_iter().enumerate() {
+            for col in 0..5 {
+                if bits & (0b10000 >> col) != 0 {
+                    let (px, py) = (left + col * dot, y + row as u32 * dot);
+                    fill(picture, px, py, dot, dot, color);
+                }
+            }
+        }
+    }
+}
+
+/// The dots of `character`, a row of five per line with the leftmost in
+/// the highest bit. Characters without a glyph are left blank.
+fn glyph(character: char) -> [u8; 7] {
+    let character = match character.to_ascii_uppercase() {
+        'ä' | 'Ä' => 'A',
+        'ö' | 'Ö' => 'O',
+        'ü' | 'Ü' => 'U',
+        other => other,
+    };
+
+    match character {
+        '0' => [0x0e, 0x11, 0x13, 0x15, 0x19, 0x11, 0x0e],
+        '1' => [0x04, 0x0c, 0x04, 0x04, 0x04, 0x04, 0x0e],
+        '2' => [0x0e, 0x11, 0x01, 0x02, 0x04, 0x08, 0x1f],
+        '3' => [0x1f, 0x02, 0x04, 0x02, 0x01, 0x11, 0x0e],
+        '4' => [0x02, 0x06, 0x0a, 0x12, 0x1f, 0x02, 0x02],
+        '5' => [0x1f, 0x10, 0x1e, 0x01, 0x01, 0x11, 0x0e],
+        '6' => [0x06, 0x08, 0x10, 0x1e, 0x11, 0x11, 0x0e],
+        '7' => [0x1f, 0x01, 0x02, 0x04, 0x08, 0x08, 0x08],
+        '8' => [0x0e, 0x11, 0x11, 0x0e, 0x11, 0x11, 0x0e],
+        '9' => [0x0e, 0x11, 0x11, 0x0f, 0x01, 0x02, 0x0c],
+        'A' => [0x0e, 0x11, 0x11, 0x1f, 0x11, 0x11, 0x11],
+        'B' => [0x1e, 0x11, 0x11, 0x1e, 0x11, 0x11, 0x1e],
+        'C' => [0x0e, 0x11, 0x10, 0x10, 0x10, 0x11, 0x0e],
+        'D' => [0x1e, 0x11, 0x11, 0x11, 0x11, 0x11, 0x1e],
+        'E' => [0x1f, 0x10, 0x10, 0x1e, 0x10, 0x10, 0x1f],
+        'F' => [0x1f, 0x10, 0x10, 0x1e, 0x10, 0x10, 0x10],
+        'G' => [0x0e, 0x11, 0x10, 0x17, 0x11, 0x11, 0x0f],
+        'H' => [0x11, 0x11, 0x11, 0x1f, 0x11, 0x11, 0x11],
+        'I' => [0x0e, 0x04, 0x04, 0x04, 0x04, 0x04, 0x0e],
+        'J' => [0x07, 0x02, 0x02, 0x02, 0x02, 0x12, 0x0c],
+        'K' => [0x11, 0x12, 0x14, 0x18, 0x14, 0x12, 0x11],
+        'L' => [0x10, 0x10, 0x10, 0x10, 0x10, 0x10, 0x1f],
+        'M' => [0x11, 0x1b, 0x15, 0x15, 0x11, 0x11, 0x11],
+        'N' => [0x11, 0x11, 0x19, 0x15, 0x13, 0x11, 0x11],
+        'O' => [0x0e, 0x11, 0x11, 0x11, 0x11, 0x11, 0x0e],
+        'P' => [0x1e, 0x11, 0x11, 0x1e, 0x10, 0x10, 0x10],
+        'Q' => [0x0e, 0x11, 0x11, 0x11, 0x15, 0x12, 0x0d],
+        'R' => [0x1e, 0x11, 0x11, 0x1e, 0x14, 0x12, 0x11],
+        'S' => [0x0f, 0x10, 0x10, 0x0e, 0x01, 0x01, 0x1e],
+        'T' => [0x1f, 0x04, 0x04, 0x04, 0x04, 0x04, 0x04],
+        'U' => [0x11, 0x11, 0x11, 0x11, 0x11, 0x11, 0x0e],
+        'V' => [0x11, 0x11, 0x11, 0x11, 0x11, 0x0a, 0x04],
+        'W' => [0x11, 0x11, 0x11, 0x15, 0x15, 0x15, 0x0a],
+        'X' => [0x11, 0x11, 0x0a, 0x04, 0x0a, 0x11, 0x11],
+        'Y' => [0x11, 0x11, 0x11, 0x0a, 0x04, 0x04, 0x04],
+        'Z' => [0x1f, 0x01, 0x02, 0x04, 0x08, 0x10, 0x1f],
+        ':' => [0x00, 0x0c, 0x0c, 0x00, 0x0c, 0x0c, 0x00],
+        '.' => [0x00, 0x00, 0x00, 0x00, 0x00, 0x0c, 0x0c],
+        ',' => [0x00, 0x00, 0x00, 0x00, 0x0c, 0x04, 0x08],
+        '-' => [0x00, 0x00, 0x00, 0x1f, 0x00, 0x00, 0x00],
+        '/' => [0x01, 0x02, 0x02, 0x04, 0x08, 0x08, 0x10],
+        '·' => [0x00, 0x00, 0x00, 0x0c, 0x0c, 0x00, 0x00],
+        _ => [0; 7],
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// FNV-1a over the raw pixels, so a change to the picture shows up as a
+    /// changed number.
+    fn pixel_hash(picture: &RgbaImage) -> u64 {
+        picture
+            .as_raw()
+            .iter()
+            .fold(0xcbf2_9ce4_8422_2325, |hash, &byte| {
+                (hash ^ u64::from(byte)).wrapping_mul(0x0100_0000_01b3)
+            })
+    }
+
+    #[test]
+    fn a_known_board_always_draws_the_same_pixels() {
+        let picture = render(
+            &Puzzle::new(3, 3),
+            LabelMode::Numbers,
+            Some("0 moves in 0:00.0"),
+        );
+
+        assert_eq!(picture.dimensions(), (252, 278));
+        assert_eq!(pixel_hash(&picture), 15_261_266_459_449_300_517);
+    }
+
+    #[test]
+    fn pictures_follow_the_board() {
+        let solved = Puzzle::new(3, 3);
+        let mut moved = solved.clone();
+        let (row, col) = moved.movable_tiles()[0];
+        assert!(moved.move_tile(row, col));
+
+        assert_ne!(
+            pixel_hash(&render(&solved, LabelMode::Numbers, None)),
+            pixel_hash(&render(&moved, LabelMode::Numbers, None))
+        );
+        // Labels sit in the middle of their tile, in the label colour
+        let picture = render(&solved, LabelMode::Numbers, None);
+        assert_eq!(*picture.get_pixel(MARGIN + 1, MARGIN + 1), TILE_COLOR);
+        assert_eq!(
+            *picture.get_pixel(MARGIN + TILE / 2, MARGIN + TILE / 2),
+            LABEL_COLOR
+        );
+        assert!(encode_png(&picture).unwrap().starts_with(b"\x89PNG"));
+    }
+}
@@ -0,0 +1,69 @@
+use serde::{Deserialize, Serialize};
+
+use std::time::Duration;
+
+/// The countdown a time attack starts from unless configured otherwise.
+pub const DEFAULT_TIME_LIMIT: Duration = Duration::from_secs(3 * 60);
+
+/// How many moves a move-limit challenge allows for every move of the
+/// optimal solution, as a fraction.
+const MOVE_BUDGET_FACTOR: (u32, u32) = (3, 2);
+
+/// The rules an attempt is played under.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum GameMode {
+    /// Untimed apart from the stopwatch, and pausable.
+    #[default]
+    Casual,
+    /// The board must be solved before `remaining` runs out, without pausing
+    /// other than while the window is out of focus.
+    TimeAttack { remaining: Duration },
+    /// The board must be solved within `budget` moves, which is `None` until
+    /// it has been worked out for the scramble. Undoing refunds moves.
+    MoveLimit { budget: Option<u32> },
+}
+
+impl GameMode {
+    pub fn is_time_attack(self) -> bool {
+        matches!(self, GameMode::TimeAttack { .. })
+    }
+
+    pub fn is_move_limit(self) -> bool {
+        matches!(self, GameMode::MoveLimit { .. })
+    }
+
+    /// The time left on a time attack's clock.
+    pub fn remaining(self) -> Option<Duration> {
+        match self {
+            GameMode::TimeAttack { remaining } => Some(remaining),
+            _ => None,
+        }
+    }
+
+    /// The moves a move-limit challenge allows in total, once known.
+    pub fn budget(self) -> Option<u32> {
+        match self {
+            GameMode::MoveLimit { budget } => budget,
+            _ => None,
+        }
+    }
+}
+
+/// The move budget for a scramble whose shortest solution takes
+/// `solution_length` moves: half as many again, rounded up.
+pub fn move_budget(solution_length: u32) -> u32 {
+    let (numerator, denominator) = MOVE_BUDGET_FACTOR;
+    (solution_length * numerator).div_ceil(denominator)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn move_budgets_allow_half_as_many_again() {
+        assert_eq!(move_budget(10), 15);
+        assert_eq!(move_budget(11), 17);
+        assert_eq!(move_budget(1), 2);
+    }
+}
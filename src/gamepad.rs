@@ -0,0 +1,184 @@
+//! Controller input, read through `gilrs` when the `gamepad` feature is
+//! enabled and absent otherwise.
+
+use iced::Subscription;
+use iced_15_puzzle::Direction;
+
+use crate::i18n::Text;
+
+// The stick counts as pushed past this deflection, and as back at rest below
+// the second, so one flick is one move
+#[cfg(any(feature = "gamepad", test))]
+const STICK_PUSHED: f32 = 0.6;
+#[cfg(any(feature = "gamepad", test))]
+const STICK_RELEASED: f32 = 0.3;
+
+/// What a controller asked for.
+// Only the polling thread builds these, so without it they go unused
+#[cfg_attr(not(feature = "gamepad"), allow(dead_code))]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Input {
+    Move(Direction),
+    Shuffle,
+    Undo,
+}
+
+/// The controller mapping, for the help overlay.
+pub const BUTTONS: [(&str, Text); 3] = [
+    ("D-pad, left stick", Text::SlideTile),
+    ("South (A / ✕)", Text::Shuffle),
+    ("East (B / ○)", Text::Undo),
+];
+
+/// Inputs from every connected controller, including ones plugged in later.
+#[cfg(feature = "gamepad")]
+pub fn inputs() -> Subscription<Input> {
+    Subscription::run(poll)
+}
+
+#[cfg(not(feature = "gamepad"))]
+pub fn inputs() -> Subscription<Input> {
+    Subscription::none()
+}
+
+/// Reads controller events on a thread of its own, since the `gilrs`
+/// context cannot move between the executor's threads.
+#[cfg(feature = "gamepad")]
+fn poll() -> impl iced::futures::Stream<Item = Input> {
+    use gilrs::{Axis, Button, EventType, Gilrs};
+
+    use std::time::Duration;
+
+    iced::stream::channel(16, async |mut output| {
+        std::thread::spawn(move || {
+            // Without controller support there is nothing to poll; say so once
+            let mut gilrs = match Gilrs::new() {
+                Ok(gilrs) => gilrs,
+                Err(error) => {
+                    eprintln!("warning: controller input unavailable: {error}");
+                    return;
+                }
+            };
+            let mut stick = Stick::default();
+
+            loop {
+                // Connecting and disconnecting arrive as events too, so a
+                // controller plugged in mid-game just starts working
+                let Some(event) = gilrs.next_event_blocking(Some(Duration::from_millis(500)))
+                else {
+                    continue;
+                };
+
+                let input = match event.event {
+                    EventType::ButtonPressed(Button::DPadUp, _) => Some(Input::Move(Direction::Up)),
+                    EventType::ButtonPressed(Button::DPadDown, _) => {
+                        Some(Input::Move(Direction::Down))
+                    }
+                    EventType::ButtonPressed(Button::DPadLeft, _) => {
+                        Some(Input::Move(Direction::Left))
+                    }
+                    EventType::ButtonPressed(Button::DPadRight, _) => {
+                        Some(Input::Move(Direction::Right))
+                    }
+                    EventType::ButtonPressed(Button::South, _) => Some(Input::Shuffle),
+                    EventType::ButtonPressed(Button::East, _) => Some(Input::Undo),
+                    EventType::AxisChanged(Axis::LeftStickX, value, _) => {
+                        stick.horizontal(value).map(Input::Move)
+                    }
+                    EventType::AxisChanged(Axis::LeftStickY, value, _) => {
+                        stick.vertical(value).map(Input::Move)
+                    }
+                    _ => None,
+                };
+
+                // The app has shut down once nobody is listening
+                if let Some(input) = input
+                    && output.try_send(input).is_err()
+                    && output.is_closed()
+                {
+                    return;
+                }
+            }
+        });
+    })
+}
+
+/// Turns analogue stick deflection into single moves: one when the stick
+/// is pushed out, and no more until it returns to rest.
+#[cfg(any(feature = "gamepad", test))]
+#[derive(Debug, Default)]
+struct Stick {
+    horizontal_held: bool,
+    vertical_held: bool,
+}
+
+#[cfg(any(feature = "gamepad", test))]
+impl Stick {
+    /// A new horizontal position, positive to the right.
+    fn horizontal(&mut self, value: f32) -> Option<Direction> {
+        Self::flick(
+            &mut self.horizontal_held,
+            value,
+            Direction::Right,
+            Direction::Left,
+        )
+    }
+
+    /// A new vertical position, positive upwards.
+    fn vertical(&mut self, value: f32) -> Option<Direction> {
+        Self::flick(
+            &mut self.vertical_held,
+            value,
+            Direction::Up,
+            Direction::Down,
+        )
+    }
+
+    fn flick(
+        held: &mut bool,
+        value: f32,
+        positive: Direction,
+        negative: Direction,
+    ) -> Option<Direction> {
+        if *held {
+            *held = value.abs() >= STICK_RELEASED;
+            return None;
+        }
+        if value.abs() < STICK_PUSHED {
+            return None;
+        }
+
+        *held = true;
+        Some(if value > 0.0 { positive } else { negative })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn one_flick_is_one_move() {
+        let mut stick = Stick::default();
+
+        assert_eq!(stick.horizontal(0.2), None);
+        assert_eq!(stick.horizontal(0.7), Some(Direction::Right));
+        // Wobbling while held, even back below the push threshold, does nothing
+        assert_eq!(stick.horizontal(1.0), None);
+        assert_eq!(stick.horizontal(0.5), None);
+        assert_eq!(stick.horizontal(0.9), None);
+
+        assert_eq!(stick.horizontal(0.1), None);
+        assert_eq!(stick.horizontal(-0.8), Some(Direction::Left));
+    }
+
+    #[test]
+    fn axes_are_debounced_separately() {
+        let mut stick = Stick::default();
+
+        assert_eq!(stick.vertical(0.9), Some(Direction::Up));
+        assert_eq!(stick.horizontal(-0.9), Some(Direction::Left));
+        assert_eq!(stick.vertical(0.0), None);
+        assert_eq!(stick.vertical(-0.9), Some(Direction::Down));
+    }
+}
@@ -0,0 +1,188 @@
+use crate::{MigrateError, Puzzle, Replay, storage::Versioned};
+
+use serde::{Deserialize, Serialize};
+use serde_json::{Value, json};
+
+use std::time::Duration;
+
+/// How many scrambles keep a personal-best replay; the least recently
+/// improved make way for new ones.
+pub const MAX_GHOSTS: usize = 50;
+
+/// The fastest recorded solve of every recent scramble, raced as a ghost
+/// when the scramble is retried.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Ghosts {
+    replays: Vec<Replay>, // Least recently improved first
+}
+
+/// Version 0 was the list of replays on its own.
+impl Versioned for Ghosts {
+    const VERSION: u32 = 1;
+
+    fn upgrade(document: Value, from: u32) -> Result<Value, MigrateError> {
+        match (from, document) {
+            (0, replays @ Value::Array(_)) => Ok(json!({ "replays": replays })),
+            (0, _) => Err(MigrateError::Unexpected {
+                version: 0,
+                found: String::from("object instead of a list of replays"),
+            }),
+            (_, document) => Ok(document),
+        }
+    }
+}
+
+impl Ghosts {
+    /// The personal-best replay of the scramble `start`, if there is one.
+    pub fn best(&self, start: &Puzzle) -> Option<&Replay> {
+        self.replays.iter().find(|replay| replay.is_of(start))
+    }
+
+    /// Keeps `replay` of a finished solve if it is the fastest of its
+    /// scramble so far, returning whether it was.
+    pub fn submit(&mut self, replay: Replay) -> bool {
+        let index = self
+            .replays
+            .iter()
+            .position(|best| best.is_of(replay.start()));
+
+        if let Some(index) = index {
+            if self.replays[index].duration() <= replay.duration() {
+                return false;
+            }
+            self.replays.remove(index);
+        }
+        self.replays.push(replay);
+        if self.replays.len() > MAX_GHOSTS {
+            self.replays.remove(0);
+        }
+        true
+    }
+}
+
+/// A personal best played back on a board of its own, kept in step with the
+/// clock of the attempt racing it.
+#[derive(Debug, Clone)]
+pub struct Ghost {
+    replay: Replay,
+    board: Puzzle, // After the first `step` moves of `replay`
+    step: usize,
+}
+
+impl Ghost {
+    pub fn new(replay: Replay) -> Self {
+        Self {
+            board: replay.start().clone(),
+            replay,
+            step: 0,
+        }
+    }
+
+    /// Where the personal best had got to.
+    pub fn board(&self) -> &Puzzle {
+        &self.board
+    }
+
+    /// Plays the personal best on to the moves it had made `elapsed` into
+    /// its solve.
+    pub fn advance_to(&mut self, elapsed: Duration) {
+        let moves = self.replay.moves();
+
+        while self.step < moves.len() && moves[self.step].1 <= elapsed {
+            self.replay.apply(self.step, &mut self.board);
+            self.step += 1;
+        }
+    }
+
+    /// The share of the personal best's moves played so far, from 0 to 1.
+    pub fn progress(&self) -> f32 {
+        if self.replay.is_empty() {
+            return 1.0;
+        }
+        self.step as f32 / self.replay.len() as f32
+    }
+
+    pub fn is_finished(&self) -> bool {
+        self.step == self.replay.len()
+    }
+
+    /// How far the player on `board` is ahead of the ghost, in Manhattan
+    /// distance to the goal: positive when closer to solved, negative when
+    /// further away.
+    pub fn lead(&self, board: &Puzzle) -> i64 {
+        i64::from(self.board.manhattan_distance()) - i64::from(board.manhattan_distance())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::solver;
+
+    /// A solve of `start` along its optimal solution, one move every
+    /// `interval`.
+    fn solve(start: &Puzzle, interval: Duration) -> Replay {
+        let mut board = start.clone();
+        let mut replay = Replay::new(start.clone());
+
+        for (index, direction) in solver::solve(start, None).unwrap().into_iter().enumerate() {
+            let previous_empty = board.empty_position();
+            assert!(board.move_direction(direction));
+            replay.record(previous_empty, &board, interval * (index as u32 + 1));
+        }
+        replay
+    }
+
+    fn scramble(seed: u64) -> Puzzle {
+        let mut puzzle = Puzzle::new(3, 3);
+        puzzle.shuffle_with_seed(seed);
+        puzzle
+    }
+
+    #[test]
+    fn only_faster_solves_replace_a_ghost() {
+        let start = scramble(1);
+        let mut ghosts = Ghosts::default();
+
+        assert!(ghosts.submit(solve(&start, Duration::from_secs(2))));
+        assert!(!ghosts.submit(solve(&start, Duration::from_secs(3))));
+        assert!(ghosts.submit(solve(&start, Duration::from_secs(1))));
+        assert_eq!(
+            ghosts.best(&start).unwrap().moves()[0].1,
+            Duration::from_secs(1)
+        );
+        assert!(ghosts.best(&scramble(2)).is_none());
+
+        // Filling up with other scrambles eventually pushes out the oldest
+        let mut seed = 1;
+        while ghosts.best(&start).is_some() {
+            seed += 1;
+            ghosts.submit(solve(&scramble(seed), Duration::from_secs(1)));
+        }
+        assert!(seed > MAX_GHOSTS as u64);
+        assert_eq!(ghosts.replays.len(), MAX_GHOSTS);
+    }
+
+    #[test]
+    fn the_ghost_keeps_to_the_recorded_timing() {
+        let start = scramble(3);
+        let replay = solve(&start, Duration::from_secs(1));
+        let length = replay.len();
+        let mut ghost = Ghost::new(replay);
+
+        ghost.advance_to(Duration::from_millis(500));
+        assert_eq!(ghost.progress(), 0.0);
+        assert_eq!(ghost.lead(&start), 0);
+
+        ghost.advance_to(Duration::from_secs(2));
+        assert_eq!(ghost.progress(), 2.0 / length as f32);
+        assert!(!ghost.is_finished());
+
+        ghost.advance_to(Duration::from_secs(length as u64));
+        assert!(ghost.is_finished() && ghost.board().is_solved());
+        assert_eq!(ghost.progress(), 1.0);
+        // Standing still on the scramble falls behind
+        assert_eq!(ghost.lead(&start), -i64::from(start.manhattan_distance()));
+    }
+}
@@ -0,0 +1,130 @@
+use crate::{BoardError, MIN_GRID_SIZE, puzzle::tiles_from_values};
+
+use serde::{Deserialize, Serialize};
+
+use std::fmt;
+
+/// The arrangement that counts as solved.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum Goal {
+    /// Tiles in ascending row-major order with the blank in the bottom-right
+    /// corner.
+    #[default]
+    Standard,
+    /// Tiles in boustrophedon order: even rows run left to right, odd rows
+    /// right to left, and the blank ends the last row.
+    Snake,
+    /// Tiles wound clockwise from the top-left corner, around the border and
+    /// inwards, with the blank in the last cell of the spiral.
+    ///
+    /// On odd square boards that is the centre cell. On even square boards
+    /// the spiral ends just below and left of the centre, e.g. `(2, 1)` on a
+    /// 4x4 board.
+    Spiral,
+    /// A user-defined arrangement: row-major tile numbers with `0` for the
+    /// blank, as checked by [`Goal::custom`]. Boards with a different number
+    /// of cells fall back to the standard goal.
+    Custom(Box<[u16]>),
+}
+
+impl Goal {
+    /// The built-in goals.
+    pub const ALL: [Goal; 3] = [Goal::Standard, Goal::Snake, Goal::Spiral];
+
+    /// A custom goal from row-major tile numbers, `0` being the blank. Every
+    /// value in `0..values.len()` must appear exactly once.
+    pub fn custom(values: Vec<u16>) -> Result<Goal, BoardError> {
+        let cells = values.len();
+        if cells < MIN_GRID_SIZE * MIN_GRID_SIZE {
+            return Err(BoardError::WrongCellCount {
+                expected: MIN_GRID_SIZE * MIN_GRID_SIZE,
+                found: cells,
+            });
+        }
+
+        tiles_from_values(cells, &values)?;
+        Ok(Goal::Custom(values.into_boxed_slice()))
+    }
+
+    /// The solved board in row-major order, with `0` for the blank.
+    pub fn layout(&self, rows: usize, cols: usize) -> Vec<u16> {
+        let mut values = vec![0; rows * cols];
+        for value in 0..rows * cols {
+            let (row, col) = goal_position(value as u16, (rows, cols), self);
+            values[row * cols + col] = value as u16;
+        }
+        values
+    }
+}
+
+impl fmt::Display for Goal {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Goal::Standard => "Standard goal",
+            Goal::Snake => "Snake goal",
+            Goal::Spiral => "Spiral goal",
+            Goal::Custom(_) => "Custom goal",
+        })
+    }
+}
+
+/// The `(row, col)` where `value` belongs on a solved board of `size`
+/// (rows, columns) under `goal`, `0` being the blank.
+pub fn goal_position(value: u16, size: (usize, usize), goal: &Goal) -> (usize, usize) {
+    let (rows, cols) = size;
+
+    // Place along the goal's reading order, the blank coming last
+    let order = match value {
+        0 => rows * cols - 1,
+        value => usize::from(value) - 1,
+    };
+    let (row, col) = (order / cols, order % cols);
+
+    match goal {
+        Goal::Standard => (row, col),
+        Goal::Snake if row % 2 == 1 => (row, cols - 1 - col),
+        Goal::Snake => (row, col),
+        Goal::Spiral => spiral_cell(order, rows, cols),
+        Goal::Custom(layout) => match layout.iter().position(|&cell| cell == value) {
+            Some(index) if layout.len() == rows * cols => (index / cols, index % cols),
+            _ => (row, col),
+        },
+    }
+}
+
+/// The cell `order` steps along a clockwise spiral from the top-left corner.
+fn spiral_cell(order: usize, rows: usize, cols: usize) -> (usize, usize) {
+    let (mut top, mut left) = (0, 0);
+    let (mut bottom, mut right) = (rows - 1, cols - 1);
+    let mut remaining = order;
+
+    // Peel off one ring at a time until `order` falls inside it
+    loop {
+        let (height, width) = (bottom - top + 1, right - left + 1);
+        if height == 1 {
+            return (top, left + remaining);
+        }
+        if width == 1 {
+            return (top + remaining, left);
+        }
+
+        let ring = 2 * (height + width) - 4;
+        if remaining < ring {
+            return if remaining < width {
+                (top, left + remaining)
+            } else if remaining < width + height - 1 {
+                (top + remaining - (width - 1), right)
+            } else if remaining < 2 * width + height - 2 {
+                (bottom, right - (remaining - (width + height - 2)))
+            } else {
+                (bottom - (remaining - (2 * width + height - 3)), left)
+            };
+        }
+
+        remaining -= ring;
+        top += 1;
+        left += 1;
+        bottom -= 1;
+        right -= 1;
+    }
+}
@@ -0,0 +1,381 @@
+//! Lower bounds on the moves left to solve a board, which guide the optimal
+//! solver.
+//!
+//! A [`Heuristic`] must never overestimate, or the solver stops finding
+//! shortest solutions. The stronger one is, the fewer boards the search
+//! looks at; [`PatternDatabase`](crate::pattern_database::PatternDatabase)
+//! and [`WalkingDistance`](crate::walking_distance::WalkingDistance)
+//! implement it too.
+
+use crate::{MAX_GRID_SIZE, Puzzle, goal_position, puzzle::tile_distance};
+
+/// An estimate of the moves a board needs, from below.
+pub trait Heuristic {
+    /// A lower bound on the length of a shortest solution of `board`, zero
+    /// only once it is solved.
+    fn estimate(&self, board: &Puzzle) -> u32;
+
+    /// How the estimate changes when the tile in cell `from` slides into
+    /// the empty cell of `board`, the search's compact copy of it.
+    ///
+    /// The search calls this for every move it tries, so it only looks at
+    /// the cells the move disturbs.
+    fn slide_delta(&self, board: &Board, from: usize) -> i32;
+}
+
+/// The compact copy of a board the solver plays moves on.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Board {
+    rows: usize,
+    cols: usize,
+    wrap: bool,
+    cells: Vec<u16>,       // Row-major tile values, 0 for the blank
+    positions: Vec<usize>, // Cell of each value
+    goals: Vec<usize>,     // Goal cell of each value
+}
+
+impl Board {
+    pub fn new(puzzle: &Puzzle) -> Self {
+        let (rows, cols) = (puzzle.rows(), puzzle.cols());
+        let cells: Vec<u16> = puzzle
+            .tiles()
+            .iter()
+            .map(|tile| tile.value().unwrap_or(0))
+            .collect();
+        let mut positions = vec![0; cells.len()];
+        for (cell, &value) in cells.iter().enumerate() {
+            positions[usize::from(value)] = cell;
+        }
+        let goals = (0..cells.len())
+            .map(|value| {
+                let (row, col) = goal_position(value as u16, (rows, cols), puzzle.goal());
+                row * cols + col
+            })
+            .collect();
+
+        Self {
+            rows,
+            cols,
+            wrap: puzzle.wrap(),
+            cells,
+            positions,
+            goals,
+        }
+    }
+
+    pub fn rows(&self) -> usize {
+        self.rows
+    }
+
+    pub fn cols(&self) -> usize {
+        self.cols
+    }
+
+    pub fn wrap(&self) -> bool {
+        self.wrap
+    }
+
+    /// The value of the tile in `cell`, 0 for the blank.
+    pub fn value(&self, cell: usize) -> u16 {
+        self.cells[cell]
+    }
+
+    /// The cell of every value, indexed by value, the blank's first.
+    pub fn positions(&self) -> &[usize] {
+        &self.positions
+    }
+
+    pub fn empty(&self) -> usize {
+        self.positions[0]
+    }
+
+    /// The cell `value` belongs in.
+    pub fn goal(&self, value: u16) -> usize {
+        self.goals[usize::from(value)]
+    }
+
+    /// Row plus column distance from `cell` to the goal of `value`.
+    pub fn distance(&self, value: u16, cell: usize) -> u32 {
+        tile_distance((self.rows, self.cols), self.wrap, self.goal(value), cell)
+    }
+
+    /// Slides the tile in cell `from` into the empty cell.
+    pub(crate) fn slide(&mut self, from: usize) {
+        let (value, empty) = (self.cells[from], self.empty());
+
+        self.cells.swap(from, empty);
+        self.positions[usize::from(value)] = empty;
+        self.positions[0] = from;
+    }
+}
+
+/// Counts the tiles outside their goal cell: each needs at least one move.
+/// The weakest of the heuristics, kept for comparison.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct MisplacedTiles;
+
+impl Heuristic for MisplacedTiles {
+    fn estimate(&self, board: &Puzzle) -> u32 {
+        board.misplaced_count()
+    }
+
+    fn slide_delta(&self, board: &Board, from: usize) -> i32 {
+        let goal = board.goal(board.value(from));
+        i32::from(board.empty() != goal) - i32::from(from != goal)
+    }
+}
+
+/// Adds up how far every tile is from its goal cell, each move bringing one
+/// tile a single cell closer at best.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct Manhattan;
+
+impl Heuristic for Manhattan {
+    fn estimate(&self, board: &Puzzle) -> u32 {
+        board.manhattan_distance()
+    }
+
+    fn slide_delta(&self, board: &Board, from: usize) -> i32 {
+        let value = board.value(from);
+        board.distance(value, board.empty()) as i32 - board.distance(value, from) as i32
+    }
+}
+
+/// The Manhattan distance plus two moves for every tile that has to step
+/// out of its goal row or column to let another tile past.
+///
+/// Two tiles in the line they both belong in, in the wrong order, block
+/// each other: one of them has to leave the line and come back. A line
+/// needs as many of those detours as it has tiles outside its longest run
+/// already in order. Around wrapping edges tiles can pass each other by
+/// going the other way, so there it is the Manhattan distance alone.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct LinearConflict;
+
+/// A row or a column of the board.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Line {
+    Row(usize),
+    Col(usize),
+}
+
+impl Heuristic for LinearConflict {
+    fn estimate(&self, board: &Puzzle) -> u32 {
+        let manhattan = board.manhattan_distance();
+        if board.wrap() {
+            return manhattan;
+        }
+
+        let board = Board::new(board);
+        let lines = (0..board.rows)
+            .map(Line::Row)
+            .chain((0..board.cols).map(Line::Col));
+        manhattan + 2 * lines.map(|line| detours(&board, line, None)).sum::<u32>()
+    }
+
+    fn slide_delta(&self, board: &Board, from: usize) -> i32 {
+        let manhattan = Manhattan.slide_delta(board, from);
+        if board.wrap {
+            return manhattan;
+        }
+
+        // The tile keeps to the line it slides along, passing no other
+        // tile, so only the two lines across it change
+        let to = board.empty();
+        let lines = if from / board.cols == to / board.cols {
+            [Line::Col(from % board.cols), Line::Col(to % board.cols)]
+        } else {
+            [Line::Row(from / board.cols), Line::Row(to / board.cols)]
+        };
+        let change: i32 = lines
+            .into_iter()
+            .map(|line| {
+                detours(board, line, Some((from, to))) as i32 - detours(board, line, None) as i32
+            })
+            .sum();
+        manhattan + 2 * change
+    }
+}
+
+/// How many tiles of `line` must leave it for the rest, those that belong
+/// in it, to reach their goals in order. With `slide`, the tile in its first
+/// cell is taken to have moved to its second.
+fn detours(board: &Board, line: Line, slide: Option<(usize, usize)>) -> u32 {
+    let cols = board.cols;
+    let (first, step, length) = match line {
+        Line::Row(row) => (row * cols, 1, cols),
+        Line::Col(col) => (col, cols, board.rows),
+    };
+    let in_line = |cell: usize| match line {
+        Line::Row(row) => cell / cols == row,
+        Line::Col(col) => cell % cols == col,
+    };
+
+    // Where each tile that belongs in the line has to end up, in the order
+    // they stand in it. This runs for every move searched, so it keeps off
+    // the heap.
+    let mut order = [0; MAX_GRID_SIZE];
+    let mut count = 0;
+    for cell in (0..length).map(|index| first + index * step) {
+        let value = match slide {
+            Some((from, to)) if cell == to => board.value(from),
+            Some((from, _)) if cell == from => 0,
+            _ => board.value(cell),
+        };
+        if value != 0 && in_line(board.goal(value)) {
+            order[count] = board.goal(value);
+            count += 1;
+        }
+    }
+
+    (count - longest_increasing_run(&order[..count])) as u32
+}
+
+/// The length of the longest subsequence of `values`, at most
+/// [`MAX_GRID_SIZE`] long, that increases.
+fn longest_increasing_run(values: &[usize]) -> usize {
+    // The smallest last value of an increasing run of each length so far
+    let mut tails = [0; MAX_GRID_SIZE];
+    let mut length = 0;
+    for &value in values {
+        let place = tails[..length].partition_point(|&tail| tail < value);
+        tails[place] = value;
+        length = length.max(place + 1);
+    }
+    length
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        Direction, Goal, pattern_database::PatternDatabase, walking_distance::WalkingDistance,
+    };
+
+    use std::collections::{HashMap, VecDeque};
+
+    /// The length of a shortest solution of every solvable 3x3 board, keyed
+    /// by its row-major values, worked out breadth first from the goal.
+    fn solution_lengths() -> HashMap<Vec<u16>, u32> {
+        let goal = Board::new(&Puzzle::new(3, 3));
+        let mut lengths = HashMap::from([(goal.cells.clone(), 0)]);
+        let mut queue = VecDeque::from([goal]);
+
+        while let Some(board) = queue.pop_front() {
+            let length = lengths[&board.cells];
+            let (row, col) = (board.empty() / 3, board.empty() % 3);
+            let neighbours = [
+                (row > 0).then(|| board.empty() - 3),
+                (row < 2).then(|| board.empty() + 3),
+                (col > 0).then(|| board.empty() - 1),
+                (col < 2).then(|| board.empty() + 1),
+            ];
+
+            for from in neighbours.into_iter().flatten() {
+                let mut next = board.clone();
+                next.slide(from);
+                if !lengths.contains_key(&next.cells) {
+                    lengths.insert(next.cells.clone(), length + 1);
+                    queue.push_back(next);
+                }
+            }
+        }
+        lengths
+    }
+
+    fn puzzle(cells: &[u16]) -> Puzzle {
+        let text: Vec<String> = cells.iter().map(u16::to_string).collect();
+        Puzzle::parse_permutation(&text.join(" ")).unwrap()
+    }
+
+    #[test]
+    fn no_heuristic_overestimates_any_3x3_board() {
+        let lengths = solution_lengths();
+        assert_eq!(lengths.len(), 181_440);
+        let database = PatternDatabase::generate(3, 3, &[&[1, 2, 3, 4], &[5, 6, 7, 8]], |_| {});
+        let walking_distance = WalkingDistance::new(3, 3, 8);
+        let heuristics: [(&str, &dyn Heuristic); 5] = [
+            ("misplaced", &MisplacedTiles),
+            ("manhattan", &Manhattan),
+            ("linear", &LinearConflict),
+            ("pattern database", &database),
+            ("walking distance", &walking_distance),
+        ];
+
+        let mut tighter = 0;
+        for (cells, &length) in &lengths {
+            let board = puzzle(cells);
+            let estimates = heuristics.map(|(name, heuristic)| {
+                let estimate = heuristic.estimate(&board);
+                assert!(estimate <= length, "{name} on {cells:?}");
+                estimate
+            });
+            tighter += u32::from(estimates[2] > estimates[1]);
+            assert!(
+                estimates[4] >= estimates[1],
+                "walking distance on {cells:?}"
+            );
+        }
+        // Conflicts are common enough to make a difference
+        assert!(tighter > 10_000, "{tighter}");
+    }
+
+    #[test]
+    fn opposed_tiles_in_their_row_cost_two_more_moves() {
+        let board = puzzle(&[2, 1, 3, 4, 5, 6, 7, 8, 0]);
+        assert_eq!(Manhattan.estimate(&board), 2);
+        assert_eq!(LinearConflict.estimate(&board), 4);
+
+        // Three tiles in reverse only need two of them out of the way
+        let board = puzzle(&[3, 2, 1, 4, 5, 6, 7, 8, 0]);
+        assert_eq!(
+            LinearConflict.estimate(&board),
+            Manhattan.estimate(&board) + 4
+        );
+        assert_eq!(longest_increasing_run(&[2, 0, 1, 3]), 3);
+    }
+
+    #[test]
+    fn slide_deltas_match_estimating_afresh() {
+        for goal in Goal::ALL {
+            for wrap in [false, true] {
+                let mut puzzle = Puzzle::with_seed(4, 4, 7);
+                puzzle.set_goal(goal.clone());
+                puzzle.set_wrap(wrap);
+                puzzle.shuffle();
+
+                let walking_distance = WalkingDistance::for_puzzle(&puzzle);
+                let mut heuristics: Vec<&dyn Heuristic> =
+                    vec![&MisplacedTiles, &Manhattan, &LinearConflict];
+                if let Some(walking_distance) = &walking_distance {
+                    heuristics.push(&**walking_distance);
+                }
+
+                for step in 0..40 {
+                    let direction = Direction::ALL[step * 7 % 4];
+                    let Some((row, col)) = puzzle.tile_for_direction(direction) else {
+                        continue;
+                    };
+                    let board = Board::new(&puzzle);
+                    let before: Vec<u32> = heuristics
+                        .iter()
+                        .map(|heuristic| heuristic.estimate(&puzzle))
+                        .collect();
+                    let deltas: Vec<i32> = heuristics
+                        .iter()
+                        .map(|heuristic| heuristic.slide_delta(&board, row * 4 + col))
+                        .collect();
+
+                    assert!(puzzle.move_tile(row, col));
+                    for (index, heuristic) in heuristics.iter().enumerate() {
+                        assert_eq!(
+                            before[index].wrapping_add_signed(deltas[index]),
+                            heuristic.estimate(&puzzle),
+                            "heuristic {index}, {goal}, wrap {wrap}, step {step}"
+                        );
+                    }
+                }
+            }
+        }
+    }
+}
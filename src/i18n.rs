@@ -0,0 +1,1217 @@
+//! Interface text in every supported language.
+//!
+//! Each piece of text has a [`Text`] key. English covers every key, which
+//! the compiler checks; other languages may leave keys out, and those show
+//! in English until someone translates them.
+
+use iced::time::Duration;
+use iced_15_puzzle::{
+    BoardRenderer, ColorScheme, Difficulty, Easing, Goal, KeyScheme, LabelMode, Language, Lesson,
+    Precision, ThemeMode, TileFont, format_duration,
+};
+
+use std::fmt;
+
+/// A piece of interface text, named after what it says in English.
+///
+/// Texts taking values have `{0}`, `{1}`, … where those go, so a
+/// translation can put them in whatever order its grammar needs.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Text {
+    // The game screen
+    AppName,
+    WindowTitle,
+    WindowTitleSolved,
+    WindowTitleSolving,
+    TimeLeft,
+    Time,
+    MovesLeft,
+    WorkingOutBudget,
+    YourSolveAndOptimal,
+    YourSolveAndOptimalBound,
+    WorkingOutOptimal,
+    EfficiencyStars,
+    EfficiencyAtLeast,
+    Moves,
+    MovesWithAutoMoves,
+    Best,
+    PuzzleSolved,
+    CopySolution,
+    WatchReplay,
+    MemoriseBoard,
+    Unsolvable,
+    BuildingSolverTables,
+    DistanceAndMisplaced,
+    NewRecord,
+    Solved,
+    MovesInTime,
+    BlindfoldSolve,
+    BlindfoldSolveOnePeek,
+    BlindfoldSolvePeeks,
+    ShuffleAgain,
+    Retry,
+    Close,
+    TimesUp,
+    OutOfMoves,
+    DistanceRemaining,
+    AbandonSolve,
+    Yes,
+    No,
+    DailyPuzzleFor,
+    DifficultyAndSeed,
+    Seed,
+    ScrambleFromSeed,
+    BoardExample,
+    ImportBoard,
+    CopyPosition,
+    ScrambleCode,
+    LoadCode,
+    CopyScrambleCode,
+    InvalidCode,
+    Copied,
+    PastePosition,
+    InvalidSeed,
+    PresetBest,
+    DailyDone,
+    Daily,
+    Relay,
+    RelayStage,
+    GhostAhead,
+    GhostBehind,
+    GhostLevel,
+    RaceYourBest,
+    RelayNextStage,
+    RaceTheBot,
+    Bot,
+    BotThinking,
+    BotWon,
+    YouBeatTheBot,
+    RaceAgain,
+    KeepPlaying,
+    BotGaveUp,
+    DailyStreak,
+    AbandonForPreset,
+    Columns,
+    Rows,
+    Paused,
+    ClickToResume,
+    SkipCountdown,
+    Undo,
+    Redo,
+    Resume,
+    Pause,
+    Shuffle,
+    Reset,
+    RetryScramble,
+    Hint,
+    Stop,
+    SolveIt,
+    Peek,
+    EditGoal,
+    Save,
+    Load,
+    ExportImage,
+    Stats,
+    Fullscreen,
+    ExitFullscreen,
+    ResumeOnLaunch,
+    AskBeforeAbandoning,
+    PauseWhenAway,
+    TimeAttack,
+    MoveLimit,
+    Blindfold,
+    Picture,
+    NumbersOnPicture,
+    ColourPlacedTiles,
+    WrapAroundEdges,
+    ShowDistance,
+    ProgressByDistance,
+    DirectionKeys,
+    KeyRepeatDelay,
+    KeyRepeatInterval,
+    ArrowMovesTile,
+    ArrowMovesBlank,
+    NumpadRelative,
+    ShowGoal,
+    HideGoal,
+    CountLineSlidesAsOne,
+    Sound,
+    TimeLimit,
+    Preview,
+    Minutes,
+    Seconds,
+    Preset8,
+    Preset15,
+    Preset24,
+    // Notices below the controls
+    BoardImported,
+    BoardImportedUnsolvable,
+    ImportFailed,
+    ClipboardEmpty,
+    NoSolutionInTime,
+    SearchingForSolution,
+    SolutionMovesLeft,
+    SolutionCopied,
+    GameSaved,
+    SaveFailed,
+    GameLoaded,
+    LoadFailed,
+    // The saves screen
+    Saves,
+    SaveHere,
+    Rename,
+    SlotName,
+    EmptySlot,
+    UnreadableSlot,
+    SlotDetails,
+    Casual,
+    DeleteSlot,
+    SlotSaved,
+    SlotLoaded,
+    SlotDeleted,
+    // Profiles
+    Profiles,
+    PlayingAs,
+    SwitchProfile,
+    NewProfileName,
+    CreateProfile,
+    PlayAsProfile,
+    Playing,
+    TypeToDeleteProfile,
+    ProfileNameEmpty,
+    ProfileNameTooLong,
+    ProfileNameTaken,
+    WindowTitleProfile,
+    ImageExported,
+    ExportFailed,
+    ExportCsv,
+    CsvExported,
+    InvalidGoal,
+    // The menus
+    Menu,
+    ContinueGame,
+    QuitToMenu,
+    // The tutorial
+    Tutorial,
+    TryTheTutorial,
+    TutorialStep,
+    LessonSlideIntoGap,
+    LessonFinishTheBoard,
+    LessonTopRow,
+    LessonLastTwoRows,
+    TutorialStray,
+    TutorialStepDone,
+    TutorialFinished,
+    NextStep,
+    LeaveTutorial,
+    // The help overlay
+    KeyboardShortcuts,
+    Controller,
+    SlideTile,
+    MoveBlank,
+    SlideTileBesideBlank,
+    ResetToSolved,
+    PauseOrResume,
+    ToggleFullscreen,
+    CloseOverlays,
+    FocusTiles,
+    PressFocusedTile,
+    ShowOrHideHelp,
+    // The statistics screen
+    Statistics,
+    ShuffledAndSolved,
+    TotalMovesAndTime,
+    AverageMovesAndTime,
+    Streaks,
+    BlindfoldStats,
+    MoveLimitStats,
+    AverageEfficiency,
+    RelaysNotFinished,
+    RacesAgainstTheBot,
+    RaceStats,
+    TimeAttackStats,
+    NoGamesYet,
+    ResetAllStatistics,
+    Cancel,
+    ResetStatistics,
+    Back,
+    // The leaderboard
+    Leaderboard,
+    MadeTheLeaderboard,
+    YourName,
+    AddToLeaderboard,
+    NoLeaderboardEntries,
+    Delete,
+    // Achievements
+    Achievements,
+    SessionLog,
+    CopyLog,
+    LogEmpty,
+    AchievementUnlocked,
+    UnlockedOn,
+    Locked,
+    FirstSolve,
+    FirstSolveDescription,
+    NoUndo,
+    NoUndoDescription,
+    NoHints,
+    NoHintsDescription,
+    Sub100Moves,
+    Sub100MovesDescription,
+    SubMinute,
+    SubMinuteDescription,
+    Solve5x5,
+    Solve5x5Description,
+    BeatTheClock,
+    BeatTheClockDescription,
+    BlindSolve,
+    BlindSolveDescription,
+    HundredSolves,
+    HundredSolvesDescription,
+    WeekOfDailies,
+    WeekOfDailiesDescription,
+    // The settings screen
+    Settings,
+    Appearance,
+    Gameplay,
+    NextScrambleChallenges,
+    SlideAnimation,
+    SlideAnimationOff,
+    ReduceMotion,
+    EasingLinear,
+    EasingEaseOut,
+    EasingEaseInOut,
+    EasingOvershoot,
+    BoardDrawnWith,
+    RendererWidgets,
+    RendererCanvas,
+    TileGap,
+    CornerRadius,
+    TileNumbersIn,
+    FontStandard,
+    FontInter,
+    FontFiraMono,
+    // The goal editor
+    CustomGoal,
+    GoalEditorHelp,
+    UnassignedCells,
+    Blank,
+    Clear,
+    SaveGoal,
+    // The replay screen
+    Replay,
+    ReplayProgress,
+    Restart,
+    Play,
+    Step,
+    RealTimeSpeed,
+    // Choices in the pickers
+    Easy,
+    Medium,
+    Hard,
+    Expert,
+    SystemTheme,
+    LightTheme,
+    DarkTheme,
+    StandardGoal,
+    SnakeGoal,
+    SpiralGoal,
+    StandardColors,
+    HighContrast,
+    DeuteranopiaSafe,
+    NumberLabels,
+    LetterLabels,
+    HexadecimalLabels,
+    ZeroBasedLabels,
+    LettersFallBack,
+    // The status line read out for the board
+    TileDescription,
+    MovableTileDescription,
+    SolvedAnnouncement,
+}
+
+/// `text` in `language`, or in English if it has no translation.
+pub fn tr(language: Language, text: Text) -> &'static str {
+    let translated = match language {
+        Language::English => None,
+        Language::German => german(text),
+    };
+
+    translated.unwrap_or_else(|| english(text))
+}
+
+/// `text` in `language` with `{0}`, `{1}`, … replaced by `args` in turn.
+pub fn trf(language: Language, text: Text, args: &[&dyn fmt::Display]) -> String {
+    let template = tr(language, text);
+    let mut filled = String::with_capacity(template.len());
+    let mut rest = template;
+
+    while let Some(open) = rest.find('{') {
+        filled.push_str(&rest[..open]);
+        rest = &rest[open..];
+
+        let placeholder = rest
+            .find('}')
+            .and_then(|close| Some((close, rest[1..close].parse::<usize>().ok()?)));
+        match placeholder.and_then(|(close, index)| Some((close, args.get(index)?))) {
+            Some((close, arg)) => {
+                filled.push_str(&arg.to_string());
+                rest = &rest[close + 1..];
+            }
+            // Not a placeholder, or one without a value; keep it as written
+            None => {
+                filled.push('{');
+                rest = &rest[1..];
+            }
+        }
+    }
+
+    filled.push_str(rest);
+    filled
+}
+
+/// Formats a duration as [`format_duration`] does, with the decimal
+/// separator `language` writes.
+pub fn format_time(language: Language, duration: Duration, precision: Precision) -> String {
+    format_duration(duration, precision).replace('.', decimal_separator(language))
+}
+
+/// Formats `value` with one decimal place, e.g. "12.5".
+pub fn format_decimal(language: Language, value: f64) -> String {
+    format!("{value:.1}").replace('.', decimal_separator(language))
+}
+
+fn decimal_separator(language: Language) -> &'static str {
+    match language {
+        Language::English => ".",
+        Language::German => ",",
+    }
+}
+
+/// A picker option showing a translated name for its value.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Choice<T> {
+    pub value: T,
+    label: &'static str,
+}
+
+impl<T> Choice<T>
+where
+    for<'a> &'a T: Into<Text>,
+{
+    pub fn new(language: Language, value: T) -> Self {
+        let label = tr(language, (&value).into());
+        Self { value, label }
+    }
+}
+
+impl<T> fmt::Display for Choice<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.label)
+    }
+}
+
+impl From<&Difficulty> for Text {
+    fn from(difficulty: &Difficulty) -> Self {
+        match difficulty {
+            Difficulty::Easy => Text::Easy,
+            Difficulty::Medium => Text::Medium,
+            Difficulty::Hard => Text::Hard,
+            Difficulty::Expert => Text::Expert,
+        }
+    }
+}
+
+impl From<&ThemeMode> for Text {
+    fn from(theme: &ThemeMode) -> Self {
+        match theme {
+            ThemeMode::System => Text::SystemTheme,
+            ThemeMode::Light => Text::LightTheme,
+            ThemeMode::Dark => Text::DarkTheme,
+        }
+    }
+}
+
+impl From<&Easing> for Text {
+    fn from(easing: &Easing) -> Self {
+        match easing {
+            Easing::Linear => Text::EasingLinear,
+            Easing::EaseOut => Text::EasingEaseOut,
+            Easing::EaseInOut => Text::EasingEaseInOut,
+            Easing::Overshoot => Text::EasingOvershoot,
+        }
+    }
+}
+
+impl From<&BoardRenderer> for Text {
+    fn from(renderer: &BoardRenderer) -> Self {
+        match renderer {
+            BoardRenderer::Widgets => Text::RendererWidgets,
+            BoardRenderer::Canvas => Text::RendererCanvas,
+        }
+    }
+}
+
+impl From<&Lesson> for Text {
+    fn from(lesson: &Lesson) -> Self {
+        match lesson {
+            Lesson::SlideIntoGap => Text::LessonSlideIntoGap,
+            Lesson::FinishTheBoard => Text::LessonFinishTheBoard,
+            Lesson::TopRow => Text::LessonTopRow,
+            Lesson::LastTwoRows => Text::LessonLastTwoRows,
+        }
+    }
+}
+
+impl From<&TileFont> for Text {
+    fn from(font: &TileFont) -> Self {
+        match font {
+            TileFont::Standard => Text::FontStandard,
+            TileFont::Inter => Text::FontInter,
+            TileFont::FiraMono => Text::FontFiraMono,
+        }
+    }
+}
+
+impl From<&ColorScheme> for Text {
+    fn from(scheme: &ColorScheme) -> Self {
+        match scheme {
+            ColorScheme::Standard => Text::StandardColors,
+            ColorScheme::HighContrast => Text::HighContrast,
+            ColorScheme::Deuteranopia => Text::DeuteranopiaSafe,
+        }
+    }
+}
+
+impl From<&LabelMode> for Text {
+    fn from(mode: &LabelMode) -> Self {
+        match mode {
+            LabelMode::Numbers => Text::NumberLabels,
+            LabelMode::Letters => Text::LetterLabels,
+            LabelMode::Hexadecimal => Text::HexadecimalLabels,
+            LabelMode::ZeroBased => Text::ZeroBasedLabels,
+        }
+    }
+}
+
+impl From<&KeyScheme> for Text {
+    fn from(scheme: &KeyScheme) -> Self {
+        match scheme {
+            KeyScheme::TileMoves => Text::ArrowMovesTile,
+            KeyScheme::BlankMoves => Text::ArrowMovesBlank,
+            KeyScheme::NumpadRelative => Text::NumpadRelative,
+        }
+    }
+}
+
+impl From<&Goal> for Text {
+    fn from(goal: &Goal) -> Self {
+        match goal {
+            Goal::Standard => Text::StandardGoal,
+            Goal::Snake => Text::SnakeGoal,
+            Goal::Spiral => Text::SpiralGoal,
+            Goal::Custom(_) => Text::CustomGoal,
+        }
+    }
+}
+
+fn english(text: Text) -> &'static str {
+    match text {
+        Text::AppName => "15 Puzzle",
+        Text::WindowTitle => "{0} Puzzle",
+        Text::WindowTitleSolved => "{0} — Solved in {1} moves!",
+        Text::WindowTitleSolving => "{0} — {1} moves — {2}",
+        Text::TimeLeft => "Time left: {0}",
+        Text::Time => "Time: {0}",
+        Text::MovesLeft => "Moves left: {0}",
+        Text::WorkingOutBudget => "Working out the move budget…",
+        Text::YourSolveAndOptimal => "Your solve: {0} moves · Optimal: {1}",
+        Text::YourSolveAndOptimalBound => "Your solve: {0} moves · Optimal ≥ {1}",
+        Text::WorkingOutOptimal => "Working out the optimal solution…",
+        Text::EfficiencyStars => "Efficiency: {0}% {1}",
+        Text::EfficiencyAtLeast => "Efficiency: ≥ {0}%",
+        Text::Moves => "Moves: {0}",
+        Text::MovesWithAutoMoves => "Moves: {0} · Auto moves: {1}",
+        Text::Best => "Best: {0} / {1} moves",
+        Text::PuzzleSolved => "Puzzle Solved! 🎉",
+        Text::CopySolution => "Copy solution",
+        Text::WatchReplay => "Watch replay",
+        Text::MemoriseBoard => "Memorise the board: {0} s left",
+        Text::Unsolvable => "Unsolvable",
+        Text::BuildingSolverTables => "Building solver tables… {0}%",
+        Text::DistanceAndMisplaced => "Distance: {0} · Misplaced: {1}",
+        Text::NewRecord => "New record! 🏆",
+        Text::Solved => "Solved! 🎉",
+        Text::MovesInTime => "{0} moves in {1}",
+        Text::BlindfoldSolve => "Blindfold solve 🙈",
+        Text::BlindfoldSolveOnePeek => "Blindfold solve 🙈 · 1 peek",
+        Text::BlindfoldSolvePeeks => "Blindfold solve 🙈 · {0} peeks",
+        Text::ShuffleAgain => "Shuffle again",
+        Text::Retry => "Retry",
+        Text::Close => "Close",
+        Text::TimesUp => "Time's up",
+        Text::OutOfMoves => "Out of moves",
+        Text::DistanceRemaining => "Distance remaining: {0}",
+        Text::AbandonSolve => "Abandon current solve?",
+        Text::Yes => "Yes",
+        Text::No => "No",
+        Text::DailyPuzzleFor => "Daily puzzle for {0} · code {1}",
+        Text::DifficultyAndSeed => "Difficulty: {0} · Seed: {1}",
+        Text::Seed => "Seed",
+        Text::ScrambleFromSeed => "Scramble from seed",
+        Text::BoardExample => "Board, e.g. 1 2 3 / 4 5 6 / 7 8 0",
+        Text::ImportBoard => "Import board",
+        Text::CopyPosition => "Copy position",
+        Text::ScrambleCode => "Scramble code",
+        Text::LoadCode => "Load code",
+        Text::CopyScrambleCode => "Copy scramble code",
+        Text::InvalidCode => "Invalid code: {0}",
+        Text::Copied => "Copied!",
+        Text::PastePosition => "Paste position",
+        Text::InvalidSeed => "Seed must be a whole number between 0 and 18446744073709551615",
+        Text::PresetBest => "{0} · PB {1}",
+        Text::DailyDone => "Daily done · {0} / {1} moves",
+        Text::Daily => "Daily",
+        Text::Relay => "Relay",
+        Text::GhostAhead => "Ahead of your best",
+        Text::GhostBehind => "Behind your best",
+        Text::GhostLevel => "Level with your best",
+        Text::RaceYourBest => "Race a ghost of your best when retrying",
+        Text::RelayStage => "Relay stage {0}/{1}",
+        Text::RelayNextStage => "Stage {0}/{1}",
+        Text::RaceTheBot => "Race the bot",
+        Text::Bot => "Bot",
+        Text::BotThinking => "Working out a solution…",
+        Text::BotWon => "The bot got there first",
+        Text::YouBeatTheBot => "You beat the bot!",
+        Text::RaceAgain => "Race again",
+        Text::KeepPlaying => "Keep playing",
+        Text::BotGaveUp => "The bot found no solution in time, so the race is off",
+        Text::DailyStreak => "Daily streak: {0}",
+        Text::AbandonForPreset => "Abandon this solve for the {0}?",
+        Text::Columns => "Columns",
+        Text::Rows => "Rows",
+        Text::Paused => "Paused",
+        Text::ClickToResume => "Paused while you were away · Click to resume",
+        Text::SkipCountdown => "Press any key to start now",
+        Text::Undo => "Undo",
+        Text::Redo => "Redo",
+        Text::Resume => "Resume",
+        Text::Pause => "Pause",
+        Text::Shuffle => "Shuffle",
+        Text::Reset => "Reset",
+        Text::RetryScramble => "Retry scramble",
+        Text::Hint => "Hint",
+        Text::Stop => "Stop",
+        Text::SolveIt => "Solve it",
+        Text::Peek => "Peek",
+        Text::EditGoal => "Edit goal",
+        Text::Save => "Save",
+        Text::Load => "Load",
+        Text::ExportImage => "Export image",
+        Text::Stats => "Stats",
+        Text::Fullscreen => "Fullscreen",
+        Text::ExitFullscreen => "Exit fullscreen",
+        Text::ResumeOnLaunch => "Resume last game on launch",
+        Text::AskBeforeAbandoning => "Ask before abandoning a solve",
+        Text::PauseWhenAway => "Pause when the window loses focus",
+        Text::TimeAttack => "Time attack",
+        Text::MoveLimit => "Move limit",
+        Text::Blindfold => "Blindfold",
+        Text::Picture => "Picture",
+        Text::NumbersOnPicture => "Numbers on picture",
+        Text::ColourPlacedTiles => "Colour placed tiles",
+        Text::WrapAroundEdges => "Wrap around edges",
+        Text::ShowDistance => "Show distance",
+        Text::ProgressByDistance => "Measure progress by distance",
+        Text::DirectionKeys => "Direction keys:",
+        Text::KeyRepeatDelay => "Held key repeats after: {0} ms",
+        Text::KeyRepeatInterval => "Then moves every: {0} ms",
+        Text::ArrowMovesTile => "Arrow = tile moves",
+        Text::ArrowMovesBlank => "Arrow = blank moves",
+        Text::NumpadRelative => "Numpad relative",
+        Text::ShowGoal => "Show goal",
+        Text::HideGoal => "Hide goal",
+        Text::CountLineSlidesAsOne => "Count line slides as one move",
+        Text::Sound => "Sound",
+        Text::TimeLimit => "Time limit: ",
+        Text::Preview => "Preview: ",
+        Text::Minutes => "{0} min",
+        Text::Seconds => "{0} s",
+        Text::Preset8 => "8-puzzle",
+        Text::Preset15 => "15-puzzle",
+        Text::Preset24 => "24-puzzle",
+        Text::BoardImported => "Board imported",
+        Text::BoardImportedUnsolvable => "Board imported, but it cannot be solved",
+        Text::ImportFailed => "Import failed: {0}",
+        Text::ClipboardEmpty => "The clipboard holds no board to paste",
+        Text::NoSolutionInTime => "No solution found in time",
+        Text::SearchingForSolution => "Searching: {0} moves deep, {1} boards, {2} s",
+        Text::SolutionMovesLeft => "Optimal solution: {0} moves to go",
+        Text::SolutionCopied => "Solution copied",
+        Text::GameSaved => "Game saved",
+        Text::SaveFailed => "Save failed: {0}",
+        Text::GameLoaded => "Game loaded",
+        Text::LoadFailed => "Load failed: {0}",
+        Text::Saves => "Saves",
+        Text::SaveHere => "Save here",
+        Text::Rename => "Rename",
+        Text::SlotName => "Slot {0}",
+        Text::EmptySlot => "Empty slot",
+        Text::UnreadableSlot => "Unreadable save: {0}",
+        Text::SlotDetails => "{0} · {1} · {2} moves · {3}",
+        Text::Casual => "Casual",
+        Text::DeleteSlot => "Delete “{0}”?",
+        Text::SlotSaved => "Saved to “{0}”",
+        Text::SlotLoaded => "Loaded “{0}”",
+        Text::SlotDeleted => "Save deleted",
+        Text::Profiles => "Profiles",
+        Text::PlayingAs => "Playing as {0}",
+        Text::SwitchProfile => "Switch profile",
+        Text::NewProfileName => "Name of a new profile",
+        Text::CreateProfile => "Create",
+        Text::PlayAsProfile => "Play",
+        Text::Playing => "Playing",
+        Text::TypeToDeleteProfile => {
+            "Type “{0}” to delete the profile with its statistics, achievements and saves"
+        }
+        Text::ProfileNameEmpty => "Enter a name",
+        Text::ProfileNameTooLong => "Names have at most {0} characters",
+        Text::ProfileNameTaken => "There is already a profile of that name",
+        Text::WindowTitleProfile => "{0} · {1}",
+        Text::ImageExported => "Image saved to {0}",
+        Text::ExportFailed => "Export failed: {0}",
+        Text::ExportCsv => "Export CSV",
+        Text::CsvExported => "Solves saved to {0}",
+        Text::InvalidGoal => "Invalid goal: {0}",
+        Text::Menu => "Menu",
+        Text::ContinueGame => "Continue last game",
+        Text::QuitToMenu => "Quit to menu",
+        Text::Tutorial => "Tutorial",
+        Text::TryTheTutorial => "New to sliding puzzles? The tutorial shows you how.",
+        Text::TutorialStep => "Tutorial: step {0} of {1}",
+        Text::LessonSlideIntoGap => {
+            "Tiles next to the gap can slide into it. Press the tile the arrow points at."
+        }
+        Text::LessonFinishTheBoard => {
+            "One more slide puts the last tile back, and the board is solved."
+        }
+        Text::LessonTopRow => {
+            "On a scrambled board, put the top row in place first: 1, 2 and 3. Follow the arrow."
+        }
+        Text::LessonLastTwoRows => {
+            "Now leave the top row be, and circle the last two rows round until every tile drops into place."
+        }
+        Text::TutorialStray => "Not that one. It slides back so you can try again.",
+        Text::TutorialStepDone => "Well done! A new board is up next.",
+        Text::TutorialFinished => {
+            "That's all there is to it: every board is solved row by row, the last two together."
+        }
+        Text::NextStep => "Next",
+        Text::LeaveTutorial => "Leave tutorial",
+        Text::KeyboardShortcuts => "Keyboard shortcuts",
+        Text::Controller => "Controller",
+        Text::SlideTile => "Slide a tile",
+        Text::MoveBlank => "Move the blank",
+        Text::SlideTileBesideBlank => "Slide the tile on that side of the blank",
+        Text::ResetToSolved => "Reset to solved",
+        Text::PauseOrResume => "Pause or resume",
+        Text::ToggleFullscreen => "Toggle fullscreen",
+        Text::CloseOverlays => "Close overlays, or open the menu",
+        Text::FocusTiles => "Focus the next or previous tile",
+        Text::PressFocusedTile => "Slide the focused tile",
+        Text::ShowOrHideHelp => "Show or hide this help",
+        Text::Statistics => "Statistics",
+        Text::ShuffledAndSolved => "Shuffled: {0} · Solved: {1}",
+        Text::TotalMovesAndTime => "Total moves: {0} · Total time: {1}",
+        Text::AverageMovesAndTime => "Average moves: {0} · Average time: {1}",
+        Text::Streaks => "Streak: {0} · Longest streak: {1}",
+        Text::BlindfoldStats => "Blindfold solves: {0} · Peeks: {1}",
+        Text::MoveLimitStats => "Move limits won: {0} · Lost: {1} · Best: {2} moves, {3}",
+        Text::AverageEfficiency => "Average efficiency: {0}%",
+        Text::RelaysNotFinished => "Relays not finished: {0}",
+        Text::RacesAgainstTheBot => "Races against the bot",
+        Text::RaceStats => "{0}: won {1} · lost {2}",
+        Text::TimeAttackStats => "Time attacks failed: {0} · Most time left: {1}",
+        Text::NoGamesYet => "No games played yet",
+        Text::ResetAllStatistics => "Reset all statistics?",
+        Text::Cancel => "Cancel",
+        Text::ResetStatistics => "Reset statistics",
+        Text::Back => "Back",
+        Text::Leaderboard => "Leaderboard",
+        Text::MadeTheLeaderboard => "You made the leaderboard!",
+        Text::YourName => "Your name",
+        Text::AddToLeaderboard => "Add",
+        Text::NoLeaderboardEntries => "No leaderboard entries yet",
+        Text::Delete => "Delete",
+        Text::Achievements => "Achievements",
+        Text::SessionLog => "Session log",
+        Text::CopyLog => "Copy log",
+        Text::LogEmpty => "Nothing has happened yet",
+        Text::AchievementUnlocked => "Achievement unlocked: {0}",
+        Text::UnlockedOn => "Unlocked {0}",
+        Text::Locked => "Locked",
+        Text::FirstSolve => "First solve",
+        Text::FirstSolveDescription => "Solve a scramble of any size",
+        Text::NoUndo => "No regrets",
+        Text::NoUndoDescription => "Solve a scramble without undoing a move",
+        Text::NoHints => "On your own",
+        Text::NoHintsDescription => "Solve a 4x4 or larger without a hint",
+        Text::Sub100Moves => "Economical",
+        Text::Sub100MovesDescription => "Solve a 4x4 in fewer than 100 moves",
+        Text::SubMinute => "Quick fingers",
+        Text::SubMinuteDescription => "Solve a 4x4 or larger in under a minute",
+        Text::Solve5x5 => "Going bigger",
+        Text::Solve5x5Description => "Solve a 5x5 or larger",
+        Text::BeatTheClock => "Beat the clock",
+        Text::BeatTheClockDescription => "Finish a time attack before the time runs out",
+        Text::BlindSolve => "Sight unseen",
+        Text::BlindSolveDescription => "Solve a blindfolded scramble without peeking",
+        Text::HundredSolves => "Centurion",
+        Text::HundredSolvesDescription => "Solve 100 scrambles",
+        Text::WeekOfDailies => "Week of dailies",
+        Text::WeekOfDailiesDescription => "Solve the daily puzzle seven days in a row",
+        Text::Settings => "Settings",
+        Text::Appearance => "Appearance",
+        Text::Gameplay => "Gameplay",
+        Text::NextScrambleChallenges => "Challenges for the next scramble",
+        Text::SlideAnimation => "Slide animation: {0} ms",
+        Text::SlideAnimationOff => "Slide animation: off",
+        Text::ReduceMotion => "Reduce motion",
+        Text::EasingLinear => "Steady",
+        Text::EasingEaseOut => "Ease out",
+        Text::EasingEaseInOut => "Ease in and out",
+        Text::EasingOvershoot => "Springy",
+        Text::BoardDrawnWith => "Board drawn with:",
+        Text::RendererWidgets => "Widgets",
+        Text::RendererCanvas => "Canvas",
+        Text::TileGap => "Tile gap: {0} px",
+        Text::CornerRadius => "Corner radius: {0} px",
+        Text::TileNumbersIn => "Tile numbers in:",
+        Text::FontStandard => "Standard",
+        Text::FontInter => "Inter",
+        Text::FontFiraMono => "Fira Mono",
+        Text::CustomGoal => "Custom goal",
+        Text::GoalEditorHelp => {
+            "Click cells in the order the tiles should go; click again to clear"
+        }
+        Text::UnassignedCells => "{0} cells still need a tile; click them to assign one",
+        Text::Blank => "Blank",
+        Text::Clear => "Clear",
+        Text::SaveGoal => "Save goal",
+        Text::Replay => "Replay",
+        Text::ReplayProgress => "Move {0} of {1} · Time: {2}",
+        Text::Restart => "Restart",
+        Text::Play => "Play",
+        Text::Step => "Step",
+        Text::RealTimeSpeed => "Real-time speed",
+        Text::Easy => "Easy",
+        Text::Medium => "Medium",
+        Text::Hard => "Hard",
+        Text::Expert => "Expert",
+        Text::SystemTheme => "System theme",
+        Text::LightTheme => "Light",
+        Text::DarkTheme => "Dark",
+        Text::StandardGoal => "Standard goal",
+        Text::SnakeGoal => "Snake goal",
+        Text::SpiralGoal => "Spiral goal",
+        Text::StandardColors => "Standard colours",
+        Text::HighContrast => "High contrast",
+        Text::DeuteranopiaSafe => "Deuteranopia-safe",
+        Text::NumberLabels => "Numbers 1-15",
+        Text::LetterLabels => "Letters A-O",
+        Text::HexadecimalLabels => "Hexadecimal 1-F",
+        Text::ZeroBasedLabels => "Zero-based 0-14",
+        Text::LettersFallBack => {
+            "Boards of more than 27 cells show numbers, as the letters run out"
+        }
+        Text::TileDescription => "Tile {0}, row {1}, column {2}",
+        Text::MovableTileDescription => "Tile {0}, row {1}, column {2}, movable",
+        Text::SolvedAnnouncement => "Puzzle solved in {0} moves and {1}",
+    }
+}
+
+fn german(text: Text) -> Option<&'static str> {
+    Some(match text {
+        Text::AppName => "15-Puzzle",
+        Text::WindowTitle => "{0}-Puzzle",
+        Text::WindowTitleSolved => "{0} — In {1} Zügen gelöst!",
+        Text::WindowTitleSolving => "{0} — {1} Züge — {2}",
+        Text::TimeLeft => "Verbleibende Zeit: {0}",
+        Text::Time => "Zeit: {0}",
+        Text::MovesLeft => "Verbleibende Züge: {0}",
+        Text::WorkingOutBudget => "Zugbudget wird berechnet …",
+        Text::YourSolveAndOptimal => "Deine Lösung: {0} Züge · Optimal: {1}",
+        Text::YourSolveAndOptimalBound => "Deine Lösung: {0} Züge · Optimal ≥ {1}",
+        Text::WorkingOutOptimal => "Optimale Lösung wird berechnet …",
+        Text::EfficiencyStars => "Effizienz: {0} % {1}",
+        Text::EfficiencyAtLeast => "Effizienz: ≥ {0} %",
+        Text::Moves => "Züge: {0}",
+        Text::MovesWithAutoMoves => "Züge: {0} · Automatische Züge: {1}",
+        Text::Best => "Bestwert: {0} / {1} Züge",
+        Text::PuzzleSolved => "Puzzle gelöst! 🎉",
+        Text::CopySolution => "Lösung kopieren",
+        Text::WatchReplay => "Wiederholung ansehen",
+        Text::MemoriseBoard => "Brett einprägen: noch {0} s",
+        Text::Unsolvable => "Unlösbar",
+        Text::BuildingSolverTables => "Löser-Tabellen werden erstellt… {0}%",
+        Text::DistanceAndMisplaced => "Abstand: {0} · Falsch platziert: {1}",
+        Text::NewRecord => "Neuer Rekord! 🏆",
+        Text::Solved => "Gelöst! 🎉",
+        Text::MovesInTime => "{0} Züge in {1}",
+        Text::BlindfoldSolve => "Blind gelöst 🙈",
+        Text::BlindfoldSolveOnePeek => "Blind gelöst 🙈 · 1 Blick",
+        Text::BlindfoldSolvePeeks => "Blind gelöst 🙈 · {0} Blicke",
+        Text::ShuffleAgain => "Neu mischen",
+        Text::Retry => "Nochmal",
+        Text::Close => "Schließen",
+        Text::TimesUp => "Zeit abgelaufen",
+        Text::OutOfMoves => "Keine Züge mehr",
+        Text::DistanceRemaining => "Verbleibender Abstand: {0}",
+        Text::AbandonSolve => "Aktuellen Lösungsversuch abbrechen?",
+        Text::Yes => "Ja",
+        Text::No => "Nein",
+        Text::DailyPuzzleFor => "Tagespuzzle vom {0} · Code {1}",
+        Text::DifficultyAndSeed => "Schwierigkeit: {0} · Seed: {1}",
+        Text::ScrambleFromSeed => "Aus Seed mischen",
+        Text::BoardExample => "Brett, z. B. 1 2 3 / 4 5 6 / 7 8 0",
+        Text::ImportBoard => "Brett importieren",
+        Text::CopyPosition => "Stellung kopieren",
+        Text::ScrambleCode => "Stellungscode",
+        Text::LoadCode => "Code laden",
+        Text::CopyScrambleCode => "Stellungscode kopieren",
+        Text::InvalidCode => "Ungültiger Code: {0}",
+        Text::Copied => "Kopiert!",
+        Text::PastePosition => "Stellung einfügen",
+        Text::InvalidSeed => {
+            "Der Seed muss eine ganze Zahl zwischen 0 und 18446744073709551615 sein"
+        }
+        Text::PresetBest => "{0} · Bestzeit {1}",
+        Text::DailyDone => "Tagespuzzle gelöst · {0} / {1} Züge",
+        Text::Daily => "Tagespuzzle",
+        Text::Relay => "Staffel",
+        Text::GhostAhead => "Vor deiner Bestzeit",
+        Text::GhostBehind => "Hinter deiner Bestzeit",
+        Text::GhostLevel => "Gleichauf mit deiner Bestzeit",
+        Text::RaceYourBest => "Beim Wiederholen gegen deine Bestzeit antreten",
+        Text::RelayStage => "Staffel: Etappe {0}/{1}",
+        Text::RelayNextStage => "Etappe {0}/{1}",
+        Text::RaceTheBot => "Gegen den Bot",
+        Text::Bot => "Bot",
+        Text::BotThinking => "Sucht eine Lösung…",
+        Text::BotWon => "Der Bot war schneller",
+        Text::YouBeatTheBot => "Du hast den Bot geschlagen!",
+        Text::RaceAgain => "Noch ein Rennen",
+        Text::KeepPlaying => "Weiterspielen",
+        Text::BotGaveUp => "Der Bot hat keine Lösung gefunden, das Rennen fällt aus",
+        Text::DailyStreak => "Tagesserie: {0}",
+        Text::AbandonForPreset => "Diesen Versuch für das {0} abbrechen?",
+        Text::Columns => "Spalten",
+        Text::Rows => "Zeilen",
+        Text::Paused => "Pausiert",
+        Text::ClickToResume => "Pausiert, während du weg warst · Zum Fortsetzen klicken",
+        Text::SkipCountdown => "Beliebige Taste für sofortigen Start",
+        Text::Undo => "Rückgängig",
+        Text::Redo => "Wiederholen",
+        Text::Resume => "Fortsetzen",
+        Text::Pause => "Pause",
+        Text::Shuffle => "Mischen",
+        Text::Reset => "Zurücksetzen",
+        Text::RetryScramble => "Mischung nochmal",
+        Text::Hint => "Tipp",
+        Text::Stop => "Anhalten",
+        Text::SolveIt => "Lösen",
+        Text::Peek => "Spicken",
+        Text::EditGoal => "Ziel bearbeiten",
+        Text::Save => "Speichern",
+        Text::Load => "Laden",
+        Text::ExportImage => "Bild exportieren",
+        Text::Stats => "Statistik",
+        Text::Fullscreen => "Vollbild",
+        Text::ExitFullscreen => "Vollbild beenden",
+        Text::ResumeOnLaunch => "Letztes Spiel beim Start fortsetzen",
+        Text::AskBeforeAbandoning => "Vor dem Abbrechen nachfragen",
+        Text::PauseWhenAway => "Pausieren, wenn das Fenster den Fokus verliert",
+        Text::TimeAttack => "Zeitangriff",
+        Text::MoveLimit => "Zuglimit",
+        Text::Blindfold => "Blind",
+        Text::Picture => "Bild",
+        Text::NumbersOnPicture => "Zahlen auf dem Bild",
+        Text::ColourPlacedTiles => "Platzierte Steine färben",
+        Text::WrapAroundEdges => "Über die Ränder schieben",
+        Text::ShowDistance => "Abstand anzeigen",
+        Text::ProgressByDistance => "Fortschritt am Abstand messen",
+        Text::DirectionKeys => "Richtungstasten:",
+        Text::KeyRepeatDelay => "Gehaltene Taste wiederholt nach: {0} ms",
+        Text::KeyRepeatInterval => "Danach alle: {0} ms",
+        Text::ArrowMovesTile => "Pfeil = Stein bewegt sich",
+        Text::ArrowMovesBlank => "Pfeil = Lücke bewegt sich",
+        Text::NumpadRelative => "Ziffernblock relativ",
+        Text::ShowGoal => "Ziel zeigen",
+        Text::HideGoal => "Ziel ausblenden",
+        Text::CountLineSlidesAsOne => "Reihenzüge als einen Zug zählen",
+        Text::Sound => "Ton",
+        Text::TimeLimit => "Zeitlimit: ",
+        Text::Preview => "Vorschau: ",
+        Text::Preset8 => "8-Puzzle",
+        Text::Preset15 => "15-Puzzle",
+        Text::Preset24 => "24-Puzzle",
+        Text::BoardImported => "Brett importiert",
+        Text::BoardImportedUnsolvable => "Brett importiert, aber es ist nicht lösbar",
+        Text::ImportFailed => "Import fehlgeschlagen: {0}",
+        Text::ClipboardEmpty => "Die Zwischenablage enthält kein Brett",
+        Text::NoSolutionInTime => "Keine Lösung rechtzeitig gefunden",
+        Text::SearchingForSolution => "Suche: {0} Züge tief, {1} Stellungen, {2} s",
+        Text::SolutionMovesLeft => "Optimale Lösung: noch {0} Züge",
+        Text::SolutionCopied => "Lösung kopiert",
+        Text::GameSaved => "Spiel gespeichert",
+        Text::SaveFailed => "Speichern fehlgeschlagen: {0}",
+        Text::GameLoaded => "Spiel geladen",
+        Text::LoadFailed => "Laden fehlgeschlagen: {0}",
+        Text::Saves => "Spielstände",
+        Text::SaveHere => "Hier speichern",
+        Text::Rename => "Umbenennen",
+        Text::SlotName => "Platz {0}",
+        Text::EmptySlot => "Freier Platz",
+        Text::UnreadableSlot => "Unlesbarer Spielstand: {0}",
+        Text::SlotDetails => "{0} · {1} · {2} Züge · {3}",
+        Text::Casual => "Locker",
+        Text::DeleteSlot => "„{0}“ löschen?",
+        Text::SlotSaved => "In „{0}“ gespeichert",
+        Text::SlotLoaded => "„{0}“ geladen",
+        Text::SlotDeleted => "Spielstand gelöscht",
+        Text::Profiles => "Profile",
+        Text::PlayingAs => "Es spielt {0}",
+        Text::SwitchProfile => "Profil wechseln",
+        Text::NewProfileName => "Name eines neuen Profils",
+        Text::CreateProfile => "Anlegen",
+        Text::PlayAsProfile => "Spielen",
+        Text::Playing => "Spielt",
+        Text::TypeToDeleteProfile => {
+            "„{0}“ eingeben, um das Profil mit Statistiken, Erfolgen und Spielständen zu löschen"
+        }
+        Text::ProfileNameEmpty => "Einen Namen eingeben",
+        Text::ProfileNameTooLong => "Namen haben höchstens {0} Zeichen",
+        Text::ProfileNameTaken => "Es gibt schon ein Profil mit diesem Namen",
+        Text::ImageExported => "Bild gespeichert unter {0}",
+        Text::ExportFailed => "Export fehlgeschlagen: {0}",
+        Text::ExportCsv => "CSV exportieren",
+        Text::CsvExported => "Lösungen gespeichert unter {0}",
+        Text::InvalidGoal => "Ungültiges Ziel: {0}",
+        Text::Menu => "Menü",
+        Text::ContinueGame => "Letztes Spiel fortsetzen",
+        Text::QuitToMenu => "Zurück zum Menü",
+        Text::TryTheTutorial => "Neu bei Schiebepuzzles? Das Tutorial zeigt, wie es geht.",
+        Text::TutorialStep => "Tutorial: Schritt {0} von {1}",
+        Text::LessonSlideIntoGap => {
+            "Steine neben der Lücke lassen sich hineinschieben. Drücke den Stein, auf den der Pfeil zeigt."
+        }
+        Text::LessonFinishTheBoard => {
+            "Noch ein Zug bringt den letzten Stein zurück, und das Brett ist gelöst."
+        }
+        Text::LessonTopRow => {
+            "Auf einem gemischten Brett kommt zuerst die oberste Reihe an ihren Platz: 1, 2 und 3. Folge dem Pfeil."
+        }
+        Text::LessonLastTwoRows => {
+            "Lass die oberste Reihe jetzt in Ruhe und schiebe die letzten beiden Reihen im Kreis, bis jeder Stein an seinem Platz ist."
+        }
+        Text::TutorialStray => {
+            "Nicht dieser. Er rutscht zurück, damit du es noch einmal versuchen kannst."
+        }
+        Text::TutorialStepDone => "Gut gemacht! Als Nächstes kommt ein neues Brett.",
+        Text::TutorialFinished => {
+            "Mehr steckt nicht dahinter: Jedes Brett wird Reihe für Reihe gelöst, die letzten beiden zusammen."
+        }
+        Text::NextStep => "Weiter",
+        Text::LeaveTutorial => "Tutorial verlassen",
+        Text::KeyboardShortcuts => "Tastenkürzel",
+        Text::SlideTile => "Stein schieben",
+        Text::MoveBlank => "Lücke bewegen",
+        Text::SlideTileBesideBlank => "Stein auf dieser Seite der Lücke schieben",
+        Text::ResetToSolved => "Auf gelöst zurücksetzen",
+        Text::PauseOrResume => "Pausieren oder fortsetzen",
+        Text::ToggleFullscreen => "Vollbild umschalten",
+        Text::CloseOverlays => "Einblendungen schließen oder Menü öffnen",
+        Text::FocusTiles => "Nächsten oder vorigen Stein auswählen",
+        Text::PressFocusedTile => "Ausgewählten Stein schieben",
+        Text::ShowOrHideHelp => "Diese Hilfe ein- oder ausblenden",
+        Text::Statistics => "Statistik",
+        Text::ShuffledAndSolved => "Gemischt: {0} · Gelöst: {1}",
+        Text::TotalMovesAndTime => "Züge insgesamt: {0} · Zeit insgesamt: {1}",
+        Text::AverageMovesAndTime => "Züge im Schnitt: {0} · Zeit im Schnitt: {1}",
+        Text::Streaks => "Serie: {0} · Längste Serie: {1}",
+        Text::BlindfoldStats => "Blind gelöst: {0} · Blicke: {1}",
+        Text::MoveLimitStats => {
+            "Zuglimits geschafft: {0} · Verfehlt: {1} · Bestwert: {2} Züge, {3}"
+        }
+        Text::AverageEfficiency => "Durchschnittliche Effizienz: {0} %",
+        Text::RelaysNotFinished => "Staffeln abgebrochen: {0}",
+        Text::RacesAgainstTheBot => "Rennen gegen den Bot",
+        Text::RaceStats => "{0}: gewonnen {1} · verloren {2}",
+        Text::TimeAttackStats => "Zeitangriffe verloren: {0} · Meiste Restzeit: {1}",
+        Text::NoGamesYet => "Noch keine Spiele gespielt",
+        Text::ResetAllStatistics => "Die gesamte Statistik zurücksetzen?",
+        Text::Cancel => "Abbrechen",
+        Text::ResetStatistics => "Statistik zurücksetzen",
+        Text::Back => "Zurück",
+        Text::Leaderboard => "Bestenliste",
+        Text::MadeTheLeaderboard => "Du bist in der Bestenliste!",
+        Text::YourName => "Dein Name",
+        Text::AddToLeaderboard => "Eintragen",
+        Text::NoLeaderboardEntries => "Noch keine Einträge in der Bestenliste",
+        Text::Delete => "Löschen",
+        Text::Achievements => "Erfolge",
+        Text::SessionLog => "Sitzungsprotokoll",
+        Text::CopyLog => "Protokoll kopieren",
+        Text::LogEmpty => "Noch ist nichts passiert",
+        Text::AchievementUnlocked => "Erfolg freigeschaltet: {0}",
+        Text::UnlockedOn => "Freigeschaltet am {0}",
+        Text::Locked => "Gesperrt",
+        Text::FirstSolve => "Erste Lösung",
+        Text::FirstSolveDescription => "Löse ein Puzzle beliebiger Größe",
+        Text::NoUndo => "Nichts zu bereuen",
+        Text::NoUndoDescription => "Löse ein Puzzle, ohne einen Zug rückgängig zu machen",
+        Text::NoHints => "Ganz allein",
+        Text::NoHintsDescription => "Löse ein 4x4 oder größer ohne Tipp",
+        Text::Sub100Moves => "Sparsam",
+        Text::Sub100MovesDescription => "Löse ein 4x4 in weniger als 100 Zügen",
+        Text::SubMinute => "Flinke Finger",
+        Text::SubMinuteDescription => "Löse ein 4x4 oder größer in unter einer Minute",
+        Text::Solve5x5 => "Eine Nummer größer",
+        Text::Solve5x5Description => "Löse ein 5x5 oder größer",
+        Text::BeatTheClock => "Schneller als die Uhr",
+        Text::BeatTheClockDescription => "Beende einen Zeitangriff, bevor die Zeit abläuft",
+        Text::BlindSolve => "Blind vertraut",
+        Text::BlindSolveDescription => "Löse ein verdecktes Puzzle, ohne hinzusehen",
+        Text::HundredSolves => "Hundertschaft",
+        Text::HundredSolvesDescription => "Löse 100 Puzzles",
+        Text::WeekOfDailies => "Eine Woche täglich",
+        Text::WeekOfDailiesDescription => "Löse das Tagespuzzle sieben Tage in Folge",
+        Text::Settings => "Einstellungen",
+        Text::Appearance => "Darstellung",
+        Text::Gameplay => "Spiel",
+        Text::NextScrambleChallenges => "Herausforderungen für das nächste Puzzle",
+        Text::SlideAnimation => "Schiebeanimation: {0} ms",
+        Text::SlideAnimationOff => "Schiebeanimation: aus",
+        Text::ReduceMotion => "Bewegung reduzieren",
+        Text::EasingLinear => "Gleichmäßig",
+        Text::EasingEaseOut => "Abbremsen",
+        Text::EasingEaseInOut => "Anfahren und abbremsen",
+        Text::EasingOvershoot => "Federnd",
+        Text::BoardDrawnWith => "Spielfeld gezeichnet mit:",
+        Text::RendererWidgets => "Bedienelementen",
+        Text::RendererCanvas => "Leinwand",
+        Text::TileGap => "Abstand der Steine: {0} px",
+        Text::CornerRadius => "Eckenradius: {0} px",
+        Text::TileNumbersIn => "Steinnummern in:",
+        Text::CustomGoal => "Eigenes Ziel",
+        Text::GoalEditorHelp => {
+            "Felder in der Reihenfolge der Steine anklicken; erneut klicken zum Leeren"
+        }
+        Text::UnassignedCells => "{0} Felder brauchen noch einen Stein; zum Zuweisen anklicken",
+        Text::Blank => "Lücke",
+        Text::Clear => "Leeren",
+        Text::SaveGoal => "Ziel speichern",
+        Text::Replay => "Wiederholung",
+        Text::ReplayProgress => "Zug {0} von {1} · Zeit: {2}",
+        Text::Restart => "Von vorn",
+        Text::Play => "Abspielen",
+        Text::Step => "Schritt",
+        Text::RealTimeSpeed => "Echtzeit",
+        Text::Easy => "Leicht",
+        Text::Medium => "Mittel",
+        Text::Hard => "Schwer",
+        Text::Expert => "Experte",
+        Text::SystemTheme => "Systemfarben",
+        Text::LightTheme => "Hell",
+        Text::DarkTheme => "Dunkel",
+        Text::StandardGoal => "Standardziel",
+        Text::SnakeGoal => "Schlangenziel",
+        Text::SpiralGoal => "Spiralziel",
+        Text::StandardColors => "Standardfarben",
+        Text::HighContrast => "Hoher Kontrast",
+        Text::DeuteranopiaSafe => "Für Deuteranopie",
+        Text::NumberLabels => "Zahlen 1-15",
+        Text::LetterLabels => "Buchstaben A-O",
+        Text::HexadecimalLabels => "Hexadezimal 1-F",
+        Text::ZeroBasedLabels => "Ab null 0-14",
+        Text::LettersFallBack => {
+            "Bretter mit mehr als 27 Feldern zeigen Zahlen, da die Buchstaben ausgehen"
+        }
+        Text::TileDescription => "Stein {0}, Zeile {1}, Spalte {2}",
+        Text::MovableTileDescription => "Stein {0}, Zeile {1}, Spalte {2}, verschiebbar",
+        Text::SolvedAnnouncement => "Puzzle in {0} Zügen und {1} gelöst",
+        // The same in German
+        Text::Seed
+        | Text::Controller
+        | Text::Minutes
+        | Text::Seconds
+        | Text::WindowTitleProfile
+        | Text::Tutorial
+        | Text::FontStandard
+        | Text::FontInter
+        | Text::FontFiraMono => return None,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn placeholders_are_filled_in_order_of_their_index() {
+        assert_eq!(
+            trf(Language::English, Text::Best, &[&"1:02.3", &40]),
+            "Best: 1:02.3 / 40 moves"
+        );
+        assert_eq!(
+            trf(
+                Language::German,
+                Text::WindowTitleSolved,
+                &[&"15-Puzzle", &96]
+            ),
+            "15-Puzzle — In 96 Zügen gelöst!"
+        );
+    }
+
+    #[test]
+    fn missing_translations_fall_back_to_english() {
+        assert_eq!(tr(Language::German, Text::Seed), "Seed");
+        assert_eq!(
+            trf(Language::German, Text::Minutes, &[&3]),
+            trf(Language::English, Text::Minutes, &[&3])
+        );
+    }
+
+    #[test]
+    fn times_format_as_minutes_seconds_and_tenths() {
+        let time = Duration::from_millis(83_450);
+
+        assert_eq!(
+            format_time(Language::English, Duration::ZERO, Precision::Tenths),
+            "0:00.0"
+        );
+        assert_eq!(
+            format_time(Language::English, time, Precision::Tenths),
+            "1:23.4"
+        );
+        assert_eq!(
+            format_time(
+                Language::English,
+                Duration::from_secs(600),
+                Precision::Millis
+            ),
+            "10:00.000"
+        );
+        // German writes a decimal comma
+        assert_eq!(
+            format_time(Language::German, time, Precision::Tenths),
+            "1:23,4"
+        );
+        assert_eq!(
+            format_time(Language::German, time, Precision::Millis),
+            "1:23,450"
+        );
+        assert_eq!(format_decimal(Language::German, 12.5), "12,5");
+    }
+}
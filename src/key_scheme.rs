@@ -0,0 +1,45 @@
+use crate::Direction;
+
+use serde::{Deserialize, Serialize};
+
+use std::fmt;
+
+/// What the direction keys move, a matter players have never agreed on.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum KeyScheme {
+    /// The arrow points the way a tile slides into the blank.
+    #[default]
+    TileMoves,
+    /// The arrow points the way the blank moves, so the tile slides the
+    /// other way.
+    BlankMoves,
+    /// As [`TileMoves`](Self::TileMoves), and with the blank on numpad 5
+    /// the digits around it pick the tile beside it in that direction.
+    NumpadRelative,
+}
+
+impl KeyScheme {
+    pub const ALL: [KeyScheme; 3] = [
+        KeyScheme::TileMoves,
+        KeyScheme::BlankMoves,
+        KeyScheme::NumpadRelative,
+    ];
+
+    /// The way a tile slides when the direction key for `pressed` is hit.
+    pub fn slide(self, pressed: Direction) -> Direction {
+        match self {
+            KeyScheme::BlankMoves => pressed.opposite(),
+            KeyScheme::TileMoves | KeyScheme::NumpadRelative => pressed,
+        }
+    }
+}
+
+impl fmt::Display for KeyScheme {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            KeyScheme::TileMoves => "Arrow = tile moves",
+            KeyScheme::BlankMoves => "Arrow = blank moves",
+            KeyScheme::NumpadRelative => "Numpad relative",
+        })
+    }
+}
@@ -0,0 +1,159 @@
+use crate::BoardError;
+
+use serde::{Deserialize, Serialize};
+
+use std::fmt;
+
+/// How tile values are written, on the tiles and in boards copied as text.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum LabelMode {
+    /// 1 to 15 on the 4x4 board.
+    #[default]
+    Numbers,
+    /// A to O on the 4x4 board, for boards of up to
+    /// [`MAX_LETTER_CELLS`](Self::MAX_LETTER_CELLS) cells.
+    Letters,
+    /// 1 to F on the 4x4 board.
+    Hexadecimal,
+    /// 0 to 14 on the 4x4 board.
+    ZeroBased,
+}
+
+impl LabelMode {
+    pub const ALL: [LabelMode; 4] = [
+        LabelMode::Numbers,
+        LabelMode::Letters,
+        LabelMode::Hexadecimal,
+        LabelMode::ZeroBased,
+    ];
+
+    /// The most cells a board can have for every tile to get a letter of its
+    /// own, A to Z.
+    pub const MAX_LETTER_CELLS: usize = 27;
+
+    /// The mode a board of `cells` cells is labelled in: letters fall back to
+    /// numbers once the alphabet runs out.
+    pub fn for_cells(self, cells: usize) -> Self {
+        match self {
+            LabelMode::Letters if cells > Self::MAX_LETTER_CELLS => LabelMode::Numbers,
+            mode => mode,
+        }
+    }
+}
+
+impl fmt::Display for LabelMode {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            LabelMode::Numbers => "Numbers",
+            LabelMode::Letters => "Letters",
+            LabelMode::Hexadecimal => "Hexadecimal",
+            LabelMode::ZeroBased => "Zero-based",
+        })
+    }
+}
+
+/// The label of the tile with `value`, which counts from 1 in every mode.
+///
+/// Pass the mode through [`LabelMode::for_cells`] first, as letters run out
+/// past Z.
+pub fn label_for(value: u16, mode: LabelMode) -> String {
+    match mode {
+        LabelMode::Numbers => value.to_string(),
+        LabelMode::Letters => char::from_u32('A' as u32 + u32::from(value) - 1)
+            .filter(char::is_ascii_uppercase)
+            .map_or_else(|| value.to_string(), String::from),
+        LabelMode::Hexadecimal => format!("{value:X}"),
+        LabelMode::ZeroBased => (value - 1).to_string(),
+    }
+}
+
+/// Reads the tile values, `0` for the blank, from the tokens of a board
+/// written in `mode` or in plain numbers.
+///
+/// `_` is always the blank. A board is only read as hexadecimal when a token
+/// has a letter in it, and as zero-based when it has both a `_` and a `0`,
+/// since otherwise it reads the same as plain numbers.
+pub(crate) fn parse_values(tokens: &[&str], mode: LabelMode) -> Result<Vec<u16>, BoardError> {
+    let has = |token| tokens.contains(&token);
+    let radix = match mode {
+        LabelMode::Hexadecimal
+            if tokens
+                .iter()
+                .any(|token| token.bytes().any(|byte| byte.is_ascii_alphabetic())) =>
+        {
+            16
+        }
+        _ => 10,
+    };
+    let offset = u16::from(mode == LabelMode::ZeroBased && has("_") && has("0"));
+
+    tokens
+        .iter()
+        .map(|&token| {
+            let invalid = || BoardError::InvalidNumber(token.to_owned());
+            match token.as_bytes() {
+                b"_" => Ok(0),
+                &[letter] if mode == LabelMode::Letters && letter.is_ascii_alphabetic() => {
+                    Ok(u16::from(letter.to_ascii_uppercase() - b'A') + 1)
+                }
+                _ => u16::from_str_radix(token, radix)
+                    .ok()
+                    .and_then(|value| value.checked_add(offset))
+                    .ok_or_else(invalid),
+            }
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn labels(mode: LabelMode) -> Vec<String> {
+        (1..=15).map(|value| label_for(value, mode)).collect()
+    }
+
+    #[test]
+    fn every_mode_labels_the_fifteen_puzzle() {
+        assert_eq!(labels(LabelMode::Numbers)[14], "15");
+        assert_eq!(labels(LabelMode::Letters).concat(), "ABCDEFGHIJKLMNO");
+        assert_eq!(labels(LabelMode::Hexadecimal).concat(), "123456789ABCDEF");
+        assert_eq!(labels(LabelMode::ZeroBased)[..3], ["0", "1", "2"]);
+        assert_eq!(label_for(26, LabelMode::Letters), "Z");
+    }
+
+    #[test]
+    fn letters_fall_back_to_numbers_past_z() {
+        assert_eq!(LabelMode::Letters.for_cells(25), LabelMode::Letters);
+        assert_eq!(LabelMode::Letters.for_cells(27), LabelMode::Letters);
+        assert_eq!(LabelMode::Letters.for_cells(36), LabelMode::Numbers);
+        assert_eq!(
+            LabelMode::Hexadecimal.for_cells(144),
+            LabelMode::Hexadecimal
+        );
+    }
+
+    #[test]
+    fn boards_read_in_the_active_mode_or_in_numbers() {
+        let parse = |board: &str, mode| {
+            let tokens: Vec<&str> = board.split(' ').collect();
+            parse_values(&tokens, mode)
+        };
+
+        assert_eq!(parse("b a c _", LabelMode::Letters), Ok(vec![2, 1, 3, 0]));
+        assert_eq!(parse("2 1 3 0", LabelMode::Letters), Ok(vec![2, 1, 3, 0]));
+        assert_eq!(
+            parse("1 2 3 4 5 6 7 8 9 A B C D E F _", LabelMode::Hexadecimal).unwrap()[9..12],
+            [10, 11, 12]
+        );
+        assert_eq!(
+            parse("1 2 10 0", LabelMode::Hexadecimal),
+            Ok(vec![1, 2, 10, 0])
+        );
+        assert_eq!(parse("1 0 2 _", LabelMode::ZeroBased), Ok(vec![2, 1, 3, 0]));
+        assert_eq!(parse("2 1 3 0", LabelMode::ZeroBased), Ok(vec![2, 1, 3, 0]));
+
+        assert!(parse("a b c _", LabelMode::Numbers).is_err());
+        assert!(parse("ab 1 2 _", LabelMode::Letters).is_err());
+    }
+}
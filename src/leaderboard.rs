@@ -0,0 +1,267 @@
+use crate::{Day, Efficiency, GameMode, Records, storage::Versioned};
+
+use serde::{Deserialize, Serialize};
+
+use std::collections::BTreeMap;
+use std::time::Duration;
+
+/// How many entries each table keeps.
+pub const LEADERBOARD_SIZE: usize = 10;
+
+/// The name an entry goes under when the player leaves it blank.
+pub const ANONYMOUS: &str = "Anonymous";
+
+/// The fastest named solves, a table of up to [`LEADERBOARD_SIZE`] for every
+/// grid size and mode under the keys built by [`Leaderboard::key`].
+///
+/// Tables are put back in order and cut to size as they load, so a file
+/// edited by hand still reads as a valid leaderboard.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(
+    from = "BTreeMap<String, Vec<Entry>>",
+    into = "BTreeMap<String, Vec<Entry>>"
+)]
+pub struct Leaderboard {
+    tables: BTreeMap<String, Vec<Entry>>, // Fastest first, never empty
+}
+
+impl Versioned for Leaderboard {
+    const VERSION: u32 = 1;
+}
+
+/// One solve on the leaderboard.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Entry {
+    pub name: String,
+    pub time: Duration,
+    pub moves: u32,
+    pub day: Day,
+    /// How close the solve came to the optimal length, if that was worked
+    /// out before the entry was made.
+    #[serde(default)]
+    pub efficiency: Option<Efficiency>,
+}
+
+impl Entry {
+    /// An entry under `name`, trimmed, or [`ANONYMOUS`] if that leaves
+    /// nothing.
+    pub fn new(name: &str, time: Duration, moves: u32, day: Day) -> Self {
+        let name = match name.trim() {
+            "" => ANONYMOUS,
+            name => name,
+        };
+
+        Self {
+            name: name.to_owned(),
+            time,
+            moves,
+            day,
+            efficiency: None,
+        }
+    }
+
+    /// Faster times rank higher, with fewer moves breaking ties.
+    fn rank(&self) -> (Duration, u32) {
+        (self.time, self.moves)
+    }
+}
+
+impl Leaderboard {
+    /// The key a table is kept under: the [records key](Records::key), with
+    /// a suffix for the timed and move-limited modes.
+    pub fn key(rows: usize, cols: usize, wrap: bool, mode: GameMode) -> String {
+        let key = Records::key(rows, cols, wrap);
+
+        match mode {
+            GameMode::Casual => key,
+            GameMode::TimeAttack { .. } => format!("{key} time attack"),
+            GameMode::MoveLimit { .. } => format!("{key} move limit"),
+        }
+    }
+
+    /// The entries of one table, fastest first.
+    pub fn entries(&self, key: &str) -> &[Entry] {
+        self.tables.get(key).map_or(&[], Vec::as_slice)
+    }
+
+    /// Every table with an entry, by key.
+    pub fn tables(&self) -> impl Iterator<Item = (&str, &[Entry])> {
+        self.tables
+            .iter()
+            .map(|(key, entries)| (key.as_str(), entries.as_slice()))
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.tables.is_empty()
+    }
+
+    /// Whether a solve in `time` and `moves` would make the table `key`.
+    /// A tie with the last entry does not knock it off a full table.
+    pub fn qualifies(&self, key: &str, time: Duration, moves: u32) -> bool {
+        let entries = self.entries(key);
+
+        entries.len() < LEADERBOARD_SIZE
+            || entries
+                .last()
+                .is_some_and(|last| (time, moves) < last.rank())
+    }
+
+    /// Adds `entry` to the table `key`, returning its place counting from 0,
+    /// or `None` if it did not make the table. It goes below any entries it
+    /// ties with, which got there first.
+    pub fn insert(&mut self, key: &str, entry: Entry) -> Option<usize> {
+        if !self.qualifies(key, entry.time, entry.moves) {
+            return None;
+        }
+
+        let entries = self.tables.entry(key.to_owned()).or_default();
+        let place = entries.partition_point(|existing| existing.rank() <= entry.rank());
+        entries.insert(place, entry);
+        entries.truncate(LEADERBOARD_SIZE);
+        Some(place)
+    }
+
+    /// Deletes the entry at `place` in the table `key`, if there is one.
+    pub fn remove(&mut self, key: &str, place: usize) -> Option<Entry> {
+        let entries = self.tables.get_mut(key)?;
+        let entry = (place < entries.len()).then(|| entries.remove(place))?;
+
+        if entries.is_empty() {
+            self.tables.remove(key);
+        }
+        Some(entry)
+    }
+}
+
+impl From<BTreeMap<String, Vec<Entry>>> for Leaderboard {
+    fn from(mut tables: BTreeMap<String, Vec<Entry>>) -> Self {
+        tables.retain(|_, entries| {
+            // A stable sort keeps tied entries in the order they were made
+            entries.sort_by_key(Entry::rank);
+            entries.truncate(LEADERBOARD_SIZE);
+            !entries.is_empty()
+        });
+
+        Self { tables }
+    }
+}
+
+impl From<Leaderboard> for BTreeMap<String, Vec<Entry>> {
+    fn from(leaderboard: Leaderboard) -> Self {
+        leaderboard.tables
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const KEY: &str = "4x4";
+
+    fn entry(name: &str, seconds: u64, moves: u32) -> Entry {
+        Entry::new(
+            name,
+            Duration::from_secs(seconds),
+            moves,
+            Day::from_days_since_epoch(20_000),
+        )
+    }
+
+    fn names(leaderboard: &Leaderboard) -> Vec<&str> {
+        leaderboard
+            .entries(KEY)
+            .iter()
+            .map(|entry| entry.name.as_str())
+            .collect()
+    }
+
+    #[test]
+    fn tables_rank_by_time_then_moves_and_keep_the_top_ten() {
+        let mut leaderboard = Leaderboard::default();
+        assert_eq!(leaderboard.insert(KEY, entry("slow", 90, 100)), Some(0));
+        assert_eq!(leaderboard.insert(KEY, entry("fast", 30, 100)), Some(0));
+        assert_eq!(leaderboard.insert(KEY, entry("fewer", 90, 80)), Some(1));
+        assert_eq!(names(&leaderboard), ["fast", "fewer", "slow"]);
+
+        for place in 3..LEADERBOARD_SIZE {
+            leaderboard.insert(KEY, entry("filler", 100 + place as u64, 100));
+        }
+        assert_eq!(leaderboard.entries(KEY).len(), LEADERBOARD_SIZE);
+        assert!(!leaderboard.qualifies(KEY, Duration::from_secs(200), 1));
+        assert_eq!(leaderboard.insert(KEY, entry("late", 200, 1)), None);
+
+        assert_eq!(leaderboard.insert(KEY, entry("new", 60, 50)), Some(1));
+        assert_eq!(leaderboard.entries(KEY).len(), LEADERBOARD_SIZE);
+        assert_eq!(leaderboard.entries(KEY).last().unwrap().time.as_secs(), 108);
+        assert!(leaderboard.entries("3x3").is_empty());
+    }
+
+    #[test]
+    fn ties_go_to_whoever_got_there_first() {
+        let mut leaderboard = Leaderboard::default();
+        leaderboard.insert(KEY, entry("first", 60, 50));
+        assert_eq!(leaderboard.insert(KEY, entry("second", 60, 50)), Some(1));
+        assert_eq!(names(&leaderboard), ["first", "second"]);
+
+        for _ in 2..LEADERBOARD_SIZE {
+            leaderboard.insert(KEY, entry("full", 60, 50));
+        }
+        // Matching the last entry is not enough to push it off
+        assert_eq!(leaderboard.insert(KEY, entry("tied", 60, 50)), None);
+    }
+
+    #[test]
+    fn blank_names_are_anonymous() {
+        assert_eq!(entry("  ", 1, 1).name, ANONYMOUS);
+        assert_eq!(entry(" Ada ", 1, 1).name, "Ada");
+    }
+
+    #[test]
+    fn entries_can_be_deleted() {
+        let mut leaderboard = Leaderboard::default();
+        leaderboard.insert(KEY, entry("a", 10, 10));
+        leaderboard.insert(KEY, entry("b", 20, 10));
+
+        assert_eq!(leaderboard.remove(KEY, 5), None);
+        assert_eq!(leaderboard.remove(KEY, 0).unwrap().name, "a");
+        assert_eq!(names(&leaderboard), ["b"]);
+        leaderboard.remove(KEY, 0);
+        assert!(leaderboard.is_empty());
+    }
+
+    #[test]
+    fn modes_keep_their_own_tables() {
+        assert_eq!(Leaderboard::key(4, 4, false, GameMode::Casual), "4x4");
+        assert_eq!(
+            Leaderboard::key(3, 4, true, GameMode::MoveLimit { budget: Some(9) }),
+            "4x3 wrap move limit"
+        );
+        assert_ne!(
+            Leaderboard::key(4, 4, false, GameMode::Casual),
+            Leaderboard::key(
+                4,
+                4,
+                false,
+                GameMode::TimeAttack {
+                    remaining: Duration::ZERO
+                }
+            )
+        );
+    }
+
+    #[test]
+    fn loading_puts_tables_back_in_order() {
+        let mut leaderboard = Leaderboard::default();
+        leaderboard.insert(KEY, entry("a", 10, 10));
+        leaderboard.insert(KEY, entry("b", 20, 10));
+        let json = serde_json::to_string(&leaderboard)
+            .unwrap()
+            .replace("\"name\":\"a\"", "\"name\":\"c\"")
+            .replace("\"secs\":10", "\"secs\":30");
+
+        let loaded: Leaderboard = serde_json::from_str(&json).unwrap();
+        assert_eq!(names(&loaded), ["b", "c"]);
+        let empty: Leaderboard = serde_json::from_str(r#"{"4x4": []}"#).unwrap();
+        assert!(empty.is_empty());
+    }
+}
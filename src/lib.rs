@@ -0,0 +1,75 @@
+//! The sliding puzzle model, independent of any user interface.
+
+mod board_renderer;
+mod color_scheme;
+mod daily;
+mod difficulty;
+mod easing;
+mod efficiency;
+mod error;
+mod game_mode;
+mod ghost;
+mod goal;
+mod key_scheme;
+mod label_mode;
+mod language;
+mod leaderboard;
+mod profile;
+mod progress_metric;
+mod puzzle;
+mod race;
+mod records;
+mod relay;
+mod replay;
+mod save;
+mod scramble_code;
+mod session_log;
+mod settings;
+mod slide_count;
+mod theme_mode;
+mod tile_font;
+mod timer;
+mod tutorial;
+mod unlocked;
+
+pub mod heuristic;
+pub mod pattern_database;
+pub mod solver;
+pub mod storage;
+pub mod walking_distance;
+
+pub use board_renderer::BoardRenderer;
+pub use color_scheme::ColorScheme;
+pub use daily::{Day, daily_seed};
+pub use difficulty::Difficulty;
+pub use easing::Easing;
+pub use efficiency::Efficiency;
+pub use error::{BoardError, CodeError, MigrateError, NotationError, ProfileError};
+pub use game_mode::{DEFAULT_TIME_LIMIT, GameMode, move_budget};
+pub use ghost::{Ghost, Ghosts, MAX_GHOSTS};
+pub use goal::{Goal, goal_position};
+pub use key_scheme::KeyScheme;
+pub use label_mode::{LabelMode, label_for};
+pub use language::Language;
+pub use leaderboard::{ANONYMOUS, Entry, LEADERBOARD_SIZE, Leaderboard};
+pub use profile::{DEFAULT_PROFILE_NAME, MAX_PROFILE_NAME, Profile, Profiles};
+pub use progress_metric::ProgressMetric;
+pub use puzzle::{
+    DEFAULT_GRID_SIZE, Direction, Drag, MAX_CELLS, MAX_GRID_SIZE, MIN_GRID_SIZE, Puzzle, Tile,
+};
+pub use race::{Race, RaceStats, Racer};
+pub use records::{Best, CSV_HEADER, Records, SolveRecord, Stats};
+pub use relay::{RELAY_SIZES, Relay};
+pub use replay::Replay;
+pub use save::{
+    SAVE_SLOTS, SaveSlot, SavedGame, SlotContents, clear_slot, read_slots, slot_file, write_slot,
+};
+pub use scramble_code::{decode_board, encode_board};
+pub use session_log::{LOG_HEADER, LogEntry, LogEvent, LoggedMode, MAX_LOG_ENTRIES, SessionLog};
+pub use settings::Settings;
+pub use slide_count::SlideCount;
+pub use theme_mode::ThemeMode;
+pub use tile_font::TileFont;
+pub use timer::{Precision, Timer, format_duration};
+pub use tutorial::{Lesson, Tutorial, TutorialProgress};
+pub use unlocked::Unlocked;
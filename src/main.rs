@@ -1,220 +1,8092 @@
+use iced_15_puzzle::{
+    BoardRenderer, ColorScheme, DEFAULT_GRID_SIZE, DEFAULT_PROFILE_NAME, Day, Difficulty,
+    Direction, Easing, Efficiency, Entry, GameMode, Ghost, Ghosts, Goal, KeyScheme, LabelMode,
+    Language, Leaderboard, LogEvent, LoggedMode, MAX_GRID_SIZE, MAX_PROFILE_NAME, MIN_GRID_SIZE,
+    Precision, Profile, ProfileError, Profiles, ProgressMetric, Puzzle, RELAY_SIZES, Race, Racer,
+    Records, Relay, Replay, SAVE_SLOTS, SaveSlot, SavedGame, SessionLog, Settings, SlideCount,
+    SlotContents, SolveRecord, ThemeMode, Tile, TileFont, Timer, Tutorial, TutorialProgress,
+    Unlocked, clear_slot, daily_seed, decode_board, encode_board, goal_position, label_for,
+    move_budget,
+    pattern_database::PatternDatabase,
+    read_slots, slot_file,
+    solver::{self, Cancel, OptimalLength, Progress, Snapshot},
+    storage::{self, Loaded, Persistence, Storage, Versioned},
+    write_slot,
+};
+
+mod achievements;
+mod board;
+mod cli;
+mod export;
+mod gamepad;
+mod i18n;
+mod palette;
+mod sound;
+mod swipe;
+
+use achievements::{ACHIEVEMENTS, SolveSummary};
+use board::{
+    BoardCanvas, BoardLayout, Corners, TileLook, TileSprite, blank_style, goal_flash_style,
+};
+use gamepad::Input;
+use i18n::{Choice, Text};
+use palette::Palette;
+use sound::{Player, Sound};
+use swipe::swipe;
+
+use std::collections::{HashMap, VecDeque};
+use std::ops::RangeInclusive;
+use std::sync::{
+    Arc, Mutex, PoisonError,
+    atomic::{AtomicU32, Ordering},
+};
+
 use iced::{
-    Alignment, Element, Length,
-    widget::{button, column, container, row, text},
+    Alignment, ContentFit, Element, Font, Length, Point, Rectangle, Size, Subscription, Task,
+    Theme, Vector, clipboard,
+    futures::{SinkExt, Stream, channel::mpsc},
+    keyboard::{self, Key, Location, Modifiers, key::Named},
+    stream,
+    time::{self, Duration, Instant},
+    widget::{
+        Stack, button, canvas, center, checkbox, column, container, image, opaque, pick_list, pin,
+        progress_bar, row, scrollable, slider, stack, text, text_input,
+    },
+    window,
 };
 
-const GRID_SIZE: usize = 4;
+// Edge length of the whole board in logical pixels, at its smallest
+const BOARD_SIZE: f32 = 400.0;
+// Roughly the room the controls around the board take up, which a larger
+// window leaves to the board
+const CONTROLS_WIDTH: f32 = 40.0;
+const CONTROLS_HEIGHT: f32 = 600.0;
+const MIN_BOARD_SIZE: f32 = 200.0;
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+// The smallest window that stays usable, and the height below which the
+// layout tightens up
+const MIN_WINDOW_SIZE: Size = Size::new(400.0, 300.0);
+const COMPACT_HEIGHT: f32 = 720.0;
+const TILE_SPACING: f32 = 5.0; // At the smallest board size, growing with it
+
+// Tile labels are this fraction of the tile's edge, but never smaller than
+// the minimum; each digit takes up roughly `DIGIT_WIDTH` of the label size
+const LABEL_SCALE: f32 = 0.25;
+const MIN_LABEL_SIZE: f32 = 10.0;
+const DIGIT_WIDTH: f32 = 0.6;
+
+// The picture for picture mode, a square of `PICTURE_SIZE` pixels
+const PICTURE: &[u8] = include_bytes!("../assets/picture.png");
+const PICTURE_SIZE: u32 = 384;
+const THUMBNAIL_SIZE: f32 = 96.0;
+const PREVIEW_SPACING: f32 = 1.0;
+
+// Rows of the saves screen, each led by a miniature of its board
+const SAVES_WIDTH: f32 = 760.0;
+const SLOT_PREVIEW_SIZE: f32 = 64.0;
+const PROFILES_WIDTH: f32 = 600.0;
+
+// The bar of how close the board is to solved, under the status
+const PROGRESS_BAR_WIDTH: f32 = 200.0;
+// The session log beside the board, when open
+const LOG_PANEL_WIDTH: f32 = 360.0;
+
+// How long the solver may search for a hint before settling for a greedy move
+const HINT_BUDGET: Duration = Duration::from_secs(1);
+const HINT_DURATION: Duration = Duration::from_secs(2);
+const SOLVE_BUDGET: Duration = Duration::from_secs(10);
+// How often a running solve reports how far it has got
+const SOLVER_PROGRESS_INTERVAL: Duration = Duration::from_millis(100);
+// How long to look for the optimal length of a finished scramble before
+// settling for a lower bound
+const OPTIMAL_BUDGET: Duration = Duration::from_secs(5);
+// How long a move-limit challenge searches for the optimal length before estimating
+const MOVE_BUDGET_SEARCH: Duration = Duration::from_secs(3);
+const AUTO_STEP_INTERVAL: Duration = Duration::from_millis(200);
+const FRAME_INTERVAL: Duration = Duration::from_millis(16);
+const CELEBRATION_DURATION: Duration = Duration::from_secs(2);
+const LAST_MOVE_ACCENT: Duration = Duration::from_millis(500);
+// How long a tile that cannot move shakes when pressed, and how far, in pixels
+const REJECTION_CUE: Duration = Duration::from_millis(150);
+const REJECTION_SHAKE: f32 = 3.0;
+// How long the cell a right-clicked tile belongs in stays outlined
+const GOAL_FLASH: Duration = Duration::from_secs(1);
+const PEEK_DURATION: Duration = Duration::from_secs(2);
+// How long the 3-2-1 before the clock starts, on resuming or in a time attack
+const COUNTDOWN: Duration = Duration::from_secs(3);
+// How long a move off the tutorial's script stays before it slides back
+const TUTORIAL_UNDO_DELAY: Duration = Duration::from_millis(700);
+// How long a toast over the board stays up
+const TOAST_DURATION: Duration = Duration::from_millis(1500);
+// The bot's board beside the player's in a race, as a share of its size
+const BOT_BOARD_SCALE: f32 = 0.6;
+// How long the next relay stage is announced over its board
+const RELAY_INTERSTITIAL: Duration = Duration::from_millis(1500);
+// How long settings must stay unchanged before they are written, so that
+// dragging a slider saves once
+const SETTINGS_SAVE_DELAY: Duration = Duration::from_millis(500);
+// The held-key repeat sliders' ranges and step, in milliseconds
+const KEY_REPEAT_DELAYS: RangeInclusive<u32> = 100..=600;
+const KEY_REPEAT_INTERVALS: RangeInclusive<u32> = 40..=300;
+const KEY_REPEAT_STEP: u32 = 20;
+// The slide animation slider's range and step, in milliseconds
+const MIN_ANIMATION_MS: u32 = 50;
+const MAX_ANIMATION_MS: u32 = 400;
+const ANIMATION_STEP: u32 = 20;
+// The largest tile gap and corner radius offered, in pixels
+const MAX_TILE_GAP: u32 = 12;
+const MAX_TILE_RADIUS: u32 = 40;
+
+// The tile label fonts that come with the game, loaded at startup
+const INTER: &[u8] = include_bytes!("../assets/fonts/Inter-Regular.ttf");
+const FIRA_MONO: &[u8] = include_bytes!("../assets/fonts/FiraMono-Medium.ttf");
+// Time between replayed moves when not following the recorded timing
+const REPLAY_INTERVAL: Duration = Duration::from_millis(300);
+
+// One-click square games, by name and edge length
+const PRESETS: [(Text, usize); 3] = [(Text::Preset8, 3), (Text::Preset15, 4), (Text::Preset24, 5)];
+
+// Range and step of the time attack countdown
+const TIME_LIMIT_STEP: u64 = 60;
+const MAX_TIME_LIMIT: u64 = 30 * 60;
+
+// Range and step of the blindfold preview, in seconds
+const PREVIEW_STEP: u64 = 5;
+const MAX_PREVIEW: u64 = 60;
+
+// Days shown in the daily streak calendar, ending today
+const STREAK_CALENDAR_DAYS: u32 = 7;
+
+#[derive(Debug, Clone)]
 enum Message {
     TilePressed(usize, usize),
+    Dragged(Point, Vector),
+    Released(Option<Direction>),
+    Scrolled(Point, Direction),
+    ShowGoal(Point),
+    Move(Direction),
     Shuffle,
+    ConfirmShuffle,
+    CancelShuffle,
+    Reset,
+    ShuffleSeeded(u64),
+    SeedInputChanged(String),
+    ImportInputChanged(String),
+    CodeInputChanged(String),
+    LoadCode(String),
+    CopyCode,
+    ImportBoard(String),
+    SetSize(usize, usize), // Rows, columns
+    SetDifficulty(Difficulty),
+    SetGoal(Goal),
+    EditGoal,
+    EditGoalCell(usize), // Row-major cell index
+    ClearGoalEditor,
+    SaveCustomGoal,
+    Preset(usize), // Index into `PRESETS`
+    Daily,
+    StartRelay,
+    StartRace,
+    RaceSolution(Vec<Tile>, Option<Vec<Direction>>), // Bot's board, its solution
+    DismissRace,
+    ConfirmPreset(bool),
+    Undo,
+    Redo,
+    Retry,
+    Pause,
+    Resume,
+    SkipCountdown,
+    TogglePause,
+    ToggleHelp,
+    ToggleLog,
+    CopyLog,
+    FocusNext,
+    FocusPrevious,
+    PressFocused,
+    Dismiss, // Closes the topmost overlay or prompt
+    ShowScreen(Screen),
+    Play(PlayMode),
+    SetMenuSize(usize, usize), // Rows, columns
+    OpenGameMenu,
+    CloseGameMenu,
+    QuitToMenu,
+    StartTutorial,
+    BeginTutorialStep,
+    EndTutorial,
+    ResetStats,
+    ConfirmResetStats(bool),
+    Hint,
+    HintReady(Vec<Tile>, Option<(usize, usize)>), // Board searched, tile to move
+    Solve,
+    SolverProgress(Vec<Tile>, Snapshot), // Board being searched, how far along
+    SolutionReady(Vec<Tile>, Option<Vec<Direction>>), // Board searched, solution
+    AutoStep,
+    StopSolve,
+    Tick(Instant),
+    CopySolution,
+    CopyPosition,
+    PastePosition,
+    ClipboardRead(Option<String>),
+    WatchReplay,
+    ToggleReplay, // Plays or pauses the replay
+    StepReplay,
+    RestartReplay,
+    SetReplayRealTime(bool),
+    ReplayTick(Instant),
+    DismissVictory,
+    NameInputChanged(String),
+    SubmitName,
+    DeleteEntry(String, usize), // Leaderboard key, place in its table
+    Save,
+    Saved(Result<(), String>),
+    Load,
+    Loaded(Result<Box<SavedGame>, String>),
+    SlotsRead(Vec<SlotContents>),
+    SaveToSlot(usize),
+    SlotSaved(String, Result<(), String>), // The slot's name, and how saving went
+    LoadSlot(usize),
+    RenameSlot(usize),
+    SlotNameChanged(String),
+    SubmitSlotName,
+    SlotRenamed(Result<(), String>),
+    DeleteSlot(usize),
+    ConfirmDeleteSlot(bool),
+    SlotDeleted(Result<(), String>),
+    ProfileInputChanged(String),
+    CreateProfile,
+    SelectProfile(u32),
+    DeleteProfile(u32),
+    DeleteConfirmationChanged(String),
+    ConfirmDeleteProfile(bool),
+    ExportImage,
+    Exported(Result<Option<String>, String>),
+    ExportCsv,
+    CsvExported(Result<Option<String>, String>),
+    SetAutoSave(bool),
+    SetShowDistance(bool),
+    SetProgressMetric(ProgressMetric),
+    SetSlideCount(SlideCount),
+    SetKeyScheme(KeyScheme),
+    SetKeyRepeatDelay(u32),    // Milliseconds
+    SetKeyRepeatInterval(u32), // Milliseconds
+    MoveKeyPressed(Direction),
+    MoveKeyReleased(Direction),
+    WindowUnfocused,
+    ThemeChanged(ThemeMode),
+    SetLanguage(Language),
+    SetColorScheme(ColorScheme),
+    SetLabelMode(LabelMode),
+    SetGoalPreview(bool),
+    SetGhost(bool),
+    SetGoalColors(bool),
+    SetWrap(bool),
+    SetPictureMode(bool),
+    SetPictureNumbers(bool),
+    SetMuted(bool),
+    SetConfirmShuffle(bool),
+    SetPauseWhenAway(bool),
+    SetTimeAttack(bool),
+    SetTimeLimit(u64), // Seconds
+    SetMoveLimit(bool),
+    SetBlindfold(bool),
+    SetBlindfoldPreview(u64), // Seconds
+    SetAnimationSpeed(u32),   // Milliseconds per slide
+    SetReduceMotion(bool),
+    SetEasing(Easing),
+    SetBoardRenderer(BoardRenderer),
+    SetTileGap(u32),    // Pixels
+    SetTileRadius(u32), // Pixels
+    SetTileFont(TileFont),
+    FontLoaded(TileFont, Result<(), iced::font::Error>),
+    Peek,
+    BudgetReady(Vec<Tile>, u32), // Board searched, moves allowed
+    OptimalFound(ScrambleKey, Option<OptimalLength>),
+    DailiesAnalysed(Vec<(ScrambleKey, OptimalLength)>),
+    PatternDatabaseReady,
+    ToggleFullscreen,
+    WindowResized(Size),
+    WindowMoved(Point),
+    CloseRequested(window::Id),
+}
+
+/// `day`'s daily puzzle on a board of `rows` by `cols`: the default goal,
+/// difficulty and no wrapping, so that the position is the same for everyone.
+fn daily_puzzle(day: Day, rows: usize, cols: usize) -> Puzzle {
+    let mut puzzle = Puzzle::new(rows, cols);
+    puzzle.shuffle_with_seed(daily_seed(day, rows, cols));
+    puzzle
+}
+
+/// What makes two scrambles the same puzzle: the starting tiles, the goal
+/// and whether the edges wrap.
+type ScrambleKey = (Vec<Tile>, Goal, bool);
+
+fn scramble_key(start: &Puzzle) -> ScrambleKey {
+    (start.tiles().to_vec(), start.goal().clone(), start.wrap())
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Screen {
+    Menu,
+    Game,
+    Stats,
+    Leaderboard,
+    Achievements,
+    Settings,
+    GoalEditor,
+    Replay,
+    Saves,
+    Profiles,
 }
 
+/// The ways to play on offer from the menu.
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
-struct Tile {
-    value: Option<u8>, // None represents the empty tile
+enum PlayMode {
+    Casual,
+    Daily,
+    TimeAttack,
+    MoveLimit,
+    Blindfold,
+    Relay,
+}
+
+impl PlayMode {
+    const ALL: [PlayMode; 6] = [
+        PlayMode::Casual,
+        PlayMode::Daily,
+        PlayMode::TimeAttack,
+        PlayMode::MoveLimit,
+        PlayMode::Blindfold,
+        PlayMode::Relay,
+    ];
+
+    fn name(self) -> Text {
+        match self {
+            PlayMode::Casual => Text::Casual,
+            PlayMode::Daily => Text::Daily,
+            PlayMode::TimeAttack => Text::TimeAttack,
+            PlayMode::MoveLimit => Text::MoveLimit,
+            PlayMode::Blindfold => Text::Blindfold,
+            PlayMode::Relay => Text::Relay,
+        }
+    }
+}
+
+/// A transient line of feedback shown below the controls.
+#[derive(Debug, Clone)]
+enum Notice {
+    Info(String),
+    Error(String),
+}
+
+/// A slide in progress, drawn by offsetting the tiles that just moved from
+/// where they came from to where the model already has them.
+#[derive(Debug, Clone)]
+struct Animation {
+    tiles: Vec<(usize, usize)>, // Cells the moved tiles now occupy
+    offset: (f32, f32),         // Rows and columns back to where each tile started
+    started: Instant,
+    duration: Duration,
+    easing: Easing,
+}
+
+impl Animation {
+    /// The animation for the slide that emptied `puzzle`'s blank cell and
+    /// filled `previous_empty`, if there was one. Moves around the edge of a
+    /// wrapping board jump instead.
+    ///
+    /// The slide keeps the `duration` and `easing` it started with, so new
+    /// settings apply from the next one.
+    fn new(
+        previous_empty: (usize, usize),
+        puzzle: &Puzzle,
+        now: Instant,
+        duration: Duration,
+        easing: Easing,
+    ) -> Option<Self> {
+        let (row, col) = puzzle.empty_position();
+        let (previous_row, previous_col) = previous_empty;
+        let length = row.abs_diff(previous_row) + col.abs_diff(previous_col);
+        let step = (
+            (row as isize - previous_row as isize).signum(),
+            (col as isize - previous_col as isize).signum(),
+        );
+
+        // The moved tiles run from the old blank towards the new one
+        let tiles = (0..length as isize)
+            .map(|k| {
+                (
+                    previous_row.wrapping_add_signed(step.0 * k),
+                    previous_col.wrapping_add_signed(step.1 * k),
+                )
+            })
+            .collect();
+
+        (length > 0 && !(puzzle.wrap() && length > 1)).then_some(Self {
+            tiles,
+            offset: (step.0 as f32, step.1 as f32),
+            started: now,
+            duration,
+            easing,
+        })
+    }
+
+    /// How far along the slide is, from 0 to 1 once eased, which may run
+    /// past 1 before settling.
+    fn progress(&self, now: Instant) -> f32 {
+        self.easing
+            .ease(now.duration_since(self.started).as_secs_f32() / self.duration.as_secs_f32())
+    }
+
+    fn is_finished(&self, now: Instant) -> bool {
+        now.duration_since(self.started) >= self.duration
+    }
 }
 
-impl Tile {
-    fn empty() -> Self {
-        Self { value: None }
+/// The direction keys held down, which repeat their moves at the player's
+/// chosen rate rather than the system's.
+#[derive(Debug, Clone, Default)]
+struct HeldKeys {
+    held: Vec<Direction>,         // In the order they were pressed
+    next_repeat: Option<Instant>, // When the newest key moves again
+}
+
+impl HeldKeys {
+    /// The way the held keys move: the newest one's, unless the opposite key
+    /// is held as well and the two cancel out.
+    fn direction(&self) -> Option<Direction> {
+        let newest = *self.held.last()?;
+        (!self.held.contains(&newest.opposite())).then_some(newest)
+    }
+
+    /// Holds the key for `direction`, returning the move it makes straight
+    /// away. The next one comes `delay` later.
+    fn press(&mut self, direction: Direction, now: Instant, delay: Duration) -> Option<Direction> {
+        if self.held.contains(&direction) {
+            return None;
+        }
+        self.held.push(direction);
+        self.next_repeat = Some(now + delay);
+        self.direction()
+    }
+
+    /// Lets go of the key for `direction`. Any key still held waits `delay`
+    /// before moving again.
+    fn release(&mut self, direction: Direction, now: Instant, delay: Duration) {
+        self.held.retain(|&held| held != direction);
+        self.next_repeat = (!self.held.is_empty()).then_some(now + delay);
     }
 
-    fn new(value: u8) -> Self {
-        Self { value: Some(value) }
+    /// The repeated move due at `now`, if one is, scheduling the next one
+    /// `interval` later.
+    fn repeat(&mut self, now: Instant, interval: Duration) -> Option<Direction> {
+        if self.next_repeat.is_none_or(|at| now < at) {
+            return None;
+        }
+        self.next_repeat = Some(now + interval);
+        self.direction()
     }
 
     fn is_empty(&self) -> bool {
-        self.value.is_none()
+        self.held.is_empty()
+    }
+}
+
+/// How far through watching [`App::last_replay`] the player is.
+#[derive(Debug, Clone)]
+struct Playback {
+    board: Puzzle,
+    step: usize,     // Recorded moves already played on `board`
+    clock: Timer,    // Replay time, running while playing
+    real_time: bool, // Follow the recorded timing instead of `REPLAY_INTERVAL`
+}
+
+impl Playback {
+    fn new(replay: &Replay, real_time: bool) -> Self {
+        Self {
+            board: replay.start().clone(),
+            step: 0,
+            clock: Timer::default(),
+            real_time,
+        }
+    }
+
+    fn is_playing(&self) -> bool {
+        self.clock.is_running()
+    }
+
+    /// Plays the next recorded move, if any, keeping the clock at its time.
+    fn advance(&mut self, replay: &Replay) {
+        if self.step < replay.len() {
+            replay.apply(self.step, &mut self.board);
+            self.step += 1;
+        }
     }
+
+    /// Recorded time of the last move played.
+    fn time(&self, replay: &Replay) -> Duration {
+        self.step
+            .checked_sub(1)
+            .map_or(Duration::ZERO, |last| replay.moves()[last].1)
+    }
+}
+
+/// A search for a solution running in the background for auto-solve.
+#[derive(Debug)]
+struct Solving {
+    cancel: Cancel,
+    progress: Snapshot, // The latest the search reported
+    started: Instant,
 }
 
+/// What a search for a solution streams back: how far it has got while it
+/// runs, then what it found.
 #[derive(Debug, Clone)]
-struct Puzzle {
-    tiles: [[Tile; GRID_SIZE]; GRID_SIZE],
-    moves: u32,
+enum SolveEvent {
+    Progress(Snapshot),
+    Finished(Option<Vec<Direction>>),
 }
 
-impl Default for Puzzle {
+/// The iced application state wrapping the puzzle model.
+#[derive(Debug)]
+struct App {
+    puzzle: Puzzle,
+    timer: Timer,
+    now: Instant, // Refreshed by every tick and move
+    records: Records,
+    leaderboard: Leaderboard,
+    pending_entry: Option<(String, Entry)>, // Qualifying solve waiting for a name, and its table
+    name_input: String,
+    achievements: Unlocked,
+    data: Option<Storage>, // The data directory every document is kept under
+    profiles: Profiles,
+    profile: Profile,      // The one playing, whose documents are loaded
+    profile_input: String, // Name of the profile to create
+    profile_error: Option<ProfileError>,
+    deleting_profile: Option<(u32, String)>, // Profile to delete, and the name typed to confirm it
+    settings: Settings,
+    picture: image::Handle,
+    sound: Player,
+    new_record: bool,           // Whether the last solve set a personal best
+    paused: bool,               // Timer frozen and board hidden
+    away: bool,                 // Paused by the window losing focus, until clicked to resume
+    countdown: Option<Instant>, // When the 3-2-1 before the clock starts began
+    show_help: bool,            // Keyboard shortcut overlay
+    log_open: bool,             // Session log panel beside the board
+    log: SessionLog,
+    session_started: Instant,
+    screen: Screen,
+    back_to: Screen, // Where Back and Escape lead from the other screens
+    game_menu: bool, // The in-game menu over the board
+    confirm_reset_stats: bool,
+    csv_notice: Option<Notice>, // How the last CSV export went, on the stats screen
+    slots: Vec<SlotContents>,   // As last read, empty until the saves screen is first opened
+    slot_notice: Option<Notice>, // How the last change to a slot went, on the saves screen
+    renaming_slot: Option<(usize, String)>, // Slot being renamed, and its new name so far
+    confirm_delete_slot: Option<usize>,
+    pending_preset: Option<usize>, // Preset waiting for the player to abandon their solve
+    confirming_shuffle: bool,      // Shuffle waiting for the player to abandon their solve
+    goal_editor: Vec<Option<u16>>, // Cells of the layout being edited, `None` while unassigned
+    goal_editor_error: Option<String>,
+    seed_input: String,
+    import_input: String,
+    code_input: String,
+    notice: Option<Notice>,
+    animation: Option<Animation>,
+    last_move_at: Option<Instant>,
+    victory: Option<Instant>, // When the player solved the current scramble, while celebrating
+    hint: Option<((usize, usize), Instant)>, // Highlighted tile and when it appeared
+    hint_pending: bool,
+    rejected: Option<(usize, usize, Instant)>, // Tile pressed that could not move, while it shakes
+    goal_flash: Option<((usize, usize), bool, Instant)>, // Cell outlined, whether the tile was already home, and when
+    held_keys: HeldKeys,
+    solving: Option<Solving>, // The search for a solution, while one runs
+    solution: Option<VecDeque<Direction>>, // Remaining auto-solve moves while playing back
+    auto_moves: u32,
+    assisted: bool, // Whether auto-solve touched this attempt, which keeps it out of the records
+    recording: Option<Replay>, // Moves of the current scramble, while it is unsolved
+    last_replay: Option<Replay>, // The most recent solve
+    ghosts: Ghosts,
+    ghost: Option<Ghost>, // The personal best raced on a retried scramble
+    playback: Option<Playback>,
+    daily: Option<Day>,              // The day whose daily puzzle is being played
+    relay: Option<Relay>,            // The relay being played, kept once finished
+    relay_stage_at: Option<Instant>, // When the relay moved on to the current stage
+    tutorial: Option<Tutorial>,
+    tutorial_undo_at: Option<Instant>, // When a move off the tutorial's script was made
+    race: Option<Race>,                // The race against the bot, until dismissed
+    counted_down: Duration,            // Timer reading the time attack clock was last brought up to
+    budget_pending: bool,              // Whether a move-limit budget is being worked out
+    pattern_database_requested: bool,  // Whether the solver's 4x4 tables were sent for
+    pattern_progress: Option<Arc<AtomicU32>>, // Percent of the tables built, while building
+    solved_scramble: Option<Puzzle>,   // Where the scramble just solved started
+    optimal_lengths: HashMap<ScrambleKey, Option<OptimalLength>>, // Searched so far
+    optimal_pending: bool,             // Whether an optimal length is being worked out
+    dailies_analysed: Option<Day>,     // The day whose daily puzzles were sent to be solved
+    solve_scored: bool,                // Whether the solve just finished has its efficiency
+    preview_tick: Option<Instant>,     // When the blindfold preview was last counted down
+    peek_until: Option<Instant>,       // When a peek at a blindfolded board ends
+    toast: Option<(String, Instant)>,  // Brief confirmation over the board and when it appeared
+    settings_changed_at: Option<Instant>, // When unsaved settings last changed
+    peeks: u32,                        // Peeks during this attempt
+    hints_used: u32,                   // Hints shown during this attempt
+    undos_used: u32,                   // Moves undone during this attempt
+    focused: Option<u16>,              // Tile with keyboard focus, which follows it as it slides
+    fullscreen: bool,
+    loaded_fonts: Vec<TileFont>,      // Bundled label fonts ready to use
+    window_size: Size,                // The window's current size, fullscreen or not
+    windowed_size: Size,              // The size to return to when leaving fullscreen
+    windowed_position: Option<Point>, // Where to return to, once the window has moved
+    system_reduces_motion: bool,      // The system's reduced-motion preference at launch
+}
+
+/// The app on the platform's storage. Tests get none at all, so they
+/// neither read nor change the documents of whoever runs them.
+impl Default for App {
     fn default() -> Self {
-        let mut tiles = [[Tile::empty(); GRID_SIZE]; GRID_SIZE];
-        let mut value = 1u8;
-
-        for i in 0..GRID_SIZE {
-            for j in 0..GRID_SIZE {
-                if i == GRID_SIZE - 1 && j == GRID_SIZE - 1 {
-                    // Last tile is empty
-                    tiles[i][j] = Tile::empty();
-                } else {
-                    tiles[i][j] = Tile::new(value);
-                    value += 1;
-                }
-            }
+        if cfg!(test) {
+            Self::with_storage(None, None)
+        } else {
+            Self::with_storage(Storage::data(), Storage::config())
         }
+    }
+}
 
-        Self { tiles, moves: 0 }
+impl App {
+    /// The app with its documents kept under the data directory `data`,
+    /// taking over those an earlier version kept in `config`.
+    fn with_storage(data: Option<Storage>, config: Option<Storage>) -> Self {
+        let profiles = open_profiles(data.as_ref(), config.as_ref());
+        let profile = profiles
+            .active()
+            .expect("there is always a profile")
+            .clone();
+        let storage = data.as_ref().map(|data| profile_paths(data, &profile));
+        let settings: Settings = load_document(storage.clone(), SETTINGS);
+        let (windowed_size, windowed_position) = saved_window(&settings);
+
+        Self {
+            puzzle: Puzzle::default(),
+            timer: Timer::default(),
+            now: Instant::now(),
+            records: load_document(storage.clone(), RECORDS),
+            leaderboard: load_leaderboard(data.as_ref()),
+            pending_entry: None,
+            name_input: String::new(),
+            achievements: load_document(storage.clone(), ACHIEVEMENTS_FILE),
+            data,
+            profiles,
+            profile,
+            profile_input: String::new(),
+            profile_error: None,
+            deleting_profile: None,
+            settings,
+            picture: image::Handle::from_bytes(PICTURE),
+            sound: Player::open(),
+            new_record: false,
+            paused: false,
+            away: false,
+            countdown: None,
+            show_help: false,
+            log_open: false,
+            log: SessionLog::default(),
+            session_started: Instant::now(),
+            screen: Screen::Game,
+            back_to: Screen::Game,
+            game_menu: false,
+            confirm_reset_stats: false,
+            csv_notice: None,
+            slots: Vec::new(),
+            slot_notice: None,
+            renaming_slot: None,
+            confirm_delete_slot: None,
+            pending_preset: None,
+            confirming_shuffle: false,
+            goal_editor: Vec::new(),
+            goal_editor_error: None,
+            seed_input: String::new(),
+            import_input: String::new(),
+            code_input: String::new(),
+            notice: None,
+            animation: None,
+            last_move_at: None,
+            victory: None,
+            hint: None,
+            hint_pending: false,
+            rejected: None,
+            goal_flash: None,
+            held_keys: HeldKeys::default(),
+            solving: None,
+            solution: None,
+            auto_moves: 0,
+            assisted: false,
+            recording: None,
+            last_replay: None,
+            ghosts: load_document(storage, GHOSTS),
+            ghost: None,
+            playback: None,
+            daily: None,
+            relay: None,
+            relay_stage_at: None,
+            tutorial: None,
+            tutorial_undo_at: None,
+            race: None,
+            counted_down: Duration::ZERO,
+            budget_pending: false,
+            pattern_database_requested: false,
+            pattern_progress: None,
+            solved_scramble: None,
+            optimal_lengths: HashMap::new(),
+            optimal_pending: false,
+            dailies_analysed: None,
+            solve_scored: false,
+            preview_tick: None,
+            peek_until: None,
+            toast: None,
+            settings_changed_at: None,
+            peeks: 0,
+            hints_used: 0,
+            undos_used: 0,
+            focused: None,
+            fullscreen: false,
+            loaded_fonts: Vec::new(),
+            window_size: windowed_size,
+            windowed_size,
+            windowed_position,
+            system_reduces_motion: system_reduces_motion(),
+        }
     }
 }
 
-impl Puzzle {
-    fn find_empty(&self) -> Option<(usize, usize)> {
-        for i in 0..GRID_SIZE {
-            for j in 0..GRID_SIZE {
-                if self.tiles[i][j].is_empty() {
-                    return Some((i, j));
-                }
+/// Whether the browser asks pages to keep animation to a minimum.
+#[cfg(target_arch = "wasm32")]
+fn system_reduces_motion() -> bool {
+    web_sys::window()
+        .and_then(|window| window.match_media("(prefers-reduced-motion: reduce)").ok())
+        .flatten()
+        .is_some_and(|query| query.matches())
+}
+
+/// Desktop systems keep the preference in too many places to look it up, so
+/// motion stays on until the setting turns it off.
+#[cfg(not(target_arch = "wasm32"))]
+fn system_reduces_motion() -> bool {
+    false
+}
+
+/// The window geometry saved in `settings`, or the default size and
+/// position for a first launch.
+fn saved_window(settings: &Settings) -> (Size, Option<Point>) {
+    let size = settings
+        .window_size
+        .map_or(window::Settings::default().size, |(width, height)| {
+            Size::new(
+                (width as f32).max(MIN_WINDOW_SIZE.width),
+                (height as f32).max(MIN_WINDOW_SIZE.height),
+            )
+        });
+    let position = settings
+        .window_position
+        .map(|(x, y)| Point::new(x as f32, y as f32));
+
+    (size, position)
+}
+
+// Names of the documents kept in storage
+const PROFILES: &str = "profiles.json";
+const RECORDS: &str = "records.json";
+const LEADERBOARD: &str = "leaderboard.json";
+const LEADERBOARD_BACKUP: &str = "leaderboard.json.bak";
+const ACHIEVEMENTS_FILE: &str = "achievements.json";
+const GHOSTS: &str = "ghosts.json";
+const SETTINGS: &str = "settings.json";
+const GAME: &str = "game.json";
+#[cfg(not(target_arch = "wasm32"))]
+const PATTERN_DATABASE_FILE: &str = "pattern-database-4x4.bin";
+
+/// The storage for the documents of `profile`, a subdirectory of the data
+/// directory `root`. Every document of a player goes through here, so that
+/// no profile reads another's; only the list of profiles, the leaderboard
+/// and the solver's tables are shared, in the data directory itself.
+fn profile_paths(root: &Storage, profile: &Profile) -> Storage {
+    root.subdirectory(&profile.dir())
+}
+
+/// The documents each profile keeps, which go with it when it is deleted.
+fn player_documents() -> Vec<String> {
+    [SETTINGS, RECORDS, ACHIEVEMENTS_FILE, GHOSTS, GAME]
+        .map(String::from)
+        .into_iter()
+        .chain((0..SAVE_SLOTS).map(slot_file))
+        .collect()
+}
+
+/// Deletes the documents `profile` kept under the data directory `root`.
+fn delete_profile_documents(root: &Storage, profile: &Profile) {
+    let storage = profile_paths(root, profile);
+    for name in player_documents() {
+        if let Err(error) = storage.remove(&name) {
+            eprintln!(
+                "warning: failed to delete {name} of {}: {error}",
+                profile.name
+            );
+        }
+    }
+}
+
+/// The list of profiles kept in the data directory `data`. The first launch
+/// with profiles makes one for whoever played before, which takes over a
+/// copy of their documents, some of them from the configuration directory
+/// `config`.
+fn open_profiles(data: Option<&Storage>, config: Option<&Storage>) -> Profiles {
+    let mut profiles: Profiles = load_document(data.cloned(), PROFILES);
+    if profiles.active().is_some() {
+        return profiles;
+    }
+
+    let earlier: Settings = load_document(config.cloned(), SETTINGS);
+    let name: String = earlier.player_name.chars().take(MAX_PROFILE_NAME).collect();
+    let profile = match profiles.create(&name) {
+        Ok(profile) => profile.clone(),
+        Err(_) => profiles
+            .create(DEFAULT_PROFILE_NAME)
+            .expect("the first profile's name is free")
+            .clone(),
+    };
+
+    let Some(data) = data else {
+        return profiles;
+    };
+    let own = profile_paths(data, &profile);
+    // Settings and records used to be kept with the configuration
+    let copied = config
+        .map_or(Ok(()), |config| {
+            storage::copy(config, &own, &[SETTINGS, RECORDS])
+        })
+        .and_then(|()| {
+            let documents = player_documents();
+            let names: Vec<&str> = documents.iter().map(String::as_str).collect();
+            storage::copy(data, &own, &names)
+        })
+        .and_then(|()| storage::save(data, PROFILES, &profiles));
+    if let Err(error) = copied {
+        eprintln!("warning: failed to set up the first profile: {error}");
+    }
+    profiles
+}
+
+/// The leaderboard saved in the data directory `data`. One that cannot be
+/// read is set aside as [`LEADERBOARD_BACKUP`] rather than lost to the next
+/// save, and the leaderboard starts afresh.
+fn load_leaderboard(data: Option<&Storage>) -> Leaderboard {
+    let Some(storage) = data else {
+        return Leaderboard::default();
+    };
+    let Some(json) = storage.load(LEADERBOARD) else {
+        return Leaderboard::default();
+    };
+
+    match storage::parse(&json) {
+        Ok(loaded) => {
+            warn_if_read_only(&loaded, LEADERBOARD);
+            loaded.document
+        }
+        Err(error) => {
+            eprintln!("warning: the leaderboard could not be read, starting a new one: {error}");
+            if let Err(error) = storage.save(LEADERBOARD_BACKUP, &json) {
+                eprintln!("warning: failed to keep the unreadable leaderboard: {error}");
             }
+            Leaderboard::default()
         }
-        None
     }
+}
 
-    fn is_adjacent_to_empty(&self, row: usize, col: usize) -> bool {
-        if let Some((empty_row, empty_col)) = self.find_empty() {
-            // Check if the tile is adjacent to the empty tile
-            (row == empty_row && (col as isize - empty_col as isize).abs() == 1)
-                || (col == empty_col && (row as isize - empty_row as isize).abs() == 1)
-        } else {
-            false
+/// Reads the document `name`, or the default if there is none or it cannot
+/// be read.
+fn load_document<T: Versioned + Default>(storage: Option<Storage>, name: &str) -> T {
+    match storage.and_then(|storage| storage::load(&storage, name)) {
+        Some(Ok(loaded)) => {
+            warn_if_read_only(&loaded, name);
+            loaded.document
+        }
+        Some(Err(error)) => {
+            eprintln!("warning: {name} could not be read, starting afresh: {error}");
+            T::default()
         }
+        None => T::default(),
     }
+}
 
-    fn move_tile(&mut self, row: usize, col: usize) -> bool {
-        if self.is_adjacent_to_empty(row, col) {
-            if let Some((empty_row, empty_col)) = self.find_empty() {
-                // Swap the tile with the empty tile
-                let temp = self.tiles[row][col];
-                self.tiles[row][col] = self.tiles[empty_row][empty_col];
-                self.tiles[empty_row][empty_col] = temp;
-                self.moves += 1;
-                return true;
+/// A document from a newer version still loads, but is never saved over.
+fn warn_if_read_only<T: Versioned>(loaded: &Loaded<T>, name: &str) {
+    if loaded.is_read_only() {
+        eprintln!(
+            "warning: {name} is from a newer version of the game; changes made now will not be saved to it"
+        );
+    }
+}
+
+/// The time every `interval`. The browser's timer reports its own kind of
+/// instant, so the time is read afresh at each tick.
+fn every(interval: Duration) -> Subscription<Instant> {
+    time::every(interval).map(|_| Instant::now())
+}
+
+/// Runs `work` on a blocking thread, away from the UI.
+#[cfg(not(target_arch = "wasm32"))]
+async fn off_ui_thread<T: Send + 'static>(work: impl FnOnce() -> T + Send + 'static) -> Option<T> {
+    tokio::task::spawn_blocking(work).await.ok()
+}
+
+/// Runs `work` straight away: the browser gives the page a single thread, so
+/// there is nowhere else to run it.
+#[cfg(target_arch = "wasm32")]
+async fn off_ui_thread<T>(work: impl FnOnce() -> T) -> Option<T> {
+    Some(work())
+}
+
+/// Hands the solver its 4x4 pattern database, loaded from the data
+/// directory `data`, or built and saved there if there is no readable copy
+/// yet. Building takes a while, and `progress` follows it in percent.
+/// Without a data directory the solver makes do with the walking distance.
+#[cfg(not(target_arch = "wasm32"))]
+async fn prepare_pattern_database(data: Option<Storage>, progress: Arc<AtomicU32>) {
+    off_ui_thread(move || {
+        let database = load_pattern_database(data.as_ref(), |done| {
+            progress.store((done * 100.0) as u32, Ordering::Relaxed);
+        });
+        if let Some(database) = database {
+            solver::install_pattern_database(database);
+        }
+    })
+    .await;
+}
+
+/// The 4x4 pattern database kept in the data directory `data`, or a new one
+/// built and saved there if there is no readable copy, `progress` following
+/// the build from 0 to 1. Returns `None` if there is no data directory.
+#[cfg(not(target_arch = "wasm32"))]
+fn load_pattern_database(
+    data: Option<&Storage>,
+    progress: impl FnMut(f32),
+) -> Option<PatternDatabase> {
+    let path = data?.path(PATTERN_DATABASE_FILE);
+
+    Some(PatternDatabase::load(&path).unwrap_or_else(|error| {
+        if path.exists() {
+            eprintln!("warning: rebuilding the solver's tables: {error}");
+        }
+        let partition = &iced_15_puzzle::pattern_database::PARTITION_4X4;
+        let database = PatternDatabase::generate(4, 4, partition, progress);
+        if let Err(error) = database.save(&path) {
+            eprintln!("warning: failed to save the solver's tables: {error}");
+        }
+        database
+    }))
+}
+
+/// Leaves the solver to the walking distance: the browser would have to
+/// build the tables on the page's only thread.
+#[cfg(target_arch = "wasm32")]
+async fn prepare_pattern_database(_data: Option<Storage>, _progress: Arc<AtomicU32>) {}
+
+async fn write_game(storage: Option<Storage>, game: SavedGame) -> Result<(), String> {
+    let storage = storage.ok_or("no data directory available")?;
+
+    off_ui_thread(move || storage::save(&storage, GAME, &game))
+        .await
+        .unwrap_or_else(|| Err(String::from("saving was interrupted")))
+}
+
+async fn read_game(storage: Option<Storage>) -> Result<Box<SavedGame>, String> {
+    let storage = storage.ok_or("no data directory available")?;
+    let loaded = off_ui_thread(move || storage::load::<SavedGame>(&storage, GAME))
+        .await
+        .flatten()
+        .ok_or("no saved game to load")?
+        .map_err(|error| format!("invalid saved game: {error}"))?;
+
+    warn_if_read_only(&loaded, GAME);
+    Ok(Box::new(loaded.document))
+}
+
+/// Reads every save slot, all of them empty without a data directory.
+async fn read_save_slots(storage: Option<Storage>) -> Vec<SlotContents> {
+    let Some(storage) = storage else {
+        return vec![SlotContents::Empty; SAVE_SLOTS];
+    };
+
+    off_ui_thread(move || read_slots(&storage))
+        .await
+        .unwrap_or_else(|| vec![SlotContents::Empty; SAVE_SLOTS])
+}
+
+async fn write_save_slot(
+    storage: Option<Storage>,
+    index: usize,
+    slot: SaveSlot,
+) -> Result<(), String> {
+    let storage = storage.ok_or("no data directory available")?;
+
+    off_ui_thread(move || write_slot(&storage, index, &slot))
+        .await
+        .unwrap_or_else(|| Err(String::from("saving was interrupted")))
+}
+
+async fn clear_save_slot(storage: Option<Storage>, index: usize) -> Result<(), String> {
+    let storage = storage.ok_or("no data directory available")?;
+
+    off_ui_thread(move || clear_slot(&storage, index))
+        .await
+        .unwrap_or_else(|| Err(String::from("deleting was interrupted")))
+}
+
+/// Searches for the next optimal move off the UI thread, falling back to the
+/// greedy move if the search runs out of time.
+async fn find_hint(puzzle: Puzzle) -> Option<(usize, usize)> {
+    off_ui_thread(move || {
+        let deadline = Instant::now() + HINT_BUDGET;
+        let direction = solver::solve(&puzzle, Some(deadline))
+            .and_then(|solution| solution.first().copied())
+            .or_else(|| solver::greedy_move(&puzzle))?;
+
+        puzzle.tile_for_direction(direction)
+    })
+    .await
+    .flatten()
+}
+
+/// Searches for a shortest solution off the UI thread, giving up after
+/// [`SOLVE_BUDGET`] or once `cancel` is raised.
+async fn find_solution(puzzle: Puzzle, cancel: Cancel) -> Option<Vec<Direction>> {
+    off_ui_thread(move || {
+        solver::solve_cancellable(&puzzle, Some(Instant::now() + SOLVE_BUDGET), &cancel)
+    })
+    .await
+    .flatten()
+}
+
+/// Like [`find_solution`], streaming how far the search has got, at most
+/// every [`SOLVER_PROGRESS_INTERVAL`], before what it found.
+fn find_solution_with_progress(puzzle: Puzzle, cancel: Cancel) -> impl Stream<Item = SolveEvent> {
+    stream::channel(1, async move |mut output: mpsc::Sender<SolveEvent>| {
+        let reporter = Mutex::new((output.clone(), None::<Instant>));
+        let progress = Progress::reporting(move |snapshot| {
+            let mut reporter = reporter.lock().unwrap_or_else(PoisonError::into_inner);
+            let (sender, sent) = &mut *reporter;
+
+            if sent.is_none_or(|sent| sent.elapsed() >= SOLVER_PROGRESS_INTERVAL) {
+                *sent = Some(Instant::now());
+                // A full channel still holds the last report, as good as this one
+                let _ = sender.try_send(SolveEvent::Progress(snapshot));
             }
+        });
+
+        let solution = off_ui_thread(move || {
+            let deadline = Some(Instant::now() + SOLVE_BUDGET);
+            solver::solve_with_progress(&puzzle, deadline, &cancel, &progress)
+        })
+        .await
+        .flatten();
+        let _ = output.send(SolveEvent::Finished(solution)).await;
+    })
+}
+
+/// The length of a shortest solution of `puzzle`, or a lower bound on it if
+/// the search runs out of time, worked out off the UI thread.
+async fn find_optimal_length(puzzle: Puzzle) -> Option<OptimalLength> {
+    off_ui_thread(move || solver::optimal_length(&puzzle, Some(Instant::now() + OPTIMAL_BUDGET)))
+        .await
+        .flatten()
+}
+
+/// The optimal lengths of `puzzles`, solved side by side off the UI thread
+/// within a single [`OPTIMAL_BUDGET`]. Boards still unsolved by then are
+/// left out.
+async fn find_optimal_lengths(puzzles: Vec<Puzzle>) -> Vec<(ScrambleKey, OptimalLength)> {
+    off_ui_thread(move || {
+        let deadline = Some(Instant::now() + OPTIMAL_BUDGET);
+        let solutions = solver::solve_many(&puzzles, deadline, &Cancel::new());
+
+        puzzles
+            .iter()
+            .zip(solutions)
+            .filter_map(|(puzzle, solution)| {
+                Some((
+                    scramble_key(puzzle),
+                    OptimalLength::Exact(solution?.len() as u32),
+                ))
+            })
+            .collect()
+    })
+    .await
+    .unwrap_or_default()
+}
+
+/// The move budget for a move-limit challenge on `puzzle`, worked out off the
+/// UI thread from its optimal solution. If the search runs out of time, twice
+/// the Manhattan distance stands in for the optimal length.
+async fn find_move_budget(puzzle: Puzzle) -> u32 {
+    let estimate = puzzle.manhattan_distance() * 2;
+
+    off_ui_thread(move || {
+        let length = solver::solve(&puzzle, Some(Instant::now() + MOVE_BUDGET_SEARCH))
+            .map_or(estimate, |solution| solution.len() as u32);
+
+        move_budget(length)
+    })
+    .await
+    .unwrap_or_else(|| move_budget(estimate))
+}
+
+impl App {
+    fn update(&mut self, message: Message) -> Task<Message> {
+        let mut tasks = vec![self.handle(message)];
+
+        // The other screens lead back to whichever of these was last open
+        if matches!(self.screen, Screen::Menu | Screen::Game) {
+            self.back_to = self.screen;
         }
-        false
-    }
 
-    fn shuffle(&mut self) {
-        // Reset to solved state first
-        *self = Self::default();
+        // Whatever replaced the board, a new move-limit challenge needs its budget
+        if self.puzzle.mode() == (GameMode::MoveLimit { budget: None }) && !self.budget_pending {
+            self.budget_pending = true;
+            let board = self.puzzle.tiles().to_vec();
 
-        // Make a series of random valid moves
-        use rand::Rng;
-        let mut rng = rand::thread_rng();
+            tasks.push(Task::perform(
+                find_move_budget(self.puzzle.clone()),
+                move |budget| Message::BudgetReady(board.clone(), budget),
+            ));
+        }
 
-        for _ in 0..100 {
-            if let Some((empty_row, empty_col)) = self.find_empty() {
-                // Find all valid moves
-                let mut valid_moves = Vec::new();
+        // The first 4x4 board of the session sets up the solver's tables
+        if !self.pattern_database_requested && PatternDatabase::fits(4, 4, &self.puzzle) {
+            self.pattern_database_requested = true;
+            let progress = Arc::new(AtomicU32::new(0));
+            self.pattern_progress = Some(progress.clone());
 
-                if empty_row > 0 {
-                    valid_moves.push((empty_row - 1, empty_col));
-                }
-                if empty_row < GRID_SIZE - 1 {
-                    valid_moves.push((empty_row + 1, empty_col));
-                }
-                if empty_col > 0 {
-                    valid_moves.push((empty_row, empty_col - 1));
-                }
-                if empty_col < GRID_SIZE - 1 {
-                    valid_moves.push((empty_row, empty_col + 1));
-                }
+            tasks.push(Task::perform(
+                prepare_pattern_database(self.data.clone(), progress),
+                |()| Message::PatternDatabaseReady,
+            ));
+        }
+
+        // A solved scramble is searched once, however often it is retried,
+        // and the solve scored once the answer is in
+        if let Some(start) = &self.solved_scramble
+            && !self.solve_scored
+        {
+            let key = scramble_key(start);
 
-                // Make a random valid move
-                if let Some((row, col)) = valid_moves.get(rng.gen_range(0..valid_moves.len())) {
-                    self.move_tile(*row, *col);
+            match self.optimal_lengths.get(&key) {
+                Some(&length) => {
+                    self.solve_scored = true;
+                    self.score_solve(length);
                 }
+                None if !self.optimal_pending => {
+                    self.optimal_pending = true;
+                    tasks.push(Task::perform(
+                        find_optimal_length(start.clone()),
+                        move |length| Message::OptimalFound(key.clone(), length),
+                    ));
+                }
+                None => {}
             }
         }
-
-        // Reset move counter after shuffling
-        self.moves = 0;
+        Task::batch(tasks)
     }
 
-    fn is_solved(&self) -> bool {
-        let mut expected_value = 1u8;
-
-        for i in 0..GRID_SIZE {
-            for j in 0..GRID_SIZE {
-                if i == GRID_SIZE - 1 && j == GRID_SIZE - 1 {
-                    // Last tile should be empty
-                    if !self.tiles[i][j].is_empty() {
-                        return false;
+    fn handle(&mut self, message: Message) -> Task<Message> {
+        match message {
+            // Letting go always ends a drag; the guards below applied when the
+            // tile was picked up
+            Message::Released(swipe) => {
+                if self.puzzle.drag().is_some() {
+                    // The tile is already most of the way there
+                    if self.make_move(Puzzle::end_drag) {
+                        self.animation = None;
                     }
                 } else {
-                    if self.tiles[i][j].value != Some(expected_value) {
-                        return false;
+                    self.puzzle.cancel_drag();
+                    if let Some(direction) = swipe {
+                        return self.handle(Message::Move(direction));
                     }
-                    expected_value += 1;
                 }
             }
-        }
+            // Stray keys and buttons reach the board only from the game
+            // screen, and not through the in-game menu
+            Message::TilePressed(..)
+            | Message::Dragged(..)
+            | Message::Scrolled(..)
+            | Message::ShowGoal(_)
+            | Message::Move(_)
+            | Message::MoveKeyPressed(_)
+            | Message::Shuffle
+            | Message::Reset
+            | Message::PastePosition
+            | Message::Undo
+            | Message::Redo
+            | Message::Hint
+            | Message::Solve
+            | Message::Peek
+            | Message::TogglePause
+            | Message::SkipCountdown
+            | Message::FocusNext
+            | Message::FocusPrevious
+            | Message::PressFocused
+                if self.screen != Screen::Game || self.game_menu => {}
+            // A paused board can be neither played nor studied, and stays
+            // out of play until the countdown is over
+            Message::TilePressed(..)
+            | Message::Dragged(..)
+            | Message::Scrolled(..)
+            | Message::Move(_)
+            | Message::Undo
+            | Message::Redo
+            | Message::Hint
+            | Message::Solve
+            | Message::Peek
+            | Message::ShowGoal(_)
+                if self.paused || self.countdown.is_some() => {}
+            // The tutorial takes moves off its script back itself, and holds
+            // the board between steps and once over
+            Message::Undo | Message::Redo | Message::Hint | Message::Solve
+                if self.tutorial.is_some() => {}
+            Message::TilePressed(..)
+            | Message::Dragged(..)
+            | Message::Scrolled(..)
+            | Message::Move(_)
+                if self.tutorial.as_ref().is_some_and(|tutorial| {
+                    self.tutorial_undo_at.is_some()
+                        || tutorial.is_waiting()
+                        || tutorial.is_finished()
+                }) => {}
+            // The board locks once a time attack runs out
+            Message::TilePressed(..)
+            | Message::Dragged(..)
+            | Message::Scrolled(..)
+            | Message::Move(_)
+            | Message::Undo
+            | Message::Redo
+            | Message::Hint
+            | Message::Solve
+                if self.puzzle.is_locked() => {}
+            // Time attacks race the clock without a break
+            Message::Pause if self.puzzle.mode().is_time_attack() => {}
+            // The player keeps their hands off the board during auto-solve
+            Message::TilePressed(..)
+            | Message::Dragged(..)
+            | Message::Scrolled(..)
+            | Message::Move(_)
+            | Message::Undo
+            | Message::Redo
+                if self.is_auto_solving() => {}
+            // Shortcuts reach the game even from other screens
+            Message::Shuffle | Message::Reset if self.screen != Screen::Game => {}
+            Message::TilePressed(..)
+            | Message::Dragged(..)
+            | Message::Scrolled(..)
+            | Message::Move(_)
+            | Message::Undo
+            | Message::Redo
+                if self.confirming_shuffle => {}
+            Message::TilePressed(..) | Message::Dragged(..) | Message::Scrolled(..)
+                if self.victory.is_some() => {}
+            Message::TilePressed(row, col) => {
+                let slide_count = self.settings.slide_count;
+                if !self.make_move(|puzzle| puzzle.move_line(row, col, slide_count)) {
+                    self.play(Sound::Rejected);
+                    if !self.puzzle.tile(row, col).is_empty() {
+                        self.rejected = Some((row, col, self.now));
+                    }
+                }
+            }
+            Message::Dragged(start, offset) => {
+                let pitch = self.tile_pitch();
 
-        true
-    }
+                // Only a tile beside the blank can be picked up
+                if self.puzzle.drag().is_none() {
+                    let Some((row, col)) = self.cell_at(start) else {
+                        return Task::none();
+                    };
+                    if !self.puzzle.start_drag(row, col) {
+                        return Task::none();
+                    }
+                }
 
-    fn update(&mut self, message: Message) {
-        match message {
-            Message::TilePressed(row, col) => {
-                self.move_tile(row, col);
+                if let Some(drag) = self.puzzle.drag() {
+                    let along = match drag.direction() {
+                        Direction::Up => -offset.y,
+                        Direction::Down => offset.y,
+                        Direction::Left => -offset.x,
+                        Direction::Right => offset.x,
+                    };
+                    self.puzzle.drag_to(along / pitch);
+                }
+            }
+            // The wheel slides the hovered tile, and any between it and the
+            // blank, when that is the way it turned; other turns do nothing
+            Message::Scrolled(at, direction) => {
+                if self.puzzle.drag().is_none()
+                    && let Some((row, col)) = self.cell_at(at)
+                    && self.puzzle.line_direction(row, col) == Some(direction)
+                {
+                    let slide_count = self.settings.slide_count;
+                    self.make_move(|puzzle| puzzle.move_line(row, col, slide_count));
+                }
+            }
+            // Blindfolded tiles keep where they belong to themselves
+            Message::ShowGoal(_) if self.is_hidden() => {}
+            Message::ShowGoal(at) => {
+                if let Some((row, col)) = self.cell_at(at) {
+                    // The clock may not have ticked for a while on an idle board
+                    self.now = Instant::now();
+                    let size = (self.puzzle.rows(), self.puzzle.cols());
+                    self.goal_flash = Some(match self.puzzle.tile(row, col).value() {
+                        Some(value) if !self.puzzle.is_tile_in_place(row, col) => (
+                            goal_position(value, size, self.puzzle.goal()),
+                            false,
+                            self.now,
+                        ),
+                        // The blank, or a tile already home
+                        _ => ((row, col), true, self.now),
+                    });
+                }
+            }
+            Message::MoveKeyPressed(direction) => {
+                let (now, delay) = (Instant::now(), self.key_repeat_delay());
+                if let Some(direction) = self.held_keys.press(direction, now, delay) {
+                    return self.handle(Message::Move(direction));
+                }
+            }
+            Message::MoveKeyReleased(direction) => {
+                let delay = self.key_repeat_delay();
+                self.held_keys.release(direction, Instant::now(), delay);
+            }
+            // Keys let go of elsewhere never send their release
+            // Time attacks pause too, or their clock would drain unseen
+            Message::WindowUnfocused => {
+                self.held_keys = HeldKeys::default();
+                if self.settings.pause_when_away
+                    && (self.timer.is_running() || self.countdown.is_some())
+                {
+                    self.pause();
+                    self.away = true;
+                }
+            }
+            Message::Move(direction) => {
+                if !self.make_move(|puzzle| puzzle.move_direction(direction)) {
+                    self.play(Sound::Rejected);
+                }
             }
             Message::Shuffle => {
-                self.shuffle();
+                if self.settings.confirm_shuffle && self.is_mid_solve() {
+                    self.confirming_shuffle = true;
+                } else {
+                    self.puzzle.shuffle();
+                    self.record_shuffle();
+                }
             }
-        }
-    }
+            Message::ConfirmShuffle => {
+                if self.confirming_shuffle {
+                    self.confirming_shuffle = false;
+                    self.puzzle.shuffle();
+                    self.record_shuffle();
+                }
+            }
+            Message::CancelShuffle => {
+                self.confirming_shuffle = false;
+            }
+            Message::ShuffleSeeded(seed) => {
+                self.puzzle.shuffle_with_seed(seed);
+                self.record_shuffle();
+            }
+            Message::SeedInputChanged(input) => {
+                self.seed_input = input;
+            }
+            Message::ImportInputChanged(input) => {
+                self.import_input = input;
+            }
+            Message::ImportBoard(input) => {
+                match Puzzle::parse_permutation_in(&input, self.settings.label_mode) {
+                    Ok(puzzle) => self.import(puzzle),
+                    Err(error) => {
+                        self.notice = Some(Notice::Error(self.trf(Text::ImportFailed, &[&error])));
+                    }
+                }
+            }
+            Message::CodeInputChanged(input) => {
+                self.code_input = input;
+            }
+            Message::LoadCode(code) => match decode_board(&code) {
+                Ok(puzzle) => self.import(puzzle),
+                Err(error) => {
+                    self.notice = Some(Notice::Error(self.trf(Text::InvalidCode, &[&error])));
+                }
+            },
+            Message::SetSize(rows, cols) => {
+                self.puzzle = self.new_board(
+                    rows.clamp(MIN_GRID_SIZE, MAX_GRID_SIZE),
+                    cols.clamp(MIN_GRID_SIZE, MAX_GRID_SIZE),
+                );
+                self.restart();
+                self.remember_size();
+            }
+            Message::Preset(index) => {
+                if self.is_mid_solve() {
+                    self.pending_preset = Some(index);
+                } else {
+                    self.start_preset(index);
+                }
+            }
+            Message::Daily => {
+                let today = Day::today();
+                let (rows, cols) = (self.puzzle.rows(), self.puzzle.cols());
 
-    fn view(&self) -> Element<'_, Message> {
-        let mut grid_rows = Vec::new();
+                // Each day's result stands; there is no second attempt
+                if self
+                    .records
+                    .daily_result(&Records::key(rows, cols, false), today)
+                    .is_none()
+                {
+                    self.puzzle = daily_puzzle(today, rows, cols);
+                    self.record_shuffle();
+                    self.daily = Some(today);
+                }
 
-        for i in 0..GRID_SIZE {
-            let mut row_tiles = Vec::new();
+                // The first daily of the day has every preset size's worked
+                // out together, so whichever is solved is scored at once
+                if self.dailies_analysed != Some(today) {
+                    self.dailies_analysed = Some(today);
+                    let mut sizes: Vec<_> = PRESETS.iter().map(|&(_, size)| (size, size)).collect();
+                    if !sizes.contains(&(rows, cols)) {
+                        sizes.push((rows, cols));
+                    }
+                    let puzzles = sizes
+                        .into_iter()
+                        .map(|(rows, cols)| daily_puzzle(today, rows, cols))
+                        .collect();
 
-            for j in 0..GRID_SIZE {
-                let tile = &self.tiles[i][j];
+                    return Task::perform(find_optimal_lengths(puzzles), Message::DailiesAnalysed);
+                }
+            }
+            Message::StartRelay => {
+                let size = RELAY_SIZES[0];
 
-                let tile_button = if tile.is_empty() {
-                    button("").width(Length::Fill).height(Length::Fill)
-                } else {
-                    button(
-                        text(tile.value.unwrap().to_string())
-                            .size(24)
-                            .height(Length::Fill)
-                            .width(Length::Fill)
-                            .center(),
-                    )
-                    .width(Length::Fill)
-                    .height(Length::Fill)
-                    .on_press(Message::TilePressed(i, j))
-                };
+                self.puzzle = self.new_board(size, size);
+                self.puzzle.shuffle();
+                self.restart();
+                self.relay = Some(Relay::default());
+                self.log_shuffle();
+                self.records.record_shuffle(&self.records_key());
+                self.save_records();
+            }
+            Message::StartRace => {
+                // Both boards come from one seed, so the bot faces the very
+                // same scramble
+                let seed = rand::random();
+                let (rows, cols) = (self.puzzle.rows(), self.puzzle.cols());
+                let mut bot = self.new_board(rows, cols);
+                bot.shuffle_with_seed(seed);
 
-                row_tiles.push(tile_button.into());
+                self.puzzle = self.new_board(rows, cols);
+                self.puzzle.shuffle_with_seed(seed);
+                self.record_shuffle();
+                self.race = Some(Race::new(bot.clone()));
+                let board = bot.tiles().to_vec();
+
+                return Task::perform(find_solution(bot, Cancel::new()), move |solution| {
+                    Message::RaceSolution(board.clone(), solution)
+                });
             }
+            Message::RaceSolution(board, solution) => {
+                // A race abandoned since has nothing left to play
+                let Some(race) = self
+                    .race
+                    .as_mut()
+                    .filter(|race| race.bot().tiles() == board && !race.is_ready())
+                else {
+                    return Task::none();
+                };
 
-            grid_rows.push(row(row_tiles).spacing(5).into());
-        }
+                match solution {
+                    Some(solution) => race.set_solution(solution),
+                    None => {
+                        self.race = None;
+                        self.notice = Some(Notice::Error(String::from(self.tr(Text::BotGaveUp))));
+                    }
+                }
+            }
+            Message::DismissRace => {
+                self.race = None;
+            }
+            Message::ConfirmPreset(confirmed) => {
+                if let Some(index) = self.pending_preset.take()
+                    && confirmed
+                {
+                    self.start_preset(index);
+                }
+            }
+            Message::SetDifficulty(difficulty) => {
+                self.puzzle.set_difficulty(difficulty);
+            }
+            Message::SetGoal(goal) => {
+                self.puzzle.set_goal(goal);
+                self.puzzle.reset();
+                self.restart();
+            }
+            Message::SetWrap(wrap) => {
+                self.log_mode(LoggedMode::Wrap, self.puzzle.wrap(), wrap);
+                // The variant plays too differently to continue a solve
+                self.puzzle.set_wrap(wrap);
+                self.puzzle.reset();
+                self.restart();
+            }
+            Message::EditGoal => {
+                let cells = self.puzzle.rows() * self.puzzle.cols();
 
-        let status_text = if self.is_solved() {
-            text("Puzzle Solved! 🎉").size(24)
-        } else {
-            text(format!("Moves: {}", self.moves)).size(20)
-        };
+                self.goal_editor = match &self.settings.custom_goal {
+                    Some(layout) if layout.len() == cells => {
+                        layout.iter().copied().map(Some).collect()
+                    }
+                    _ => vec![None; cells],
+                };
+                self.goal_editor_error = None;
+                self.screen = Screen::GoalEditor;
+            }
+            Message::EditGoalCell(index) => {
+                if self.goal_editor[index].take().is_none() {
+                    // Hand out the numbers in order, the blank last
+                    let cells = self.goal_editor.len();
+                    self.goal_editor[index] = (1..cells as u16)
+                        .chain([0])
+                        .find(|value| !self.goal_editor.contains(&Some(*value)));
+                }
+                self.goal_editor_error = None;
+            }
+            Message::ClearGoalEditor => {
+                self.goal_editor.fill(None);
+                self.goal_editor_error = None;
+            }
+            Message::SaveCustomGoal => {
+                let unassigned = self
+                    .goal_editor
+                    .iter()
+                    .filter(|cell| cell.is_none())
+                    .count();
+                if unassigned > 0 {
+                    self.goal_editor_error = Some(self.trf(Text::UnassignedCells, &[&unassigned]));
+                    return Task::none();
+                }
 
-        container(
-            column![
-                text("15 Puzzle").size(32),
-                status_text,
-                column(grid_rows).spacing(5),
-                button("Shuffle").on_press(Message::Shuffle)
-            ]
-            .spacing(20)
-            .align_x(Alignment::Center),
+                let layout: Vec<u16> = self.goal_editor.iter().flatten().copied().collect();
+                match Goal::custom(layout.clone()) {
+                    Ok(goal) => {
+                        self.settings.custom_goal = Some(layout);
+                        self.save_settings();
+                        self.puzzle.set_goal(goal);
+                        self.puzzle.reset();
+                        self.restart();
+                        self.screen = Screen::Game;
+                    }
+                    Err(error) => {
+                        self.goal_editor_error = Some(self.trf(Text::InvalidGoal, &[&error]));
+                    }
+                }
+            }
+            Message::Undo => {
+                if self.make_move(Puzzle::undo) {
+                    self.undos_used += 1;
+                }
+            }
+            Message::Redo => {
+                self.make_move(Puzzle::redo);
+            }
+            Message::Retry => {
+                // Retrying the daily is still the daily, and the same
+                // scramble keeps its move budget
+                let (daily, budget) = (self.daily, self.puzzle.mode().budget());
+                self.puzzle.retry();
+                self.restart();
+                self.daily = daily;
+                if let Some(budget) = budget
+                    && self.puzzle.mode().is_move_limit()
+                {
+                    self.puzzle.set_mode(GameMode::MoveLimit {
+                        budget: Some(budget),
+                    });
+                }
+            }
+            Message::Reset => {
+                self.puzzle.reset();
+                self.restart();
+            }
+            Message::Pause => self.pause(),
+            Message::Resume => {
+                // The clock waits for the countdown, so it finds the player ready
+                if self.paused {
+                    self.now = Instant::now();
+                    self.countdown = Some(self.now);
+                    self.paused = false;
+                    self.away = false;
+                }
+            }
+            Message::SkipCountdown => {
+                if self.countdown.is_some() {
+                    self.end_countdown(Instant::now());
+                }
+            }
+            Message::TogglePause => {
+                return self.update(if self.paused {
+                    Message::Resume
+                } else {
+                    Message::Pause
+                });
+            }
+            Message::ToggleHelp => {
+                self.show_help = !self.show_help;
+            }
+            Message::FocusNext => self.cycle_focus(true),
+            Message::FocusPrevious => self.cycle_focus(false),
+            // Pressing goes through the same checks as a click
+            Message::PressFocused => {
+                if let Some((row, col)) = self.focused_cell() {
+                    return self.handle(Message::TilePressed(row, col));
+                }
+            }
+            Message::Dismiss => {
+                if self.show_help {
+                    self.show_help = false;
+                } else if self.confirming_shuffle {
+                    self.confirming_shuffle = false;
+                } else if self.pending_preset.is_some() {
+                    self.pending_preset = None;
+                } else if self.confirm_reset_stats {
+                    self.confirm_reset_stats = false;
+                } else if self.deleting_profile.is_some() {
+                    self.deleting_profile = None;
+                } else if self.confirm_delete_slot.is_some() {
+                    self.confirm_delete_slot = None;
+                } else if self.renaming_slot.is_some() {
+                    self.renaming_slot = None;
+                } else if self.victory.is_some() {
+                    self.submit_entry();
+                    self.victory = None;
+                } else if self.screen == Screen::Game {
+                    return self.handle(if self.game_menu {
+                        Message::CloseGameMenu
+                    } else {
+                        Message::OpenGameMenu
+                    });
+                } else if self.screen != Screen::Menu {
+                    self.screen = self.back_to;
+                }
+            }
+            Message::ShowScreen(screen) => {
+                self.screen = screen;
+                self.game_menu = false;
+                self.confirm_reset_stats = false;
+                self.csv_notice = None;
+                self.slot_notice = None;
+                self.renaming_slot = None;
+                self.confirm_delete_slot = None;
+                self.profile_input.clear();
+                self.profile_error = None;
+                self.deleting_profile = None;
+                if screen == Screen::Saves {
+                    return Task::perform(read_save_slots(self.storage()), Message::SlotsRead);
+                }
+            }
+            Message::Play(mode) => {
+                let (rows, cols) = self.settings.grid_size;
+                self.screen = Screen::Game;
+
+                match mode {
+                    PlayMode::Relay => return self.handle(Message::StartRelay),
+                    PlayMode::Daily => {
+                        self.puzzle = self.new_board(rows, cols);
+                        self.restart();
+                        return self.handle(Message::Daily);
+                    }
+                    _ => {
+                        // The challenges are the settings' own, one at a time
+                        let _ = self.handle(Message::SetTimeAttack(mode == PlayMode::TimeAttack));
+                        let _ = self.handle(Message::SetMoveLimit(mode == PlayMode::MoveLimit));
+                        let _ = self.handle(Message::SetBlindfold(mode == PlayMode::Blindfold));
+                        self.puzzle = self.new_board(rows, cols);
+                        self.puzzle.shuffle();
+                        self.record_shuffle();
+                    }
+                }
+            }
+            Message::SetMenuSize(rows, cols) => {
+                self.settings.grid_size = (
+                    rows.clamp(MIN_GRID_SIZE, MAX_GRID_SIZE),
+                    cols.clamp(MIN_GRID_SIZE, MAX_GRID_SIZE),
+                );
+                self.save_settings();
+            }
+            // The menu pauses the game as the pause button would
+            Message::OpenGameMenu => {
+                let _ = self.handle(Message::Pause);
+                self.game_menu = true;
+            }
+            Message::CloseGameMenu => {
+                self.game_menu = false;
+                return self.handle(Message::Resume);
+            }
+            Message::QuitToMenu => {
+                let _ = self.handle(Message::Pause);
+                self.game_menu = false;
+                self.screen = Screen::Menu;
+            }
+            Message::StartTutorial => {
+                self.screen = Screen::Game;
+                self.play_tutorial(Tutorial::default());
+            }
+            Message::BeginTutorialStep => {
+                if self.tutorial.as_ref().is_some_and(Tutorial::is_waiting)
+                    && let Some(mut tutorial) = self.tutorial.take()
+                {
+                    tutorial.begin_step();
+                    self.play_tutorial(tutorial);
+                }
+            }
+            Message::EndTutorial => {
+                let (rows, cols) = self.settings.grid_size;
+                self.puzzle = self.new_board(rows, cols);
+                self.restart();
+                self.screen = Screen::Menu;
+            }
+            Message::ExportCsv => {
+                self.csv_notice = None;
+                return Task::perform(
+                    export::save_csv(self.records.solves_csv()),
+                    Message::CsvExported,
+                );
+            }
+            Message::CsvExported(Ok(None)) => {}
+            Message::CsvExported(Ok(Some(path))) => {
+                self.csv_notice = Some(Notice::Info(self.trf(Text::CsvExported, &[&path])));
+            }
+            Message::CsvExported(Err(error)) => {
+                self.csv_notice = Some(Notice::Error(self.trf(Text::ExportFailed, &[&error])));
+            }
+            Message::ResetStats => {
+                self.confirm_reset_stats = true;
+            }
+            Message::ConfirmResetStats(confirmed) => {
+                if confirmed {
+                    self.records.reset_stats();
+                    self.save_records();
+                }
+                self.confirm_reset_stats = false;
+            }
+            Message::Hint => {
+                self.hint_pending = true;
+                let board = self.puzzle.tiles().to_vec();
+
+                return Task::perform(find_hint(self.puzzle.clone()), move |hint| {
+                    Message::HintReady(board.clone(), hint)
+                });
+            }
+            Message::HintReady(board, hint) => {
+                self.hint_pending = false;
+
+                // Drop hints for a board the player has already moved on from
+                if board == self.puzzle.tiles() {
+                    self.now = Instant::now();
+                    self.hint = hint.map(|cell| (cell, self.now));
+                    self.hints_used += u32::from(self.hint.is_some());
+                    if self.hint.is_some() {
+                        self.log(LogEvent::Hint);
+                    }
+                }
+            }
+            Message::Solve => {
+                let solving = Solving {
+                    cancel: Cancel::new(),
+                    progress: Snapshot::default(),
+                    started: Instant::now(),
+                };
+                let search =
+                    find_solution_with_progress(self.puzzle.clone(), solving.cancel.clone());
+                self.solving = Some(solving);
+                let board = self.puzzle.tiles().to_vec();
+
+                return Task::run(search, move |event| match event {
+                    SolveEvent::Progress(snapshot) => {
+                        Message::SolverProgress(board.clone(), snapshot)
+                    }
+                    SolveEvent::Finished(solution) => {
+                        Message::SolutionReady(board.clone(), solution)
+                    }
+                });
+            }
+            Message::SolverProgress(board, snapshot) => {
+                if let Some(solving) = &mut self.solving
+                    && board == self.puzzle.tiles()
+                {
+                    solving.progress = snapshot;
+                }
+            }
+            Message::SolutionReady(board, solution) => {
+                // A stopped or outdated search has nothing left to play
+                if self.solving.is_none() || board != self.puzzle.tiles() {
+                    return Task::none();
+                }
+
+                self.solving = None;
+                match solution {
+                    Some(solution) => self.solution = Some(solution.into()),
+                    None => {
+                        self.notice =
+                            Some(Notice::Error(String::from(self.tr(Text::NoSolutionInTime))));
+                    }
+                }
+            }
+            Message::AutoStep => {
+                let Some(direction) = self.solution.as_mut().and_then(VecDeque::pop_front) else {
+                    self.solution = None;
+                    return Task::none();
+                };
+
+                self.assisted = true;
+                if self.make_move(|puzzle| puzzle.move_direction(direction)) {
+                    self.auto_moves += 1;
+                }
+
+                if self.solution.as_ref().is_some_and(VecDeque::is_empty) {
+                    self.solution = None;
+                }
+            }
+            Message::StopSolve => {
+                self.cancel_solve();
+                self.solution = None;
+            }
+            Message::Tick(now) => {
+                self.now = now;
+                if self
+                    .countdown
+                    .is_some_and(|began| now.duration_since(began) >= COUNTDOWN)
+                {
+                    self.end_countdown(now);
+                }
+                if self.timer.is_running() {
+                    self.count_down();
+                }
+                if self
+                    .tutorial_undo_at
+                    .is_some_and(|at| now.duration_since(at) >= TUTORIAL_UNDO_DELAY)
+                {
+                    self.tutorial_undo_at = None;
+                    self.make_move(Puzzle::undo);
+                }
+
+                self.advance_race();
+                if let Some(ghost) = &mut self.ghost {
+                    ghost.advance_to(self.timer.elapsed(now));
+                }
+
+                if let Some(counted) = self.preview_tick {
+                    self.puzzle
+                        .count_down_preview(now.saturating_duration_since(counted));
+                }
+                self.preview_tick = Some(now);
+                if self.peek_until.is_some_and(|until| now >= until) {
+                    self.peek_until = None;
+                }
+
+                if self
+                    .animation
+                    .as_ref()
+                    .is_some_and(|animation| animation.is_finished(now))
+                {
+                    self.animation = None;
+                }
+                if self
+                    .rejected
+                    .is_some_and(|(_, _, at)| now.duration_since(at) >= REJECTION_CUE)
+                {
+                    self.rejected = None;
+                }
+                if self
+                    .goal_flash
+                    .is_some_and(|(_, _, at)| now.duration_since(at) >= GOAL_FLASH)
+                {
+                    self.goal_flash = None;
+                }
+
+                if let Some((_, shown_at)) = self.hint
+                    && now.duration_since(shown_at) >= HINT_DURATION
+                {
+                    self.hint = None;
+                }
+                if let Some((_, shown_at)) = self.toast
+                    && now.duration_since(shown_at) >= TOAST_DURATION
+                {
+                    self.toast = None;
+                }
+                if self
+                    .relay_stage_at
+                    .is_some_and(|staged_at| now.duration_since(staged_at) >= RELAY_INTERSTITIAL)
+                {
+                    self.relay_stage_at = None;
+                }
+                if self
+                    .settings_changed_at
+                    .is_some_and(|changed_at| now.duration_since(changed_at) >= SETTINGS_SAVE_DELAY)
+                {
+                    self.write_settings();
+                }
+
+                let interval = Duration::from_millis(self.settings.key_repeat_interval_ms);
+                if let Some(direction) = self.held_keys.repeat(now, interval) {
+                    return self.handle(Message::Move(direction));
+                }
+            }
+            Message::DismissVictory => {
+                self.submit_entry();
+                self.victory = None;
+            }
+            Message::NameInputChanged(name) => {
+                self.name_input = name;
+            }
+            Message::SubmitName => {
+                self.submit_entry();
+            }
+            Message::DeleteEntry(key, place) => {
+                if self.leaderboard.remove(&key, place).is_some() {
+                    self.save_leaderboard();
+                }
+            }
+            Message::WatchReplay => {
+                if let Some(replay) = &self.last_replay {
+                    let real_time = self.playback.as_ref().is_some_and(|p| p.real_time);
+                    self.playback = Some(Playback::new(replay, real_time));
+                    self.screen = Screen::Replay;
+                }
+            }
+            Message::ToggleReplay => {
+                if let (Some(playback), Some(replay)) = (&mut self.playback, &self.last_replay) {
+                    self.now = Instant::now();
+
+                    if playback.is_playing() {
+                        playback.clock.stop(self.now);
+                    } else {
+                        // Playing from the end starts over
+                        if playback.step == replay.len() {
+                            *playback = Playback::new(replay, playback.real_time);
+                        }
+                        playback.clock = Timer::with_elapsed(playback.time(replay));
+                        playback.clock.start(self.now);
+                    }
+                }
+            }
+            Message::StepReplay => {
+                if let (Some(playback), Some(replay)) = (&mut self.playback, &self.last_replay) {
+                    playback.advance(replay);
+                    playback.clock = Timer::with_elapsed(playback.time(replay));
+                }
+            }
+            Message::RestartReplay => {
+                if let (Some(playback), Some(replay)) = (&mut self.playback, &self.last_replay) {
+                    *playback = Playback::new(replay, playback.real_time);
+                }
+            }
+            Message::SetReplayRealTime(real_time) => {
+                if let Some(playback) = &mut self.playback {
+                    playback.real_time = real_time;
+                }
+            }
+            Message::ReplayTick(now) => {
+                if let (Some(playback), Some(replay)) = (&mut self.playback, &self.last_replay) {
+                    self.now = now;
+
+                    if playback.real_time {
+                        let clock = playback.clock.elapsed(now);
+                        while playback.step < replay.len()
+                            && replay.moves()[playback.step].1 <= clock
+                        {
+                            playback.advance(replay);
+                        }
+                    } else {
+                        playback.advance(replay);
+                    }
+
+                    if playback.step == replay.len() {
+                        playback.clock.stop(now);
+                    }
+                }
+            }
+            // Sharing the position would give away a blindfolded board
+            Message::CopyPosition if self.is_hidden() => {}
+            Message::ToggleLog => self.log_open = !self.log_open,
+            Message::CopyLog => {
+                self.toast = Some((String::from(self.tr(Text::Copied)), self.now));
+                return clipboard::write(self.log.text());
+            }
+            Message::CopyPosition => {
+                self.toast = Some((String::from(self.tr(Text::Copied)), self.now));
+                return clipboard::write(self.puzzle.board_text_in(self.settings.label_mode));
+            }
+            Message::CopyCode if self.is_hidden() => {}
+            Message::CopyCode => {
+                self.toast = Some((String::from(self.tr(Text::Copied)), self.now));
+                return clipboard::write(encode_board(&self.puzzle));
+            }
+            Message::PastePosition => {
+                return clipboard::read().map(Message::ClipboardRead);
+            }
+            // A pasted board goes through the same checks as a typed one
+            Message::ClipboardRead(Some(contents)) if !contents.trim().is_empty() => {
+                return self.handle(Message::ImportBoard(contents));
+            }
+            Message::ClipboardRead(_) => {
+                self.notice = Some(Notice::Error(String::from(self.tr(Text::ClipboardEmpty))));
+            }
+            Message::CopySolution => {
+                self.notice = Some(Notice::Info(String::from(self.tr(Text::SolutionCopied))));
+                return clipboard::write(self.puzzle.notation());
+            }
+            Message::Save => {
+                return Task::perform(
+                    write_game(self.storage(), self.saved_game()),
+                    Message::Saved,
+                );
+            }
+            Message::Saved(result) => {
+                self.notice = Some(match result {
+                    Ok(()) => Notice::Info(String::from(self.tr(Text::GameSaved))),
+                    Err(error) => Notice::Error(self.trf(Text::SaveFailed, &[&error])),
+                });
+            }
+            // A picture would give away a blindfolded board too
+            Message::ExportImage if self.is_hidden() => {}
+            Message::ExportImage => {
+                let caption = self.puzzle.is_scrambled().then(|| {
+                    self.trf(
+                        Text::MovesInTime,
+                        &[
+                            &self.puzzle.moves(),
+                            &self.format_time(self.timer.elapsed(self.now), Precision::Tenths),
+                        ],
+                    )
+                });
+                let picture =
+                    export::render(&self.puzzle, self.settings.label_mode, caption.as_deref());
+
+                return match export::encode_png(&picture) {
+                    Ok(png) => Task::perform(export::save_png(png), Message::Exported),
+                    Err(error) => self.handle(Message::Exported(Err(error))),
+                };
+            }
+            Message::Exported(Ok(None)) => {}
+            Message::Exported(Ok(Some(path))) => {
+                self.notice = Some(Notice::Info(self.trf(Text::ImageExported, &[&path])));
+            }
+            Message::Exported(Err(error)) => {
+                self.notice = Some(Notice::Error(self.trf(Text::ExportFailed, &[&error])));
+            }
+            Message::Load => {
+                return Task::perform(read_game(self.storage()), Message::Loaded);
+            }
+            Message::Loaded(Ok(saved)) => {
+                self.resume(*saved);
+                self.notice = Some(Notice::Info(String::from(self.tr(Text::GameLoaded))));
+            }
+            Message::Loaded(Err(error)) => {
+                self.notice = Some(Notice::Error(self.trf(Text::LoadFailed, &[&error])));
+            }
+            Message::SlotsRead(slots) => {
+                self.slots = slots;
+            }
+            // Saving over a slot keeps its name
+            Message::SaveToSlot(index) => {
+                let name = match self.slots.get(index).and_then(SlotContents::saved) {
+                    Some(slot) => slot.name.clone(),
+                    None => self.trf(Text::SlotName, &[&(index + 1)]),
+                };
+                let slot = SaveSlot {
+                    name: name.clone(),
+                    game: self.saved_game(),
+                };
+
+                return Task::perform(
+                    write_save_slot(self.storage(), index, slot),
+                    move |result| Message::SlotSaved(name.clone(), result),
+                );
+            }
+            Message::SlotSaved(name, result) => {
+                return self.slot_changed(result, self.trf(Text::SlotSaved, &[&name]));
+            }
+            Message::LoadSlot(index) => {
+                if let Some(slot) = self.slots.get(index).and_then(SlotContents::saved) {
+                    let (name, game) = (slot.name.clone(), slot.game.clone());
+                    self.resume(game);
+                    self.screen = Screen::Game;
+                    self.notice = Some(Notice::Info(self.trf(Text::SlotLoaded, &[&name])));
+                }
+            }
+            Message::RenameSlot(index) => {
+                if let Some(slot) = self.slots.get(index).and_then(SlotContents::saved) {
+                    self.renaming_slot = Some((index, slot.name.clone()));
+                    self.confirm_delete_slot = None;
+                }
+            }
+            Message::SlotNameChanged(input) => {
+                if let Some((_, name)) = &mut self.renaming_slot {
+                    *name = input;
+                }
+            }
+            Message::SubmitSlotName => {
+                let Some((index, name)) = self.renaming_slot.take() else {
+                    return Task::none();
+                };
+                let name = name.trim();
+                if let Some(slot) = self.slots.get(index).and_then(SlotContents::saved)
+                    && !name.is_empty()
+                {
+                    let renamed = SaveSlot {
+                        name: String::from(name),
+                        game: slot.game.clone(),
+                    };
+                    return Task::perform(
+                        write_save_slot(self.storage(), index, renamed),
+                        Message::SlotRenamed,
+                    );
+                }
+            }
+            Message::SlotRenamed(result) => {
+                return self.slot_changed(result, String::new());
+            }
+            Message::DeleteSlot(index) => {
+                self.confirm_delete_slot = Some(index);
+                self.renaming_slot = None;
+            }
+            Message::ConfirmDeleteSlot(confirmed) => {
+                if let Some(index) = self.confirm_delete_slot.take()
+                    && confirmed
+                {
+                    return Task::perform(
+                        clear_save_slot(self.storage(), index),
+                        Message::SlotDeleted,
+                    );
+                }
+            }
+            Message::SlotDeleted(result) => {
+                return self.slot_changed(result, String::from(self.tr(Text::SlotDeleted)));
+            }
+            Message::ProfileInputChanged(input) => {
+                self.profile_input = input;
+                self.profile_error = None;
+            }
+            // A new profile is picked straight away
+            Message::CreateProfile => match self.profiles.create(&self.profile_input) {
+                Ok(profile) => {
+                    let id = profile.id;
+                    self.profile_input.clear();
+                    return self.handle(Message::SelectProfile(id));
+                }
+                Err(error) => self.profile_error = Some(error),
+            },
+            Message::SelectProfile(id) => {
+                if id != self.profile.id && self.profiles.select(id) {
+                    // The profile leaving keeps its game, as on closing
+                    let leaving = self.settings.auto_save.then(|| {
+                        Task::future(write_game(self.storage(), self.saved_game())).discard()
+                    });
+                    if self.settings_changed_at.is_some() {
+                        self.write_settings();
+                    }
+                    self.save_profiles();
+                    self.open_profile();
+                    self.screen = Screen::Menu;
+                    return leaving.unwrap_or_else(Task::none);
+                }
+                self.save_profiles();
+                self.screen = Screen::Menu;
+            }
+            Message::DeleteProfile(id) => {
+                self.deleting_profile = Some((id, String::new()));
+            }
+            Message::DeleteConfirmationChanged(input) => {
+                if let Some((_, typed)) = &mut self.deleting_profile {
+                    *typed = input;
+                }
+            }
+            // Only the name typed out in full deletes a profile
+            Message::ConfirmDeleteProfile(confirmed) => {
+                if let Some((id, typed)) = self.deleting_profile.take()
+                    && confirmed
+                    && self
+                        .profiles
+                        .get(id)
+                        .is_some_and(|profile| profile.name == typed)
+                    && let Some(profile) = self.profiles.remove(id)
+                {
+                    self.save_profiles();
+                    if let Some(data) = &self.data {
+                        delete_profile_documents(data, &profile);
+                    }
+                }
+            }
+            Message::SetReduceMotion(reduce_motion) => {
+                self.settings.reduce_motion = Some(reduce_motion);
+                self.save_settings();
+            }
+            Message::SetKeyScheme(key_scheme) => {
+                self.settings.key_scheme = key_scheme;
+                self.save_settings();
+            }
+            Message::SetKeyRepeatDelay(ms) => {
+                self.settings.key_repeat_delay_ms = u64::from(ms);
+                self.save_settings();
+            }
+            Message::SetKeyRepeatInterval(ms) => {
+                self.settings.key_repeat_interval_ms = u64::from(ms);
+                self.save_settings();
+            }
+            Message::SetAutoSave(auto_save) => {
+                self.settings.auto_save = auto_save;
+                self.save_settings();
+            }
+            Message::SetShowDistance(show_distance) => {
+                self.settings.show_distance = show_distance;
+                self.save_settings();
+            }
+            Message::SetProgressMetric(progress_metric) => {
+                self.settings.progress_metric = progress_metric;
+                self.save_settings();
+            }
+            Message::SetSlideCount(slide_count) => {
+                self.settings.slide_count = slide_count;
+                self.save_settings();
+            }
+            Message::ThemeChanged(theme) => {
+                self.settings.theme = theme;
+                self.save_settings();
+            }
+            Message::SetLanguage(language) => {
+                self.settings.language = language;
+                self.save_settings();
+            }
+            Message::SetGoalPreview(shown) => {
+                self.settings.goal_preview = shown;
+                self.save_settings();
+            }
+            Message::SetGhost(enabled) => {
+                self.settings.ghost = enabled;
+                if !enabled {
+                    self.ghost = None;
+                }
+                self.save_settings();
+            }
+            Message::SetLabelMode(mode) => {
+                self.settings.label_mode = mode;
+                self.save_settings();
+            }
+            Message::SetColorScheme(scheme) => {
+                self.settings.color_scheme = scheme;
+                self.save_settings();
+            }
+            Message::SetGoalColors(goal_colors) => {
+                self.settings.goal_colors = goal_colors;
+                self.save_settings();
+            }
+            Message::SetPictureMode(picture_mode) => {
+                self.settings.picture_mode = picture_mode;
+                self.save_settings();
+            }
+            Message::SetPictureNumbers(picture_numbers) => {
+                self.settings.picture_numbers = picture_numbers;
+                self.save_settings();
+            }
+            Message::SetMuted(muted) => {
+                self.settings.muted = muted;
+                self.save_settings();
+            }
+            Message::SetConfirmShuffle(confirm) => {
+                self.settings.confirm_shuffle = confirm;
+                self.save_settings();
+            }
+            Message::SetPauseWhenAway(pause) => {
+                self.settings.pause_when_away = pause;
+                self.save_settings();
+            }
+            // Challenges take effect from the next scramble, one at a time
+            Message::SetTimeAttack(time_attack) => {
+                self.log_mode(
+                    LoggedMode::TimeAttack,
+                    self.settings.time_attack,
+                    time_attack,
+                );
+                self.settings.time_attack = time_attack;
+                self.settings.move_limit &= !time_attack;
+                self.save_settings();
+            }
+            Message::SetMoveLimit(move_limit) => {
+                self.log_mode(LoggedMode::MoveLimit, self.settings.move_limit, move_limit);
+                self.settings.move_limit = move_limit;
+                self.settings.time_attack &= !move_limit;
+                self.save_settings();
+            }
+            Message::SetBlindfold(blindfold) => {
+                self.log_mode(LoggedMode::Blindfold, self.settings.blindfold, blindfold);
+                self.settings.blindfold = blindfold;
+                self.save_settings();
+            }
+            Message::SetBlindfoldPreview(seconds) => {
+                self.settings.blindfold_preview_secs = seconds.min(MAX_PREVIEW);
+                self.save_settings();
+            }
+            Message::SetAnimationSpeed(ms) => {
+                self.settings.animation_ms =
+                    u64::from(ms.clamp(MIN_ANIMATION_MS, MAX_ANIMATION_MS));
+                self.save_settings();
+            }
+            Message::SetEasing(easing) => {
+                self.settings.easing = easing;
+                self.save_settings();
+            }
+            Message::SetBoardRenderer(renderer) => {
+                self.settings.board_renderer = renderer;
+                self.save_settings();
+            }
+            Message::SetTileGap(gap) => {
+                self.settings.tile_gap = gap.min(MAX_TILE_GAP);
+                self.save_settings();
+            }
+            Message::SetTileRadius(radius) => {
+                self.settings.tile_radius = radius.min(MAX_TILE_RADIUS);
+                self.save_settings();
+            }
+            Message::SetTileFont(font) => {
+                self.settings.tile_font = font;
+                self.save_settings();
+            }
+            Message::FontLoaded(font, result) => match result {
+                Ok(()) => self.loaded_fonts.push(font),
+                // The labels stay in the standard font
+                Err(error) => eprintln!("warning: could not load the {font} font: {error:?}"),
+            },
+            Message::Peek => {
+                if self.puzzle.is_hidden() && self.peek_until.is_none() {
+                    self.now = Instant::now();
+                    self.peek_until = Some(self.now + PEEK_DURATION);
+                    self.peeks += 1;
+                    self.records.record_peek(&self.records_key());
+                    self.save_records();
+                }
+            }
+            Message::OptimalFound(key, length) => {
+                self.optimal_pending = false;
+                self.optimal_lengths.insert(key, length);
+            }
+            Message::DailiesAnalysed(lengths) => {
+                for (key, length) in lengths {
+                    self.optimal_lengths.entry(key).or_insert(Some(length));
+                }
+            }
+            Message::PatternDatabaseReady => self.pattern_progress = None,
+            Message::BudgetReady(board, budget) => {
+                self.budget_pending = false;
+
+                // The board is locked while waiting, so only a replaced board
+                // can differ
+                if board == self.puzzle.tiles()
+                    && self.puzzle.mode() == (GameMode::MoveLimit { budget: None })
+                {
+                    self.puzzle.set_mode(GameMode::MoveLimit {
+                        budget: Some(budget),
+                    });
+                }
+            }
+            Message::SetTimeLimit(seconds) => {
+                self.settings.time_limit_secs = seconds.clamp(TIME_LIMIT_STEP, MAX_TIME_LIMIT);
+                self.save_settings();
+            }
+            Message::ToggleFullscreen => {
+                self.fullscreen = !self.fullscreen;
+                let (fullscreen, size, position) =
+                    (self.fullscreen, self.windowed_size, self.windowed_position);
+
+                return window::latest().and_then(move |id| {
+                    if fullscreen {
+                        return window::set_mode(id, window::Mode::Fullscreen);
+                    }
+
+                    // Some platforms come back from fullscreen a little off,
+                    // so put the window exactly where it was
+                    let restore = window::set_mode(id, window::Mode::Windowed)
+                        .chain(window::resize(id, size));
+                    match position {
+                        Some(position) => restore.chain(window::move_to(id, position)),
+                        None => restore,
+                    }
+                });
+            }
+            Message::WindowResized(size) => {
+                self.window_size = size;
+                if !self.fullscreen {
+                    self.windowed_size = size;
+                }
+            }
+            Message::WindowMoved(position) => {
+                if !self.fullscreen {
+                    self.windowed_position = Some(position);
+                }
+            }
+            Message::CloseRequested(id) => {
+                self.remember_window();
+                if self.settings_changed_at.is_some() {
+                    self.write_settings();
+                }
+
+                if !self.settings.auto_save {
+                    return window::close(id);
+                }
+
+                // Close regardless of whether saving worked; there is no one
+                // left to show an error to
+                return Task::future(write_game(self.storage(), self.saved_game()))
+                    .then(move |_| window::close(id));
+            }
+        }
+
+        Task::none()
+    }
+
+    /// The storage for the documents of the profile playing.
+    fn storage(&self) -> Option<Storage> {
+        self.data
+            .as_ref()
+            .map(|data| profile_paths(data, &self.profile))
+    }
+
+    fn save_profiles(&self) {
+        if let Some(storage) = &self.data
+            && let Err(error) = storage::save(storage, PROFILES, &self.profiles)
+        {
+            eprintln!("warning: failed to save profiles: {error}");
+        }
+    }
+
+    /// Swaps in the documents of the profile now playing, with its saved
+    /// game if it keeps one.
+    fn open_profile(&mut self) {
+        self.profile = self
+            .profiles
+            .active()
+            .expect("there is always a profile")
+            .clone();
+        let storage = self.storage();
+        self.settings = load_document(storage.clone(), SETTINGS);
+        self.records = load_document(storage.clone(), RECORDS);
+        self.achievements = load_document(storage.clone(), ACHIEVEMENTS_FILE);
+        self.ghosts = load_document(storage, GHOSTS);
+        self.slots.clear();
+        self.last_replay = None;
+        self.optimal_lengths.clear();
+
+        self.resume(SavedGame::default());
+        self.resume_saved();
+    }
+
+    /// Resumes the profile's saved game, if it has auto-save on and there is one.
+    fn resume_saved(&mut self) {
+        if self.settings.auto_save
+            && let Some(Ok(loaded)) = self
+                .storage()
+                .and_then(|storage| storage::load(&storage, GAME))
+        {
+            warn_if_read_only(&loaded, GAME);
+            self.resume(loaded.document);
+        }
+    }
+
+    /// Freezes the clock and hides the board, if the clock is running or
+    /// counting down to start.
+    fn pause(&mut self) {
+        if self.timer.is_running() || self.countdown.is_some() {
+            self.now = Instant::now();
+            self.timer.stop(self.now);
+            self.countdown = None;
+            self.paused = true;
+            self.hint = None;
+            self.puzzle.cancel_drag();
+        }
+    }
+
+    fn saved_game(&self) -> SavedGame {
+        SavedGame {
+            puzzle: self.puzzle.clone(),
+            elapsed: self.timer.elapsed(Instant::now()),
+            relay: self.relay,
+            daily: self.daily,
+            hints: self.hints_used,
+            undos: self.undos_used,
+            peeks: self.peeks,
+            assisted: self.assisted,
+        }
+    }
+
+    /// Reports how a change to a save slot went, with `done` once it has
+    /// if there is anything to say, and reads the slots back.
+    fn slot_changed(&mut self, result: Result<(), String>, done: String) -> Task<Message> {
+        self.slot_notice = match result {
+            Ok(()) => (!done.is_empty()).then_some(Notice::Info(done)),
+            Err(error) => Some(Notice::Error(self.trf(Text::SaveFailed, &[&error]))),
+        };
+
+        Task::perform(read_save_slots(self.storage()), Message::SlotsRead)
+    }
+
+    /// Replaces the current game with a saved one.
+    fn resume(&mut self, saved: SavedGame) {
+        self.abandon_race();
+        self.abandon_relay();
+        self.relay = saved.relay;
+        self.relay_stage_at = None;
+        self.puzzle = saved.puzzle;
+        self.timer = Timer::with_elapsed(saved.elapsed);
+        self.counted_down = saved.elapsed;
+        self.preview_tick = Some(Instant::now());
+        self.peek_until = None;
+        self.peeks = saved.peeks;
+        self.hints_used = saved.hints;
+        self.undos_used = saved.undos;
+        self.paused = false;
+        self.away = false;
+        self.countdown = None;
+        self.new_record = false;
+        self.hint = None;
+        self.animation = None;
+        self.submit_entry();
+        self.victory = None;
+        self.recording = None; // The moves that led here are unknown
+        self.ghost = None;
+        self.daily = saved.daily;
+        self.stop_auto_solve();
+        self.assisted = saved.assisted;
+    }
+
+    /// The edge length of the board, which grows into whatever room a
+    /// large or fullscreen window leaves around the controls.
+    fn board_size(&self) -> f32 {
+        let Size { width, height } = self.window_size;
+        let room = (width - CONTROLS_WIDTH).min(height - CONTROLS_HEIGHT);
+
+        // Short windows scroll rather than shrink the board, but a narrow one
+        // shrinks it to fit across
+        room.max(BOARD_SIZE)
+            .min(width - CONTROLS_WIDTH)
+            .max(MIN_BOARD_SIZE)
+    }
+
+    /// Where the tiles go on the board, at its current size and with the
+    /// gap between them set.
+    fn board_layout(&self) -> BoardLayout {
+        let board_size = self.board_size();
+        let (rows, cols) = (self.puzzle.rows(), self.puzzle.cols());
+        let spacing = tile_spacing(board_size, self.settings.tile_gap as f32);
+
+        BoardLayout {
+            rows,
+            cols,
+            tile_size: tile_size(board_size, spacing, rows, cols),
+            spacing,
+        }
+    }
+
+    /// The distance from one tile on the board to the next.
+    fn tile_pitch(&self) -> f32 {
+        self.board_layout().pitch()
+    }
+
+    /// How round the corners of the board's tiles are, as set.
+    fn corners(&self) -> Corners {
+        Corners::new(
+            self.settings.tile_radius as f32,
+            self.board_layout().tile_size,
         )
-        .center_x(Length::Fill)
-        .center_y(Length::Fill)
-        .into()
     }
-}
 
-fn main() -> iced::Result {
-    iced::run(Puzzle::update, Puzzle::view)
+    /// The font of the tile labels, or the standard one until the font set
+    /// has loaded, or if it failed to.
+    fn label_font(&self) -> Font {
+        let font = self.settings.tile_font;
+        if self.loaded_fonts.contains(&font) {
+            tile_font(font)
+        } else {
+            Font::DEFAULT
+        }
+    }
+
+    /// The cell under `point`, measured from the board's top left corner.
+    fn cell_at(&self, point: Point) -> Option<(usize, usize)> {
+        let pitch = self.tile_pitch();
+        let (row, col) = ((point.y / pitch) as usize, (point.x / pitch) as usize);
+
+        (row < self.puzzle.rows() && col < self.puzzle.cols()).then_some((row, col))
+    }
+
+    /// Whether the window is too short for the full layout.
+    fn is_compact(&self) -> bool {
+        self.window_size.height < COMPACT_HEIGHT
+    }
+
+    /// Stores the windowed geometry in the settings, to reopen at next time.
+    fn remember_window(&mut self) {
+        let size = (
+            self.windowed_size.width.round() as u32,
+            self.windowed_size.height.round() as u32,
+        );
+        let position = self
+            .windowed_position
+            .map(|position| (position.x.round() as i32, position.y.round() as i32));
+
+        if self.settings.window_size != Some(size) || self.settings.window_position != position {
+            self.settings.window_size = Some(size);
+            self.settings.window_position = position;
+            self.save_settings();
+        }
+    }
+
+    /// Saves the settings once they have gone [`SETTINGS_SAVE_DELAY`] without
+    /// another change.
+    fn save_settings(&mut self) {
+        self.settings_changed_at = Some(Instant::now());
+    }
+
+    fn write_settings(&mut self) {
+        self.settings_changed_at = None;
+        if let Some(storage) = self.storage()
+            && let Err(error) = storage::save(&storage, SETTINGS, &self.settings)
+        {
+            eprintln!("warning: failed to save settings: {error}");
+        }
+    }
+
+    /// Clears per-attempt state after the board is replaced or restarted.
+    fn restart(&mut self) {
+        self.tutorial = None;
+        self.tutorial_undo_at = None;
+        self.abandon_race();
+        self.timer.reset();
+        self.paused = false;
+        self.game_menu = false;
+        self.away = false;
+        self.pending_preset = None;
+        self.new_record = false;
+        self.hint = None;
+        self.animation = None;
+        self.submit_entry();
+        self.victory = None;
+        self.solved_scramble = None;
+        self.recording = self
+            .puzzle
+            .is_scrambled()
+            .then(|| Replay::new(self.puzzle.clone()));
+        self.ghost = self
+            .recording
+            .as_ref()
+            .filter(|_| self.settings.ghost)
+            .and_then(|recording| self.ghosts.best(recording.start()))
+            .cloned()
+            .map(Ghost::new);
+        self.daily = None;
+        self.abandon_relay();
+        self.relay_stage_at = None;
+        self.counted_down = Duration::ZERO;
+        self.puzzle
+            .set_blindfold(self.settings.blindfold && self.puzzle.is_scrambled());
+        self.puzzle
+            .set_preview(Duration::from_secs(self.settings.blindfold_preview_secs));
+        self.preview_tick = Some(Instant::now());
+        self.peek_until = None;
+        self.peeks = 0;
+        self.hints_used = 0;
+        self.undos_used = 0;
+        self.puzzle.set_mode(if !self.puzzle.is_scrambled() {
+            GameMode::Casual
+        } else if self.settings.time_attack {
+            GameMode::TimeAttack {
+                remaining: Duration::from_secs(self.settings.time_limit_secs),
+            }
+        } else if self.settings.move_limit {
+            GameMode::MoveLimit { budget: None }
+        } else {
+            GameMode::Casual
+        });
+        self.stop_auto_solve();
+        // A time attack gives the player a moment to take in the board
+        self.countdown = self.puzzle.mode().is_time_attack().then(Instant::now);
+    }
+
+    /// Starts the clock at `now`, the end of the countdown, so the countdown
+    /// itself never counts.
+    fn end_countdown(&mut self, now: Instant) {
+        self.countdown = None;
+        self.now = now;
+        self.timer.start(now);
+    }
+
+    /// Brings a time attack's clock up to date with the timer, ending the
+    /// attempt as a failure when it runs out. Returns whether it has.
+    fn count_down(&mut self) -> bool {
+        let elapsed = self.timer.elapsed(self.now);
+        let out_of_time = self
+            .puzzle
+            .count_down(elapsed.saturating_sub(self.counted_down));
+        self.counted_down = elapsed;
+
+        if out_of_time && self.timer.is_running() {
+            self.timer.stop(self.now);
+            self.hint = None;
+            self.stop_auto_solve();
+            self.records.record_timeout(&self.records_key());
+            self.save_records();
+            self.play(Sound::Rejected);
+        }
+        out_of_time
+    }
+
+    /// Whether the colour wave from solving is still running.
+    fn is_celebrating(&self) -> bool {
+        self.motion_enabled()
+            && self
+                .victory
+                .is_some_and(|solved_at| self.now.duration_since(solved_at) < CELEBRATION_DURATION)
+    }
+
+    fn play(&self, sound: Sound) {
+        if !self.settings.muted {
+            self.sound.play(sound);
+        }
+    }
+
+    /// Whether the last moved tile is still highlighted.
+    fn is_accenting(&self) -> bool {
+        self.last_move_at
+            .is_some_and(|moved_at| self.now.duration_since(moved_at) < LAST_MOVE_ACCENT)
+    }
+
+    /// The label of the tile with `value` on a board of `cells` cells.
+    fn label(&self, value: u16, cells: usize) -> String {
+        label_for(value, self.settings.label_mode.for_cells(cells))
+    }
+
+    /// Whether the board may move on its own: every animation asks this
+    /// first, so reduced motion turns them all off.
+    fn motion_enabled(&self) -> bool {
+        !self
+            .settings
+            .reduce_motion
+            .unwrap_or(self.system_reduces_motion)
+    }
+
+    /// How long a direction key is held before it starts repeating.
+    fn key_repeat_delay(&self) -> Duration {
+        Duration::from_millis(self.settings.key_repeat_delay_ms)
+    }
+
+    /// How long a slide takes, zero when tiles jump to their cells.
+    fn animation_duration(&self) -> Duration {
+        if self.motion_enabled() {
+            Duration::from_millis(self.settings.animation_ms)
+        } else {
+            Duration::ZERO
+        }
+    }
+
+    fn is_auto_solving(&self) -> bool {
+        self.solving.is_some() || self.solution.is_some()
+    }
+
+    /// Whether the tile labels are blindfolded right now, with no peek.
+    fn is_hidden(&self) -> bool {
+        self.puzzle.is_hidden() && self.peek_until.is_none()
+    }
+
+    /// Abandons any auto-solve and forgets that the attempt was assisted.
+    fn stop_auto_solve(&mut self) {
+        self.cancel_solve();
+        self.solution = None;
+        self.auto_moves = 0;
+        self.assisted = false;
+    }
+
+    /// Stops the search for a solution, if one is running, so it frees its
+    /// thread rather than running out its budget.
+    fn cancel_solve(&mut self) {
+        if let Some(solving) = self.solving.take() {
+            solving.cancel.cancel();
+        }
+    }
+
+    /// Applies a move, animates it, and runs the timer from the first move of
+    /// a scramble until it is solved, then records the result.
+    ///
+    /// Returns whether anything moved.
+    fn make_move(&mut self, apply: impl FnOnce(&mut Puzzle) -> bool) -> bool {
+        let previous_empty = self.puzzle.empty_position();
+        let moved = apply(&mut self.puzzle);
+        self.now = Instant::now();
+
+        if !moved {
+            return false;
+        }
+
+        // The model is already in its final state, so a move made mid-animation
+        // simply replaces the running one
+        self.hint = None;
+        self.rejected = None;
+        self.victory = None;
+        self.last_move_at = Some(self.now);
+        self.play(Sound::Move);
+        let duration = self.animation_duration();
+        self.animation = (!duration.is_zero())
+            .then(|| {
+                Animation::new(
+                    previous_empty,
+                    &self.puzzle,
+                    self.now,
+                    duration,
+                    self.settings.easing,
+                )
+            })
+            .flatten();
+
+        // The tutorial keeps no time or records
+        if self.tutorial.is_some() {
+            self.follow_tutorial();
+            return true;
+        }
+        if !self.puzzle.is_scrambled() {
+            return true;
+        }
+
+        self.new_record = false;
+        self.timer.start(self.now);
+        if let Some(recording) = &mut self.recording {
+            recording.record(previous_empty, &self.puzzle, self.timer.elapsed(self.now));
+        }
+        if self.count_down() {
+            return true;
+        }
+        if self.puzzle.is_out_of_moves() {
+            self.timer.stop(self.now);
+            self.hint = None;
+            self.stop_auto_solve();
+            self.records.record_move_limit_loss(&self.records_key());
+            self.save_records();
+            self.play(Sound::Rejected);
+            return true;
+        }
+
+        if self.puzzle.is_solved() {
+            if !self.assisted && self.next_relay_stage() {
+                return true;
+            }
+            self.timer.stop(self.now);
+            if let Some(ghost) = &mut self.ghost {
+                ghost.advance_to(self.timer.elapsed(self.now));
+            }
+            self.last_replay = self.recording.take();
+            if !self.assisted {
+                if let Some(race) = &mut self.race
+                    && race.finish()
+                {
+                    self.records.record_race(race.bot().difficulty(), true);
+                }
+                self.record_solve();
+                self.submit_ghost();
+                self.victory = Some(self.now);
+                // A relay's stages add up to more than any one scramble's
+                // optimal length
+                self.solved_scramble = self
+                    .last_replay
+                    .as_ref()
+                    .filter(|_| self.relay.is_none())
+                    .map(|replay| replay.start().clone());
+                self.solve_scored = false;
+                self.play(Sound::Solved);
+            }
+        }
+
+        true
+    }
+
+    /// Whether a scramble is under way, so replacing the board would lose
+    /// progress.
+    fn is_mid_solve(&self) -> bool {
+        self.puzzle.is_scrambled()
+            && self.total_moves() > 0
+            && !self.puzzle.is_solved()
+            && !self.puzzle.is_locked()
+    }
+
+    /// Banks a relay stage just solved and scrambles the next, larger board,
+    /// leaving the timer running. Returns whether there was a next stage.
+    fn next_relay_stage(&mut self) -> bool {
+        let Some(mut relay) = self.relay else {
+            return false;
+        };
+        let Some(size) = relay.complete_stage(self.puzzle.moves()) else {
+            self.relay = Some(relay);
+            return false;
+        };
+        let elapsed = self.timer.elapsed(self.now);
+
+        self.puzzle = self.new_board(size, size);
+        self.puzzle.shuffle();
+        self.relay = None; // Not abandoned, so restarting must not count it
+        self.restart();
+        self.log_shuffle();
+        self.relay = Some(relay);
+        self.timer = Timer::with_elapsed(elapsed);
+        self.timer.start(self.now);
+        self.countdown = None; // The clock runs on through the stages
+        self.counted_down = elapsed;
+        self.relay_stage_at = Some(self.now);
+        self.play(Sound::Solved);
+        true
+    }
+
+    /// Keeps the solve just finished as the ghost of its scramble, if it was
+    /// the fastest yet.
+    fn submit_ghost(&mut self) {
+        let Some(replay) = self.last_replay.clone() else {
+            return;
+        };
+
+        if self.ghosts.submit(replay)
+            && let Some(storage) = self.storage()
+            && let Err(error) = storage::save(&storage, GHOSTS, &self.ghosts)
+        {
+            eprintln!("warning: failed to save ghosts: {error}");
+        }
+    }
+
+    /// Plays the bot's moves due by now, counting the race as lost if that
+    /// solves its board.
+    fn advance_race(&mut self) {
+        let elapsed = self.timer.elapsed(self.now);
+        let Some(race) = &mut self.race else {
+            return;
+        };
+
+        if race.advance(elapsed) && race.winner() == Some(Racer::Bot) {
+            self.records.record_race(race.bot().difficulty(), false);
+            self.save_records();
+            self.play(Sound::Rejected);
+        }
+    }
+
+    /// Drops the race, counting it as lost if the player had started and
+    /// nobody has won.
+    fn abandon_race(&mut self) {
+        let Some(race) = self.race.take() else {
+            return;
+        };
+
+        if race.winner().is_none() && !self.timer.elapsed(self.now).is_zero() {
+            self.records.record_race(race.bot().difficulty(), false);
+            self.save_records();
+        }
+    }
+
+    /// Counts a relay left before its last stage as not finished, then
+    /// drops it.
+    fn abandon_relay(&mut self) {
+        let Some(relay) = self.relay.take() else {
+            return;
+        };
+
+        if !relay.is_finished() {
+            self.records
+                .record_did_not_finish(&Relay::key(self.puzzle.wrap()));
+            self.save_records();
+        }
+    }
+
+    /// The moves made on the current board, or across every stage of a
+    /// relay.
+    fn total_moves(&self) -> u32 {
+        let moves = self.puzzle.moves();
+        self.relay.map_or(moves, |relay| relay.total_moves(moves))
+    }
+
+    /// Resizes to a preset and scrambles straight away.
+    fn start_preset(&mut self, index: usize) {
+        let (_, size) = PRESETS[index];
+
+        self.puzzle = self.new_board(size, size);
+        self.puzzle.shuffle();
+        self.record_shuffle();
+        self.remember_size();
+    }
+
+    /// The tile the tutorial points out to press next, unless a move off its
+    /// script is about to slide back.
+    fn tutorial_press(&self) -> Option<(usize, usize)> {
+        self.tutorial
+            .as_ref()
+            .filter(|_| self.tutorial_undo_at.is_none())
+            .and_then(Tutorial::next_press)
+    }
+
+    /// Puts the tutorial's board in play as the script has it, untimed and
+    /// whatever challenges are set.
+    fn play_tutorial(&mut self, tutorial: Tutorial) {
+        self.puzzle = tutorial.board().clone();
+        self.restart();
+        self.puzzle.set_mode(GameMode::Casual);
+        self.puzzle.set_blindfold(false);
+        self.countdown = None;
+        self.recording = None;
+        self.ghost = None;
+        self.tutorial = Some(tutorial);
+    }
+
+    /// Moves the tutorial on after a move, or takes a move off its script
+    /// back a moment later.
+    fn follow_tutorial(&mut self) {
+        let Some(tutorial) = &mut self.tutorial else {
+            return;
+        };
+
+        match tutorial.moved(&self.puzzle) {
+            TutorialProgress::OnTrack => {}
+            TutorialProgress::Stray => self.tutorial_undo_at = Some(self.now),
+            TutorialProgress::StepDone => self.play(Sound::Solved),
+            TutorialProgress::Finished => {
+                self.settings.tutorial_done = true;
+                self.save_settings();
+                self.play(Sound::Solved);
+            }
+        }
+    }
+
+    /// Keeps the board's size for the menu to start the next game at.
+    fn remember_size(&mut self) {
+        self.settings.grid_size = (self.puzzle.rows(), self.puzzle.cols());
+        self.save_settings();
+    }
+
+    /// Whether the menu can take the player back to a game still to finish.
+    fn can_continue(&self) -> bool {
+        !self.puzzle.is_solved()
+    }
+
+    /// A solved board of the given size that keeps the current difficulty,
+    /// goal and wrapping.
+    fn new_board(&self, rows: usize, cols: usize) -> Puzzle {
+        let mut puzzle = Puzzle::new(rows, cols);
+        puzzle.set_difficulty(self.puzzle.difficulty());
+        puzzle.set_goal(self.goal_for(rows, cols));
+        puzzle.set_wrap(self.puzzle.wrap());
+        puzzle.reset();
+        puzzle
+    }
+
+    /// The current goal, or the standard one if it is a custom layout that
+    /// does not fit a board of the given size.
+    fn goal_for(&self, rows: usize, cols: usize) -> Goal {
+        match self.puzzle.goal() {
+            Goal::Custom(layout) if layout.len() != rows * cols => Goal::Standard,
+            goal => goal.clone(),
+        }
+    }
+
+    /// The goals offered for the current board: the built-in ones, plus the
+    /// saved custom layout if it fits.
+    fn goal_options(&self) -> Vec<Goal> {
+        let cells = self.puzzle.rows() * self.puzzle.cols();
+        let custom = self
+            .settings
+            .custom_goal
+            .clone()
+            .filter(|layout| layout.len() == cells)
+            .and_then(|layout| Goal::custom(layout).ok());
+
+        Goal::ALL.into_iter().chain(custom).collect()
+    }
+
+    /// Restarts after a fresh scramble and counts it in the statistics. A
+    /// move-limit challenge is tallied by its outcome, not as a casual
+    /// scramble.
+    fn record_shuffle(&mut self) {
+        self.restart();
+        self.log_shuffle();
+        if !self.puzzle.mode().is_move_limit() {
+            self.records.record_shuffle(&self.records_key());
+        }
+        self.save_records();
+    }
+
+    /// Notes an event in the session log, timed from the launch.
+    fn log(&mut self, event: LogEvent) {
+        let at = Instant::now().saturating_duration_since(self.session_started);
+        self.log.push(at, event);
+    }
+
+    /// Notes the scramble just drawn in the session log.
+    fn log_shuffle(&mut self) {
+        self.log(LogEvent::Shuffle {
+            rows: self.puzzle.rows(),
+            cols: self.puzzle.cols(),
+            difficulty: self.puzzle.difficulty(),
+            seed: self.puzzle.seed(),
+        });
+    }
+
+    /// Notes `mode` turning from `was` to `now` in the session log, if it did.
+    fn log_mode(&mut self, mode: LoggedMode, was: bool, now: bool) {
+        if was != now {
+            self.log(LogEvent::Mode { mode, on: now });
+        }
+    }
+
+    /// Plays `puzzle`, typed, pasted or loaded from a code, in the current
+    /// goal, difficulty and wrapping.
+    fn import(&mut self, mut puzzle: Puzzle) {
+        puzzle.set_difficulty(self.puzzle.difficulty());
+        puzzle.set_goal(self.goal_for(puzzle.rows(), puzzle.cols()));
+        puzzle.set_wrap(self.puzzle.wrap());
+        self.puzzle = puzzle;
+        self.restart();
+        self.log(LogEvent::Import {
+            rows: self.puzzle.rows(),
+            cols: self.puzzle.cols(),
+        });
+        self.notice = Some(if self.puzzle.is_solvable() {
+            Notice::Info(String::from(self.tr(Text::BoardImported)))
+        } else {
+            Notice::Error(String::from(self.tr(Text::BoardImportedUnsolvable)))
+        });
+    }
+
+    /// The key the current board's records are kept under.
+    fn records_key(&self) -> String {
+        if self.relay.is_some() {
+            return Relay::key(self.puzzle.wrap());
+        }
+        Records::key(self.puzzle.rows(), self.puzzle.cols(), self.puzzle.wrap())
+    }
+
+    fn record_solve(&mut self) {
+        let (key, time, moves) = (
+            self.records_key(),
+            self.timer.elapsed(self.now),
+            self.total_moves(),
+        );
+
+        self.log(LogEvent::Solve {
+            moves,
+            time,
+            optimal: None,
+        });
+        // A daily finished after midnight UTC is too late to count
+        let daily = self.daily.filter(|&day| day == Day::today());
+        self.new_record = self.records.submit(SolveRecord {
+            hints: self.hints_used,
+            undos: self.undos_used,
+            time_attack: self.puzzle.mode().is_time_attack(),
+            move_limit: self.puzzle.mode().is_move_limit(),
+            blindfold: self.puzzle.is_blindfold(),
+            daily: daily.is_some(),
+            ..SolveRecord::new(&key, Day::today(), time, moves)
+        });
+        if let Some(time_left) = self.puzzle.mode().remaining() {
+            self.new_record |= self.records.submit_time_left(&key, time_left);
+        }
+        if let Some(day) = daily {
+            self.records.submit_daily(&key, day, time, moves);
+        }
+        self.save_records();
+        self.unlock_achievements(time, moves);
+
+        let table = if self.relay.is_some() {
+            key
+        } else {
+            Leaderboard::key(
+                self.puzzle.rows(),
+                self.puzzle.cols(),
+                self.puzzle.wrap(),
+                self.puzzle.mode(),
+            )
+        };
+        if self.leaderboard.qualifies(&table, time, moves) {
+            let entry = Entry::new(&self.settings.player_name, time, moves, Day::today());
+            self.pending_entry = Some((table, entry));
+            self.name_input = self.settings.player_name.clone();
+        }
+    }
+
+    /// Adds how efficient the solve just finished was to the statistics and
+    /// to its leaderboard entry, if it is still waiting for a name.
+    fn score_solve(&mut self, optimal: Option<OptimalLength>) {
+        let Some(optimal) = optimal else {
+            return;
+        };
+        let efficiency = Efficiency::new(optimal, self.puzzle.moves());
+
+        self.log.set_optimal(optimal);
+        self.records
+            .record_efficiency(&self.records_key(), efficiency);
+        self.save_records();
+        if let Some((_, entry)) = &mut self.pending_entry {
+            entry.efficiency = Some(efficiency);
+        }
+    }
+
+    /// The efficiency of the solve just finished, once it is known.
+    fn efficiency(&self) -> Option<Efficiency> {
+        let start = self.solved_scramble.as_ref()?;
+        let optimal = (*self.optimal_lengths.get(&scramble_key(start))?)?;
+
+        Some(Efficiency::new(optimal, self.puzzle.moves()))
+    }
+
+    /// An efficiency as a percentage, with its stars when it has them.
+    fn efficiency_text(&self, efficiency: Efficiency) -> String {
+        match efficiency.stars() {
+            Some(stars) => self.trf(
+                Text::EfficiencyStars,
+                &[&efficiency.percent(), &star_rating(stars)],
+            ),
+            None => self.trf(Text::EfficiencyAtLeast, &[&efficiency.percent()]),
+        }
+    }
+
+    /// Unlocks whatever the solve just made reaches, announcing the first of
+    /// them in a toast.
+    fn unlock_achievements(&mut self, time: Duration, moves: u32) {
+        let solve = SolveSummary {
+            rows: self.puzzle.rows(),
+            cols: self.puzzle.cols(),
+            moves,
+            time,
+            hints: self.hints_used,
+            undos: self.undos_used,
+            time_attack: self.puzzle.mode().is_time_attack(),
+            blind: self.puzzle.is_blindfold() && self.peeks == 0,
+            total_solves: self
+                .records
+                .all_stats()
+                .map(|(_, stats)| stats.solved)
+                .sum(),
+            daily_streak: self.records.daily_streak(Day::today()),
+        };
+        let now = web_time::SystemTime::now()
+            .duration_since(web_time::UNIX_EPOCH)
+            .map_or(0, |since| since.as_secs());
+
+        let unlocked = achievements::evaluate(&mut self.achievements, &solve, now);
+        let Some(first) = unlocked.first() else {
+            return;
+        };
+
+        self.toast = Some((
+            self.trf(Text::AchievementUnlocked, &[&self.tr(first.name)]),
+            self.now,
+        ));
+        if let Some(storage) = self.storage()
+            && let Err(error) = storage::save(&storage, ACHIEVEMENTS_FILE, &self.achievements)
+        {
+            eprintln!("warning: failed to save achievements: {error}");
+        }
+    }
+
+    /// Puts the solve waiting for a name on the leaderboard under the name
+    /// entered, and remembers the name for next time.
+    fn submit_entry(&mut self) {
+        let Some((table, entry)) = self.pending_entry.take() else {
+            return;
+        };
+
+        let entry = Entry {
+            efficiency: entry.efficiency,
+            ..Entry::new(&self.name_input, entry.time, entry.moves, entry.day)
+        };
+        if self.settings.player_name != self.name_input.trim() {
+            self.settings.player_name = self.name_input.trim().to_owned();
+            self.save_settings();
+        }
+        self.leaderboard.insert(&table, entry);
+        self.save_leaderboard();
+    }
+
+    fn save_leaderboard(&self) {
+        if let Some(storage) = &self.data
+            && let Err(error) = storage::save(storage, LEADERBOARD, &self.leaderboard)
+        {
+            eprintln!("warning: failed to save the leaderboard: {error}");
+        }
+    }
+
+    fn save_records(&self) {
+        if let Some(storage) = self.storage()
+            && let Err(error) = storage::save(&storage, RECORDS, &self.records)
+        {
+            eprintln!("warning: failed to save records: {error}");
+        }
+    }
+
+    fn subscription(&self) -> Subscription<Message> {
+        // Only key presses not captured by a widget (e.g. a focused text input) arrive here
+        let keys = keyboard::listen()
+            .with((self.settings.key_scheme, self.countdown.is_some()))
+            .filter_map(|((scheme, counting_down), event)| match event {
+                // Any key cuts the countdown short for the impatient
+                keyboard::Event::KeyPressed { repeat: false, .. } if counting_down => {
+                    Some(Message::SkipCountdown)
+                }
+                event => message_for_key_event(scheme, event),
+            });
+
+        // Stay idle unless the clock is running or a hint needs to expire
+        let ticks = if self.timer.is_running()
+            || self.countdown.is_some()
+            || self.tutorial_undo_at.is_some()
+            || self.hint.is_some()
+            || self.toast.is_some()
+            || self.puzzle.is_previewing()
+            || self.peek_until.is_some()
+            || self.settings_changed_at.is_some()
+            || self.relay_stage_at.is_some()
+            || self.pattern_progress.is_some()
+            || self.solving.is_some()
+        {
+            every(Duration::from_millis(100)).map(Message::Tick)
+        } else {
+            Subscription::none()
+        };
+
+        let frames = if self.animation.is_some()
+            || self.rejected.is_some()
+            || self.goal_flash.is_some()
+            || !self.held_keys.is_empty()
+            || self.is_celebrating()
+            || self.is_accenting()
+        {
+            every(FRAME_INTERVAL).map(Message::Tick)
+        } else {
+            Subscription::none()
+        };
+
+        // Each auto-solve move waits for the slide before it to finish
+        let auto_steps = if self.solution.is_some() && !self.paused {
+            time::every(AUTO_STEP_INTERVAL.max(self.animation_duration()))
+                .map(|_| Message::AutoStep)
+        } else {
+            Subscription::none()
+        };
+
+        let replay_ticks = match &self.playback {
+            Some(playback) if playback.is_playing() && self.screen == Screen::Replay => {
+                let interval = if playback.real_time {
+                    Duration::from_millis(50)
+                } else {
+                    REPLAY_INTERVAL
+                };
+                every(interval).map(Message::ReplayTick)
+            }
+            _ => Subscription::none(),
+        };
+
+        let window_events = window::events().filter_map(|(_, event)| match event {
+            window::Event::Resized(size) => Some(Message::WindowResized(size)),
+            window::Event::Moved(position) => Some(Message::WindowMoved(position)),
+            window::Event::Unfocused => Some(Message::WindowUnfocused),
+            _ => None,
+        });
+
+        let close_requests = window::close_requests().map(Message::CloseRequested);
+
+        Subscription::batch([
+            keys,
+            gamepad::inputs().map(message_for_input),
+            ticks,
+            frames,
+            auto_steps,
+            replay_ticks,
+            window_events,
+            close_requests,
+        ])
+    }
+
+    /// Where the tile with keyboard focus is now, if it is on the board.
+    fn focused_cell(&self) -> Option<(usize, usize)> {
+        let value = self.focused?;
+        let index = self
+            .puzzle
+            .tiles()
+            .iter()
+            .position(|tile| tile.value() == Some(value))?;
+
+        Some((index / self.puzzle.cols(), index % self.puzzle.cols()))
+    }
+
+    /// Moves keyboard focus to the next tile in reading order, or the
+    /// previous one, wrapping around the board. The blank is skipped, since
+    /// there is nothing to press there.
+    fn cycle_focus(&mut self, forward: bool) {
+        let tiles: Vec<u16> = self.puzzle.tiles().iter().filter_map(Tile::value).collect();
+        let current = self
+            .focused
+            .and_then(|value| tiles.iter().position(|&tile| tile == value));
+
+        let next = match (current, forward) {
+            (None, true) => 0,
+            (None, false) => tiles.len() - 1,
+            (Some(index), true) => (index + 1) % tiles.len(),
+            (Some(index), false) => (index + tiles.len() - 1) % tiles.len(),
+        };
+        self.focused = Some(tiles[next]);
+    }
+
+    /// A line describing the board for screen readers and anyone playing by
+    /// keyboard: the solve once it is done, otherwise the focused tile.
+    fn status_line(&self) -> Option<String> {
+        let puzzle = &self.puzzle;
+
+        if self.victory.is_some() {
+            return Some(self.trf(
+                Text::SolvedAnnouncement,
+                &[
+                    &puzzle.moves(),
+                    &self.format_time(self.timer.elapsed(self.now), Precision::Millis),
+                ],
+            ));
+        }
+
+        let (row, col) = self.focused_cell()?;
+        let hidden = self.is_hidden();
+        let value: &dyn std::fmt::Display = if hidden { &"?" } else { self.focused.as_ref()? };
+        let movable = Direction::ALL
+            .into_iter()
+            .any(|direction| puzzle.tile_for_direction(direction) == Some((row, col)));
+        let description = if movable {
+            Text::MovableTileDescription
+        } else {
+            Text::TileDescription
+        };
+
+        Some(self.trf(description, &[value, &(row + 1), &(col + 1)]))
+    }
+
+    /// The window title, which follows the game so it can be watched from
+    /// the taskbar. The clock in it only counts whole seconds, so the title
+    /// changes at most once a second.
+    fn title(&self) -> String {
+        let puzzle = &self.puzzle;
+        let name = self.trf(
+            Text::WindowTitleProfile,
+            &[
+                &self.trf(Text::WindowTitle, &[&(puzzle.rows() * puzzle.cols() - 1)]),
+                &self.profile.name,
+            ],
+        );
+
+        if !puzzle.is_scrambled() || puzzle.moves() == 0 {
+            return name;
+        }
+        if puzzle.is_solved() {
+            return self.trf(Text::WindowTitleSolved, &[&name, &puzzle.moves()]);
+        }
+
+        let clock = puzzle
+            .mode()
+            .remaining()
+            .unwrap_or_else(|| self.timer.elapsed(self.now))
+            .as_secs();
+        self.trf(
+            Text::WindowTitleSolving,
+            &[
+                &name,
+                &puzzle.moves(),
+                &format!("{}:{:02}", clock / 60, clock % 60),
+            ],
+        )
+    }
+
+    /// `text` in the chosen language.
+    fn tr(&self, text: Text) -> &'static str {
+        i18n::tr(self.settings.language, text)
+    }
+
+    /// `text` in the chosen language, filled in with `args`.
+    fn trf(&self, text: Text, args: &[&dyn std::fmt::Display]) -> String {
+        i18n::trf(self.settings.language, text, args)
+    }
+
+    /// `duration` to `precision`: tenths for a clock still running,
+    /// milliseconds for a finished time and the records.
+    fn format_time(&self, duration: Duration, precision: Precision) -> String {
+        i18n::format_time(self.settings.language, duration, precision)
+    }
+
+    /// Picker options for `values`, named in the chosen language.
+    fn choices<T>(&self, values: impl IntoIterator<Item = T>) -> Vec<Choice<T>>
+    where
+        for<'a> &'a T: Into<Text>,
+    {
+        values
+            .into_iter()
+            .map(|value| Choice::new(self.settings.language, value))
+            .collect()
+    }
+
+    /// The chosen theme, or `None` to follow the system colour scheme.
+    fn theme(&self) -> Option<Theme> {
+        match self.settings.theme {
+            ThemeMode::System => None,
+            ThemeMode::Light => Some(Theme::Light),
+            ThemeMode::Dark => Some(Theme::Dark),
+        }
+    }
+
+    /// The slice of the picture that belongs in tile `value`'s goal cell.
+    fn picture_crop(&self, value: u16) -> Rectangle<u32> {
+        let (rows, cols) = (self.puzzle.rows(), self.puzzle.cols());
+        let (goal_row, goal_col) = goal_position(value, (rows, cols), self.puzzle.goal());
+        let (width, height) = (PICTURE_SIZE / cols as u32, PICTURE_SIZE / rows as u32);
+
+        Rectangle {
+            x: goal_col as u32 * width,
+            y: goal_row as u32 * height,
+            width,
+            height,
+        }
+    }
+
+    /// Every cell of the board as it is to be drawn now, in the order to
+    /// draw them, whichever renderer draws them.
+    fn tile_sprites(&self) -> Vec<TileSprite> {
+        let puzzle = &self.puzzle;
+        let (rows, cols) = (puzzle.rows(), puzzle.cols());
+        let pitch = self.tile_pitch();
+        let corners = self.corners();
+        let hidden = self.is_hidden();
+
+        // Time into the victory colour wave, while it lasts
+        let wave = self
+            .victory
+            .filter(|_| self.is_celebrating())
+            .map(|solved_at| self.now.duration_since(solved_at));
+
+        // The tiles beside the blank, found once rather than once per tile
+        let movable = Direction::ALL.map(|direction| puzzle.tile_for_direction(direction));
+        let focused = self.focused_cell();
+        let tutorial_press = self.tutorial_press();
+
+        // Tiles that just moved are drawn part of the way back to where they
+        // came from
+        let slide = self.animation.as_ref().map(|animation| {
+            let remaining = 1.0 - animation.progress(self.now);
+            (animation, remaining)
+        });
+
+        let mut sprites = Vec::with_capacity(rows * cols);
+        for i in 0..rows {
+            for j in 0..cols {
+                let tile = puzzle.tile(i, j);
+                let (mut y, mut x) = (i as f32, j as f32);
+
+                if let Some((animation, remaining)) = slide
+                    && animation.tiles.contains(&(i, j))
+                {
+                    y += animation.offset.0 * remaining;
+                    x += animation.offset.1 * remaining;
+                }
+                let rejected = self
+                    .rejected
+                    .filter(|&(row, col, _)| (row, col) == (i, j))
+                    .map(|(_, _, at)| self.now.duration_since(at));
+                // Two quick shakes from side to side, dying down
+                if let Some(elapsed) = rejected
+                    && self.motion_enabled()
+                {
+                    let t = (elapsed.as_secs_f32() / REJECTION_CUE.as_secs_f32()).min(1.0);
+                    x += (t * 4.0 * std::f32::consts::PI).sin() * (1.0 - t) * REJECTION_SHAKE
+                        / pitch;
+                }
+
+                // A dragged tile follows the pointer towards the blank
+                if let Some(drag) = puzzle.drag()
+                    && drag.tile() == (i, j)
+                {
+                    match drag.direction() {
+                        Direction::Up => y -= drag.progress(),
+                        Direction::Down => y += drag.progress(),
+                        Direction::Left => x -= drag.progress(),
+                        Direction::Right => x += drag.progress(),
+                    }
+                }
+
+                let value = tile.value();
+                let picture = value
+                    .filter(|_| self.settings.picture_mode && !hidden)
+                    .map(|value| self.picture_crop(value));
+                sprites.push(TileSprite {
+                    cell: (i, j),
+                    at: (y, x),
+                    label: value.map(|value| {
+                        if hidden || (picture.is_some() && !self.settings.picture_numbers) {
+                            String::new()
+                        } else {
+                            self.label(value, rows * cols)
+                        }
+                    }),
+                    picture,
+                    look: TileLook {
+                        hinted: self.hint.is_some_and(|(cell, _)| cell == (i, j))
+                            || tutorial_press == Some((i, j)),
+                        goal_colors: self.settings.goal_colors && !hidden,
+                        in_place: puzzle.is_tile_in_place(i, j),
+                        movable: movable.contains(&Some((i, j))),
+                        focused: focused == Some((i, j)),
+                        last_moved: self.is_accenting() && puzzle.last_moved() == Some((i, j)),
+                        rejected: rejected.is_some(),
+                        wave: wave.map(|elapsed| (elapsed, i + j)),
+                        corners,
+                    },
+                });
+            }
+        }
+        sprites
+    }
+
+    /// How the solve just finished compares with the optimal solution, or
+    /// that it is still being worked out.
+    fn optimal_comparison(&self) -> Option<String> {
+        let key = scramble_key(self.solved_scramble.as_ref()?);
+        let moves = self.puzzle.moves();
+
+        match self.optimal_lengths.get(&key) {
+            Some(Some(OptimalLength::Exact(length))) => {
+                Some(self.trf(Text::YourSolveAndOptimal, &[&moves, length]))
+            }
+            Some(Some(OptimalLength::AtLeast(bound))) => {
+                Some(self.trf(Text::YourSolveAndOptimalBound, &[&moves, bound]))
+            }
+            Some(None) => None,
+            None => Some(String::from(self.tr(Text::WorkingOutOptimal))),
+        }
+    }
+
+    /// A small, inert board of `values` in rows of `cols`, `0` for the
+    /// blank, that fits in `size`.
+    fn mini_grid(&self, values: &[u16], cols: usize, size: f32) -> Element<'_, Message> {
+        let rows = values.len() / cols;
+        let cells = rows.max(cols);
+        let cell_size = (size - (cells - 1) as f32 * PREVIEW_SPACING) / cells as f32;
+
+        let mut grid = column![].spacing(PREVIEW_SPACING);
+        for values in values.chunks(cols) {
+            let mut cells = row![].spacing(PREVIEW_SPACING);
+            for &value in values {
+                let label = match value {
+                    0 => String::new(),
+                    value => self.label(value, rows * cols),
+                };
+                cells = cells.push(
+                    container(text(label).size(cell_size * 0.5))
+                        .center(cell_size)
+                        .style(move |theme: &Theme| {
+                            let palette = theme.extended_palette();
+                            let pair = if value == 0 {
+                                palette.background.strong
+                            } else {
+                                palette.primary.base
+                            };
+
+                            container::Style::default()
+                                .background(pair.color)
+                                .color(pair.text)
+                        }),
+                );
+            }
+            grid = grid.push(cells);
+        }
+        grid.into()
+    }
+
+    /// The session log, newest first, in a column `height` tall.
+    fn log_panel(&self, height: f32) -> Element<'_, Message> {
+        let entries: Element<'_, Message> = if self.log.is_empty() {
+            text(self.tr(Text::LogEmpty)).size(14).into()
+        } else {
+            column(self.log.newest_first().map(|entry| {
+                text(entry.to_string())
+                    .size(12)
+                    .font(Font::MONOSPACE)
+                    .into()
+            }))
+            .spacing(2)
+            .into()
+        };
+
+        container(
+            column![
+                row![
+                    text(self.tr(Text::SessionLog)).size(18),
+                    button(text(self.tr(Text::CopyLog)).size(14))
+                        .on_press_maybe((!self.log.is_empty()).then_some(Message::CopyLog))
+                        .padding([2, 8])
+                        .style(button::secondary),
+                ]
+                .spacing(10)
+                .align_y(Alignment::Center),
+                scrollable(entries).height(Length::Fill),
+            ]
+            .spacing(10),
+        )
+        .width(LOG_PANEL_WIDTH)
+        .height(height)
+        .padding(10)
+        .style(container::rounded_box)
+        .into()
+    }
+
+    /// The bot's side of a race: its board as it plays, and how far it has
+    /// got.
+    fn bot_board(&self, race: &Race, size: f32) -> Element<'_, Message> {
+        let bot = race.bot();
+        let values: Vec<u16> = bot
+            .tiles()
+            .iter()
+            .map(|tile| tile.value().unwrap_or(0))
+            .collect();
+        let progress = if race.is_ready() {
+            self.trf(Text::Moves, &[&bot.moves()])
+        } else {
+            String::from(self.tr(Text::BotThinking))
+        };
+
+        column![
+            text(self.tr(Text::Bot)).size(20),
+            self.mini_grid(&values, bot.cols(), size),
+            text(progress).size(16),
+        ]
+        .spacing(5)
+        .align_x(Alignment::Center)
+        .into()
+    }
+
+    /// A miniature of the solved board, or of the picture in picture mode,
+    /// under a toggle that folds it away.
+    ///
+    /// It is laid out from [`Goal::layout`], which places tiles by the same
+    /// [`goal_position`] that decides whether the board is solved.
+    fn goal_preview(&self) -> Element<'_, Message> {
+        let shown = self.settings.goal_preview;
+        let toggle = button(
+            text(self.tr(if shown {
+                Text::HideGoal
+            } else {
+                Text::ShowGoal
+            }))
+            .size(14),
+        )
+        .style(button::text)
+        .padding(2)
+        .on_press(Message::SetGoalPreview(!shown));
+
+        if !shown {
+            return toggle.into();
+        }
+
+        let preview: Element<'_, Message> = if self.settings.picture_mode {
+            image(self.picture.clone())
+                .width(THUMBNAIL_SIZE)
+                .height(THUMBNAIL_SIZE)
+                .into()
+        } else {
+            let (rows, cols) = (self.puzzle.rows(), self.puzzle.cols());
+            self.mini_grid(&self.puzzle.goal().layout(rows, cols), cols, THUMBNAIL_SIZE)
+        };
+
+        column![toggle, preview]
+            .spacing(5)
+            .align_x(Alignment::Center)
+            .into()
+    }
+
+    fn view(&self) -> Element<'_, Message> {
+        let screen = match self.screen {
+            Screen::Menu => self.menu_view(),
+            Screen::Game => self.game_view(),
+            Screen::Stats => self.stats_view(),
+            Screen::Leaderboard => self.leaderboard_view(),
+            Screen::Achievements => self.achievements_view(),
+            Screen::Settings => self.settings_view(),
+            Screen::GoalEditor => self.goal_editor_view(),
+            Screen::Replay => self.replay_view(),
+            Screen::Saves => self.saves_view(),
+            Screen::Profiles => self.profiles_view(),
+        };
+
+        stack![screen]
+            .push((self.screen == Screen::Game && self.game_menu).then(|| {
+                let entry = |label, message| {
+                    button(text(self.tr(label)).width(Length::Fill).center())
+                        .on_press(message)
+                        .width(200)
+                };
+
+                opaque(center(
+                    container(
+                        column![
+                            text(self.tr(Text::Menu)).size(24),
+                            entry(Text::Resume, Message::CloseGameMenu),
+                            entry(Text::Restart, Message::Retry),
+                            entry(Text::Settings, Message::ShowScreen(Screen::Settings)),
+                            entry(Text::QuitToMenu, Message::QuitToMenu),
+                        ]
+                        .spacing(10)
+                        .align_x(Alignment::Center),
+                    )
+                    .padding(20)
+                    .style(container::rounded_box),
+                ))
+            }))
+            .push(self.show_help.then(|| {
+                let mut bindings =
+                    column![text(self.tr(Text::KeyboardShortcuts)).size(24)].spacing(5);
+                let movement = movement_shortcuts(self.settings.key_scheme);
+                for &(keys, action) in movement.iter().chain(&SHORTCUTS) {
+                    bindings = bindings.push(row![text(keys).width(180), text(self.tr(action))]);
+                }
+                if cfg!(feature = "gamepad") {
+                    bindings = bindings.push(text(self.tr(Text::Controller)).size(24));
+                    for (buttons, action) in gamepad::BUTTONS {
+                        bindings =
+                            bindings.push(row![text(buttons).width(180), text(self.tr(action))]);
+                    }
+                }
+
+                opaque(center(
+                    container(
+                        bindings
+                            .push(button(self.tr(Text::Close)).on_press(Message::ToggleHelp))
+                            .align_x(Alignment::Center),
+                    )
+                    .padding(20)
+                    .style(container::rounded_box),
+                ))
+            }))
+            .into()
+    }
+
+    /// What the tutorial says to do, with the step it is on.
+    fn tutorial_panel(&self, tutorial: &Tutorial, width: f32) -> Element<'_, Message> {
+        let caption = if self.tutorial_undo_at.is_some() {
+            Text::TutorialStray
+        } else if tutorial.is_waiting() {
+            Text::TutorialStepDone
+        } else {
+            tutorial
+                .lesson()
+                .map_or(Text::TutorialFinished, |lesson| Text::from(&lesson))
+        };
+
+        container(
+            column![
+                text(self.trf(Text::TutorialStep, &[&tutorial.step(), &Tutorial::STEPS])).size(20),
+                text(self.tr(caption)).size(16).center(),
+                row![]
+                    .push(tutorial.is_waiting().then(|| {
+                        button(self.tr(Text::NextStep)).on_press(Message::BeginTutorialStep)
+                    }))
+                    .push(
+                        button(self.tr(if tutorial.is_finished() {
+                            Text::QuitToMenu
+                        } else {
+                            Text::LeaveTutorial
+                        }))
+                        .on_press(Message::EndTutorial),
+                    )
+                    .spacing(10),
+            ]
+            .spacing(5)
+            .align_x(Alignment::Center),
+        )
+        .padding(10)
+        .max_width(width)
+        .style(container::rounded_box)
+        .into()
+    }
+
+    fn menu_view(&self) -> Element<'_, Message> {
+        let (rows, cols) = self.settings.grid_size;
+        let daily_done = self
+            .records
+            .daily_result(&Records::key(rows, cols, false), Day::today())
+            .is_some();
+
+        let modes = row(PlayMode::ALL.map(|mode| {
+            let playable = mode != PlayMode::Daily || !daily_done;
+            button(self.tr(mode.name()))
+                .on_press_maybe(playable.then_some(Message::Play(mode)))
+                .into()
+        }))
+        .spacing(10)
+        .wrap()
+        .align_x(Alignment::Center);
+
+        let size = row![
+            dimension_control(
+                self.tr(Text::Columns),
+                cols,
+                Message::SetMenuSize(rows, cols.saturating_sub(1)),
+                Message::SetMenuSize(rows, cols + 1),
+            ),
+            dimension_control(
+                self.tr(Text::Rows),
+                rows,
+                Message::SetMenuSize(rows.saturating_sub(1), cols),
+                Message::SetMenuSize(rows + 1, cols),
+            ),
+        ]
+        .spacing(20)
+        .align_y(Alignment::Center);
+
+        let screens = row![
+            button(self.tr(Text::Stats)).on_press(Message::ShowScreen(Screen::Stats)),
+            button(self.tr(Text::Leaderboard)).on_press(Message::ShowScreen(Screen::Leaderboard)),
+            button(self.tr(Text::Achievements)).on_press(Message::ShowScreen(Screen::Achievements)),
+            button(self.tr(Text::Settings)).on_press(Message::ShowScreen(Screen::Settings)),
+        ]
+        .spacing(10)
+        .wrap()
+        .align_x(Alignment::Center);
+
+        center(
+            column![text(self.tr(Text::AppName)).size(32)]
+                .push(self.can_continue().then(|| {
+                    button(self.tr(Text::ContinueGame)).on_press(Message::ShowScreen(Screen::Game))
+                }))
+                .push(modes)
+                .push(
+                    column![]
+                        .push(
+                            (!self.settings.tutorial_done)
+                                .then(|| text(self.tr(Text::TryTheTutorial)).size(16)),
+                        )
+                        .push(
+                            button(self.tr(Text::Tutorial))
+                                .style(if self.settings.tutorial_done {
+                                    button::secondary
+                                } else {
+                                    button::primary
+                                })
+                                .on_press(Message::StartTutorial),
+                        )
+                        .spacing(5)
+                        .align_x(Alignment::Center),
+                )
+                .push(size)
+                .push(screens)
+                .spacing(20)
+                .align_x(Alignment::Center),
+        )
+        .padding(20)
+        .into()
+    }
+
+    fn leaderboard_view(&self) -> Element<'_, Message> {
+        let mut tables = column![].spacing(15).align_x(Alignment::Center);
+
+        for (key, entries) in self.leaderboard.tables() {
+            let mut table = column![text(key).size(24)].spacing(5);
+
+            for (place, entry) in entries.iter().enumerate() {
+                table = table.push(
+                    row![
+                        text!("{}.", place + 1).width(30),
+                        text(&entry.name).width(160),
+                        text(self.format_time(entry.time, Precision::Millis)).width(80),
+                        text(self.trf(Text::Moves, &[&entry.moves])).width(100),
+                        text(entry.day.to_string()).width(100),
+                        text(
+                            entry
+                                .efficiency
+                                .and_then(Efficiency::stars)
+                                .map_or_else(String::new, star_rating)
+                        )
+                        .width(50),
+                        button(self.tr(Text::Delete))
+                            .style(button::danger)
+                            .on_press(Message::DeleteEntry(key.to_owned(), place)),
+                    ]
+                    .spacing(10)
+                    .align_y(Alignment::Center),
+                );
+            }
+            tables = tables.push(table);
+        }
+
+        if self.leaderboard.is_empty() {
+            tables = tables.push(text(self.tr(Text::NoLeaderboardEntries)));
+        }
+
+        container(
+            column![
+                text(self.tr(Text::Leaderboard)).size(32),
+                scrollable(tables).height(BOARD_SIZE),
+                button(self.tr(Text::Back)).on_press(Message::ShowScreen(self.back_to)),
+            ]
+            .spacing(20)
+            .align_x(Alignment::Center),
+        )
+        .center_x(Length::Fill)
+        .center_y(Length::Fill)
+        .into()
+    }
+
+    fn achievements_view(&self) -> Element<'_, Message> {
+        let mut list = column![].spacing(10);
+
+        for achievement in &ACHIEVEMENTS {
+            let unlocked = self.achievements.day(achievement.id);
+            // Locked achievements are greyed out
+            let style = move |theme: &Theme| text::Style {
+                color: unlocked
+                    .is_none()
+                    .then(|| theme.extended_palette().background.strong.color),
+            };
+            let status = unlocked.map_or_else(
+                || String::from(self.tr(Text::Locked)),
+                |day| self.trf(Text::UnlockedOn, &[&day]),
+            );
+
+            list = list.push(
+                column![
+                    text(self.tr(achievement.name)).size(20).style(style),
+                    text(self.tr(achievement.description)).style(style),
+                    text(status).size(14).style(style),
+                ]
+                .spacing(2),
+            );
+        }
+
+        container(
+            column![
+                text(self.tr(Text::Achievements)).size(32),
+                scrollable(list).height(BOARD_SIZE),
+                button(self.tr(Text::Back)).on_press(Message::ShowScreen(self.back_to)),
+            ]
+            .spacing(20)
+            .align_x(Alignment::Center),
+        )
+        .center_x(Length::Fill)
+        .center_y(Length::Fill)
+        .into()
+    }
+
+    fn settings_view(&self) -> Element<'_, Message> {
+        let heading = |title| text(self.tr(title)).size(24);
+        let animation = if self.animation_duration().is_zero() {
+            String::from(self.tr(Text::SlideAnimationOff))
+        } else {
+            self.trf(Text::SlideAnimation, &[&self.settings.animation_ms])
+        };
+
+        let appearance = column![
+            heading(Text::Appearance),
+            row![
+                pick_list(
+                    self.choices(ThemeMode::ALL),
+                    Some(Choice::new(self.settings.language, self.settings.theme)),
+                    |choice| Message::ThemeChanged(choice.value)
+                ),
+                pick_list(
+                    self.choices(ColorScheme::ALL),
+                    Some(Choice::new(
+                        self.settings.language,
+                        self.settings.color_scheme
+                    )),
+                    |choice| Message::SetColorScheme(choice.value)
+                ),
+                pick_list(
+                    self.choices(LabelMode::ALL),
+                    Some(Choice::new(
+                        self.settings.language,
+                        self.settings.label_mode
+                    )),
+                    |choice| Message::SetLabelMode(choice.value)
+                ),
+                pick_list(
+                    Language::ALL,
+                    Some(self.settings.language),
+                    Message::SetLanguage
+                ),
+            ]
+            .spacing(10),
+        ]
+        .push(
+            (self.settings.label_mode == LabelMode::Letters)
+                .then(|| text(self.tr(Text::LettersFallBack)).size(14)),
+        )
+        .push(
+            column![
+                checkbox(self.settings.goal_colors)
+                    .label(self.tr(Text::ColourPlacedTiles))
+                    .on_toggle(Message::SetGoalColors),
+                checkbox(!self.motion_enabled())
+                    .label(self.tr(Text::ReduceMotion))
+                    .on_toggle(Message::SetReduceMotion),
+                checkbox(self.settings.picture_mode)
+                    .label(self.tr(Text::Picture))
+                    .on_toggle(Message::SetPictureMode),
+                checkbox(self.settings.picture_numbers)
+                    .label(self.tr(Text::NumbersOnPicture))
+                    .on_toggle_maybe(
+                        self.settings
+                            .picture_mode
+                            .then_some(Message::SetPictureNumbers),
+                    ),
+                row![
+                    text(animation).width(220),
+                    slider(
+                        MIN_ANIMATION_MS..=MAX_ANIMATION_MS,
+                        self.settings
+                            .animation_ms
+                            .clamp(u64::from(MIN_ANIMATION_MS), u64::from(MAX_ANIMATION_MS))
+                            as u32,
+                        Message::SetAnimationSpeed
+                    )
+                    .step(ANIMATION_STEP)
+                    .width(200),
+                    pick_list(
+                        self.choices(Easing::ALL),
+                        Some(Choice::new(self.settings.language, self.settings.easing)),
+                        |choice| Message::SetEasing(choice.value)
+                    ),
+                ]
+                .spacing(10)
+                .align_y(Alignment::Center),
+                row![
+                    text(self.trf(Text::TileGap, &[&self.settings.tile_gap])).width(220),
+                    slider(
+                        0..=MAX_TILE_GAP,
+                        self.settings.tile_gap.min(MAX_TILE_GAP),
+                        Message::SetTileGap
+                    )
+                    .width(200),
+                ]
+                .spacing(10)
+                .align_y(Alignment::Center),
+                row![
+                    text(self.trf(Text::CornerRadius, &[&self.settings.tile_radius])).width(220),
+                    slider(
+                        0..=MAX_TILE_RADIUS,
+                        self.settings.tile_radius.min(MAX_TILE_RADIUS),
+                        Message::SetTileRadius
+                    )
+                    .width(200),
+                ]
+                .spacing(10)
+                .align_y(Alignment::Center),
+                row![
+                    text(self.tr(Text::TileNumbersIn)),
+                    pick_list(
+                        self.choices(
+                            TileFont::ALL
+                                .into_iter()
+                                .filter(|font| *font == TileFont::Standard
+                                    || self.loaded_fonts.contains(font))
+                        ),
+                        Some(Choice::new(self.settings.language, self.settings.tile_font)),
+                        |choice| Message::SetTileFont(choice.value)
+                    ),
+                ]
+                .spacing(10)
+                .align_y(Alignment::Center),
+                row![
+                    text(self.tr(Text::BoardDrawnWith)),
+                    pick_list(
+                        self.choices(BoardRenderer::ALL),
+                        Some(Choice::new(
+                            self.settings.language,
+                            self.settings.board_renderer
+                        )),
+                        |choice| Message::SetBoardRenderer(choice.value)
+                    ),
+                ]
+                .spacing(10)
+                .align_y(Alignment::Center),
+            ]
+            .spacing(10),
+        )
+        .spacing(10);
+
+        let gameplay = column![
+            heading(Text::Gameplay),
+            checkbox(self.settings.slide_count == SlideCount::PerSlide)
+                .label(self.tr(Text::CountLineSlidesAsOne))
+                .on_toggle(|per_slide| {
+                    Message::SetSlideCount(if per_slide {
+                        SlideCount::PerSlide
+                    } else {
+                        SlideCount::PerTile
+                    })
+                }),
+            row![
+                text(self.tr(Text::DirectionKeys)),
+                pick_list(
+                    self.choices(KeyScheme::ALL),
+                    Some(Choice::new(
+                        self.settings.language,
+                        self.settings.key_scheme
+                    )),
+                    |choice| Message::SetKeyScheme(choice.value)
+                ),
+            ]
+            .spacing(10)
+            .align_y(Alignment::Center),
+            row![
+                text(self.trf(Text::KeyRepeatDelay, &[&self.settings.key_repeat_delay_ms]))
+                    .width(220),
+                slider(
+                    KEY_REPEAT_DELAYS,
+                    self.settings.key_repeat_delay_ms as u32,
+                    Message::SetKeyRepeatDelay
+                )
+                .step(KEY_REPEAT_STEP)
+                .width(200),
+            ]
+            .spacing(10)
+            .align_y(Alignment::Center),
+            row![
+                text(self.trf(
+                    Text::KeyRepeatInterval,
+                    &[&self.settings.key_repeat_interval_ms]
+                ))
+                .width(220),
+                slider(
+                    KEY_REPEAT_INTERVALS,
+                    self.settings.key_repeat_interval_ms as u32,
+                    Message::SetKeyRepeatInterval
+                )
+                .step(KEY_REPEAT_STEP)
+                .width(200),
+            ]
+            .spacing(10)
+            .align_y(Alignment::Center),
+            checkbox(self.settings.show_distance)
+                .label(self.tr(Text::ShowDistance))
+                .on_toggle(Message::SetShowDistance),
+            checkbox(self.settings.progress_metric == ProgressMetric::Distance)
+                .label(self.tr(Text::ProgressByDistance))
+                .on_toggle(|by_distance| {
+                    Message::SetProgressMetric(if by_distance {
+                        ProgressMetric::Distance
+                    } else {
+                        ProgressMetric::TilesInPlace
+                    })
+                }),
+            checkbox(self.settings.confirm_shuffle)
+                .label(self.tr(Text::AskBeforeAbandoning))
+                .on_toggle(Message::SetConfirmShuffle),
+            checkbox(self.settings.pause_when_away)
+                .label(self.tr(Text::PauseWhenAway))
+                .on_toggle(Message::SetPauseWhenAway),
+            checkbox(self.settings.ghost)
+                .label(self.tr(Text::RaceYourBest))
+                .on_toggle(Message::SetGhost),
+            checkbox(self.settings.auto_save)
+                .label(self.tr(Text::ResumeOnLaunch))
+                .on_toggle(Message::SetAutoSave),
+        ]
+        .push(cfg!(feature = "sound").then(|| {
+            checkbox(!self.settings.muted)
+                .label(self.tr(Text::Sound))
+                .on_toggle(|sound| Message::SetMuted(!sound))
+        }))
+        .spacing(10);
+
+        let challenges = column![
+            heading(Text::NextScrambleChallenges),
+            row![
+                checkbox(self.settings.time_attack)
+                    .label(self.tr(Text::TimeAttack))
+                    .on_toggle(Message::SetTimeAttack),
+                time_limit_control(self.settings.language, self.settings.time_limit_secs),
+            ]
+            .spacing(10)
+            .align_y(Alignment::Center),
+            checkbox(self.settings.move_limit)
+                .label(self.tr(Text::MoveLimit))
+                .on_toggle(Message::SetMoveLimit),
+            row![
+                checkbox(self.settings.blindfold)
+                    .label(self.tr(Text::Blindfold))
+                    .on_toggle(Message::SetBlindfold),
+                preview_control(self.settings.language, self.settings.blindfold_preview_secs),
+            ]
+            .spacing(10)
+            .align_y(Alignment::Center),
+        ]
+        .spacing(10);
+
+        let profile = row![
+            text(self.trf(Text::PlayingAs, &[&self.profile.name])),
+            button(self.tr(Text::SwitchProfile)).on_press(Message::ShowScreen(Screen::Profiles)),
+        ]
+        .spacing(10)
+        .align_y(Alignment::Center);
+
+        container(
+            column![
+                text(self.tr(Text::Settings)).size(32),
+                profile,
+                scrollable(column![appearance, gameplay, challenges].spacing(25))
+                    .height(BOARD_SIZE),
+                button(self.tr(Text::Back)).on_press(Message::ShowScreen(self.back_to)),
+            ]
+            .spacing(20)
+            .align_x(Alignment::Center),
+        )
+        .center_x(Length::Fill)
+        .center_y(Length::Fill)
+        .into()
+    }
+
+    fn stats_view(&self) -> Element<'_, Message> {
+        let mut sizes = column![].spacing(15).align_x(Alignment::Center);
+
+        for (size, stats) in self.records.all_stats() {
+            let average_moves = stats.average_moves().map_or_else(
+                || String::from("-"),
+                |moves| i18n::format_decimal(self.settings.language, moves),
+            );
+            let average_time = stats.average_time().map_or_else(
+                || String::from("-"),
+                |time| self.format_time(time, Precision::Millis),
+            );
+
+            sizes = sizes.push(
+                column![
+                    text(size).size(24),
+                    text(self.trf(Text::ShuffledAndSolved, &[&stats.shuffled, &stats.solved])),
+                    text(self.trf(
+                        Text::TotalMovesAndTime,
+                        &[
+                            &stats.total_moves,
+                            &self.format_time(stats.total_time, Precision::Millis)
+                        ]
+                    )),
+                    text(self.trf(Text::AverageMovesAndTime, &[&average_moves, &average_time])),
+                    text(self.trf(
+                        Text::Streaks,
+                        &[&stats.current_streak, &stats.longest_streak]
+                    )),
+                    text(self.trf(
+                        Text::BlindfoldStats,
+                        &[&stats.blindfold_solves, &stats.peeks]
+                    )),
+                    text(
+                        self.trf(
+                            Text::MoveLimitStats,
+                            &[
+                                &stats.move_limit_wins,
+                                &stats.move_limit_losses,
+                                &stats.move_limit_best.map_or_else(
+                                    || String::from("-"),
+                                    |best| best.moves.to_string()
+                                ),
+                                &stats.move_limit_best.map_or_else(
+                                    || String::from("-"),
+                                    |best| self.format_time(best.time, Precision::Millis)
+                                )
+                            ]
+                        )
+                    ),
+                    text(self.trf(
+                        Text::TimeAttackStats,
+                        &[
+                            &stats.timed_out,
+                            &stats.best_time_left.map_or_else(
+                                || String::from("-"),
+                                |time| self.format_time(time, Precision::Millis)
+                            )
+                        ]
+                    )),
+                ]
+                .push(stats.average_efficiency().map(|efficiency| {
+                    text(self.trf(Text::AverageEfficiency, &[&(efficiency * 100.0).round()]))
+                }))
+                .push(
+                    (stats.did_not_finish > 0)
+                        .then(|| text(self.trf(Text::RelaysNotFinished, &[&stats.did_not_finish]))),
+                )
+                .spacing(5)
+                .align_x(Alignment::Center),
+            );
+        }
+
+        if self.records.all_stats().next().is_none() {
+            sizes = sizes.push(text(self.tr(Text::NoGamesYet)));
+        }
+
+        let mut races = self.records.all_races().peekable();
+        if races.peek().is_some() {
+            sizes = sizes.push(text(self.tr(Text::RacesAgainstTheBot)).size(24));
+        }
+        for (difficulty, tally) in races {
+            sizes = sizes.push(text(self.trf(
+                Text::RaceStats,
+                &[&self.tr(Text::from(&difficulty)), &tally.won, &tally.lost],
+            )));
+        }
+
+        let reset: Element<'_, Message> = if self.confirm_reset_stats {
+            row![
+                text(self.tr(Text::ResetAllStatistics)),
+                button(self.tr(Text::Reset))
+                    .style(button::danger)
+                    .on_press(Message::ConfirmResetStats(true)),
+                button(self.tr(Text::Cancel)).on_press(Message::ConfirmResetStats(false)),
+            ]
+            .spacing(10)
+            .align_y(Alignment::Center)
+            .into()
+        } else {
+            button(self.tr(Text::ResetStatistics))
+                .on_press(Message::ResetStats)
+                .into()
+        };
+
+        container(
+            column![
+                text(self.tr(Text::Statistics)).size(32),
+                scrollable(sizes).height(BOARD_SIZE),
+                row![
+                    button(self.tr(Text::Back)).on_press(Message::ShowScreen(self.back_to)),
+                    button(self.tr(Text::ExportCsv)).on_press_maybe(
+                        (!self.records.solves().is_empty()).then_some(Message::ExportCsv)
+                    ),
+                    reset
+                ]
+                .spacing(10)
+                .align_y(Alignment::Center),
+            ]
+            .push(self.csv_notice.as_ref().map(|notice| match notice {
+                Notice::Info(message) => text(message).size(14),
+                Notice::Error(message) => text(message).size(14).style(text::danger),
+            }))
+            .spacing(20)
+            .align_x(Alignment::Center),
+        )
+        .center_x(Length::Fill)
+        .center_y(Length::Fill)
+        .into()
+    }
+
+    fn saves_view(&self) -> Element<'_, Message> {
+        let mut slots = column![].spacing(10);
+
+        for (index, contents) in self.slots.iter().enumerate() {
+            let saved = contents.saved();
+            let preview: Element<'_, Message> = match saved {
+                // The miniature would give a blindfolded board away
+                Some(slot) if !slot.game.puzzle.is_hidden() => {
+                    let puzzle = &slot.game.puzzle;
+                    let values: Vec<u16> = puzzle
+                        .tiles()
+                        .iter()
+                        .map(|tile| tile.value().unwrap_or(0))
+                        .collect();
+                    self.mini_grid(&values, puzzle.cols(), SLOT_PREVIEW_SIZE)
+                }
+                _ => container(text(if saved.is_some() { "🙈" } else { "" }))
+                    .center(SLOT_PREVIEW_SIZE)
+                    .style(container::rounded_box)
+                    .into(),
+            };
+
+            let mut description = column![].spacing(5).width(Length::Fill);
+            description = match (contents, &self.renaming_slot) {
+                (_, Some((renaming, name))) if *renaming == index => description.push(
+                    text_input(self.tr(Text::Rename), name)
+                        .on_input(Message::SlotNameChanged)
+                        .on_submit(Message::SubmitSlotName),
+                ),
+                (SlotContents::Saved(slot), _) => description.push(text(&slot.name).size(18)),
+                (SlotContents::Empty, _) => description.push(text(self.tr(Text::EmptySlot))),
+                (SlotContents::Unreadable(error), _) => description.push(
+                    text(self.trf(Text::UnreadableSlot, &[error]))
+                        .size(14)
+                        .style(text::danger),
+                ),
+            };
+            if let Some(slot) = saved {
+                let puzzle = &slot.game.puzzle;
+                description = description.push(
+                    text(self.trf(
+                        Text::SlotDetails,
+                        &[
+                            &format!("{}x{}", puzzle.cols(), puzzle.rows()),
+                            &self.slot_mode(&slot.game),
+                            &puzzle.moves(),
+                            &self.format_time(slot.game.elapsed, Precision::Tenths),
+                        ],
+                    ))
+                    .size(14),
+                );
+            }
+
+            let actions: Element<'_, Message> = if self.confirm_delete_slot == Some(index) {
+                let name = saved.map_or("", |slot| slot.name.as_str());
+                row![
+                    text(self.trf(Text::DeleteSlot, &[&name])),
+                    button(self.tr(Text::Delete))
+                        .style(button::danger)
+                        .on_press(Message::ConfirmDeleteSlot(true)),
+                    button(self.tr(Text::Cancel)).on_press(Message::ConfirmDeleteSlot(false)),
+                ]
+                .spacing(10)
+                .align_y(Alignment::Center)
+                .into()
+            } else {
+                let occupied = !matches!(contents, SlotContents::Empty);
+                row![
+                    button(self.tr(Text::SaveHere)).on_press(Message::SaveToSlot(index)),
+                    button(self.tr(Text::Load))
+                        .on_press_maybe(saved.map(|_| Message::LoadSlot(index))),
+                    button(self.tr(Text::Rename))
+                        .on_press_maybe(saved.map(|_| Message::RenameSlot(index))),
+                    button(self.tr(Text::Delete))
+                        .style(button::danger)
+                        .on_press_maybe(occupied.then_some(Message::DeleteSlot(index))),
+                ]
+                .spacing(10)
+                .into()
+            };
+
+            slots = slots.push(
+                row![preview, description, actions]
+                    .spacing(15)
+                    .align_y(Alignment::Center),
+            );
+        }
+
+        container(
+            column![
+                text(self.tr(Text::Saves)).size(32),
+                scrollable(slots).height(BOARD_SIZE),
+                button(self.tr(Text::Back)).on_press(Message::ShowScreen(self.back_to)),
+            ]
+            .push(self.slot_notice.as_ref().map(|notice| match notice {
+                Notice::Info(message) => text(message).size(14),
+                Notice::Error(message) => text(message).size(14).style(text::danger),
+            }))
+            .spacing(20)
+            .align_x(Alignment::Center)
+            .max_width(SAVES_WIDTH),
+        )
+        .center_x(Length::Fill)
+        .center_y(Length::Fill)
+        .into()
+    }
+
+    fn profiles_view(&self) -> Element<'_, Message> {
+        let mut profiles = column![].spacing(10);
+
+        for profile in self.profiles.all() {
+            let playing = profile.id == self.profile.id;
+            let actions: Element<'_, Message> = match &self.deleting_profile {
+                Some((id, typed)) if *id == profile.id => column![
+                    text(self.trf(Text::TypeToDeleteProfile, &[&profile.name])).size(14),
+                    row![
+                        text_input(&profile.name, typed)
+                            .on_input(Message::DeleteConfirmationChanged)
+                            .width(200),
+                        button(self.tr(Text::Delete))
+                            .style(button::danger)
+                            .on_press_maybe(
+                                (*typed == profile.name)
+                                    .then_some(Message::ConfirmDeleteProfile(true))
+                            ),
+                        button(self.tr(Text::Cancel))
+                            .on_press(Message::ConfirmDeleteProfile(false)),
+                    ]
+                    .spacing(10)
+                    .align_y(Alignment::Center),
+                ]
+                .spacing(5)
+                .into(),
+                _ if playing => text(self.tr(Text::Playing)).size(14).into(),
+                _ => row![
+                    button(self.tr(Text::PlayAsProfile))
+                        .on_press(Message::SelectProfile(profile.id)),
+                    button(self.tr(Text::Delete))
+                        .style(button::danger)
+                        .on_press(Message::DeleteProfile(profile.id)),
+                ]
+                .spacing(10)
+                .into(),
+            };
+
+            profiles = profiles.push(
+                row![text(&profile.name).size(20).width(Length::Fill), actions]
+                    .spacing(15)
+                    .align_y(Alignment::Center),
+            );
+        }
+
+        let error = self.profile_error.map(|error| {
+            let message = match error {
+                ProfileError::EmptyName => String::from(self.tr(Text::ProfileNameEmpty)),
+                ProfileError::NameTooLong => {
+                    self.trf(Text::ProfileNameTooLong, &[&MAX_PROFILE_NAME])
+                }
+                ProfileError::NameTaken => String::from(self.tr(Text::ProfileNameTaken)),
+            };
+            text(message).size(14).style(text::danger)
+        });
+
+        container(
+            column![
+                text(self.tr(Text::Profiles)).size(32),
+                scrollable(profiles).height(BOARD_SIZE),
+                row![
+                    text_input(self.tr(Text::NewProfileName), &self.profile_input)
+                        .on_input(Message::ProfileInputChanged)
+                        .on_submit(Message::CreateProfile)
+                        .width(250),
+                    button(self.tr(Text::CreateProfile)).on_press(Message::CreateProfile),
+                ]
+                .spacing(10)
+                .align_y(Alignment::Center),
+            ]
+            .push(error)
+            .push(button(self.tr(Text::Back)).on_press(Message::ShowScreen(self.back_to)))
+            .spacing(20)
+            .align_x(Alignment::Center)
+            .max_width(PROFILES_WIDTH),
+        )
+        .center_x(Length::Fill)
+        .center_y(Length::Fill)
+        .into()
+    }
+
+    /// The modes a saved game is played in, or casual if none.
+    fn slot_mode(&self, game: &SavedGame) -> String {
+        let puzzle = &game.puzzle;
+        let modes: Vec<&str> = [
+            (game.relay.is_some(), Text::Relay),
+            (game.daily.is_some(), Text::Daily),
+            (puzzle.mode().is_time_attack(), Text::TimeAttack),
+            (puzzle.mode().is_move_limit(), Text::MoveLimit),
+            (puzzle.is_blindfold(), Text::Blindfold),
+        ]
+        .into_iter()
+        .filter(|&(on, _)| on)
+        .map(|(_, mode)| self.tr(mode))
+        .collect();
+
+        if modes.is_empty() {
+            String::from(self.tr(Text::Casual))
+        } else {
+            modes.join(" + ")
+        }
+    }
+
+    fn goal_editor_view(&self) -> Element<'_, Message> {
+        let (rows, cols) = (self.puzzle.rows(), self.puzzle.cols());
+        let cell_size = tile_size(BOARD_SIZE, TILE_SPACING, rows, cols);
+
+        let mut grid = column![].spacing(TILE_SPACING);
+        for i in 0..rows {
+            let mut cells = row![].spacing(TILE_SPACING);
+            for j in 0..cols {
+                let index = i * cols + j;
+                let label = match self.goal_editor[index] {
+                    None => String::new(),
+                    Some(0) => String::from(self.tr(Text::Blank)),
+                    Some(value) => self.label(value, rows * cols),
+                };
+
+                cells = cells.push(
+                    button(text(label).size(20).center().width(Length::Fill))
+                        .width(cell_size)
+                        .height(cell_size)
+                        .style(match self.goal_editor[index] {
+                            None => button::secondary,
+                            Some(_) => button::primary,
+                        })
+                        .on_press(Message::EditGoalCell(index)),
+                );
+            }
+            grid = grid.push(cells);
+        }
+
+        let error = self
+            .goal_editor_error
+            .as_deref()
+            .map(|error| text(error).style(text::danger));
+
+        container(
+            column![
+                text(self.tr(Text::CustomGoal)).size(32),
+                text(self.tr(Text::GoalEditorHelp)),
+                grid,
+            ]
+            .push(error)
+            .push(
+                row![
+                    button(self.tr(Text::Back)).on_press(Message::ShowScreen(self.back_to)),
+                    button(self.tr(Text::Clear)).on_press(Message::ClearGoalEditor),
+                    button(self.tr(Text::SaveGoal)).on_press(Message::SaveCustomGoal),
+                ]
+                .spacing(10),
+            )
+            .spacing(20)
+            .align_x(Alignment::Center),
+        )
+        .center_x(Length::Fill)
+        .center_y(Length::Fill)
+        .into()
+    }
+
+    fn replay_view(&self) -> Element<'_, Message> {
+        let (Some(playback), Some(replay)) = (&self.playback, &self.last_replay) else {
+            return center(button(self.tr(Text::Back)).on_press(Message::ShowScreen(self.back_to)))
+                .into();
+        };
+        let board = &playback.board;
+        let (rows, cols) = (board.rows(), board.cols());
+        let tile_size = tile_size(BOARD_SIZE, TILE_SPACING, rows, cols);
+        let label_size = label_size(tile_size, rows, cols);
+
+        // Tiles without a press handler keep the replay read-only
+        let mut grid = column![].spacing(TILE_SPACING);
+        for i in 0..rows {
+            let mut cells = row![].spacing(TILE_SPACING);
+            for j in 0..cols {
+                let label = board
+                    .tile(i, j)
+                    .value()
+                    .map_or_else(String::new, |value| self.label(value, rows * cols));
+
+                cells = cells.push(
+                    button(text(label).size(label_size).center().width(Length::Fill))
+                        .width(tile_size)
+                        .height(tile_size)
+                        .style({
+                            let (empty, scheme) =
+                                (board.tile(i, j).is_empty(), self.settings.color_scheme);
+
+                            move |theme: &Theme, status| {
+                                if empty {
+                                    button::secondary(theme, status)
+                                } else {
+                                    Palette::new(scheme, theme).tile.style(status)
+                                }
+                            }
+                        }),
+                );
+            }
+            grid = grid.push(cells);
+        }
+
+        container(
+            column![
+                text(self.tr(Text::Replay)).size(32),
+                text(self.trf(
+                    Text::ReplayProgress,
+                    &[
+                        &playback.step,
+                        &replay.len(),
+                        &self.format_time(playback.time(replay), Precision::Tenths)
+                    ]
+                ))
+                .size(20),
+                grid,
+                row![
+                    button(self.tr(Text::Back)).on_press(Message::ShowScreen(self.back_to)),
+                    button(self.tr(Text::Restart)).on_press(Message::RestartReplay),
+                    button(self.tr(if playback.is_playing() {
+                        Text::Pause
+                    } else {
+                        Text::Play
+                    }))
+                    .on_press(Message::ToggleReplay),
+                    button(self.tr(Text::Step)).on_press_maybe(
+                        (playback.step < replay.len()).then_some(Message::StepReplay)
+                    ),
+                    checkbox(playback.real_time)
+                        .label(self.tr(Text::RealTimeSpeed))
+                        .on_toggle(Message::SetReplayRealTime),
+                ]
+                .spacing(10)
+                .align_y(Alignment::Center),
+            ]
+            .spacing(20)
+            .align_x(Alignment::Center),
+        )
+        .center_x(Length::Fill)
+        .center_y(Length::Fill)
+        .into()
+    }
+
+    fn game_view(&self) -> Element<'_, Message> {
+        let puzzle = &self.puzzle;
+        let (rows, cols) = (puzzle.rows(), puzzle.cols());
+        let layout = self.board_layout();
+        let label_size = label_size(layout.tile_size, rows, cols);
+        let (font, corners) = (self.label_font(), self.corners());
+        let Size {
+            width: board_width,
+            height: board_height,
+        } = layout.size();
+        let tiles = self.tile_sprites();
+        let auto_solving = self.is_auto_solving();
+        let hidden = self.is_hidden();
+        let pressable = !auto_solving && self.victory.is_none() && !puzzle.is_locked();
+
+        // A right-clicked tile's goal, outlined over whatever is there now
+        let goal_flash = self.goal_flash.map(|(cell, home, at)| {
+            let t = self.now.duration_since(at).as_secs_f32() / GOAL_FLASH.as_secs_f32();
+            let pulse = if self.motion_enabled() {
+                (t.min(1.0) * std::f32::consts::PI).sin()
+            } else {
+                1.0
+            };
+            (cell, home, pulse)
+        });
+
+        let grid: Element<'_, Message> = match self.settings.board_renderer {
+            BoardRenderer::Canvas => canvas(BoardCanvas {
+                layout,
+                tiles,
+                scheme: self.settings.color_scheme,
+                label_size,
+                font,
+                corners,
+                picture: (self.picture.clone(), PICTURE_SIZE),
+                goal_flash,
+                on_press: pressable.then_some(Message::TilePressed as fn(usize, usize) -> Message),
+            })
+            .width(board_width)
+            .height(board_height)
+            .into(),
+            BoardRenderer::Widgets => {
+                let mut grid = Stack::new().width(board_width).height(board_height);
+
+                for sprite in tiles {
+                    let (i, j) = sprite.cell;
+                    let (blank, bounds) = (sprite.is_blank(), layout.bounds(sprite.at));
+                    let tile_button: Element<'_, Message> = match sprite.label {
+                        None => container("")
+                            .width(bounds.width)
+                            .height(bounds.height)
+                            .style(move |theme| blank_style(theme, corners.well))
+                            .into(),
+                        Some(label) => {
+                            // Blindfolded tiles stay clickable with nothing on them
+                            let numbered = !label.is_empty();
+                            let number = text(label)
+                                .size(label_size)
+                                .font(font)
+                                .height(Length::Fill)
+                                .width(Length::Fill)
+                                .center();
+
+                            let face: Element<'_, Message> = match sprite.picture {
+                                Some(crop) => {
+                                    let fragment = image(self.picture.clone())
+                                        .crop(crop)
+                                        .width(Length::Fill)
+                                        .height(Length::Fill)
+                                        .content_fit(ContentFit::Fill);
+
+                                    if numbered {
+                                        stack![fragment, number].into()
+                                    } else {
+                                        fragment.into()
+                                    }
+                                }
+                                None => number.into(),
+                            };
+                            let look = sprite.look;
+                            let scheme = self.settings.color_scheme;
+
+                            button(face)
+                                .padding(if self.settings.picture_mode { 0 } else { 5 })
+                                .width(bounds.width)
+                                .height(bounds.height)
+                                .style(move |theme: &Theme, status| {
+                                    look.style(&Palette::new(scheme, theme), theme, status)
+                                })
+                                .on_press_maybe(pressable.then_some(Message::TilePressed(i, j)))
+                                .into()
+                        }
+                    };
+
+                    let positioned = pin(tile_button).x(bounds.x).y(bounds.y);
+
+                    // Keep the blank beneath tiles sliding over it
+                    grid = if blank {
+                        grid.push_under(positioned)
+                    } else {
+                        grid.push(positioned)
+                    };
+                }
+
+                if let Some(((row, col), home, pulse)) = goal_flash {
+                    let scheme = self.settings.color_scheme;
+                    let bounds = layout.bounds((row as f32, col as f32));
+
+                    grid = grid.push(
+                        pin(container("")
+                            .width(bounds.width)
+                            .height(bounds.height)
+                            .style(move |theme: &Theme| {
+                                goal_flash_style(
+                                    &Palette::new(scheme, theme),
+                                    theme,
+                                    home,
+                                    pulse,
+                                    corners.well,
+                                )
+                            }))
+                        .x(bounds.x)
+                        .y(bounds.y),
+                    );
+                }
+
+                grid.into()
+            }
+        };
+
+        // The tutorial's arrow, in the gap, pointing at the tile to press
+        let grid = match self.tutorial_press() {
+            Some((row, col)) => {
+                let gap = puzzle.empty_position();
+                let arrow = if row < gap.0 {
+                    "↑"
+                } else if row > gap.0 {
+                    "↓"
+                } else if col < gap.1 {
+                    "←"
+                } else {
+                    "→"
+                };
+                let bounds = layout.bounds((gap.0 as f32, gap.1 as f32));
+
+                Stack::new()
+                    .width(board_width)
+                    .height(board_height)
+                    .push(grid)
+                    .push(
+                        pin(text(arrow)
+                            .size(bounds.height * 0.6)
+                            .style(text::primary)
+                            .center()
+                            .width(bounds.width)
+                            .height(bounds.height))
+                        .x(bounds.x)
+                        .y(bounds.y),
+                    )
+                    .into()
+            }
+            None => grid,
+        };
+
+        let clock = match puzzle.mode().remaining() {
+            Some(remaining) => self.trf(
+                Text::TimeLeft,
+                &[&self.format_time(remaining, Precision::Tenths)],
+            ),
+            None => self.trf(
+                Text::Time,
+                &[&self.format_time(self.timer.elapsed(self.now), Precision::Tenths)],
+            ),
+        };
+        let moves = match (puzzle.mode(), puzzle.moves_left()) {
+            (_, Some(left)) => self.trf(Text::MovesLeft, &[&left]),
+            (GameMode::MoveLimit { .. }, None) => String::from(self.tr(Text::WorkingOutBudget)),
+            _ => self.trf(Text::Moves, &[&self.total_moves()]),
+        };
+        let progress_text = text(if self.assisted {
+            let moves = self.trf(
+                Text::MovesWithAutoMoves,
+                &[
+                    &puzzle.moves().saturating_sub(self.auto_moves),
+                    &self.auto_moves,
+                ],
+            );
+            format!("{moves} · {clock}")
+        } else {
+            format!("{moves} · {clock}")
+        })
+        .size(20);
+
+        // How close the board is to solved, full exactly when it is
+        let board_progress =
+            progress_bar(0.0..=1.0, self.settings.progress_metric.progress(puzzle))
+                .length(PROGRESS_BAR_WIDTH)
+                .girth(4);
+
+        let best_text = self.records.best(&self.records_key()).map(|best| {
+            text(self.trf(
+                Text::Best,
+                &[&self.format_time(best.time, Precision::Millis), &best.moves],
+            ))
+            .size(16)
+        });
+
+        let status_text = if puzzle.is_solved() {
+            column![
+                text(self.tr(Text::PuzzleSolved)).size(24),
+                progress_text,
+                board_progress,
+                row![
+                    button(self.tr(Text::CopySolution)).on_press_maybe(
+                        (!puzzle.history().is_empty()).then_some(Message::CopySolution)
+                    ),
+                    button(self.tr(Text::WatchReplay))
+                        .on_press_maybe(self.last_replay.is_some().then_some(Message::WatchReplay)),
+                ]
+                .spacing(10),
+            ]
+        } else {
+            column![progress_text, board_progress]
+        }
+        .push(best_text)
+        .push(self.relay.map(|relay| {
+            text(self.trf(Text::RelayStage, &[&relay.stage(), &Relay::STAGES])).size(16)
+        }))
+        .push(puzzle.is_previewing().then(|| {
+            text(self.trf(
+                Text::MemoriseBoard,
+                &[&puzzle.preview_left().as_secs_f32().ceil()],
+            ))
+            .size(16)
+        }))
+        .push(self.goal_preview())
+        .push(self.ghost.as_ref().map(|ghost| {
+            let lead = if ghost.is_finished() {
+                Text::GhostBehind
+            } else if puzzle.is_solved() {
+                Text::GhostAhead
+            } else {
+                match ghost.lead(puzzle) {
+                    0 => Text::GhostLevel,
+                    lead if lead > 0 => Text::GhostAhead,
+                    _ => Text::GhostBehind,
+                }
+            };
+
+            column![
+                text(self.tr(lead)).size(16),
+                progress_bar(0.0..=1.0, ghost.progress())
+                    .length(THUMBNAIL_SIZE)
+                    .girth(6)
+                    .style(progress_bar::secondary),
+            ]
+            .spacing(5)
+            .align_x(Alignment::Center)
+        }))
+        .push(self.pattern_progress.as_ref().map(|progress| {
+            text(self.trf(
+                Text::BuildingSolverTables,
+                &[&progress.load(Ordering::Relaxed)],
+            ))
+            .size(14)
+        }))
+        .push(self.solving.as_ref().map(|solving| {
+            let elapsed = self.now.saturating_duration_since(solving.started);
+
+            row![
+                text(self.trf(
+                    Text::SearchingForSolution,
+                    &[
+                        &solving.progress.bound,
+                        &solving.progress.nodes,
+                        &format!("{:.1}", elapsed.as_secs_f32()),
+                    ],
+                ))
+                .size(14),
+                button(text(self.tr(Text::Cancel)).size(14))
+                    .on_press(Message::StopSolve)
+                    .padding([2, 8])
+                    .style(button::secondary),
+            ]
+            .spacing(10)
+            .align_y(Alignment::Center)
+        }))
+        .push(
+            self.solution.as_ref().map(|solution| {
+                text(self.trf(Text::SolutionMovesLeft, &[&solution.len()])).size(14)
+            }),
+        )
+        .push(
+            (!puzzle.is_solvable())
+                .then(|| text(self.tr(Text::Unsolvable)).size(16).style(text::danger)),
+        )
+        .push(self.settings.show_distance.then(|| {
+            text(self.trf(
+                Text::DistanceAndMisplaced,
+                &[&puzzle.manhattan_distance(), &puzzle.misplaced_count()],
+            ))
+            .size(16)
+        }))
+        .push(
+            self.new_record
+                .then(|| text(self.tr(Text::NewRecord)).size(18).style(text::success)),
+        )
+        .spacing(5)
+        .align_x(Alignment::Center);
+
+        // The victory panel covers the board and swallows clicks meant for it
+        // Swiping across the board slides the tile in that direction, as the
+        // arrow keys do, dragging a tile beside the blank carries it there,
+        // the wheel slides the hovered column, or row with shift, and a right
+        // click or long press shows where a tile belongs
+        let board = Stack::new()
+            .push(
+                swipe(grid, Message::Released)
+                    .on_drag(Message::Dragged)
+                    .on_scroll(Message::Scrolled)
+                    .on_secondary(Message::ShowGoal),
+            )
+            .push(self.victory.map(|_| {
+                opaque(center(
+                    container(
+                        column![
+                            text(self.tr(Text::Solved)).size(28),
+                            text(self.trf(
+                                Text::MovesInTime,
+                                &[
+                                    &self.total_moves(),
+                                    &self.format_time(
+                                        self.timer.elapsed(self.now),
+                                        Precision::Millis
+                                    )
+                                ]
+                            ))
+                            .size(20),
+                        ]
+                        .push(self.optimal_comparison().map(|line| text(line).size(16)))
+                        .push(
+                            self.efficiency()
+                                .map(|efficiency| text(self.efficiency_text(efficiency)).size(18)),
+                        )
+                        .push(puzzle.is_blindfold().then(|| {
+                            text(match self.peeks {
+                                0 => String::from(self.tr(Text::BlindfoldSolve)),
+                                1 => String::from(self.tr(Text::BlindfoldSolveOnePeek)),
+                                peeks => self.trf(Text::BlindfoldSolvePeeks, &[&peeks]),
+                            })
+                            .size(18)
+                            .style(text::success)
+                        }))
+                        .push(
+                            self.race
+                                .as_ref()
+                                .filter(|race| race.winner() == Some(Racer::Player))
+                                .map(|_| {
+                                    text(self.tr(Text::YouBeatTheBot))
+                                        .size(18)
+                                        .style(text::success)
+                                }),
+                        )
+                        .push(self.pending_entry.is_some().then(|| {
+                            column![
+                                text(self.tr(Text::MadeTheLeaderboard))
+                                    .size(18)
+                                    .style(text::success),
+                                row![
+                                    text_input(self.tr(Text::YourName), &self.name_input)
+                                        .on_input(Message::NameInputChanged)
+                                        .on_submit(Message::SubmitName)
+                                        .width(200),
+                                    button(self.tr(Text::AddToLeaderboard))
+                                        .on_press(Message::SubmitName),
+                                ]
+                                .spacing(10)
+                                .align_y(Alignment::Center),
+                            ]
+                            .spacing(5)
+                            .align_x(Alignment::Center)
+                        }))
+                        .push(
+                            row![
+                                button(self.tr(Text::ShuffleAgain)).on_press(Message::Shuffle),
+                                button(self.tr(Text::Retry)).on_press(Message::Retry),
+                                button(self.tr(Text::Close)).on_press(Message::DismissVictory),
+                            ]
+                            .spacing(10),
+                        )
+                        .spacing(10)
+                        .align_x(Alignment::Center),
+                    )
+                    .padding(20)
+                    .style(container::rounded_box),
+                ))
+            }));
+        let failure = (puzzle.is_out_of_time() || puzzle.is_out_of_moves()).then(|| {
+            opaque(center(
+                container(
+                    column![
+                        text(self.tr(if puzzle.is_out_of_time() {
+                            Text::TimesUp
+                        } else {
+                            Text::OutOfMoves
+                        }))
+                        .size(28),
+                        text(self.trf(Text::DistanceRemaining, &[&puzzle.manhattan_distance()]))
+                            .size(20),
+                        row![
+                            button(self.tr(Text::ShuffleAgain)).on_press(Message::Shuffle),
+                            button(self.tr(Text::Retry)).on_press(Message::Retry),
+                        ]
+                        .spacing(10),
+                    ]
+                    .spacing(10)
+                    .align_x(Alignment::Center),
+                )
+                .padding(20)
+                .style(container::rounded_box),
+            ))
+        });
+        let race_lost = self
+            .race
+            .as_ref()
+            .filter(|race| race.winner() == Some(Racer::Bot))
+            .map(|race| {
+                center(
+                    container(
+                        column![
+                            text(self.tr(Text::BotWon)).size(28),
+                            text(self.trf(Text::Moves, &[&race.bot().moves()])).size(20),
+                            row![
+                                button(self.tr(Text::RaceAgain)).on_press(Message::StartRace),
+                                button(self.tr(Text::KeepPlaying)).on_press(Message::DismissRace),
+                            ]
+                            .spacing(10),
+                        ]
+                        .spacing(10)
+                        .align_x(Alignment::Center),
+                    )
+                    .padding(20)
+                    .style(container::rounded_box),
+                )
+            });
+        let board = board
+            .push(failure)
+            .push(race_lost)
+            .push(self.relay_stage_at.and(self.relay).map(|relay| {
+                center(
+                    container(
+                        text(self.trf(Text::RelayNextStage, &[&relay.stage(), &Relay::STAGES]))
+                            .size(28),
+                    )
+                    .padding([10, 25])
+                    .style(container::rounded_box),
+                )
+            }))
+            .push(self.toast.as_ref().map(|(toast, _)| {
+                container(
+                    container(text(toast).size(18))
+                        .padding([5, 15])
+                        .style(container::rounded_box),
+                )
+                .center_x(Length::Fill)
+                .padding(10)
+            }));
+
+        // Shown over the paused placeholder too, so it is never hidden
+        let shuffle_prompt = self.confirming_shuffle.then(|| {
+            opaque(center(
+                container(
+                    column![
+                        text(self.tr(Text::AbandonSolve)).size(20),
+                        row![
+                            button(self.tr(Text::Yes))
+                                .style(button::danger)
+                                .on_press(Message::ConfirmShuffle),
+                            button(self.tr(Text::No)).on_press(Message::CancelShuffle),
+                        ]
+                        .spacing(10),
+                    ]
+                    .spacing(10)
+                    .align_x(Alignment::Center),
+                )
+                .padding(20)
+                .style(container::rounded_box),
+            ))
+        });
+
+        let difficulty = self.tr(Text::from(&puzzle.difficulty()));
+        let seed_text = text(match (self.daily, puzzle.seed()) {
+            (Some(day), _) => {
+                // The code of where the daily started, for those without the
+                // same seeded shuffle
+                let mut start = puzzle.clone();
+                start.retry();
+                self.trf(Text::DailyPuzzleFor, &[&day, &encode_board(&start)])
+            }
+            (None, Some(seed)) => self.trf(Text::DifficultyAndSeed, &[&difficulty, &seed]),
+            (None, None) => self.trf(Text::DifficultyAndSeed, &[&difficulty, &"-"]),
+        })
+        .size(16);
+
+        let parsed_seed = self.seed_input.trim().parse::<u64>();
+        let seed_message = parsed_seed.clone().ok().map(Message::ShuffleSeeded);
+        let seed_controls = row![
+            text_input(self.tr(Text::Seed), &self.seed_input)
+                .on_input(Message::SeedInputChanged)
+                .on_submit_maybe(seed_message.clone())
+                .width(200),
+            button(self.tr(Text::ScrambleFromSeed)).on_press_maybe(seed_message),
+        ]
+        .spacing(10)
+        .align_y(Alignment::Center);
+
+        let import_message = (!self.import_input.trim().is_empty())
+            .then(|| Message::ImportBoard(self.import_input.clone()));
+        let import_controls = row![
+            text_input(self.tr(Text::BoardExample), &self.import_input)
+                .on_input(Message::ImportInputChanged)
+                .on_submit_maybe(import_message.clone())
+                .width(300),
+            button(self.tr(Text::ImportBoard)).on_press_maybe(import_message),
+            button(self.tr(Text::CopyPosition))
+                .on_press_maybe((!self.is_hidden()).then_some(Message::CopyPosition)),
+            button(self.tr(Text::PastePosition)).on_press(Message::PastePosition),
+        ]
+        .spacing(10)
+        .align_y(Alignment::Center);
+
+        let code_message = (!self.code_input.trim().is_empty())
+            .then(|| Message::LoadCode(self.code_input.clone()));
+        let code_controls = row![
+            text_input(self.tr(Text::ScrambleCode), &self.code_input)
+                .on_input(Message::CodeInputChanged)
+                .on_submit_maybe(code_message.clone())
+                .width(200),
+            button(self.tr(Text::LoadCode)).on_press_maybe(code_message),
+            button(self.tr(Text::CopyScrambleCode))
+                .on_press_maybe((!self.is_hidden()).then_some(Message::CopyCode)),
+        ]
+        .spacing(10)
+        .align_y(Alignment::Center);
+
+        // Only complain once something has been typed
+        let seed_error = (!self.seed_input.is_empty() && parsed_seed.is_err()).then(|| {
+            text(self.tr(Text::InvalidSeed))
+                .size(14)
+                .style(text::danger)
+        });
+
+        let presets = row(PRESETS.iter().enumerate().map(|(index, &(name, size))| {
+            let name = self.tr(name);
+            let label = match self.records.best(&Records::key(size, size, puzzle.wrap())) {
+                Some(best) => self.trf(
+                    Text::PresetBest,
+                    &[&name, &self.format_time(best.time, Precision::Millis)],
+                ),
+                None => String::from(name),
+            };
+
+            button(text(label)).on_press(Message::Preset(index)).into()
+        }))
+        .push({
+            let today = Day::today();
+
+            match self
+                .records
+                .daily_result(&Records::key(rows, cols, false), today)
+            {
+                Some(result) => button(text(self.trf(
+                    Text::DailyDone,
+                    &[
+                        &self.format_time(result.time, Precision::Millis),
+                        &result.moves,
+                    ],
+                ))),
+                None => button(self.tr(Text::Daily)).on_press(Message::Daily),
+            }
+        })
+        .push(button(self.tr(Text::Relay)).on_press(Message::StartRelay))
+        .push(button(self.tr(Text::RaceTheBot)).on_press(Message::StartRace))
+        .spacing(10);
+
+        let daily_streak = {
+            let today = Day::today();
+            let mut days = (0..STREAK_CALENDAR_DAYS)
+                .scan(today, |day, _| {
+                    let shown = *day;
+                    *day = day.previous();
+                    Some(shown)
+                })
+                .collect::<Vec<_>>();
+            days.reverse();
+
+            // One square per day, filled for days with a daily solved
+            let calendar = row(days.into_iter().map(|day| {
+                let completed = self.records.daily_completed(day);
+
+                container("")
+                    .width(12)
+                    .height(12)
+                    .style(move |theme: &Theme| {
+                        let palette = theme.extended_palette();
+                        container::Style::default()
+                            .background(if completed {
+                                palette.success.base.color
+                            } else {
+                                palette.background.strong.color
+                            })
+                            .border(iced::border::rounded(2))
+                    })
+                    .into()
+            }))
+            .spacing(3);
+
+            row![
+                text(self.trf(Text::DailyStreak, &[&self.records.daily_streak(today)])).size(14),
+                calendar,
+            ]
+            .spacing(10)
+            .align_y(Alignment::Center)
+        };
+
+        let preset_confirmation = self.pending_preset.map(|index| {
+            row![
+                text(self.trf(Text::AbandonForPreset, &[&self.tr(PRESETS[index].0)])),
+                button(self.tr(Text::Yes))
+                    .style(button::danger)
+                    .on_press(Message::ConfirmPreset(true)),
+                button(self.tr(Text::No)).on_press(Message::ConfirmPreset(false)),
+            ]
+            .spacing(10)
+            .align_y(Alignment::Center)
+        });
+
+        let size_controls = row![
+            dimension_control(
+                self.tr(Text::Columns),
+                cols,
+                Message::SetSize(rows, cols.saturating_sub(1)),
+                Message::SetSize(rows, cols + 1),
+            ),
+            dimension_control(
+                self.tr(Text::Rows),
+                rows,
+                Message::SetSize(rows.saturating_sub(1), cols),
+                Message::SetSize(rows + 1, cols),
+            ),
+        ]
+        .spacing(20)
+        .align_y(Alignment::Center);
+
+        // Short windows fit the title beside the status instead of above it
+        let compact = self.is_compact();
+        let title = text(self.tr(Text::AppName)).size(if compact { 24 } else { 32 });
+        let presets = column![presets, daily_streak]
+            .push(preset_confirmation)
+            .spacing(10)
+            .align_x(Alignment::Center);
+        let status = column![status_text, seed_text]
+            .spacing(5)
+            .align_x(Alignment::Center);
+        let header: Element<'_, Message> = if compact {
+            column![
+                row![title, status].spacing(20).align_y(Alignment::Center),
+                presets
+            ]
+            .spacing(10)
+            .align_x(Alignment::Center)
+            .into()
+        } else {
+            column![title, presets, status]
+                .spacing(20)
+                .align_x(Alignment::Center)
+                .into()
+        };
+        // The tutorial's caption sits between the header and the board
+        let header = column![header]
+            .push(
+                self.tutorial
+                    .as_ref()
+                    .map(|tutorial| self.tutorial_panel(tutorial, board_width)),
+            )
+            .spacing(10)
+            .align_x(Alignment::Center);
+
+        let board = column![
+            Stack::new()
+                // Coming back, the player clicks once their hand is on the mouse
+                .push(if self.away {
+                    button(
+                        text(self.tr(Text::ClickToResume))
+                            .size(24)
+                            .width(Length::Fill)
+                            .height(Length::Fill)
+                            .center(),
+                    )
+                    .width(board_width)
+                    .height(board_height)
+                    .style(button::secondary)
+                    .on_press(Message::Resume)
+                    .into()
+                } else if self.paused {
+                    container(text(self.tr(Text::Paused)).size(32))
+                        .width(board_width)
+                        .height(board_height)
+                        .align_x(Alignment::Center)
+                        .align_y(Alignment::Center)
+                        .style(container::rounded_box)
+                        .into()
+                } else {
+                    Element::from(board)
+                })
+                .push(shuffle_prompt)
+                // The board shows around the count, so the player can plan
+                .push(self.countdown.map(|began| {
+                    let left = COUNTDOWN.saturating_sub(self.now.saturating_duration_since(began));
+                    let count = column![
+                        text((left.as_secs() + 1).min(COUNTDOWN.as_secs())).size(96),
+                        text(self.tr(Text::SkipCountdown)).size(16),
+                    ]
+                    .spacing(10)
+                    .align_x(Alignment::Center);
+
+                    container(container(count).padding(20).style(container::rounded_box))
+                        .width(board_width)
+                        .height(board_height)
+                        .align_x(Alignment::Center)
+                        .align_y(Alignment::Center)
+                }))
+        ]
+        .push(self.status_line().map(|line| text(line).size(16)))
+        .spacing(5)
+        .align_x(Alignment::Center);
+        // Racing puts the bot's board beside the player's, which alone takes
+        // input
+        let board =
+            row![board]
+                .push(self.race.as_ref().map(|race| {
+                    self.bot_board(race, board_width.min(board_height) * BOT_BOARD_SCALE)
+                }))
+                .push(self.log_open.then(|| self.log_panel(board_height)))
+                .spacing(20)
+                .align_y(Alignment::Center);
+
+        // Rows of controls wrap in narrow windows, and the whole page scrolls
+        // in short ones
+        container(scrollable(
+            column![
+                header,
+                board,
+                row![
+                    button(self.tr(Text::Undo)).on_press_maybe(
+                        (puzzle.can_undo() && !auto_solving).then_some(Message::Undo)
+                    ),
+                    button(self.tr(Text::Redo)).on_press_maybe(
+                        (puzzle.can_redo() && !auto_solving).then_some(Message::Redo)
+                    ),
+                    if self.paused {
+                        button(self.tr(Text::Resume)).on_press(Message::Resume)
+                    } else {
+                        button(self.tr(Text::Pause)).on_press_maybe(
+                            (self.timer.is_running() && !puzzle.mode().is_time_attack())
+                                .then_some(Message::Pause),
+                        )
+                    },
+                    button(self.tr(Text::Shuffle)).on_press(Message::Shuffle),
+                    button(self.tr(Text::Reset)).on_press_maybe(
+                        (puzzle.is_scrambled() || !puzzle.is_solved()).then_some(Message::Reset)
+                    ),
+                    button(self.tr(Text::RetryScramble))
+                        .on_press_maybe(puzzle.can_retry().then_some(Message::Retry)),
+                    button(self.tr(Text::Hint)).on_press_maybe(
+                        (!puzzle.is_solved() && !self.hint_pending && !auto_solving)
+                            .then_some(Message::Hint)
+                    ),
+                    if auto_solving {
+                        button(self.tr(Text::Stop)).on_press(Message::StopSolve)
+                    } else {
+                        button(self.tr(Text::SolveIt)).on_press_maybe(
+                            (!puzzle.is_solved() && puzzle.is_solvable()).then_some(Message::Solve),
+                        )
+                    },
+                ]
+                .push(puzzle.is_blindfold().then(|| {
+                    button(self.tr(Text::Peek)).on_press_maybe(hidden.then_some(Message::Peek))
+                }))
+                .spacing(10)
+                .wrap()
+                .align_x(Alignment::Center),
+                row![
+                    pick_list(
+                        self.choices(Difficulty::ALL),
+                        Some(Choice::new(self.settings.language, puzzle.difficulty())),
+                        |choice| Message::SetDifficulty(choice.value)
+                    ),
+                    pick_list(
+                        self.choices(self.goal_options()),
+                        Some(Choice::new(self.settings.language, puzzle.goal().clone())),
+                        |choice| Message::SetGoal(choice.value)
+                    ),
+                    button(self.tr(Text::EditGoal)).on_press(Message::EditGoal),
+                    size_controls,
+                    checkbox(puzzle.wrap())
+                        .label(self.tr(Text::WrapAroundEdges))
+                        .on_toggle(Message::SetWrap),
+                ]
+                .spacing(20)
+                .align_y(Alignment::Center)
+                .wrap()
+                .vertical_spacing(10)
+                .align_x(Alignment::Center),
+                row![
+                    button(self.tr(Text::Save)).on_press(Message::Save),
+                    button(self.tr(Text::Load)).on_press(Message::Load),
+                    button(self.tr(Text::Saves)).on_press(Message::ShowScreen(Screen::Saves)),
+                    button(self.tr(Text::ExportImage))
+                        .on_press_maybe((!self.is_hidden()).then_some(Message::ExportImage)),
+                    button(self.tr(Text::Stats)).on_press(Message::ShowScreen(Screen::Stats)),
+                    button(self.tr(Text::Leaderboard))
+                        .on_press(Message::ShowScreen(Screen::Leaderboard)),
+                    button(self.tr(Text::Achievements))
+                        .on_press(Message::ShowScreen(Screen::Achievements)),
+                    button(self.tr(Text::Settings)).on_press(Message::ShowScreen(Screen::Settings)),
+                    button(self.tr(Text::Menu)).on_press(Message::OpenGameMenu),
+                    button(self.tr(Text::SessionLog)).on_press(Message::ToggleLog),
+                    button("?").on_press(Message::ToggleHelp),
+                    button(self.tr(if self.fullscreen {
+                        Text::ExitFullscreen
+                    } else {
+                        Text::Fullscreen
+                    }))
+                    .on_press(Message::ToggleFullscreen),
+                ]
+                .spacing(10)
+                .align_y(Alignment::Center)
+                .wrap()
+                .align_x(Alignment::Center),
+                column![seed_controls]
+                    .push(seed_error)
+                    .push(import_controls)
+                    .push(code_controls)
+                    .push(self.notice.as_ref().map(|notice| match notice {
+                        Notice::Info(message) => text(message).size(14),
+                        Notice::Error(message) => text(message).size(14).style(text::danger),
+                    }))
+                    .spacing(5)
+                    .align_x(Alignment::Center),
+            ]
+            .spacing(if compact { 10 } else { 20 })
+            .align_x(Alignment::Center),
+        ))
+        .center_x(Length::Fill)
+        .center_y(Length::Fill)
+        .into()
+    }
+}
+
+/// `stars` filled stars out of three.
+fn star_rating(stars: u8) -> String {
+    (1..=3)
+        .map(|star| if star <= stars { '★' } else { '☆' })
+        .collect()
+}
+
+/// A "Preview: - N s +" stepper for how long a blindfold shows the board.
+fn preview_control<'a>(language: Language, seconds: u64) -> Element<'a, Message> {
+    let decrease = Message::SetBlindfoldPreview(seconds.saturating_sub(PREVIEW_STEP));
+    let increase = Message::SetBlindfoldPreview(seconds + PREVIEW_STEP);
+
+    row![
+        text(i18n::tr(language, Text::Preview)),
+        button("-").on_press_maybe((seconds > 0).then_some(decrease)),
+        text(i18n::trf(language, Text::Seconds, &[&seconds])),
+        button("+").on_press_maybe((seconds < MAX_PREVIEW).then_some(increase)),
+    ]
+    .spacing(10)
+    .align_y(Alignment::Center)
+    .into()
+}
+
+/// Loads the bundled label fonts, each reporting back whether it is ready.
+fn load_fonts() -> Task<Message> {
+    Task::batch(
+        [(TileFont::Inter, INTER), (TileFont::FiraMono, FIRA_MONO)].map(|(font, bytes)| {
+            iced::font::load(bytes).map(move |result| Message::FontLoaded(font, result))
+        }),
+    )
+}
+
+/// The font of the labels in `font`, once loaded.
+fn tile_font(font: TileFont) -> Font {
+    match font {
+        TileFont::Standard => Font::DEFAULT,
+        TileFont::Inter => Font::with_name("Inter"),
+        TileFont::FiraMono => Font {
+            weight: iced::font::Weight::Medium,
+            ..Font::with_name("Fira Mono")
+        },
+    }
+}
+
+/// The edge length of a tile on a board `board_size` across with `rows` by
+/// `cols` cells `spacing` apart. Tiles are square for every grid shape, with
+/// the longer dimension spanning the whole board.
+fn tile_size(board_size: f32, spacing: f32, rows: usize, cols: usize) -> f32 {
+    let cells = rows.max(cols);
+    (board_size - spacing * (cells - 1) as f32) / cells as f32
+}
+
+/// The gap between tiles on a board `board_size` across for a gap of `gap`
+/// on one at the standard size, which keeps the board's proportions as it
+/// grows.
+fn tile_spacing(board_size: f32, gap: f32) -> f32 {
+    gap * board_size / BOARD_SIZE
+}
+
+/// The label size for tiles `tile_size` across on a board of `rows` by
+/// `cols`, leaving room for the board's longest number to fit on a tile
+/// with some margin to spare.
+fn label_size(tile_size: f32, rows: usize, cols: usize) -> f32 {
+    let digits = (rows * cols - 1).ilog10() + 1;
+    let widest = tile_size * 0.75 / (digits as f32 * DIGIT_WIDTH);
+
+    (tile_size * LABEL_SCALE).min(widest).max(MIN_LABEL_SIZE)
+}
+
+/// A "Label: - N +" stepper for one board dimension.
+fn dimension_control<'a>(
+    label: &'a str,
+    value: usize,
+    decrease: Message,
+    increase: Message,
+) -> Element<'a, Message> {
+    row![
+        text(format!("{label}: ")).size(20),
+        button("-").on_press_maybe((value > MIN_GRID_SIZE).then_some(decrease)),
+        text(value).size(20),
+        button("+").on_press_maybe((value < MAX_GRID_SIZE).then_some(increase)),
+    ]
+    .spacing(10)
+    .align_y(Alignment::Center)
+    .into()
+}
+
+/// A "Time limit: - N min +" stepper for the time attack countdown.
+fn time_limit_control<'a>(language: Language, seconds: u64) -> Element<'a, Message> {
+    let decrease = Message::SetTimeLimit(seconds.saturating_sub(TIME_LIMIT_STEP));
+    let increase = Message::SetTimeLimit(seconds + TIME_LIMIT_STEP);
+
+    row![
+        text(i18n::tr(language, Text::TimeLimit)),
+        button("-").on_press_maybe((seconds > TIME_LIMIT_STEP).then_some(decrease)),
+        text(i18n::trf(language, Text::Minutes, &[&(seconds / 60)])),
+        button("+").on_press_maybe((seconds < MAX_TIME_LIMIT).then_some(increase)),
+    ]
+    .spacing(10)
+    .align_y(Alignment::Center)
+    .into()
+}
+
+/// The bindings [`message_for_key`] implements besides moving, as shown in
+/// the help overlay.
+const SHORTCUTS: [(&str, Text); 13] = [
+    ("Space, N, Ctrl+N", Text::Shuffle),
+    ("Ctrl+R", Text::ResetToSolved),
+    ("Ctrl+C", Text::CopyPosition),
+    ("Ctrl+V", Text::PastePosition),
+    ("Z, Ctrl+Z", Text::Undo),
+    ("Y, Ctrl+Y, Ctrl+Shift+Z", Text::Redo),
+    ("P", Text::PauseOrResume),
+    ("Shift+H", Text::Hint),
+    ("F11", Text::ToggleFullscreen),
+    ("Tab, Shift+Tab", Text::FocusTiles),
+    ("Enter", Text::PressFocusedTile),
+    ("Esc", Text::CloseOverlays),
+    ("?", Text::ShowOrHideHelp),
+];
+
+/// The movement bindings of `scheme`, as shown in the help overlay.
+fn movement_shortcuts(scheme: KeyScheme) -> &'static [(&'static str, Text)] {
+    match scheme {
+        KeyScheme::TileMoves => &[("Arrows, WASD, hjkl", Text::SlideTile)],
+        KeyScheme::BlankMoves => &[("Arrows, WASD, hjkl", Text::MoveBlank)],
+        KeyScheme::NumpadRelative => &[
+            ("Arrows, WASD, hjkl", Text::SlideTile),
+            ("Numpad 8, 4, 6, 2", Text::SlideTileBesideBlank),
+        ],
+    }
+}
+
+/// Maps a keyboard event to its message under `scheme`. Direction keys report
+/// being pressed and let go, and the app repeats their moves itself, so the
+/// system's key repeat is left out for them.
+fn message_for_key_event(scheme: KeyScheme, event: keyboard::Event) -> Option<Message> {
+    match event {
+        keyboard::Event::KeyPressed {
+            key,
+            location,
+            modifiers,
+            repeat,
+            ..
+        } => match message_for_key_in(scheme, &key, location, modifiers)? {
+            Message::Move(_) if repeat => None,
+            Message::Move(direction) => Some(Message::MoveKeyPressed(direction)),
+            message => Some(message),
+        },
+        // Whatever modifiers came down since, the key is no longer held
+        keyboard::Event::KeyReleased { key, location, .. } => {
+            match message_for_key_in(scheme, &key, location, Modifiers::empty())? {
+                Message::Move(direction) => Some(Message::MoveKeyReleased(direction)),
+                _ => None,
+            }
+        }
+        keyboard::Event::ModifiersChanged(_) => None,
+    }
+}
+
+/// Maps a key press to its message under `scheme`: the numpad digits of
+/// [`KeyScheme::NumpadRelative`] first, then [`message_for_key`] with its
+/// moves turned the way `scheme` points them.
+fn message_for_key_in(
+    scheme: KeyScheme,
+    key: &Key,
+    location: Location,
+    modifiers: Modifiers,
+) -> Option<Message> {
+    if scheme == KeyScheme::NumpadRelative
+        && location == Location::Numpad
+        && !modifiers.command()
+        && let Some(side) = numpad_side(key)
+    {
+        // The tile on that side of the blank slides towards it
+        return Some(Message::Move(side.opposite()));
+    }
+
+    match message_for_key(key, modifiers)? {
+        Message::Move(direction) => Some(Message::Move(scheme.slide(direction))),
+        message => Some(message),
+    }
+}
+
+/// The side of numpad 5 a numpad key is on, with Num Lock on or off.
+fn numpad_side(key: &Key) -> Option<Direction> {
+    match key {
+        Key::Named(Named::ArrowUp) => Some(Direction::Up),
+        Key::Named(Named::ArrowDown) => Some(Direction::Down),
+        Key::Named(Named::ArrowLeft) => Some(Direction::Left),
+        Key::Named(Named::ArrowRight) => Some(Direction::Right),
+        Key::Character(c) => match c.as_str() {
+            "8" => Some(Direction::Up),
+            "2" => Some(Direction::Down),
+            "4" => Some(Direction::Left),
+            "6" => Some(Direction::Right),
+            _ => None,
+        },
+        _ => None,
+    }
+}
+
+/// Maps a key press to the message it triggers, if any. This is the whole
+/// keyboard mapping for the default [`KeyScheme`]; keys typed into a focused
+/// text input never reach it.
+fn message_for_key(key: &Key, modifiers: Modifiers) -> Option<Message> {
+    if modifiers.command() {
+        let Key::Character(c) = key else {
+            return None;
+        };
+
+        return match c.to_lowercase().as_str() {
+            "z" if modifiers.shift() => Some(Message::Redo),
+            "z" => Some(Message::Undo),
+            "y" => Some(Message::Redo),
+            "n" => Some(Message::Shuffle),
+            "r" => Some(Message::Reset),
+            "c" => Some(Message::CopyPosition),
+            "v" => Some(Message::PastePosition),
+            _ => None,
+        };
+    }
+
+    match key {
+        Key::Named(Named::Space) => return Some(Message::Shuffle),
+        Key::Named(Named::Escape) => return Some(Message::Dismiss),
+        Key::Named(Named::F11) => return Some(Message::ToggleFullscreen),
+        Key::Named(Named::Tab) if modifiers.shift() => return Some(Message::FocusPrevious),
+        Key::Named(Named::Tab) => return Some(Message::FocusNext),
+        Key::Named(Named::Enter) => return Some(Message::PressFocused),
+        // Plain h is taken by vim-style movement
+        Key::Character(c) if c.as_str() == "?" => return Some(Message::ToggleHelp),
+        Key::Character(c) if c.eq_ignore_ascii_case("h") && modifiers.shift() => {
+            return Some(Message::Hint);
+        }
+        Key::Character(c) => match c.to_lowercase().as_str() {
+            "n" => return Some(Message::Shuffle),
+            "z" => return Some(Message::Undo),
+            "y" => return Some(Message::Redo),
+            "p" => return Some(Message::TogglePause),
+            _ => {}
+        },
+        _ => {}
+    }
+
+    direction_for_key(key).map(Message::Move)
+}
+
+/// Maps a controller input onto the message the matching key would send.
+fn message_for_input(input: Input) -> Message {
+    match input {
+        Input::Move(direction) => Message::Move(direction),
+        Input::Shuffle => Message::Shuffle,
+        Input::Undo => Message::Undo,
+    }
+}
+
+/// Maps arrow keys, WASD, and vim-style hjkl onto movement directions.
+fn direction_for_key(key: &Key) -> Option<Direction> {
+    match key {
+        Key::Named(Named::ArrowUp) => Some(Direction::Up),
+        Key::Named(Named::ArrowDown) => Some(Direction::Down),
+        Key::Named(Named::ArrowLeft) => Some(Direction::Left),
+        Key::Named(Named::ArrowRight) => Some(Direction::Right),
+        Key::Character(c) => match c.to_lowercase().as_str() {
+            "w" | "k" => Some(Direction::Up),
+            "s" | "j" => Some(Direction::Down),
+            "a" | "h" => Some(Direction::Left),
+            "d" | "l" => Some(Direction::Right),
+            _ => None,
+        },
+        _ => None,
+    }
+}
+
+const USAGE: &str = "\
+Usage: iced_15_puzzle [OPTIONS]
+       iced_15_puzzle solve <BOARD>   Solve a board in the terminal; see solve --help
+
+Options:
+  --size <N>     Grid size as N or COLSxROWS, each from 2 to 10 (default: 4)
+  --seed <SEED>  Seed for reproducible scrambles
+  --scramble     Start with a scrambled board
+  -h, --help     Print this help
+
+Without options, the previous session is resumed if one was saved.";
+
+/// Command-line options for the initial game state.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct Args {
+    rows: usize,
+    cols: usize,
+    seed: Option<u64>,
+    scramble: bool,
+}
+
+impl Default for Args {
+    fn default() -> Self {
+        Self {
+            rows: DEFAULT_GRID_SIZE,
+            cols: DEFAULT_GRID_SIZE,
+            seed: None,
+            scramble: false,
+        }
+    }
+}
+
+impl Args {
+    /// Parses the arguments following the program name.
+    ///
+    /// Returns `Ok(None)` when help was requested.
+    fn parse(args: impl IntoIterator<Item = String>) -> Result<Option<Self>, String> {
+        let mut parsed = Self::default();
+        let mut args = args.into_iter();
+
+        while let Some(arg) = args.next() {
+            match arg.as_str() {
+                "--size" => {
+                    let value = args.next().ok_or("--size requires a value")?;
+                    (parsed.cols, parsed.rows) = parse_size(&value)?;
+                }
+                "--seed" => {
+                    let value = args.next().ok_or("--seed requires a value")?;
+                    let seed = value
+                        .parse()
+                        .map_err(|_| format!("invalid seed '{value}'"))?;
+                    parsed.seed = Some(seed);
+                }
+                "--scramble" => parsed.scramble = true,
+                "-h" | "--help" => return Ok(None),
+                _ => return Err(format!("unexpected argument '{arg}'")),
+            }
+        }
+
+        Ok(Some(parsed))
+    }
+}
+
+/// Parses "N" as an NxN grid or "COLSxROWS" as a rectangle.
+fn parse_size(value: &str) -> Result<(usize, usize), String> {
+    let invalid = || format!("invalid grid size '{value}'");
+    let dimension = |part: &str| -> Result<usize, String> {
+        let size = part.trim().parse().map_err(|_| invalid())?;
+
+        if !(MIN_GRID_SIZE..=MAX_GRID_SIZE).contains(&size) {
+            return Err(format!(
+                "grid dimensions must be between {MIN_GRID_SIZE} and {MAX_GRID_SIZE}, got {size}"
+            ));
+        }
+        Ok(size)
+    };
+
+    match value.split_once(['x', 'X']) {
+        Some((cols, rows)) => Ok((dimension(cols)?, dimension(rows)?)),
+        None => {
+            let size = dimension(value)?;
+            Ok((size, size))
+        }
+    }
+}
+
+impl App {
+    /// Builds the initial state, resuming the previous session when no
+    /// options were given and auto-save is enabled.
+    fn from_args(args: Args) -> Self {
+        if args == Args::default() {
+            let mut app = Self::default();
+
+            app.resume_saved();
+            app.screen = Screen::Menu;
+            app.back_to = Screen::Menu;
+            // Whoever sits down picks who they are
+            if app.profiles.all().len() > 1 {
+                app.screen = Screen::Profiles;
+            }
+            return app;
+        }
+
+        let mut puzzle = match args.seed {
+            Some(seed) => Puzzle::with_seed(args.rows, args.cols, seed),
+            None => Puzzle::new(args.rows, args.cols),
+        };
+
+        if args.scramble {
+            puzzle.shuffle();
+        }
+        let mut app = Self {
+            puzzle,
+            ..Self::default()
+        };
+        app.restart();
+        app
+    }
+}
+
+fn main() -> iced::Result {
+    let mut args = std::env::args().skip(1).peekable();
+    if args.next_if(|arg| arg == "solve").is_some() {
+        std::process::exit(cli::run(args));
+    }
+
+    let args = match Args::parse(args) {
+        Ok(Some(args)) => args,
+        Ok(None) => {
+            println!("{USAGE}");
+            return Ok(());
+        }
+        Err(error) => {
+            eprintln!("error: {error}\n\n{USAGE}");
+            std::process::exit(2);
+        }
+    };
+
+    let (data, config) = (Storage::data(), Storage::config());
+    let settings: Settings = data
+        .as_ref()
+        .zip(open_profiles(data.as_ref(), config.as_ref()).active())
+        .map(|(data, profile)| storage::load_or_default(&profile_paths(data, profile), SETTINGS))
+        .unwrap_or_default();
+    let (size, position) = saved_window(&settings);
+
+    iced::application(
+        move || (App::from_args(args), load_fonts()),
+        App::update,
+        App::view,
+    )
+    .title(App::title)
+    .window(window::Settings {
+        size,
+        position: position.map_or(window::Position::Default, window::Position::Specific),
+        min_size: Some(MIN_WINDOW_SIZE),
+        exit_on_close_request: false,
+        ..window::Settings::default()
+    })
+    .subscription(App::subscription)
+    .theme(App::theme)
+    .run()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn character(c: &str) -> Key {
+        Key::Character(c.into())
+    }
+
+    #[test]
+    fn arrow_keys_map_to_directions() {
+        assert_eq!(
+            direction_for_key(&Key::Named(Named::ArrowUp)),
+            Some(Direction::Up)
+        );
+        assert_eq!(
+            direction_for_key(&Key::Named(Named::ArrowDown)),
+            Some(Direction::Down)
+        );
+        assert_eq!(
+            direction_for_key(&Key::Named(Named::ArrowLeft)),
+            Some(Direction::Left)
+        );
+        assert_eq!(
+            direction_for_key(&Key::Named(Named::ArrowRight)),
+            Some(Direction::Right)
+        );
+    }
+
+    #[test]
+    fn key_schemes_point_moves_their_own_way() {
+        let moved = |scheme, key: &Key, location| match message_for_key_in(
+            scheme,
+            key,
+            location,
+            Modifiers::empty(),
+        ) {
+            Some(Message::Move(direction)) => Some(direction),
+            _ => None,
+        };
+        let up = Key::Named(Named::ArrowUp);
+
+        assert_eq!(
+            moved(KeyScheme::TileMoves, &up, Location::Standard),
+            Some(Direction::Up)
+        );
+        assert_eq!(
+            moved(KeyScheme::BlankMoves, &up, Location::Standard),
+            Some(Direction::Down)
+        );
+        assert_eq!(
+            moved(KeyScheme::BlankMoves, &character("a"), Location::Standard),
+            Some(Direction::Right)
+        );
+
+        // Numpad 8 picks the tile above the blank, which slides down, with
+        // Num Lock on or off; other schemes leave the digits alone
+        for key in [character("8"), up.clone()] {
+            assert_eq!(
+                moved(KeyScheme::NumpadRelative, &key, Location::Numpad),
+                Some(Direction::Down)
+            );
+        }
+        assert_eq!(
+            moved(KeyScheme::NumpadRelative, &character("4"), Location::Numpad),
+            Some(Direction::Right)
+        );
+        assert_eq!(
+            moved(KeyScheme::NumpadRelative, &up, Location::Standard),
+            Some(Direction::Up)
+        );
+        assert_eq!(
+            moved(KeyScheme::TileMoves, &character("8"), Location::Numpad),
+            None
+        );
+        // Shortcuts are the same under every scheme
+        assert!(matches!(
+            message_for_key_in(
+                KeyScheme::NumpadRelative,
+                &character("z"),
+                Location::Standard,
+                Modifiers::empty()
+            ),
+            Some(Message::Undo)
+        ));
+    }
+
+    #[test]
+    fn held_direction_keys_repeat_at_the_chosen_rate() {
+        let mut app = App::default();
+        let delay = app.key_repeat_delay();
+        let interval = Duration::from_millis(app.settings.key_repeat_interval_ms);
+        // With the blank in the bottom-left corner, tiles can slide left
+        // three times
+        app.puzzle.move_line(3, 0, SlideCount::PerTile);
+        let moved = app.puzzle.moves();
+        let pressed_at = Instant::now();
+
+        let _ = app.update(Message::MoveKeyPressed(Direction::Left));
+        assert_eq!(app.puzzle.moves(), moved + 1);
+        let _ = app.update(Message::Tick(pressed_at + delay / 2));
+        assert_eq!(app.puzzle.moves(), moved + 1);
+        let repeated_at = pressed_at + delay + Duration::from_millis(50);
+        let _ = app.update(Message::Tick(repeated_at));
+        assert_eq!(app.puzzle.moves(), moved + 2);
+        let _ = app.update(Message::Tick(repeated_at + interval));
+        assert_eq!(app.puzzle.moves(), moved + 3);
+
+        // The opposite key cancels it out rather than undoing it
+        let _ = app.update(Message::MoveKeyPressed(Direction::Right));
+        let _ = app.update(Message::Tick(repeated_at + interval * 10));
+        assert_eq!(app.puzzle.moves(), moved + 3);
+
+        // and letting go of both stops at once
+        let _ = app.update(Message::MoveKeyReleased(Direction::Right));
+        let _ = app.update(Message::MoveKeyReleased(Direction::Left));
+        assert!(app.held_keys.is_empty());
+        let _ = app.update(Message::Tick(repeated_at + interval * 100));
+        assert_eq!(app.puzzle.moves(), moved + 3);
+
+        // Repeated moves are undone one by one like any others
+        let _ = app.update(Message::Undo);
+        assert_eq!(app.puzzle.moves(), moved + 2);
+    }
+
+    #[test]
+    fn the_system_key_repeat_is_left_to_the_app() {
+        let event = |repeat, released| {
+            let key = Key::Named(Named::ArrowUp);
+            let physical_key = keyboard::key::Physical::Code(keyboard::key::Code::ArrowUp);
+            let message = message_for_key_event(
+                KeyScheme::TileMoves,
+                if released {
+                    keyboard::Event::KeyReleased {
+                        modified_key: key.clone(),
+                        key,
+                        physical_key,
+                        location: Location::Standard,
+                        modifiers: Modifiers::CTRL,
+                    }
+                } else {
+                    keyboard::Event::KeyPressed {
+                        modified_key: key.clone(),
+                        key,
+                        physical_key,
+                        location: Location::Standard,
+                        modifiers: Modifiers::empty(),
+                        text: None,
+                        repeat,
+                    }
+                },
+            );
+            format!("{message:?}")
+        };
+
+        assert_eq!(event(false, false), "Some(MoveKeyPressed(Up))");
+        assert_eq!(event(true, false), "None");
+        // Even with a modifier down by then
+        assert_eq!(event(false, true), "Some(MoveKeyReleased(Up))");
+    }
+
+    #[test]
+    fn wasd_maps_to_directions() {
+        assert_eq!(direction_for_key(&character("w")), Some(Direction::Up));
+        assert_eq!(direction_for_key(&character("a")), Some(Direction::Left));
+        assert_eq!(direction_for_key(&character("s")), Some(Direction::Down));
+        assert_eq!(direction_for_key(&character("d")), Some(Direction::Right));
+    }
+
+    #[test]
+    fn hjkl_maps_to_directions() {
+        assert_eq!(direction_for_key(&character("h")), Some(Direction::Left));
+        assert_eq!(direction_for_key(&character("j")), Some(Direction::Down));
+        assert_eq!(direction_for_key(&character("k")), Some(Direction::Up));
+        assert_eq!(direction_for_key(&character("l")), Some(Direction::Right));
+    }
+
+    #[test]
+    fn letter_bindings_are_case_insensitive() {
+        assert_eq!(direction_for_key(&character("W")), Some(Direction::Up));
+        assert_eq!(direction_for_key(&character("J")), Some(Direction::Down));
+        assert_eq!(direction_for_key(&character("H")), Some(Direction::Left));
+        assert_eq!(direction_for_key(&character("D")), Some(Direction::Right));
+    }
+
+    fn parse(args: &[&str]) -> Result<Option<Args>, String> {
+        Args::parse(args.iter().map(|arg| arg.to_string()))
+    }
+
+    #[test]
+    fn args_default_without_flags() {
+        assert_eq!(parse(&[]), Ok(Some(Args::default())));
+    }
+
+    #[test]
+    fn args_parse_size_seed_and_scramble() {
+        assert_eq!(
+            parse(&["--size", "5", "--seed", "12345", "--scramble"]),
+            Ok(Some(Args {
+                rows: 5,
+                cols: 5,
+                seed: Some(12345),
+                scramble: true,
+            }))
+        );
+    }
+
+    #[test]
+    fn args_reject_out_of_range_sizes() {
+        assert!(parse(&["--size", "1"]).is_err());
+        assert!(parse(&["--size", "1000"]).is_err());
+        assert!(parse(&["--size", "four"]).is_err());
+        assert!(parse(&["--size"]).is_err());
+        assert!(parse(&["--size", "4x1"]).is_err());
+    }
+
+    #[test]
+    fn args_parse_rectangular_sizes() {
+        let args = parse(&["--size", "4x3"]).unwrap().unwrap();
+
+        assert_eq!((args.cols, args.rows), (4, 3));
+    }
+
+    #[test]
+    fn same_seed_produces_same_scramble() {
+        let args = Args {
+            rows: 3,
+            cols: 5,
+            seed: Some(12345),
+            scramble: true,
+        };
+
+        assert_eq!(
+            App::from_args(args).puzzle.tiles(),
+            App::from_args(args).puzzle.tiles()
+        );
+    }
+
+    #[test]
+    fn auto_solve_plays_back_to_the_solved_board() {
+        let mut app = App::from_args(Args {
+            rows: 3,
+            cols: 3,
+            seed: Some(7),
+            scramble: true,
+        });
+        let board = app.puzzle.tiles().to_vec();
+        let solution = solver::solve(&app.puzzle, None);
+
+        let _ = app.update(Message::Solve);
+        let _ = app.update(Message::SolutionReady(board, solution));
+        while app.solution.is_some() {
+            let _ = app.update(Message::AutoStep);
+        }
+
+        assert!(app.puzzle.is_solved());
+        assert_eq!(app.auto_moves, app.puzzle.moves());
+        assert!(!app.new_record);
+    }
+
+    #[test]
+    fn stopping_an_auto_solve_cancels_its_search() {
+        let mut app = App::from_args(Args {
+            rows: 4,
+            cols: 4,
+            seed: Some(7),
+            scramble: true,
+        });
+        let board = app.puzzle.tiles().to_vec();
+
+        let _ = app.update(Message::Solve);
+        let cancel = app.solving.as_ref().unwrap().cancel.clone();
+        let _ = app.update(Message::StopSolve);
+        assert!(cancel.is_cancelled());
+        assert!(!app.is_auto_solving());
+
+        // The stopped search's answer is dropped when it comes in
+        let _ = app.update(Message::SolutionReady(board, None));
+        assert!(app.notice.is_none());
+    }
+
+    #[test]
+    fn a_solve_streams_its_progress_before_its_solution() {
+        use iced::futures::StreamExt;
+
+        let puzzle = App::from_args(Args {
+            rows: 3,
+            cols: 3,
+            seed: Some(7),
+            scramble: true,
+        })
+        .puzzle;
+        let search = find_solution_with_progress(puzzle.clone(), Cancel::new());
+        let events: Vec<SolveEvent> = tokio::runtime::Builder::new_current_thread()
+            .build()
+            .unwrap()
+            .block_on(search.collect());
+
+        let Some((SolveEvent::Finished(Some(solution)), reports)) = events.split_last() else {
+            panic!("the search ended without a solution: {events:?}");
+        };
+        assert!(!reports.is_empty());
+        assert!(
+            reports
+                .iter()
+                .all(|event| matches!(event, SolveEvent::Progress(snapshot) if snapshot.bound <= solution.len() as u32))
+        );
+        assert_eq!(solution, &solver::solve(&puzzle, None).unwrap());
+    }
+
+    #[test]
+    fn a_running_solve_shows_the_progress_it_reports() {
+        let mut app = App::from_args(Args {
+            rows: 4,
+            cols: 4,
+            seed: Some(7),
+            scramble: true,
+        });
+        let board = app.puzzle.tiles().to_vec();
+        let snapshot = Snapshot {
+            bound: 38,
+            nodes: 4096,
+        };
+
+        let _ = app.update(Message::Solve);
+        assert_eq!(app.solving.as_ref().unwrap().progress, Snapshot::default());
+        let _ = app.update(Message::SolverProgress(board.clone(), snapshot));
+        assert_eq!(app.solving.as_ref().unwrap().progress, snapshot);
+
+        // Reports about some other board are dropped
+        let _ = app.update(Message::SolverProgress(Vec::new(), Snapshot::default()));
+        assert_eq!(app.solving.as_ref().unwrap().progress, snapshot);
+
+        // The solution takes the place of the progress
+        let solution = vec![Direction::Up; 3];
+        let _ = app.update(Message::SolutionReady(board, Some(solution)));
+        assert!(app.solving.is_none());
+        assert_eq!(app.solution.as_ref().map(VecDeque::len), Some(3));
+    }
+
+    #[test]
+    fn a_qualifying_solve_asks_for_a_name_to_go_on_the_leaderboard() {
+        let mut app = App::from_args(Args {
+            rows: 3,
+            cols: 3,
+            seed: Some(7),
+            scramble: true,
+        });
+        app.leaderboard = Leaderboard::default();
+        app.settings.player_name = String::from("Ada");
+
+        for direction in solver::solve(&app.puzzle, None).unwrap() {
+            let _ = app.update(Message::Move(direction));
+        }
+
+        assert!(app.victory.is_some());
+        let (table, entry) = app.pending_entry.as_ref().unwrap();
+        assert_eq!(table, "3x3");
+        assert_eq!(entry.moves, app.puzzle.moves());
+        // The name from last time is offered again
+        assert_eq!(app.name_input, "Ada");
+    }
+
+    #[test]
+    fn a_first_solve_unlocks_and_announces_an_achievement() {
+        let mut app = App::from_args(Args {
+            rows: 3,
+            cols: 3,
+            seed: Some(7),
+            scramble: true,
+        });
+        app.achievements = Unlocked::default();
+
+        for direction in solver::solve(&app.puzzle, None).unwrap() {
+            let _ = app.update(Message::Move(direction));
+        }
+
+        // The first achievement unlocked is announced
+        let first = app.tr(ACHIEVEMENTS[0].name);
+        assert!(app.toast.as_ref().unwrap().0.ends_with(first));
+        assert!(app.achievements.day(ACHIEVEMENTS[0].id).is_some());
+    }
+
+    #[test]
+    fn every_solve_is_kept_with_its_hints_undos_and_efficiency() {
+        let mut app = App::from_args(Args {
+            rows: 3,
+            cols: 3,
+            seed: None,
+            scramble: false,
+        });
+        let _ = app.update(Message::ShuffleSeeded(7));
+        let (row, col) = app.puzzle.movable_tiles()[0];
+        let _ = app.update(Message::TilePressed(row, col));
+        let _ = app.update(Message::Undo);
+        let board = app.puzzle.tiles().to_vec();
+        let cell = app.puzzle.movable_tiles()[0];
+        let _ = app.update(Message::HintReady(board, Some(cell)));
+
+        let solution = solver::solve(&app.puzzle, None).unwrap();
+        let length = solution.len() as u32;
+        for direction in solution {
+            let _ = app.update(Message::Move(direction));
+        }
+        let key = scramble_key(app.solved_scramble.as_ref().unwrap());
+        let _ = app.update(Message::OptimalFound(
+            key,
+            Some(OptimalLength::Exact(length)),
+        ));
+
+        let solve = app.records.solves().last().unwrap();
+        assert_eq!((solve.board.as_str(), solve.day), ("3x3", Day::today()));
+        assert_eq!((solve.hints, solve.undos), (1, 1));
+        assert!(!solve.time_attack && !solve.daily);
+        let row = app.records.solves_csv();
+        assert!(row.contains(&format!(",3x3,normal,{},", app.total_moves())));
+        assert!(row.ends_with(",1,1,100%\r\n"), "{row}");
+    }
+
+    #[test]
+    fn the_session_log_notes_modes_shuffles_hints_and_solves() {
+        let mut app = App::from_args(Args {
+            rows: 3,
+            cols: 3,
+            seed: None,
+            scramble: false,
+        });
+        let _ = app.update(Message::SetTimeAttack(true));
+        let _ = app.update(Message::SetTimeAttack(true));
+        let _ = app.update(Message::SetTimeAttack(false));
+        let _ = app.update(Message::ShuffleSeeded(7));
+        let board = app.puzzle.tiles().to_vec();
+        let cell = app.puzzle.movable_tiles()[0];
+        let _ = app.update(Message::HintReady(board, Some(cell)));
+
+        let solution = solver::solve(&app.puzzle, None).unwrap();
+        let length = solution.len() as u32;
+        for direction in solution {
+            let _ = app.update(Message::Move(direction));
+        }
+        let key = scramble_key(app.solved_scramble.as_ref().unwrap());
+        let _ = app.update(Message::OptimalFound(
+            key,
+            Some(OptimalLength::Exact(length)),
+        ));
+
+        let text = app.log.text();
+        let events: Vec<_> = text
+            .lines()
+            .skip(1)
+            .map(|line| line.split_once(' ').unwrap().1)
+            .collect();
+        assert_eq!(
+            events[..4],
+            [
+                "mode time-attack=on",
+                "mode time-attack=off",
+                "shuffle size=3x3 difficulty=hard seed=7",
+                "hint",
+            ]
+        );
+        assert!(events[4].starts_with(&format!("solve moves={length} time=")));
+        assert!(events[4].ends_with(&format!("optimal={length} efficiency=100%")));
+        assert_eq!(events.len(), 5);
+
+        let _ = app.update(Message::ToggleLog);
+        assert!(app.log_open);
+    }
+
+    #[test]
+    fn a_relay_moves_on_to_a_larger_board_and_counts_abandoning_it() {
+        let mut app = App::from_args(Args {
+            rows: 4,
+            cols: 4,
+            seed: Some(3),
+            scramble: false,
+        });
+        app.settings.confirm_shuffle = false;
+        let key = Relay::key(false);
+        let not_finished = app.records.stats(&key).did_not_finish;
+
+        let _ = app.update(Message::StartRelay);
+        assert_eq!((app.puzzle.rows(), app.puzzle.cols()), (3, 3));
+        let solution = solver::solve(&app.puzzle, None).unwrap();
+        for &direction in &solution {
+            let _ = app.update(Message::Move(direction));
+        }
+
+        // The 3x3 hands straight over to a fresh 4x4 on the same clock
+        assert_eq!((app.puzzle.rows(), app.puzzle.cols()), (4, 4));
+        assert!(app.puzzle.is_scrambled() && !app.puzzle.is_solved());
+        assert_eq!(app.relay.map(Relay::stage), Some(2));
+        assert!(app.relay_stage_at.is_some());
+        assert!(app.timer.is_running());
+        assert_eq!(app.victory, None);
+        assert_eq!(
+            app.total_moves(),
+            app.puzzle.moves() + solution.len() as u32
+        );
+        assert_eq!(app.records_key(), key);
+
+        let _ = app.update(Message::Shuffle);
+        assert_eq!(app.relay, None);
+        assert_eq!(app.records.stats(&key).did_not_finish, not_finished + 1);
+    }
+
+    #[test]
+    fn the_bot_races_the_same_scramble_and_the_first_to_solve_wins() {
+        let mut app = App::from_args(Args {
+            rows: 3,
+            cols: 3,
+            seed: None,
+            scramble: false,
+        });
+        let _ = app.update(Message::SetDifficulty(Difficulty::Easy));
+        let tally = |app: &App| {
+            app.records
+                .all_races()
+                .find(|&(difficulty, _)| difficulty == Difficulty::Easy)
+                .map_or((0, 0), |(_, tally)| (tally.won, tally.lost))
+        };
+        let start = |app: &mut App| {
+            let _ = app.update(Message::StartRace);
+            let bot = app.race.as_ref().unwrap().bot();
+            assert_eq!(bot.tiles(), app.puzzle.tiles());
+            let (board, solution) = (bot.tiles().to_vec(), solver::solve(bot, None));
+            let _ = app.update(Message::RaceSolution(board, solution));
+            assert!(app.race.as_ref().unwrap().is_ready());
+        };
+        let (won, lost) = tally(&app);
+
+        // Leaving the bot time to finish loses the race
+        start(&mut app);
+        let first = solver::solve(&app.puzzle, None).unwrap()[0];
+        let _ = app.update(Message::Move(first));
+        let _ = app.update(Message::Tick(Instant::now() + Duration::from_secs(600)));
+        let race = app.race.as_ref().unwrap();
+        assert!(race.bot().is_solved());
+        assert_eq!(race.winner(), Some(Racer::Bot));
+        assert_eq!(app.puzzle.moves(), 1);
+        assert_eq!(tally(&app), (won, lost + 1));
+
+        // Solving before it gets going wins
+        start(&mut app);
+        for direction in solver::solve(&app.puzzle, None).unwrap() {
+            let _ = app.update(Message::Move(direction));
+        }
+        assert_eq!(
+            app.race.as_ref().and_then(Race::winner),
+            Some(Racer::Player)
+        );
+        assert_eq!(tally(&app), (won + 1, lost + 1));
+        let _ = app.update(Message::Tick(Instant::now() + Duration::from_secs(600)));
+        assert!(!app.race.as_ref().unwrap().bot().is_solved());
+    }
+
+    #[test]
+    fn retrying_a_scramble_races_the_ghost_of_its_best() {
+        let mut app = App::from_args(Args {
+            rows: 3,
+            cols: 3,
+            seed: Some(11),
+            scramble: true,
+        });
+        // Whatever earlier runs left on disk
+        (app.ghosts, app.ghost) = (Ghosts::default(), None);
+        app.settings.ghost = true;
+        for direction in solver::solve(&app.puzzle, None).unwrap() {
+            let _ = app.update(Message::Move(direction));
+        }
+        let best = app.last_replay.clone().unwrap();
+        assert!(app.ghosts.best(best.start()).is_some());
+
+        let _ = app.update(Message::DismissVictory);
+        let _ = app.update(Message::Retry);
+        let ghost = app.ghost.as_ref().unwrap();
+        assert_eq!(ghost.board().tiles(), app.puzzle.tiles());
+
+        // The ghost plays on the attempt's clock, never on the board
+        let first = solver::solve(&app.puzzle, None).unwrap()[0];
+        let _ = app.update(Message::Move(first));
+        let _ = app.update(Message::Tick(Instant::now() + Duration::from_secs(600)));
+        let ghost = app.ghost.as_ref().unwrap();
+        assert!(ghost.is_finished() && ghost.board().is_solved());
+        assert_eq!(app.puzzle.moves(), 1);
+        assert!(!app.puzzle.is_solved());
+
+        let _ = app.update(Message::SetGhost(false));
+        assert!(app.ghost.is_none());
+        let _ = app.update(Message::Retry);
+        assert!(app.ghost.is_none());
+    }
+
+    #[test]
+    fn the_daily_puzzles_are_solved_ahead_of_the_daily_being_played() {
+        let mut app = App::from_args(Args {
+            rows: 3,
+            cols: 3,
+            seed: None,
+            scramble: false,
+        });
+        let _ = app.update(Message::Daily);
+        assert_eq!(app.dailies_analysed, Some(Day::today()));
+
+        let start = app.puzzle.clone();
+        let solution = solver::solve(&start, None).unwrap();
+        let length = solution.len() as u32;
+        let _ = app.update(Message::DailiesAnalysed(vec![(
+            scramble_key(&start),
+            OptimalLength::Exact(length),
+        )]));
+        for direction in solution {
+            let _ = app.update(Message::Move(direction));
+        }
+
+        // The answer was in before the solve, so nothing is left to search
+        assert!(app.victory.is_some());
+        assert!(!app.optimal_pending);
+        assert_eq!(
+            app.optimal_comparison(),
+            Some(app.trf(Text::YourSolveAndOptimal, &[&length, &length]))
+        );
+    }
+
+    #[test]
+    fn a_solve_is_compared_with_the_optimal_length_once_per_scramble() {
+        let mut app = App::from_args(Args {
+            rows: 3,
+            cols: 3,
+            seed: Some(7),
+            scramble: true,
+        });
+        let optimal = solver::optimal_length(&app.puzzle, None);
+        let solve = |app: &mut App| {
+            for direction in solver::solve(&app.puzzle, None).unwrap() {
+                let _ = app.update(Message::Move(direction));
+            }
+        };
+
+        solve(&mut app);
+        assert!(app.optimal_pending);
+        assert_eq!(
+            app.optimal_comparison().as_deref(),
+            Some(app.tr(Text::WorkingOutOptimal))
+        );
+
+        let key = scramble_key(app.solved_scramble.as_ref().unwrap());
+        let _ = app.update(Message::OptimalFound(key, optimal));
+        let Some(OptimalLength::Exact(length)) = optimal else {
+            panic!("a 3x3 solves within the budget");
+        };
+        assert_eq!(
+            app.optimal_comparison(),
+            Some(app.trf(Text::YourSolveAndOptimal, &[&app.puzzle.moves(), &length]))
+        );
+
+        // Solving the same scramble again finds the answer already there
+        let _ = app.update(Message::DismissVictory);
+        let _ = app.update(Message::Retry);
+        solve(&mut app);
+        assert!(app.solved_scramble.is_some());
+        assert!(!app.optimal_pending);
+    }
+
+    #[test]
+    fn an_optimal_solve_earns_every_star_and_each_solve_is_scored() {
+        let mut app = App::from_args(Args {
+            rows: 3,
+            cols: 3,
+            seed: Some(7),
+            scramble: true,
+        });
+        let optimal = solver::optimal_length(&app.puzzle, None);
+        let solve = |app: &mut App| {
+            for direction in solver::solve(&app.puzzle, None).unwrap() {
+                let _ = app.update(Message::Move(direction));
+            }
+        };
+
+        solve(&mut app);
+        assert_eq!(app.efficiency(), None);
+        let key = scramble_key(app.solved_scramble.as_ref().unwrap());
+        let _ = app.update(Message::OptimalFound(key, optimal));
+        // Solving along an optimal solution earns every star
+        assert_eq!(app.efficiency().and_then(Efficiency::stars), Some(3));
+        let scored = app.records.stats(&app.records_key()).scored_solves;
+        assert_eq!(scored, 1);
+
+        // A retry is scored at once, with the optimal length known
+        let _ = app.update(Message::DismissVictory);
+        let _ = app.update(Message::Retry);
+        solve(&mut app);
+        assert_eq!(app.efficiency().and_then(Efficiency::stars), Some(3));
+        assert_eq!(
+            app.records.stats(&app.records_key()).scored_solves,
+            scored + 1
+        );
+    }
+
+    #[test]
+    fn line_slides_animate_every_moved_tile() {
+        let mut puzzle = Puzzle::new(4, 4);
+        puzzle.move_line(3, 1, SlideCount::PerTile);
+
+        let (duration, easing) = (Duration::from_millis(120), Easing::default());
+        let animation = Animation::new((3, 3), &puzzle, Instant::now(), duration, easing).unwrap();
+
+        assert_eq!(animation.tiles, [(3, 3), (3, 2)]);
+        assert_eq!(animation.offset, (0.0, -1.0));
+        assert!(Animation::new((3, 1), &puzzle, Instant::now(), duration, easing).is_none());
+    }
+
+    #[test]
+    fn reduced_motion_keeps_the_board_still() {
+        let mut app = App::from_args(Args {
+            rows: 3,
+            cols: 3,
+            seed: Some(2),
+            scramble: true,
+        });
+        app.system_reduces_motion = true;
+        let solution = solver::solve(&app.puzzle, None).unwrap();
+
+        for &direction in &solution[..solution.len() - 1] {
+            let _ = app.update(Message::Move(direction));
+            assert!(app.animation.is_none());
+        }
+        let _ = app.update(Message::Move(solution[solution.len() - 1]));
+        assert!(app.victory.is_some() && !app.is_celebrating());
+
+        // The setting wins over the system either way
+        let _ = app.update(Message::SetReduceMotion(false));
+        assert!(app.motion_enabled() && app.is_celebrating());
+        app.system_reduces_motion = false;
+        let _ = app.update(Message::SetReduceMotion(true));
+        assert!(!app.motion_enabled());
+        assert!(app.animation_duration().is_zero());
+    }
+
+    #[test]
+    fn a_sliding_tile_is_drawn_and_pressed_between_its_cells() {
+        let mut app = App::from_args(Args {
+            rows: 3,
+            cols: 3,
+            seed: Some(2),
+            scramble: true,
+        });
+        app.settings.reduce_motion = Some(false);
+        app.settings.easing = Easing::Linear;
+        let from = app.puzzle.movable_tiles()[0];
+        let to = app.puzzle.empty_position();
+        let _ = app.update(Message::TilePressed(from.0, from.1));
+
+        let animation = app.animation.as_ref().expect("a slide");
+        let halfway = animation.started + animation.duration / 2;
+        let _ = app.update(Message::Tick(halfway));
+        let sprites = app.tile_sprites();
+        let sliding = sprites.iter().find(|sprite| sprite.cell == to).unwrap();
+        let midway = ((from.0 + to.0) as f32 / 2.0, (from.1 + to.1) as f32 / 2.0);
+        assert!((sliding.at.0 - midway.0).abs() < 0.01 && (sliding.at.1 - midway.1).abs() < 0.01);
+        assert!(sprites[from.0 * 3 + from.1].is_blank());
+
+        // The canvas finds it where it is drawn, not in the cell it is in
+        let layout = app.board_layout();
+        let center = layout.bounds(sliding.at).center();
+        assert_eq!(board::tile_at(&sprites, layout, center), Some(to));
+    }
+
+    #[test]
+    fn pressing_a_stuck_tile_shakes_it_without_counting_a_move() {
+        let mut app = App::from_args(Args {
+            rows: 4,
+            cols: 4,
+            seed: Some(3),
+            scramble: true,
+        });
+        let (row, col) = app.puzzle.empty_position();
+        let far = ((row + 2) % 4, (col + 2) % 4);
+
+        let _ = app.update(Message::TilePressed(far.0, far.1));
+        assert_eq!(app.rejected.map(|(row, col, _)| (row, col)), Some(far));
+        assert_eq!(app.puzzle.moves(), 0);
+
+        // The cue runs out on its own
+        let _ = app.update(Message::Tick(app.now + REJECTION_CUE));
+        assert!(app.rejected.is_none());
+
+        // and gives way at once to a move that works
+        let _ = app.update(Message::TilePressed(far.0, far.1));
+        let (row, col) = Direction::ALL
+            .into_iter()
+            .find_map(|direction| app.puzzle.tile_for_direction(direction))
+            .unwrap();
+        let _ = app.update(Message::TilePressed(row, col));
+        assert!(app.rejected.is_none());
+        assert_eq!(app.puzzle.moves(), 1);
+
+        // Pressing the blank is no mistake worth pointing out
+        let (row, col) = app.puzzle.empty_position();
+        let _ = app.update(Message::TilePressed(row, col));
+        assert!(app.rejected.is_none());
+    }
+
+    #[test]
+    fn right_clicking_a_tile_outlines_where_it_belongs() {
+        let mut app = App::default();
+        app.puzzle.move_line(3, 0, SlideCount::PerTile);
+        let pitch = app.tile_pitch();
+        let over = |row: usize, col: usize| {
+            Point::new((col as f32 + 0.5) * pitch, (row as f32 + 0.5) * pitch)
+        };
+        let flash = |app: &App| app.goal_flash.map(|(cell, home, _)| (cell, home));
+
+        // Tile 13 now sits where 14 goes
+        let _ = app.update(Message::ShowGoal(over(3, 1)));
+        assert_eq!(flash(&app), Some(((3, 0), false)));
+        let _ = app.update(Message::ShowGoal(over(0, 0)));
+        assert_eq!(flash(&app), Some(((0, 0), true)));
+        let _ = app.update(Message::ShowGoal(over(3, 0)));
+        assert_eq!(flash(&app), Some(((3, 0), true)));
+        assert_eq!(app.puzzle.moves(), 3);
+
+        let _ = app.update(Message::Tick(app.now + GOAL_FLASH));
+        assert_eq!(flash(&app), None);
+    }
+
+    #[test]
+    fn pristine_board_never_celebrates() {
+        let mut app = App::default();
+
+        let _ = app.update(Message::TilePressed(3, 2));
+        let _ = app.update(Message::TilePressed(3, 3));
+
+        assert!(app.puzzle.is_solved());
+        assert!(app.victory.is_none());
+    }
+
+    #[test]
+    fn pause_freezes_the_timer_and_the_board() {
+        let mut app = App::from_args(Args {
+            rows: 3,
+            cols: 3,
+            seed: Some(7),
+            scramble: true,
+        });
+        let (row, col) = app.puzzle.movable_tiles()[0];
+        let _ = app.update(Message::TilePressed(row, col));
+        assert!(app.timer.is_running());
+
+        let _ = app.update(Message::Pause);
+        let frozen = app.timer.elapsed(Instant::now());
+        let (row, col) = app.puzzle.movable_tiles()[0];
+        let _ = app.update(Message::TilePressed(row, col));
+
+        assert!(!app.timer.is_running());
+        assert_eq!(app.timer.elapsed(Instant::now()), frozen);
+        assert_eq!(app.puzzle.moves(), 1);
+
+        let _ = app.update(Message::Resume);
+        let _ = app.update(Message::SkipCountdown);
+        assert!(app.timer.is_running());
+        assert!(app.timer.elapsed(Instant::now()) >= frozen);
+
+        app.settings.confirm_shuffle = false;
+        let _ = app.update(Message::Pause);
+        let _ = app.update(Message::Shuffle);
+        assert!(!app.paused);
+        assert_eq!(app.timer.elapsed(Instant::now()), Duration::ZERO);
+    }
+
+    #[test]
+    fn retry_while_paused_starts_the_attempt_over() {
+        let mut app = App::from_args(Args {
+            rows: 3,
+            cols: 3,
+            seed: Some(7),
+            scramble: true,
+        });
+        let scramble = app.puzzle.tiles().to_vec();
+        let (row, col) = app.puzzle.movable_tiles()[0];
+        let _ = app.update(Message::TilePressed(row, col));
+
+        let _ = app.update(Message::Pause);
+        let _ = app.update(Message::Retry);
+        assert!(!app.paused);
+        assert_eq!(app.timer.elapsed(Instant::now()), Duration::ZERO);
+        assert_eq!((app.puzzle.tiles(), app.puzzle.moves()), (&scramble[..], 0));
+    }
+
+    #[test]
+    fn resuming_counts_down_before_the_clock_starts_again() {
+        let mut app = App::from_args(Args {
+            rows: 3,
+            cols: 3,
+            seed: Some(7),
+            scramble: true,
+        });
+        let (row, col) = app.puzzle.movable_tiles()[0];
+        let _ = app.update(Message::TilePressed(row, col));
+        let _ = app.update(Message::Pause);
+        let frozen = app.timer.elapsed(Instant::now());
+
+        // The board is back but out of reach, and the clock still
+        let _ = app.update(Message::Resume);
+        let began = app.countdown.expect("the countdown");
+        assert!(!app.paused && !app.timer.is_running());
+        let (row, col) = app.puzzle.movable_tiles()[0];
+        let _ = app.update(Message::TilePressed(row, col));
+        assert_eq!(app.puzzle.moves(), 1);
+
+        let _ = app.update(Message::Tick(began + COUNTDOWN / 2));
+        assert!(app.countdown.is_some());
+        let _ = app.update(Message::Tick(began + COUNTDOWN));
+        assert!(app.countdown.is_none() && app.timer.is_running());
+        assert_eq!(app.timer.elapsed(began + COUNTDOWN), frozen);
+
+        // A time attack waits for the count too, unless the player skips it
+        app.settings.time_attack = true;
+        let _ = app.update(Message::Retry);
+        assert!(app.countdown.is_some() && !app.timer.is_running());
+        let _ = app.update(Message::SkipCountdown);
+        assert!(app.countdown.is_none() && app.timer.is_running());
+    }
+
+    #[test]
+    fn losing_focus_pauses_until_clicked_even_in_a_time_attack() {
+        let mut app = App::from_args(Args {
+            rows: 3,
+            cols: 3,
+            seed: Some(7),
+            scramble: true,
+        });
+        app.settings.pause_when_away = true;
+        app.puzzle.set_mode(GameMode::TimeAttack {
+            remaining: Duration::from_secs(60),
+        });
+        let (row, col) = app.puzzle.movable_tiles()[0];
+        let _ = app.update(Message::TilePressed(row, col));
+
+        // A time attack cannot be paused by hand, but stops while away
+        let _ = app.update(Message::Pause);
+        assert!(!app.paused);
+        let _ = app.update(Message::WindowUnfocused);
+        assert!(app.paused && app.away);
+        assert!(!app.timer.is_running());
+
+        let _ = app.update(Message::Resume);
+        assert!(!app.paused && !app.away);
+        let _ = app.update(Message::SkipCountdown);
+        assert!(app.timer.is_running());
+
+        app.settings.pause_when_away = false;
+        let _ = app.update(Message::WindowUnfocused);
+        assert!(!app.paused);
+
+        // Nothing to pause before the clock starts
+        app.settings.pause_when_away = true;
+        let _ = app.update(Message::Retry);
+        let _ = app.update(Message::WindowUnfocused);
+        assert!(!app.paused && !app.away);
+    }
+
+    #[test]
+    fn presets_confirm_before_abandoning_a_solve() {
+        let mut app = App::from_args(Args {
+            rows: 4,
+            cols: 4,
+            seed: Some(3),
+            scramble: true,
+        });
+        let (row, col) = app.puzzle.movable_tiles()[0];
+        let _ = app.update(Message::TilePressed(row, col));
+        let board = app.puzzle.tiles().to_vec();
+
+        let _ = app.update(Message::Preset(0));
+        assert_eq!(app.pending_preset, Some(0));
+        assert_eq!(app.puzzle.tiles(), board);
+
+        let _ = app.update(Message::ConfirmPreset(false));
+        assert_eq!(app.pending_preset, None);
+        assert_eq!(app.puzzle.tiles(), board);
+    }
+
+    #[test]
+    fn goal_editor_refuses_incomplete_layouts() {
+        let mut app = App::from_args(Args {
+            rows: 2,
+            cols: 2,
+            seed: None,
+            scramble: false,
+        });
+        let _ = app.update(Message::EditGoal);
+        let _ = app.update(Message::ClearGoalEditor);
+
+        for index in [3, 2, 1] {
+            let _ = app.update(Message::EditGoalCell(index));
+        }
+        assert_eq!(app.goal_editor, [None, Some(3), Some(2), Some(1)]);
+
+        let _ = app.update(Message::SaveCustomGoal);
+        assert!(app.goal_editor_error.is_some());
+        assert_eq!(app.screen, Screen::GoalEditor);
+        assert_eq!(*app.puzzle.goal(), Goal::Standard);
+
+        // Clicking an assigned cell frees its number for the next click
+        let _ = app.update(Message::EditGoalCell(2));
+        let _ = app.update(Message::EditGoalCell(0));
+        assert_eq!(app.goal_editor, [Some(2), Some(3), None, Some(1)]);
+        assert!(app.goal_editor_error.is_none());
+    }
+
+    #[test]
+    fn moves_are_recorded_and_replay_read_only() {
+        let mut app = App::from_args(Args {
+            rows: 3,
+            cols: 3,
+            seed: Some(8),
+            scramble: true,
+        });
+        for _ in 0..4 {
+            let (row, col) = app.puzzle.movable_tiles()[0];
+            let _ = app.update(Message::TilePressed(row, col));
+        }
+        let _ = app.update(Message::Undo);
+
+        let recording = app.recording.clone().expect("scrambles are recorded");
+        assert_eq!(recording.len(), 5);
+        assert_eq!(
+            recording.position(recording.len()).tiles(),
+            app.puzzle.tiles()
+        );
+
+        app.last_replay = Some(recording.clone());
+        let _ = app.update(Message::WatchReplay);
+        assert_eq!(app.screen, Screen::Replay);
+        let board = app.puzzle.tiles().to_vec();
+        let (row, col) = app.puzzle.movable_tiles()[0];
+        let _ = app.update(Message::TilePressed(row, col));
+        assert_eq!(app.puzzle.tiles(), board);
+
+        let _ = app.update(Message::StepReplay);
+        let _ = app.update(Message::StepReplay);
+        let playback = app.playback.as_ref().unwrap();
+        assert_eq!(playback.step, 2);
+        assert_eq!(playback.board.tiles(), recording.position(2).tiles());
+    }
+
+    #[test]
+    fn tile_looks_apply_at_once_and_unloaded_fonts_fall_back() {
+        let mut app = App::default();
+        let _ = app.update(Message::SetTileGap(0));
+        let _ = app.update(Message::SetTileRadius(0));
+
+        // Tiles meet edge to edge, square-cornered
+        let layout = app.board_layout();
+        let (left, right) = (layout.bounds((0.0, 0.0)), layout.bounds((0.0, 1.0)));
+        assert_eq!(left.x + left.width, right.x);
+        assert_eq!(app.tile_sprites()[0].look.corners.tile, 0.0);
+
+        let _ = app.update(Message::SetTileGap(100));
+        let _ = app.update(Message::SetTileRadius(100));
+        assert_eq!(
+            (app.settings.tile_gap, app.settings.tile_radius),
+            (MAX_TILE_GAP, MAX_TILE_RADIUS)
+        );
+        assert!(app.tile_pitch() > layout.pitch());
+
+        // Until the font has loaded, the labels stay in the standard one
+        let _ = app.update(Message::SetTileFont(TileFont::FiraMono));
+        assert_eq!(app.label_font(), Font::DEFAULT);
+        let _ = app.update(Message::FontLoaded(TileFont::FiraMono, Ok(())));
+        assert_eq!(app.label_font(), tile_font(TileFont::FiraMono));
+    }
+
+    #[test]
+    fn picture_tiles_carry_numbers_only_when_asked_for_either_renderer() {
+        let mut app = App::default();
+        let _ = app.update(Message::SetPictureMode(true));
+        let _ = app.update(Message::SetPictureNumbers(false));
+
+        // Both renderers draw whatever label a sprite has, so hiding the
+        // numbers is settled in the sprites alone
+        let tiles: Vec<_> = app
+            .tile_sprites()
+            .into_iter()
+            .filter(|sprite| !sprite.is_blank())
+            .collect();
+        assert!(tiles.iter().all(|sprite| sprite.picture.is_some()));
+        assert!(
+            tiles
+                .iter()
+                .all(|sprite| sprite.label.as_deref() == Some(""))
+        );
+
+        let _ = app.update(Message::SetPictureNumbers(true));
+        assert!(
+            app.tile_sprites()
+                .iter()
+                .filter(|sprite| !sprite.is_blank())
+                .all(|sprite| sprite.label.as_ref().is_some_and(|label| !label.is_empty()))
+        );
+    }
+
+    #[test]
+    fn dragging_a_tile_past_halfway_moves_it_once() {
+        let mut app = App::from_args(Args {
+            rows: 3,
+            cols: 3,
+            seed: Some(8),
+            scramble: true,
+        });
+        let pitch = app.tile_pitch();
+        let direction = Direction::ALL
+            .into_iter()
+            .find(|&direction| app.puzzle.tile_for_direction(direction).is_some())
+            .unwrap();
+        let (row, col) = app.puzzle.tile_for_direction(direction).unwrap();
+        let start = Point::new((col as f32 + 0.5) * pitch, (row as f32 + 0.5) * pitch);
+        let toward = match direction {
+            Direction::Up => Vector::new(0.0, -1.0),
+            Direction::Down => Vector::new(0.0, 1.0),
+            Direction::Left => Vector::new(-1.0, 0.0),
+            Direction::Right => Vector::new(1.0, 0.0),
+        };
+
+        // Not far enough: the tile snaps back
+        let _ = app.update(Message::Dragged(start, toward * (0.3 * pitch)));
+        let _ = app.update(Message::Released(Some(direction)));
+        assert_eq!(app.puzzle.moves(), 0);
+
+        let _ = app.update(Message::Dragged(start, toward * (0.3 * pitch)));
+        let _ = app.update(Message::Dragged(start, toward * (0.8 * pitch)));
+        assert_eq!(app.puzzle.drag().map(|drag| drag.tile()), Some((row, col)));
+        let _ = app.update(Message::Released(Some(direction)));
+        assert_eq!(app.puzzle.moves(), 1);
+        assert_eq!(app.puzzle.empty_position(), (row, col));
+        assert_eq!(app.puzzle.drag(), None);
+    }
+
+    #[test]
+    fn scrolling_slides_the_hovered_line_toward_the_blank() {
+        let mut app = App::default();
+        app.settings.slide_count = SlideCount::PerTile;
+        let pitch = app.tile_pitch();
+        let over = |row: usize, col: usize| {
+            Point::new((col as f32 + 0.5) * pitch, (row as f32 + 0.5) * pitch)
+        };
+
+        // The blank is below the top of the last column, so only scrolling
+        // down slides it, every tile in between going along
+        let _ = app.update(Message::Scrolled(over(0, 3), Direction::Up));
+        let _ = app.update(Message::Scrolled(over(0, 2), Direction::Down));
+        let _ = app.update(Message::Scrolled(over(3, 3), Direction::Down));
+        assert_eq!(app.puzzle.moves(), 0);
+        let _ = app.update(Message::Scrolled(over(0, 3), Direction::Down));
+        assert_eq!(app.puzzle.empty_position(), (0, 3));
+        assert_eq!(app.puzzle.moves(), 3);
+
+        app.settings.slide_count = SlideCount::PerSlide;
+        let _ = app.update(Message::Scrolled(over(0, 1), Direction::Right));
+        assert_eq!(app.puzzle.empty_position(), (0, 1));
+        assert_eq!(app.puzzle.moves(), 4);
+
+        // Off the board nothing is hovered
+        let _ = app.update(Message::Scrolled(over(0, 4), Direction::Left));
+        assert_eq!(app.puzzle.moves(), 4);
+    }
+
+    #[test]
+    fn fullscreen_keeps_the_windowed_geometry() {
+        let mut app = App::default();
+        let _ = app.update(Message::WindowResized(Size::new(900.0, 700.0)));
+        let _ = app.update(Message::WindowMoved(Point::new(120.0, 80.0)));
+        assert_eq!(app.board_size(), BOARD_SIZE);
+
+        let _ = app.update(Message::ToggleFullscreen);
+        let _ = app.update(Message::WindowMoved(Point::ORIGIN));
+        let _ = app.update(Message::WindowResized(Size::new(1920.0, 1200.0)));
+        assert!(app.board_size() > BOARD_SIZE);
+        assert_eq!(app.windowed_size, Size::new(900.0, 700.0));
+        assert_eq!(app.windowed_position, Some(Point::new(120.0, 80.0)));
+
+        let _ = app.update(Message::ToggleFullscreen);
+        assert!(!app.fullscreen);
+        assert_eq!(app.windowed_size, Size::new(900.0, 700.0));
+    }
+
+    #[test]
+    fn labels_grow_with_the_board_but_fit_their_tiles() {
+        let small = tile_size(BOARD_SIZE, TILE_SPACING, 4, 4);
+        let large = tile_size(BOARD_SIZE * 2.5, TILE_SPACING, 4, 4);
+        assert!(label_size(large, 4, 4) > label_size(small, 4, 4));
+
+        // The spacing grows with the board, keeping its proportions
+        let ratio = |board: f32| {
+            let spacing = tile_spacing(board, TILE_SPACING);
+            spacing / tile_size(board, spacing, 4, 4)
+        };
+        assert!((ratio(BOARD_SIZE) - ratio(BOARD_SIZE * 2.5)).abs() < 1e-6);
+
+        for (rows, cols) in [(3, 3), (4, 4), (10, 10), (12, 12)] {
+            for gap in [0, MAX_TILE_GAP] {
+                let tile = tile_size(BOARD_SIZE, gap as f32, rows, cols);
+                let digits = (rows * cols - 1).to_string().len() as f32;
+                let size = label_size(tile, rows, cols);
+
+                assert!(size >= MIN_LABEL_SIZE);
+                if size > MIN_LABEL_SIZE {
+                    // Even on a tile rounded into a circle, the whole label
+                    // sits inside it
+                    let diagonal = (size * DIGIT_WIDTH * digits).hypot(size);
+                    assert!(diagonal < tile, "{rows}x{cols}, {gap} px gap");
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn the_board_fits_small_and_wide_windows() {
+        let mut app = App::default();
+
+        let _ = app.update(Message::WindowResized(MIN_WINDOW_SIZE));
+        assert!(app.is_compact());
+        assert!(app.board_size() <= MIN_WINDOW_SIZE.width - CONTROLS_WIDTH);
+
+        let _ = app.update(Message::WindowResized(Size::new(3440.0, 1440.0)));
+        assert!(!app.is_compact());
+        assert_eq!(app.board_size(), 1440.0 - CONTROLS_HEIGHT);
+    }
+
+    #[test]
+    fn the_title_follows_the_game() {
+        let mut app = App::default();
+        app.profile.name = String::from("Jo");
+        assert_eq!(app.title(), "15 Puzzle · Jo");
+
+        app = App::from_args(Args {
+            rows: 3,
+            cols: 3,
+            seed: Some(8),
+            scramble: true,
+        });
+        app.profile.name = String::from("Jo");
+        assert_eq!(app.title(), "8 Puzzle · Jo");
+
+        for _ in 0..2 {
+            let (row, col) = app.puzzle.movable_tiles()[0];
+            let _ = app.update(Message::TilePressed(row, col));
+        }
+        app.now += Duration::from_millis(72_400);
+        assert_eq!(app.title(), "8 Puzzle · Jo — 2 moves — 1:12");
+
+        app.settings.language = Language::German;
+        assert_eq!(app.title(), "8-Puzzle · Jo — 2 Züge — 1:12");
+    }
+
+    #[test]
+    fn tab_skips_the_blank_and_enter_slides_the_focused_tile() {
+        let plain = Modifiers::empty();
+        assert!(matches!(
+            message_for_key(&Key::Named(Named::Tab), plain),
+            Some(Message::FocusNext)
+        ));
+        assert!(matches!(
+            message_for_key(&Key::Named(Named::Tab), Modifiers::SHIFT),
+            Some(Message::FocusPrevious)
+        ));
+        assert!(matches!(
+            message_for_key(&Key::Named(Named::Enter), plain),
+            Some(Message::PressFocused)
+        ));
+
+        // Solved, the blank is last, so focus wraps from tile 8 back to 1
+        let mut app = App::from_args(Args {
+            rows: 3,
+            cols: 3,
+            seed: None,
+            scramble: false,
+        });
+        let _ = app.update(Message::FocusPrevious);
+        assert_eq!(app.focused, Some(8));
+        let _ = app.update(Message::FocusNext);
+        assert_eq!(app.focused, Some(1));
+        assert_eq!(
+            app.status_line().as_deref(),
+            Some("Tile 1, row 1, column 1")
+        );
+
+        // Focus follows tile 8 as it slides into the blank
+        let _ = app.update(Message::FocusPrevious);
+        assert_eq!(
+            app.status_line().as_deref(),
+            Some("Tile 8, row 3, column 2, movable")
+        );
+        let _ = app.update(Message::PressFocused);
+        assert_eq!(app.puzzle.moves(), 1);
+        assert_eq!(app.focused_cell(), Some((2, 2)));
+    }
+
+    #[test]
+    fn time_attacks_count_down_without_pausing() {
+        let mut app = App::from_args(Args {
+            rows: 3,
+            cols: 3,
+            seed: Some(4),
+            scramble: true,
+        });
+        app.settings.time_attack = true;
+        app.settings.time_limit_secs = 120;
+        app.restart();
+        assert_eq!(
+            app.puzzle.mode().remaining(),
+            Some(Duration::from_secs(120))
+        );
+        let _ = app.update(Message::SkipCountdown);
+
+        let (row, col) = app.puzzle.movable_tiles()[0];
+        let _ = app.update(Message::TilePressed(row, col));
+        let _ = app.update(Message::Tick(app.now + Duration::from_secs(20)));
+        let remaining = app.puzzle.mode().remaining().unwrap();
+        assert!(remaining <= Duration::from_secs(100));
+
+        let _ = app.update(Message::TogglePause);
+        assert!(!app.paused);
+        assert!(app.timer.is_running());
+    }
+
+    #[test]
+    fn the_first_4x4_board_sends_for_the_solver_tables_once() {
+        let mut app = App::from_args(Args {
+            rows: 3,
+            cols: 3,
+            seed: Some(6),
+            scramble: true,
+        });
+        let _ = app.update(Message::Shuffle);
+        assert!(!app.pattern_database_requested);
+
+        let _ = app.update(Message::SetSize(4, 4));
+        assert!(app.pattern_database_requested);
+        assert!(app.pattern_progress.is_some());
+        let _ = app.update(Message::PatternDatabaseReady);
+        assert!(app.pattern_progress.is_none());
+
+        // Another 4x4 board does not build them again
+        let _ = app.update(Message::Shuffle);
+        assert!(app.pattern_progress.is_none());
+    }
+
+    #[test]
+    fn move_limits_wait_for_their_budget() {
+        let mut app = App::from_args(Args {
+            rows: 3,
+            cols: 3,
+            seed: Some(6),
+            scramble: true,
+        });
+        app.settings.move_limit = true;
+        app.restart();
+        let board = app.puzzle.tiles().to_vec();
+
+        let (row, col) = app.puzzle.movable_tiles()[0];
+        let _ = app.update(Message::TilePressed(row, col));
+        assert_eq!(app.puzzle.tiles(), board);
+        assert!(app.budget_pending);
+
+        // A budget for some other board is dropped
+        let _ = app.update(Message::BudgetReady(Vec::new(), 1));
+        assert!(app.puzzle.is_locked());
+
+        let _ = app.update(Message::BudgetReady(board, 30));
+        assert_eq!(app.puzzle.moves_left(), Some(30));
+        let _ = app.update(Message::TilePressed(row, col));
+        assert_eq!(app.puzzle.moves_left(), Some(29));
+    }
+
+    #[test]
+    fn a_move_limit_win_is_kept_apart_from_casual_solves() {
+        let mut app = App::from_args(Args {
+            rows: 3,
+            cols: 3,
+            seed: Some(6),
+            scramble: true,
+        });
+        app.records = Records::default();
+        app.settings.move_limit = true;
+        app.record_shuffle();
+        let solution = solver::solve(&app.puzzle, None).unwrap();
+        let _ = app.update(Message::BudgetReady(
+            app.puzzle.tiles().to_vec(),
+            solution.len() as u32,
+        ));
+
+        for direction in solution {
+            let _ = app.update(Message::Move(direction));
+        }
+
+        assert!(app.victory.is_some());
+        assert!(app.new_record);
+        let stats = app.records.stats(&app.records_key());
+        assert_eq!(stats.move_limit_wins, 1);
+        assert_eq!((stats.shuffled, stats.solved), (0, 0));
+        assert_eq!(stats.current_streak, 0);
+        assert_eq!(app.records.best(&app.records_key()), None);
+    }
+
+    #[test]
+    fn blindfolds_hide_the_board_after_the_preview() {
+        let mut app = App::from_args(Args {
+            rows: 3,
+            cols: 3,
+            seed: Some(9),
+            scramble: true,
+        });
+        app.settings.blindfold = true;
+        app.settings.blindfold_preview_secs = 10;
+        app.restart();
+        let start = app.preview_tick.unwrap();
+
+        let _ = app.update(Message::Tick(start + Duration::from_secs(4)));
+        assert!(app.puzzle.is_previewing());
+        let _ = app.update(Message::Tick(start + Duration::from_secs(11)));
+        assert!(app.puzzle.is_hidden());
+
+        // Hidden tiles still move
+        let (row, col) = app.puzzle.movable_tiles()[0];
+        let _ = app.update(Message::TilePressed(row, col));
+        assert_eq!(app.puzzle.moves(), 1);
+    }
+
+    #[test]
+    fn shuffle_mid_solve_asks_first() {
+        let mut app = App::from_args(Args {
+            rows: 3,
+            cols: 3,
+            seed: Some(11),
+            scramble: true,
+        });
+        app.settings.confirm_shuffle = true;
+        let (row, col) = app.puzzle.movable_tiles()[0];
+        let _ = app.update(Message::TilePressed(row, col));
+        let board = app.puzzle.tiles().to_vec();
+
+        let _ = app.update(Message::Shuffle);
+        assert!(app.confirming_shuffle);
+        assert_eq!(app.puzzle.tiles(), board);
+
+        // The prompt blocks the board
+        let (row, col) = app.puzzle.movable_tiles()[0];
+        let _ = app.update(Message::TilePressed(row, col));
+        assert_eq!(app.puzzle.moves(), 1);
+
+        let _ = app.update(Message::CancelShuffle);
+        assert!(!app.confirming_shuffle);
+        assert_eq!(app.puzzle.tiles(), board);
+        assert!(app.timer.is_running());
+
+        // Going ahead shuffles, and leaves the new board to play
+        let _ = app.update(Message::Shuffle);
+        let _ = app.update(Message::ConfirmShuffle);
+        assert!(!app.confirming_shuffle);
+        let (row, col) = app.puzzle.movable_tiles()[0];
+        let _ = app.update(Message::TilePressed(row, col));
+        assert_eq!(app.puzzle.moves(), 1);
+    }
+
+    #[test]
+    fn ctrl_shortcuts_map_to_undo_and_redo() {
+        assert!(matches!(
+            message_for_key(&character("z"), Modifiers::CTRL),
+            Some(Message::Undo)
+        ));
+        assert!(matches!(
+            message_for_key(&character("y"), Modifiers::CTRL),
+            Some(Message::Redo)
+        ));
+        assert!(matches!(
+            message_for_key(&character("Z"), Modifiers::CTRL | Modifiers::SHIFT),
+            Some(Message::Redo)
+        ));
+        // Modified movement keys do not move tiles
+        assert!(message_for_key(&character("d"), Modifiers::CTRL).is_none());
+    }
+
+    #[test]
+    fn ctrl_shortcuts_map_to_shuffle_and_reset() {
+        assert!(matches!(
+            message_for_key(&character("n"), Modifiers::CTRL),
+            Some(Message::Shuffle)
+        ));
+        assert!(matches!(
+            message_for_key(&character("r"), Modifiers::CTRL),
+            Some(Message::Reset)
+        ));
+    }
+
+    #[test]
+    fn pasting_loads_a_copied_board_and_rejects_anything_else() {
+        assert!(matches!(
+            message_for_key(&character("v"), Modifiers::CTRL),
+            Some(Message::PastePosition)
+        ));
+
+        let mut app = App::from_args(Args {
+            rows: 3,
+            cols: 3,
+            seed: Some(6),
+            scramble: true,
+        });
+        let copied = app.puzzle.board_text();
+        let tiles = app.puzzle.tiles().to_vec();
+
+        let mut other = App::from_args(Args {
+            rows: 4,
+            cols: 4,
+            seed: Some(7),
+            scramble: true,
+        });
+        let (row, col) = other.puzzle.movable_tiles()[0];
+        let _ = other.update(Message::TilePressed(row, col));
+        let _ = other.update(Message::ClipboardRead(Some(copied)));
+        assert_eq!(other.puzzle.tiles(), tiles.as_slice());
+        assert_eq!(other.puzzle.moves(), 0);
+        assert!(!other.puzzle.can_undo());
+        assert!(!other.timer.is_running());
+        assert!(matches!(other.notice, Some(Notice::Info(_))));
+
+        for contents in [None, Some(String::from("  ")), Some(String::from("hello"))] {
+            let _ = app.update(Message::ClipboardRead(contents));
+            assert!(matches!(app.notice, Some(Notice::Error(_))));
+            assert_eq!(app.puzzle.tiles(), tiles.as_slice());
+        }
+
+        // Two tiles swapped can never be solved
+        let _ = app.update(Message::ClipboardRead(Some(String::from(
+            "2 1 3\n4 5 6\n7 8 _",
+        ))));
+        assert!(!app.puzzle.is_solvable());
+        assert!(matches!(app.notice, Some(Notice::Error(_))));
+    }
+
+    #[test]
+    fn a_scramble_code_loads_the_same_board_in_the_current_goal() {
+        let mut shared = Puzzle::new(3, 5);
+        shared.shuffle_with_seed(21);
+        let code = encode_board(&shared);
+
+        let mut app = App::from_args(Args {
+            rows: 4,
+            cols: 4,
+            seed: Some(5),
+            scramble: true,
+        });
+        let tiles = app.puzzle.tiles().to_vec();
+        let mut typo = code.clone();
+        typo.replace_range(3..4, if &code[3..4] == "7" { "8" } else { "7" });
+        let _ = app.update(Message::LoadCode(typo));
+        assert!(matches!(app.notice, Some(Notice::Error(_))));
+        assert_eq!(app.puzzle.tiles(), tiles.as_slice());
+
+        let _ = app.update(Message::LoadCode(code.to_lowercase()));
+        assert!(matches!(app.notice, Some(Notice::Info(_))));
+        assert_eq!(app.puzzle.tiles(), shared.tiles());
+        assert_eq!(app.puzzle.moves(), 0);
+        assert!(app.puzzle.is_scrambled());
+        assert_eq!(encode_board(&app.puzzle), code);
+    }
+
+    #[test]
+    fn a_save_slot_brings_back_the_whole_attempt() {
+        let mut parked = App::from_args(Args {
+            rows: 5,
+            cols: 5,
+            seed: Some(8),
+            scramble: true,
+        });
+        parked.puzzle.set_mode(GameMode::TimeAttack {
+            remaining: Duration::from_secs(100),
+        });
+        let (row, col) = parked.puzzle.movable_tiles()[0];
+        let _ = parked.update(Message::TilePressed(row, col));
+        parked.daily = Some(Day::from_days_since_epoch(20_000));
+        parked.hints_used = 2;
+        let slot = SaveSlot {
+            name: String::from("Parked 5x5"),
+            game: parked.saved_game(),
+        };
+
+        let mut app = App::from_args(Args {
+            rows: 3,
+            cols: 3,
+            seed: Some(1),
+            scramble: true,
+        });
+        let _ = app.update(Message::ShowScreen(Screen::Saves));
+        let mut slots = vec![SlotContents::Empty; SAVE_SLOTS];
+        slots[2] = SlotContents::Saved(Box::new(slot));
+        let _ = app.update(Message::SlotsRead(slots));
+        assert_eq!(
+            app.slot_mode(&app.slots[2].saved().unwrap().game),
+            "Daily + Time attack"
+        );
+
+        // Nothing to load from an empty slot
+        let _ = app.update(Message::LoadSlot(0));
+        assert_eq!((app.screen, app.puzzle.rows()), (Screen::Saves, 3));
+
+        // Deleting asks first
+        let _ = app.update(Message::DeleteSlot(2));
+        assert_eq!(app.confirm_delete_slot, Some(2));
+        let _ = app.update(Message::Dismiss);
+        assert_eq!(app.confirm_delete_slot, None);
+        assert_eq!(app.screen, Screen::Saves);
+
+        let _ = app.update(Message::RenameSlot(2));
+        assert_eq!(app.renaming_slot, Some((2, String::from("Parked 5x5"))));
+        let _ = app.update(Message::Dismiss);
+        assert_eq!(app.renaming_slot, None);
+
+        let _ = app.update(Message::LoadSlot(2));
+        assert_eq!(app.screen, Screen::Game);
+        assert_eq!(app.puzzle.tiles(), parked.puzzle.tiles());
+        assert_eq!(app.puzzle.mode(), parked.puzzle.mode());
+        assert!(app.timer.elapsed(Instant::now()) >= parked.timer.elapsed(parked.now));
+        assert_eq!(app.daily, parked.daily);
+        assert_eq!(app.hints_used, 2);
+        assert!(app.puzzle.undo());
+    }
+
+    #[test]
+    fn documents_stay_under_the_storage_the_app_is_given() {
+        // Tests keep nothing of the player's
+        assert!(App::default().data.is_none());
+
+        let dir = std::env::temp_dir().join(format!("iced_15_puzzle-app-{}", std::process::id()));
+        let root = Storage::new(&dir);
+        let mut app = App::with_storage(Some(root.clone()), None);
+        let _ = app.update(Message::SetLanguage(Language::German));
+        app.write_settings();
+
+        let reopened = App::with_storage(Some(root), None);
+        assert_eq!(reopened.profiles, app.profiles);
+        assert_eq!(reopened.settings.language, Language::German);
+        assert!(dir.join(app.profile.dir()).join(SETTINGS).exists());
+
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn deleting_a_profile_takes_its_name_typed_out() {
+        let mut app = App::from_args(Args {
+            rows: 3,
+            cols: 3,
+            seed: Some(2),
+            scramble: true,
+        });
+        assert!(app.title().contains(&app.profile.name));
+
+        let _ = app.update(Message::CreateProfile);
+        assert_eq!(app.profile_error, Some(ProfileError::EmptyName));
+        let _ = app.update(Message::ProfileInputChanged(String::from("K")));
+        assert_eq!(app.profile_error, None);
+
+        // Kept to this app, as creating one for real would switch to it
+        let kid = app.profiles.create("Kid").unwrap().id;
+        let _ = app.update(Message::DeleteProfile(kid));
+        let _ = app.update(Message::DeleteConfirmationChanged(String::from("kid")));
+        let _ = app.update(Message::ConfirmDeleteProfile(true));
+        assert!(app.profiles.get(kid).is_some());
+        assert_eq!(app.deleting_profile, None);
+
+        let _ = app.update(Message::DeleteProfile(kid));
+        let _ = app.update(Message::DeleteConfirmationChanged(String::from("Kid")));
+        let _ = app.update(Message::Dismiss);
+        assert_eq!(app.deleting_profile, None);
+        assert!(app.profiles.get(kid).is_some());
+    }
+
+    #[test]
+    fn copying_the_position_shows_a_toast_that_fades() {
+        assert!(matches!(
+            message_for_key(&character("c"), Modifiers::CTRL),
+            Some(Message::CopyPosition)
+        ));
+
+        let mut app = App::from_args(Args {
+            rows: 3,
+            cols: 3,
+            seed: Some(5),
+            scramble: true,
+        });
+        let _ = app.update(Message::CopyPosition);
+        assert_eq!(
+            app.toast.as_ref().map(|(toast, _)| toast.as_str()),
+            Some(app.tr(Text::Copied))
+        );
+
+        let later = app.now + TOAST_DURATION;
+        let _ = app.update(Message::Tick(later));
+        assert_eq!(app.toast, None);
+    }
+
+    #[test]
+    fn settings_wait_for_changes_to_settle_before_saving() {
+        let mut app = App::from_args(Args {
+            rows: 3,
+            cols: 3,
+            seed: Some(5),
+            scramble: true,
+        });
+        assert_eq!(app.settings_changed_at, None);
+
+        let _ = app.update(Message::SetAnimationSpeed(1000));
+        assert_eq!(app.settings.animation_ms, u64::from(MAX_ANIMATION_MS));
+        let first = app.settings_changed_at.unwrap();
+
+        // Each further change pushes the save back
+        let _ = app.update(Message::SetAnimationSpeed(0));
+        assert_eq!(app.settings.animation_ms, u64::from(MIN_ANIMATION_MS));
+        let second = app.settings_changed_at.unwrap();
+        assert!(second >= first);
+        let _ = app.update(Message::Tick(second + SETTINGS_SAVE_DELAY / 2));
+        assert_eq!(app.settings_changed_at, Some(second));
+    }
+
+    #[test]
+    fn the_goal_preview_can_be_folded_away() {
+        let mut app = App::from_args(Args {
+            rows: 3,
+            cols: 3,
+            seed: Some(5),
+            scramble: true,
+        });
+        let shown = app.settings.goal_preview;
+
+        let _ = app.update(Message::SetGoalPreview(!shown));
+        assert_eq!(app.settings.goal_preview, !shown);
+        assert!(app.settings_changed_at.is_some());
+        let _ = app.goal_preview();
+    }
+
+    #[test]
+    fn global_shortcuts_map_to_actions() {
+        let plain = Modifiers::empty();
+
+        assert!(matches!(
+            message_for_key(&Key::Named(Named::Space), plain),
+            Some(Message::Shuffle)
+        ));
+        assert!(matches!(
+            message_for_key(&character("n"), plain),
+            Some(Message::Shuffle)
+        ));
+        assert!(matches!(
+            message_for_key(&character("z"), plain),
+            Some(Message::Undo)
+        ));
+        assert!(matches!(
+            message_for_key(&character("y"), plain),
+            Some(Message::Redo)
+        ));
+        assert!(matches!(
+            message_for_key(&character("p"), plain),
+            Some(Message::TogglePause)
+        ));
+        assert!(matches!(
+            message_for_key(&character("H"), Modifiers::SHIFT),
+            Some(Message::Hint)
+        ));
+        assert!(matches!(
+            message_for_key(&Key::Named(Named::Escape), plain),
+            Some(Message::Dismiss)
+        ));
+        assert!(matches!(
+            message_for_key(&character("?"), Modifiers::SHIFT),
+            Some(Message::ToggleHelp)
+        ));
+        // Plain h still moves
+        assert!(matches!(
+            message_for_key(&character("h"), plain),
+            Some(Message::Move(Direction::Left))
+        ));
+    }
+
+    #[test]
+    fn escape_closes_the_topmost_overlay_first() {
+        let mut app = App::from_args(Args {
+            rows: 3,
+            cols: 3,
+            seed: None,
+            scramble: false,
+        });
+        app.victory = Some(Instant::now());
+        let _ = app.update(Message::ToggleHelp);
+
+        let _ = app.update(Message::Dismiss);
+        assert!(!app.show_help);
+        assert!(app.victory.is_some());
+
+        let _ = app.update(Message::Dismiss);
+        assert!(app.victory.is_none());
+    }
+
+    #[test]
+    fn escape_in_a_game_opens_its_menu_which_holds_the_board() {
+        let mut app = App::from_args(Args {
+            rows: 3,
+            cols: 3,
+            seed: Some(5),
+            scramble: true,
+        });
+        let (row, col) = app.puzzle.movable_tiles()[0];
+        let _ = app.update(Message::TilePressed(row, col));
+        assert!(app.timer.is_running());
+
+        let _ = app.update(Message::Dismiss);
+        assert!(app.game_menu && app.paused);
+        let board = app.puzzle.tiles().to_vec();
+        let (row, col) = app.puzzle.movable_tiles()[0];
+        for message in [
+            Message::TilePressed(row, col),
+            Message::Undo,
+            Message::Shuffle,
+        ] {
+            let _ = app.update(message);
+        }
+        assert_eq!(app.puzzle.tiles(), board);
+
+        let _ = app.update(Message::Dismiss);
+        assert!(!app.game_menu && !app.paused && app.countdown.is_some());
+
+        // Quitting leaves the game to continue, and the screens opened from
+        // the menu lead back to it
+        let _ = app.update(Message::OpenGameMenu);
+        let _ = app.update(Message::QuitToMenu);
+        assert_eq!(app.screen, Screen::Menu);
+        assert!(app.can_continue());
+        let _ = app.update(Message::ShowScreen(Screen::Settings));
+        let _ = app.update(Message::Dismiss);
+        assert_eq!(app.screen, Screen::Menu);
+        let _ = app.update(Message::ShowScreen(Screen::Game));
+        assert_eq!(app.puzzle.tiles(), board);
+    }
+
+    #[test]
+    fn the_menu_starts_each_mode_at_the_size_last_picked() {
+        let mut app = App {
+            screen: Screen::Menu,
+            ..App::default()
+        };
+
+        // Keys on the menu leave the board alone
+        let board = app.puzzle.tiles().to_vec();
+        for message in [
+            Message::Move(Direction::Left),
+            Message::MoveKeyPressed(Direction::Up),
+            Message::Shuffle,
+            Message::PressFocused,
+        ] {
+            let _ = app.update(message);
+        }
+        assert_eq!(app.puzzle.tiles(), board);
+
+        let _ = app.update(Message::SetMenuSize(3, 5));
+        let _ = app.update(Message::Play(PlayMode::TimeAttack));
+        assert_eq!(app.screen, Screen::Game);
+        assert_eq!((app.puzzle.rows(), app.puzzle.cols()), (3, 5));
+        assert!(app.puzzle.is_scrambled() && app.puzzle.mode().is_time_attack());
+
+        let _ = app.update(Message::QuitToMenu);
+        let _ = app.update(Message::Play(PlayMode::Blindfold));
+        assert!(app.settings.blindfold && !app.settings.time_attack);
+        assert_eq!(app.puzzle.mode(), GameMode::Casual);
+
+        let _ = app.update(Message::QuitToMenu);
+        let _ = app.update(Message::Play(PlayMode::Relay));
+        assert!(app.relay.is_some());
+
+        // Picking a size in the game makes it the menu's too
+        let _ = app.update(Message::SetSize(4, 3));
+        assert_eq!(app.settings.grid_size, (4, 3));
+    }
+
+    #[test]
+    fn the_tutorial_takes_stray_moves_back_and_is_suggested_until_done() {
+        let mut app = App {
+            screen: Screen::Menu,
+            ..App::default()
+        };
+        let records = app.records.clone();
+        let _ = app.update(Message::StartTutorial);
+        assert_eq!(app.screen, Screen::Game);
+        assert_eq!((app.puzzle.rows(), app.puzzle.cols()), (3, 3));
+
+        // A tile off the script slides back a moment later
+        let board = app.puzzle.tiles().to_vec();
+        let press = app.tutorial_press();
+        let stray = app
+            .puzzle
+            .movable_tiles()
+            .into_iter()
+            .find(|&cell| Some(cell) != press)
+            .unwrap();
+        let _ = app.update(Message::TilePressed(stray.0, stray.1));
+        assert!(app.tutorial_undo_at.is_some() && app.tutorial_press().is_none());
+        let _ = app.update(Message::Undo);
+        let _ = app.update(Message::Tick(app.now + TUTORIAL_UNDO_DELAY));
+        assert_eq!(app.puzzle.tiles(), board);
+        assert_eq!(app.tutorial_press(), press);
+
+        while let Some(tutorial) = &app.tutorial
+            && !tutorial.is_finished()
+        {
+            let message = match app.tutorial_press() {
+                Some((row, col)) => Message::TilePressed(row, col),
+                None => Message::BeginTutorialStep,
+            };
+            let _ = app.update(message);
+        }
+        assert!(app.puzzle.is_solved() && app.settings.tutorial_done);
+        assert!(!app.timer.is_running() && app.victory.is_none());
+        assert_eq!(app.records, records);
+
+        let _ = app.update(Message::EndTutorial);
+        assert_eq!((app.screen, app.tutorial.is_none()), (Screen::Menu, true));
+    }
+
+    #[test]
+    fn reset_returns_to_the_solved_board_without_a_win() {
+        let mut app = App::from_args(Args {
+            rows: 3,
+            cols: 3,
+            seed: Some(5),
+            scramble: true,
+        });
+        let (row, col) = app.puzzle.movable_tiles()[0];
+        let _ = app.update(Message::TilePressed(row, col));
+        let solved = app.records.stats("3x3").solved;
+
+        let _ = app.update(Message::Reset);
+
+        assert!(app.puzzle.is_solved());
+        assert_eq!(app.puzzle.moves(), 0);
+        assert!(!app.puzzle.can_undo());
+        assert!(!app.timer.is_running());
+        assert!(app.victory.is_none());
+        assert_eq!(app.records.stats("3x3").solved, solved);
+    }
+
+    #[test]
+    fn unrelated_keys_are_ignored() {
+        assert_eq!(direction_for_key(&character("q")), None);
+        assert_eq!(direction_for_key(&character("1")), None);
+        assert_eq!(direction_for_key(&Key::Named(Named::Enter)), None);
+    }
 }
@@ -0,0 +1,219 @@
+//! The colours tiles are drawn in under each colour scheme.
+//!
+//! Every tile style reads from a [`Palette`], so another scheme only needs
+//! another set of colours here.
+
+use iced::{Color, Theme, color, time::Duration, widget::button};
+use iced_15_puzzle::ColorScheme;
+
+/// A tile background and the label drawn on it.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Swatch {
+    pub background: Color,
+    pub hovered: Color,
+    pub text: Color,
+}
+
+impl Swatch {
+    /// A tile button in these colours, fading out while disabled.
+    pub fn style(self, status: button::Status) -> button::Style {
+        let base = button::Style {
+            background: Some(self.background.into()),
+            text_color: self.text,
+            border: iced::border::rounded(2),
+            ..button::Style::default()
+        };
+
+        match status {
+            button::Status::Active | button::Status::Pressed => base,
+            button::Status::Hovered => button::Style {
+                background: Some(self.hovered.into()),
+                ..base
+            },
+            button::Status::Disabled => button::Style {
+                background: Some(self.background.scale_alpha(0.5).into()),
+                text_color: self.text.scale_alpha(0.5),
+                ..base
+            },
+        }
+    }
+}
+
+/// The named colours of one colour scheme.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Palette {
+    /// Tiles, when they are not coloured by position.
+    pub tile: Swatch,
+    /// Tiles away from their goal cell.
+    pub misplaced: Swatch,
+    /// Tiles in their goal cell.
+    pub in_place: Swatch,
+    /// The tile that just moved.
+    pub last_moved: Swatch,
+    /// The tile a hint suggests.
+    pub hint: Swatch,
+    /// The colours the victory wave cycles through, evenly spaced.
+    pub wave: [Color; 6],
+}
+
+impl Palette {
+    /// The colours `scheme` draws with under `theme`.
+    pub fn new(scheme: ColorScheme, theme: &Theme) -> Self {
+        let palette = theme.extended_palette();
+
+        match scheme {
+            ColorScheme::Standard => {
+                let swatch = |pair: iced::theme::palette::Pair, hovered: Color| Swatch {
+                    background: pair.color,
+                    hovered,
+                    text: pair.text,
+                };
+
+                Self {
+                    tile: swatch(palette.primary.base, palette.primary.strong.color),
+                    misplaced: swatch(palette.secondary.base, palette.secondary.strong.color),
+                    in_place: swatch(palette.success.weak, palette.success.base.color),
+                    last_moved: swatch(palette.primary.strong, palette.primary.base.color),
+                    hint: swatch(palette.success.base, palette.success.strong.color),
+                    // The hue wheel at 60% saturation and 85% value
+                    wave: [
+                        color!(0xd95757),
+                        color!(0xd9d957),
+                        color!(0x57d957),
+                        color!(0x57d9d9),
+                        color!(0x5757d9),
+                        color!(0xd957d9),
+                    ],
+                }
+            }
+            ColorScheme::HighContrast => {
+                // Tiles are the opposite of the background, and highlights
+                // are saturated enough to read on either
+                let (tile, label, hovered) = if palette.is_dark {
+                    (Color::WHITE, Color::BLACK, color!(0xcccccc))
+                } else {
+                    (Color::BLACK, Color::WHITE, color!(0x333333))
+                };
+                let bright = |background: Color, hovered: Color| Swatch {
+                    background,
+                    hovered,
+                    text: Color::BLACK,
+                };
+                let plain = Swatch {
+                    background: tile,
+                    hovered,
+                    text: label,
+                };
+
+                Self {
+                    tile: plain,
+                    misplaced: plain,
+                    in_place: bright(color!(0xffd700), color!(0xffe44d)),
+                    last_moved: bright(color!(0x00e5ff), color!(0x66efff)),
+                    hint: bright(color!(0xff40ff), color!(0xff80ff)),
+                    wave: [
+                        color!(0xffd700),
+                        Color::WHITE,
+                        color!(0x00e5ff),
+                        Color::WHITE,
+                        color!(0xff40ff),
+                        Color::WHITE,
+                    ],
+                }
+            }
+            // The Okabe-Ito colours, chosen to stay distinct under every
+            // common colour vision deficiency
+            ColorScheme::Deuteranopia => Self {
+                tile: Swatch {
+                    background: color!(0x0072b2),
+                    hovered: color!(0x005a8c),
+                    text: Color::WHITE,
+                },
+                misplaced: Swatch {
+                    background: color!(0x0072b2),
+                    hovered: color!(0x005a8c),
+                    text: Color::WHITE,
+                },
+                in_place: Swatch {
+                    background: color!(0xe69f00),
+                    hovered: color!(0xf0b433),
+                    text: Color::BLACK,
+                },
+                last_moved: Swatch {
+                    background: color!(0x56b4e9),
+                    hovered: color!(0x7fc6ef),
+                    text: Color::BLACK,
+                },
+                hint: Swatch {
+                    background: color!(0xf0e442),
+                    hovered: color!(0xf5ec7a),
+                    text: Color::BLACK,
+                },
+                wave: [
+                    color!(0xe69f00),
+                    color!(0x56b4e9),
+                    color!(0x009e73),
+                    color!(0xf0e442),
+                    color!(0x0072b2),
+                    color!(0xcc79a7),
+                ],
+            },
+        }
+    }
+
+    /// The colour of a tile `distance` cells from the top-left corner,
+    /// `elapsed` into the victory wave that sweeps across the board.
+    pub fn wave_color(&self, elapsed: Duration, distance: usize) -> Color {
+        // One trip round the colours a second, half a colour (a twelfth of
+        // the trip) behind per cell from the corner
+        let position = (elapsed.as_secs_f32() * 6.0 - distance as f32 * 0.5).rem_euclid(6.0);
+        let index = (position as usize).min(5);
+        let (from, to) = (self.wave[index], self.wave[(index + 1) % 6]);
+        let t = position - index as f32;
+
+        Color::from_rgb(
+            from.r + (to.r - from.r) * t,
+            from.g + (to.g - from.g) * t,
+            from.b + (to.b - from.b) * t,
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn the_standard_wave_runs_round_the_hue_wheel() {
+        let palette = Palette::new(ColorScheme::Standard, &Theme::Light);
+        let close = |a: Color, b: Color| {
+            (a.r - b.r).abs() < 0.01 && (a.g - b.g).abs() < 0.01 && (a.b - b.b).abs() < 0.01
+        };
+
+        assert!(close(
+            palette.wave_color(Duration::ZERO, 0),
+            color!(0xd95757)
+        ));
+        // Half a trip later the wave has reached cyan
+        assert!(close(
+            palette.wave_color(Duration::from_millis(500), 0),
+            color!(0x57d9d9)
+        ));
+        // A cell further out lags 30 degrees behind, between red and yellow
+        assert!(close(
+            palette.wave_color(Duration::ZERO, 1),
+            palette.wave_color(Duration::from_millis(1_000 - 1_000 / 12), 0)
+        ));
+    }
+
+    #[test]
+    fn every_scheme_separates_placed_tiles_from_the_rest() {
+        for scheme in ColorScheme::ALL {
+            for theme in [Theme::Light, Theme::Dark] {
+                let palette = Palette::new(scheme, &theme);
+                assert_ne!(palette.in_place.background, palette.misplaced.background);
+                assert_ne!(palette.hint.background, palette.misplaced.background);
+            }
+        }
+    }
+}
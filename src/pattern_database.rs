@@ -0,0 +1,512 @@
+//! Disjoint pattern databases, a stronger heuristic than the Manhattan
+//! distance for the optimal solver.
+//!
+//! The tiles are split into groups, and for every placement of a group's
+//! tiles the database holds the fewest moves of those tiles that bring them
+//! home, wherever the other tiles are. No move shifts tiles of two groups, so
+//! the costs of all groups add up to a lower bound on the full solution.
+
+use crate::{
+    Goal, MAX_GRID_SIZE, MIN_GRID_SIZE, Puzzle, goal_position,
+    heuristic::{Board, Heuristic, Manhattan},
+};
+
+#[cfg(not(target_arch = "wasm32"))]
+use std::{fs, path::Path};
+
+/// The 6-6-3 partition of the 4x4 board's tiles the solver uses.
+pub const PARTITION_4X4: [&[u16]; 3] = [&[1, 2, 5, 6, 9, 13], &[3, 4, 7, 8, 11, 12], &[10, 14, 15]];
+
+// Marks the start of a saved database, with the format's version
+const MAGIC: &[u8; 4] = b"PDB1";
+// Costs of placements not reached yet, while generating
+const UNKNOWN: u8 = u8::MAX;
+// The most tiles a group can have
+const MAX_GROUP: usize = 8;
+
+/// A disjoint pattern database for a board of one size, with the standard
+/// goal and without wrapping.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PatternDatabase {
+    rows: usize,
+    cols: usize,
+    groups: Vec<Group>,
+    group_of: Vec<Option<usize>>, // Group of each value, none for the blank
+}
+
+/// One group of tiles and the cost of each placement of it.
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct Group {
+    tiles: Vec<u16>,
+    costs: Vec<u8>, // Indexed by `rank` of the cells the tiles are in
+}
+
+impl PatternDatabase {
+    /// Works out the database for a `rows` by `cols` board with its tiles
+    /// split into the groups of `partition`, calling `progress` with the
+    /// share done so far, from 0 to 1.
+    ///
+    /// Every group is searched backwards from the goal, breadth first by
+    /// cost: the blank wanders past the other tiles for free, and each move
+    /// of one of the group's own tiles costs one.
+    pub fn generate(
+        rows: usize,
+        cols: usize,
+        partition: &[&[u16]],
+        mut progress: impl FnMut(f32),
+    ) -> Self {
+        let cells = rows * cols;
+        let total: usize = partition
+            .iter()
+            .map(|tiles| placements(cells, tiles.len()) * cells)
+            .sum();
+        let mut done = 0;
+
+        let groups = partition
+            .iter()
+            .map(|tiles| {
+                let group = Group::generate(rows, cols, tiles, |states| {
+                    progress((done + states) as f32 / total as f32);
+                });
+                done += group.costs.len() * cells;
+                group
+            })
+            .collect();
+        progress(1.0);
+
+        Self::new(rows, cols, groups)
+    }
+
+    fn new(rows: usize, cols: usize, groups: Vec<Group>) -> Self {
+        let mut group_of = vec![None; rows * cols];
+        for (index, group) in groups.iter().enumerate() {
+            for &tile in &group.tiles {
+                group_of[usize::from(tile)] = Some(index);
+            }
+        }
+
+        Self {
+            rows,
+            cols,
+            groups,
+            group_of,
+        }
+    }
+
+    /// Whether the database applies to `puzzle`, which must match the size
+    /// it was worked out for, have the standard goal and not wrap.
+    pub fn covers(&self, puzzle: &Puzzle) -> bool {
+        Self::fits(self.rows, self.cols, puzzle)
+    }
+
+    /// Whether a database worked out for `rows` by `cols` would apply to
+    /// `puzzle`, before there is one.
+    pub fn fits(rows: usize, cols: usize, puzzle: &Puzzle) -> bool {
+        (puzzle.rows(), puzzle.cols()) == (rows, cols)
+            && *puzzle.goal() == Goal::Standard
+            && !puzzle.wrap()
+    }
+
+    /// The cost of group `group` with its tiles in the cells
+    /// `positions[tile]`, or with `moved` the first of them in the second.
+    fn cost(&self, group: usize, positions: &[usize], moved: Option<(u16, usize)>) -> u32 {
+        let group = &self.groups[group];
+        let mut cells = [0; MAX_GROUP];
+        for (cell, &tile) in cells.iter_mut().zip(&group.tiles) {
+            *cell = match moved {
+                Some((value, to)) if value == tile => to,
+                _ => positions[usize::from(tile)],
+            };
+        }
+
+        u32::from(group.costs[rank(self.rows * self.cols, &cells[..group.tiles.len()])])
+    }
+
+    /// The database in a compact binary form, for saving.
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mut bytes = MAGIC.to_vec();
+        bytes.extend([self.rows as u8, self.cols as u8, self.groups.len() as u8]);
+        for group in &self.groups {
+            bytes.push(group.tiles.len() as u8);
+            for tile in &group.tiles {
+                bytes.extend(tile.to_le_bytes());
+            }
+            bytes.extend(&group.costs);
+        }
+        bytes.extend(checksum(&bytes).to_le_bytes());
+        bytes
+    }
+
+    /// Reads a database written by [`to_bytes`](Self::to_bytes), rejecting
+    /// anything truncated, altered or not a partition of the board's tiles.
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, String> {
+        let corrupt = |what: &str| format!("corrupt pattern database: {what}");
+        let (body, sum) = bytes
+            .split_last_chunk::<4>()
+            .ok_or_else(|| corrupt("too short"))?;
+        if checksum(body) != u32::from_le_bytes(*sum) {
+            return Err(corrupt("checksum mismatch"));
+        }
+
+        let mut reader = Reader(body);
+        if reader.take(MAGIC.len()) != Some(MAGIC.as_slice()) {
+            return Err(corrupt("not a pattern database"));
+        }
+        let header = reader.take(3).ok_or_else(|| corrupt("no header"))?;
+        let (rows, cols) = (usize::from(header[0]), usize::from(header[1]));
+        let sizes = MIN_GRID_SIZE..=MAX_GRID_SIZE;
+        if !sizes.contains(&rows) || !sizes.contains(&cols) {
+            return Err(corrupt("no such board size"));
+        }
+        let cells = rows * cols;
+        let mut seen = vec![false; cells];
+        let mut groups = Vec::new();
+
+        for _ in 0..header[2] {
+            let size = usize::from(reader.take(1).ok_or_else(|| corrupt("no group"))?[0]);
+            if size > MAX_GROUP {
+                return Err(corrupt("group too large"));
+            }
+            let tiles: Vec<u16> = reader
+                .take(size * 2)
+                .ok_or_else(|| corrupt("no tiles"))?
+                .chunks(2)
+                .map(|pair| u16::from_le_bytes([pair[0], pair[1]]))
+                .collect();
+            for &tile in &tiles {
+                let tile = usize::from(tile);
+                if tile == 0 || tile >= cells || std::mem::replace(&mut seen[tile], true) {
+                    return Err(corrupt("tiles overlap or are off the board"));
+                }
+            }
+
+            // Tiles are distinct and on the board, so there are fewer than
+            // its cells, but their placements can still be too many to count
+            let count = (cells - size + 1..=cells)
+                .try_fold(1, usize::checked_mul)
+                .ok_or_else(|| corrupt("group too large"))?;
+            let costs = reader
+                .take(count)
+                .ok_or_else(|| corrupt("costs cut short"))?
+                .to_vec();
+            if costs.contains(&UNKNOWN) {
+                return Err(corrupt("unfinished table"));
+            }
+            groups.push(Group { tiles, costs });
+        }
+        if !reader.0.is_empty() {
+            return Err(corrupt("trailing bytes"));
+        }
+
+        Ok(Self::new(rows, cols, groups))
+    }
+
+    /// Writes the database to the file at `path`.
+    #[cfg(not(target_arch = "wasm32"))]
+    pub fn save(&self, path: &Path) -> Result<(), String> {
+        path.parent()
+            .map_or(Ok(()), fs::create_dir_all)
+            .and_then(|()| fs::write(path, self.to_bytes()))
+            .map_err(|error| format!("could not write {}: {error}", path.display()))
+    }
+
+    /// Reads the database saved at `path`.
+    #[cfg(not(target_arch = "wasm32"))]
+    pub fn load(path: &Path) -> Result<Self, String> {
+        let bytes = fs::read(path)
+            .map_err(|error| format!("could not read {}: {error}", path.display()))?;
+        Self::from_bytes(&bytes)
+    }
+}
+
+/// The costs of all groups add up, and tiles in no group count their own
+/// Manhattan distance. Only boards the database
+/// [covers](PatternDatabase::covers) can be estimated.
+impl Heuristic for PatternDatabase {
+    fn estimate(&self, board: &Puzzle) -> u32 {
+        let board = Board::new(board);
+        let positions = board.positions();
+        let costs: u32 = (0..self.groups.len())
+            .map(|group| self.cost(group, positions, None))
+            .sum();
+
+        costs
+            + (1..positions.len() as u16)
+                .filter(|&value| self.group_of[usize::from(value)].is_none())
+                .map(|value| board.distance(value, positions[usize::from(value)]))
+                .sum::<u32>()
+    }
+
+    fn slide_delta(&self, board: &Board, from: usize) -> i32 {
+        let value = board.value(from);
+
+        match self.group_of[usize::from(value)] {
+            Some(group) => {
+                let positions = board.positions();
+                let after = self.cost(group, positions, Some((value, board.empty())));
+                after as i32 - self.cost(group, positions, None) as i32
+            }
+            None => Manhattan.slide_delta(board, from),
+        }
+    }
+}
+
+impl Group {
+    /// Works out the costs of every placement of `tiles`, calling `progress`
+    /// with how many states have been settled.
+    fn generate(rows: usize, cols: usize, tiles: &[u16], mut progress: impl FnMut(usize)) -> Self {
+        assert!(
+            tiles.len() <= MAX_GROUP,
+            "groups have at most {MAX_GROUP} tiles"
+        );
+        let cells = rows * cols;
+        let goal_cell = |value| {
+            let (row, col) = goal_position(value, (rows, cols), &Goal::Standard);
+            row * cols + col
+        };
+        let home: Vec<usize> = tiles.iter().map(|&tile| goal_cell(tile)).collect();
+
+        let mut costs = vec![UNKNOWN; placements(cells, tiles.len())];
+        // States pair a placement's rank with the blank's cell
+        let mut settled = vec![false; costs.len() * cells];
+        let mut count = 0;
+        let mut level = vec![rank(cells, &home) * cells + goal_cell(0)];
+        let mut cost = 0;
+
+        while !level.is_empty() {
+            let mut next = Vec::new();
+
+            while let Some(state) = level.pop() {
+                if std::mem::replace(&mut settled[state], true) {
+                    continue;
+                }
+                let (placement, blank) = (state / cells, state % cells);
+                costs[placement] = costs[placement].min(cost);
+                count += 1;
+                if count % (1 << 20) == 0 {
+                    progress(count);
+                }
+
+                let positions = unrank(cells, tiles.len(), placement);
+                let positions = &positions[..tiles.len()];
+                for neighbour in neighbours(rows, cols, blank) {
+                    match positions.iter().position(|&cell| cell == neighbour) {
+                        // The blank passes the other tiles for free
+                        None => level.push(placement * cells + neighbour),
+                        Some(moved) => {
+                            let mut moved_to = [0; MAX_GROUP];
+                            moved_to[..positions.len()].copy_from_slice(positions);
+                            moved_to[moved] = blank;
+                            next.push(
+                                rank(cells, &moved_to[..positions.len()]) * cells + neighbour,
+                            );
+                        }
+                    }
+                }
+            }
+
+            level = next;
+            cost += 1;
+        }
+
+        Self {
+            tiles: tiles.to_vec(),
+            costs,
+        }
+    }
+}
+
+/// The cells next to `cell` on a board without wrapping.
+fn neighbours(rows: usize, cols: usize, cell: usize) -> impl Iterator<Item = usize> {
+    let (row, col) = (cell / cols, cell % cols);
+
+    [
+        (row > 0).then(|| cell - cols),
+        (row + 1 < rows).then(|| cell + cols),
+        (col > 0).then(|| cell - 1),
+        (col + 1 < cols).then(|| cell + 1),
+    ]
+    .into_iter()
+    .flatten()
+}
+
+/// How many ways `count` tiles can be placed on `cells` cells.
+fn placements(cells: usize, count: usize) -> usize {
+    (cells - count + 1..=cells).product()
+}
+
+/// Numbers the placement of tiles in the distinct cells `positions`, from 0
+/// to `placements(cells, positions.len())`.
+fn rank(cells: usize, positions: &[usize]) -> usize {
+    positions.iter().enumerate().fold(0, |index, (i, &cell)| {
+        // Each cell counts among those the earlier tiles left free
+        let taken = positions[..i]
+            .iter()
+            .filter(|&&earlier| earlier < cell)
+            .count();
+        index * (cells - i) + cell - taken
+    })
+}
+
+/// The placement of `count` tiles numbered `index` by [`rank`].
+///
+/// Only the first `count` cells returned are used.
+fn unrank(cells: usize, count: usize, mut index: usize) -> [usize; MAX_GROUP] {
+    let mut free_ranks = [0; MAX_GROUP];
+    for i in (0..count).rev() {
+        free_ranks[i] = index % (cells - i);
+        index /= cells - i;
+    }
+
+    let mut positions = [0; MAX_GROUP];
+    let mut taken = [0; MAX_GROUP]; // The cells so far, in order
+    for i in 0..count {
+        // The cell with `free_ranks[i]` free cells before it
+        let mut cell = free_ranks[i];
+        for &earlier in &taken[..i] {
+            if earlier <= cell {
+                cell += 1;
+            }
+        }
+        positions[i] = cell;
+
+        let at = taken[..i].partition_point(|&earlier| earlier < cell);
+        taken.copy_within(at..i, at + 1);
+        taken[at] = cell;
+    }
+    positions
+}
+
+/// FNV-1a, enough to notice a damaged file.
+fn checksum(bytes: &[u8]) -> u32 {
+    bytes.iter().fold(0x811c_9dc5, |hash, &byte| {
+        (hash ^ u32::from(byte)).wrapping_mul(0x0100_0193)
+    })
+}
+
+/// Reads a byte slice from the front.
+struct Reader<'a>(&'a [u8]);
+
+impl<'a> Reader<'a> {
+    fn take(&mut self, count: usize) -> Option<&'a [u8]> {
+        let (taken, rest) = self.0.split_at_checked(count)?;
+        self.0 = rest;
+        Some(taken)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use std::cmp::Reverse;
+    use std::collections::{BinaryHeap, HashMap};
+
+    #[test]
+    fn ranks_number_every_placement_once() {
+        let cells = 5;
+        let mut seen = vec![false; placements(cells, 3)];
+
+        for a in 0..cells {
+            for b in (0..cells).filter(|&b| b != a) {
+                for c in (0..cells).filter(|&c| c != a && c != b) {
+                    let index = rank(cells, &[a, b, c]);
+                    assert!(!std::mem::replace(&mut seen[index], true));
+                    assert_eq!(unrank(cells, 3, index)[..3], [a, b, c]);
+                }
+            }
+        }
+        assert!(seen.iter().all(|&seen| seen));
+    }
+
+    /// The cost of every placement of `tiles` by a plain Dijkstra search
+    /// over whole states, positions and blank, from the goal.
+    fn brute_force(rows: usize, cols: usize, tiles: &[u16]) -> HashMap<Vec<usize>, u32> {
+        let goal_cell = |value| {
+            let (row, col) = goal_position(value, (rows, cols), &Goal::Standard);
+            row * cols + col
+        };
+        let home: Vec<usize> = tiles.iter().map(|&tile| goal_cell(tile)).collect();
+        let mut distances: HashMap<(Vec<usize>, usize), u32> = HashMap::new();
+        let mut queue = BinaryHeap::from([Reverse((0, home, goal_cell(0)))]);
+
+        while let Some(Reverse((cost, positions, blank))) = queue.pop() {
+            if distances.contains_key(&(positions.clone(), blank)) {
+                continue;
+            }
+            distances.insert((positions.clone(), blank), cost);
+
+            for neighbour in neighbours(rows, cols, blank) {
+                let mut next = positions.clone();
+                let step = match next.iter().position(|&cell| cell == neighbour) {
+                    Some(moved) => {
+                        next[moved] = blank;
+                        1
+                    }
+                    None => 0,
+                };
+                queue.push(Reverse((cost + step, next, neighbour)));
+            }
+        }
+
+        let mut costs: HashMap<Vec<usize>, u32> = HashMap::new();
+        for ((positions, _), cost) in distances {
+            let best = costs.entry(positions).or_insert(cost);
+            *best = (*best).min(cost);
+        }
+        costs
+    }
+
+    #[test]
+    fn a_three_tile_group_matches_a_brute_force_search() {
+        let tiles = [1, 2, 5];
+        let database = PatternDatabase::generate(4, 4, &[&tiles], |_| {});
+        let expected = brute_force(4, 4, &tiles);
+
+        assert_eq!(expected.len(), placements(16, 3));
+        let group = &database.groups[0];
+        for (positions, cost) in expected {
+            assert_eq!(
+                u32::from(group.costs[rank(16, &positions)]),
+                cost,
+                "tiles at {positions:?}"
+            );
+        }
+        assert!(group.costs.iter().any(|&cost| cost > 10));
+    }
+
+    #[test]
+    fn saved_databases_load_and_damaged_ones_are_refused() {
+        let database = PatternDatabase::generate(3, 3, &[&[1, 2, 3, 4], &[5, 6, 7, 8]], |_| {});
+        let bytes = database.to_bytes();
+        assert_eq!(PatternDatabase::from_bytes(&bytes), Ok(database));
+
+        let mut flipped = bytes.clone();
+        flipped[20] ^= 1;
+        assert!(PatternDatabase::from_bytes(&flipped).is_err());
+        assert!(PatternDatabase::from_bytes(&bytes[..bytes.len() / 2]).is_err());
+        assert!(PatternDatabase::from_bytes(b"PDB1").is_err());
+
+        // A header of a size no board has is refused before anything is
+        // counted from it, even with the checksum to match
+        for (rows, cols) in [(0, 3), (3, 1), (200, 200)] {
+            let mut resized = bytes[..bytes.len() - 4].to_vec();
+            resized[MAGIC.len()] = rows;
+            resized[MAGIC.len() + 1] = cols;
+            resized.extend(checksum(&resized).to_le_bytes());
+            assert_eq!(
+                PatternDatabase::from_bytes(&resized),
+                Err(String::from("corrupt pattern database: no such board size"))
+            );
+        }
+    }
+
+    #[test]
+    fn generation_reports_progress_up_to_the_end() {
+        let mut reported = Vec::new();
+        PatternDatabase::generate(3, 3, &[&[1, 2, 3, 4]], |share| reported.push(share));
+
+        assert_eq!(reported.last(), Some(&1.0));
+        assert!(reported.windows(2).all(|pair| pair[0] <= pair[1]));
+    }
+}
@@ -0,0 +1,145 @@
+//! Players sharing a machine, each with their own settings, records,
+//! achievements and saved games.
+//!
+//! The list of profiles is shared, as is the leaderboard, on which players
+//! meet. Everything else a profile plays is kept apart in its
+//! [`dir`](Profile::dir), a subdirectory of the data directory.
+
+use crate::{ProfileError, storage::Versioned};
+
+use serde::{Deserialize, Serialize};
+
+/// The most characters in a profile's name.
+pub const MAX_PROFILE_NAME: usize = 24;
+
+/// The name of the profile made for whoever played before there were
+/// profiles, when they had never entered a name.
+pub const DEFAULT_PROFILE_NAME: &str = "Player";
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Profile {
+    /// Never reused, so a new profile with an old name starts afresh.
+    pub id: u32,
+    pub name: String,
+}
+
+impl Profile {
+    /// The subdirectory of the data directory this profile's documents are
+    /// kept in.
+    pub fn dir(&self) -> String {
+        format!("profiles/{}", self.id)
+    }
+}
+
+/// Every profile on this machine, in the order they were made, and which one
+/// is playing.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Profiles {
+    profiles: Vec<Profile>,
+    active: Option<u32>,
+    next_id: u32,
+}
+
+impl Versioned for Profiles {
+    const VERSION: u32 = 1;
+}
+
+impl Profiles {
+    pub fn all(&self) -> &[Profile] {
+        &self.profiles
+    }
+
+    /// The profile playing, which is the first one if none was picked.
+    pub fn active(&self) -> Option<&Profile> {
+        self.active
+            .and_then(|id| self.get(id))
+            .or_else(|| self.profiles.first())
+    }
+
+    pub fn get(&self, id: u32) -> Option<&Profile> {
+        self.profiles.iter().find(|profile| profile.id == id)
+    }
+
+    /// Makes the profile `id` the one playing, if there is one.
+    pub fn select(&mut self, id: u32) -> bool {
+        let found = self.get(id).is_some();
+        if found {
+            self.active = Some(id);
+        }
+        found
+    }
+
+    /// Adds a profile called `name`, trimmed, without making it the one
+    /// playing.
+    pub fn create(&mut self, name: &str) -> Result<&Profile, ProfileError> {
+        let name = name.trim();
+        if name.is_empty() {
+            return Err(ProfileError::EmptyName);
+        }
+        if name.chars().count() > MAX_PROFILE_NAME {
+            return Err(ProfileError::NameTooLong);
+        }
+        if self
+            .profiles
+            .iter()
+            .any(|profile| profile.name.to_lowercase() == name.to_lowercase())
+        {
+            return Err(ProfileError::NameTaken);
+        }
+
+        // Ids count from 1, leaving 0 for a document without any
+        self.next_id = self.next_id.max(1);
+        self.profiles.push(Profile {
+            id: self.next_id,
+            name: String::from(name),
+        });
+        self.next_id += 1;
+        Ok(self.profiles.last().expect("just pushed"))
+    }
+
+    /// Takes the profile `id` off the list, unless it is the one playing,
+    /// which would leave nobody to play.
+    pub fn remove(&mut self, id: u32) -> Option<Profile> {
+        if self.active().is_some_and(|active| active.id == id) {
+            return None;
+        }
+        let index = self.profiles.iter().position(|profile| profile.id == id)?;
+        Some(self.profiles.remove(index))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn profiles_are_made_under_fresh_ids_and_names() {
+        let mut profiles = Profiles::default();
+        assert_eq!(profiles.active(), None);
+
+        let first = profiles.create("  Jo ").unwrap().clone();
+        assert_eq!((first.id, first.name.as_str()), (1, "Jo"));
+        assert_eq!(first.dir(), "profiles/1");
+        // The first profile plays until another is picked
+        assert_eq!(profiles.active(), Some(&first));
+
+        assert_eq!(profiles.create("  "), Err(ProfileError::EmptyName));
+        assert_eq!(profiles.create("JO"), Err(ProfileError::NameTaken));
+        assert_eq!(
+            profiles.create(&"x".repeat(MAX_PROFILE_NAME + 1)),
+            Err(ProfileError::NameTooLong)
+        );
+
+        let kid = profiles.create("Kid").unwrap().id;
+        assert!(profiles.select(kid));
+        assert!(!profiles.select(99));
+        assert_eq!(profiles.active().unwrap().name, "Kid");
+
+        // The one playing stays; the other goes, and its id is not reused
+        assert_eq!(profiles.remove(kid), None);
+        assert_eq!(profiles.remove(first.id), Some(first));
+        assert_eq!(profiles.create("Jo").unwrap().id, 3);
+        assert_eq!(profiles.all().len(), 2);
+    }
+}
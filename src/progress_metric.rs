@@ -0,0 +1,25 @@
+use crate::Puzzle;
+
+use serde::{Deserialize, Serialize};
+
+/// How the progress bar under the board measures how close a solve is.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum ProgressMetric {
+    /// The share of tiles in their goal cell.
+    #[default]
+    TilesInPlace,
+    /// How much of the scramble's Manhattan distance has been made up, which
+    /// drops less when placed tiles step aside to let others past.
+    Distance,
+}
+
+impl ProgressMetric {
+    /// How far along `puzzle` is, from 0 to 1, reaching 1 exactly when it is
+    /// solved.
+    pub fn progress(self, puzzle: &Puzzle) -> f32 {
+        match self {
+            ProgressMetric::TilesInPlace => puzzle.placed_fraction(),
+            ProgressMetric::Distance => puzzle.distance_progress(),
+        }
+    }
+}
@@ -0,0 +1,2290 @@
+use crate::label_mode::{self, LabelMode, label_for};
+use crate::{BoardError, Difficulty, GameMode, Goal, NotationError, SlideCount, goal_position};
+
+use rand::{Rng, SeedableRng, rngs::StdRng, seq::SliceRandom};
+use serde::{Deserialize, Serialize};
+
+use std::time::Duration;
+
+pub const DEFAULT_GRID_SIZE: usize = 4;
+pub const MIN_GRID_SIZE: usize = 2;
+pub const MAX_GRID_SIZE: usize = 10;
+
+/// The most cells a board can have, so that every tile number fits a `u16`.
+pub const MAX_CELLS: usize = u16::MAX as usize + 1;
+
+/// The direction a tile slides into the empty slot.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum Direction {
+    Up,
+    Down,
+    Left,
+    Right,
+}
+
+impl Direction {
+    pub const ALL: [Direction; 4] = [
+        Direction::Up,
+        Direction::Down,
+        Direction::Left,
+        Direction::Right,
+    ];
+
+    /// The letter for this move in U/D/L/R notation.
+    pub fn notation(self) -> char {
+        match self {
+            Direction::Up => 'U',
+            Direction::Down => 'D',
+            Direction::Left => 'L',
+            Direction::Right => 'R',
+        }
+    }
+
+    /// Parses a U/D/L/R notation letter, in either case.
+    pub fn from_notation(letter: char) -> Option<Self> {
+        match letter.to_ascii_uppercase() {
+            'U' => Some(Direction::Up),
+            'D' => Some(Direction::Down),
+            'L' => Some(Direction::Left),
+            'R' => Some(Direction::Right),
+            _ => None,
+        }
+    }
+
+    pub fn opposite(self) -> Self {
+        match self {
+            Direction::Up => Direction::Down,
+            Direction::Down => Direction::Up,
+            Direction::Left => Direction::Right,
+            Direction::Right => Direction::Left,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Tile {
+    value: Option<u16>, // None represents the empty tile
+}
+
+impl Tile {
+    pub fn empty() -> Self {
+        Self { value: None }
+    }
+
+    /// A numbered tile. `0` is reserved for the blank in board notation, so
+    /// use [`Tile::empty`] for that instead.
+    pub fn new(value: u16) -> Self {
+        debug_assert_ne!(value, 0, "tile 0 is the blank, use Tile::empty");
+        Self { value: Some(value) }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.value.is_none()
+    }
+
+    /// The number printed on the tile, or `None` for the empty tile.
+    pub fn value(&self) -> Option<u16> {
+        self.value
+    }
+}
+
+/// A tile the player is dragging by hand towards the empty cell next to it.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Drag {
+    tile: (usize, usize),
+    direction: Direction, // The way the tile slides
+    progress: f32,        // How far it has travelled, from 0 to a whole cell
+}
+
+impl Drag {
+    /// The cell the dragged tile started in.
+    pub fn tile(&self) -> (usize, usize) {
+        self.tile
+    }
+
+    pub fn direction(&self) -> Direction {
+        self.direction
+    }
+
+    /// The fraction of a cell the tile has travelled, from `0.0` to `1.0`.
+    pub fn progress(&self) -> f32 {
+        self.progress
+    }
+}
+
+/// A single applied move, recorded for undo and redo.
+///
+/// A line slide shifts every tile between `from` and `to` one cell towards
+/// `to` and is recorded as one step.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+struct Step {
+    from: (usize, usize), // Where the farthest tile was before sliding
+    to: (usize, usize),   // The empty cell the line slid into
+    #[serde(default = "Step::single")]
+    counted: u32, // What the step added to the move counter
+}
+
+impl Step {
+    fn single() -> u32 {
+        1
+    }
+
+    /// Number of tiles the step shifted.
+    fn len(self) -> usize {
+        self.from.0.abs_diff(self.to.0) + self.from.1.abs_diff(self.to.1)
+    }
+
+    /// The way the tile slid.
+    fn direction(self) -> Direction {
+        if self.to.0 < self.from.0 {
+            Direction::Up
+        } else if self.to.0 > self.from.0 {
+            Direction::Down
+        } else if self.to.1 < self.from.1 {
+            Direction::Left
+        } else {
+            Direction::Right
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(into = "PuzzleData", try_from = "PuzzleData")]
+pub struct Puzzle {
+    rows: usize,
+    cols: usize,
+    tiles: Vec<Tile>,      // Row-major, `rows * cols` entries
+    empty: (usize, usize), // Cached position of the empty tile
+    moves: u32,
+    undo_stack: Vec<Step>,
+    redo_stack: Vec<Step>,
+    seed: Option<u64>,          // Seed of the current scramble, if any
+    initial: Option<Vec<Tile>>, // Position right after the last scramble
+    difficulty: Difficulty,
+    goal: Goal,
+    wrap: bool, // Whether tiles slide across the edges
+    mode: GameMode,
+    blindfold: bool,   // Whether tile labels hide once the preview is over
+    preview: Duration, // Preview time left before a blindfold hides the labels
+    last_moved: Option<(usize, usize)>, // Where the tile that moved last ended up
+    drag: Option<Drag>, // Not saved; a dragged tile snaps back on reload
+    rng: StdRng,       // Drives `shuffle`, seeded for reproducible scrambles
+}
+
+impl Default for Puzzle {
+    fn default() -> Self {
+        Self::new(DEFAULT_GRID_SIZE, DEFAULT_GRID_SIZE)
+    }
+}
+
+impl Puzzle {
+    /// Creates a solved board with the given number of rows and columns.
+    ///
+    /// # Panics
+    ///
+    /// If the board would have more than [`MAX_CELLS`] cells.
+    pub fn new(rows: usize, cols: usize) -> Self {
+        Self::with_rng(rows, cols, StdRng::from_entropy())
+    }
+
+    /// Creates a solved board whose shuffles are fully determined by `seed`.
+    pub fn with_seed(rows: usize, cols: usize, seed: u64) -> Self {
+        Self::with_rng(rows, cols, StdRng::seed_from_u64(seed))
+    }
+
+    fn with_rng(rows: usize, cols: usize, rng: StdRng) -> Self {
+        assert!(
+            rows * cols <= MAX_CELLS,
+            "a {cols}x{rows} board has more tiles than fit a u16"
+        );
+
+        Self {
+            rows,
+            cols,
+            tiles: Self::solved_tiles(rows, cols, &Goal::default()),
+            empty: goal_position(0, (rows, cols), &Goal::default()),
+            moves: 0,
+            undo_stack: Vec::new(),
+            redo_stack: Vec::new(),
+            seed: None,
+            initial: None,
+            difficulty: Difficulty::default(),
+            goal: Goal::default(),
+            wrap: false,
+            mode: GameMode::default(),
+            blindfold: false,
+            preview: Duration::ZERO,
+            last_moved: None,
+            drag: None,
+            rng,
+        }
+    }
+
+    /// Builds a square board from row-major tile numbers, `0` being the blank.
+    ///
+    /// Every value in `0..n * n` must appear exactly once. The position need
+    /// not be solvable; check [`is_solvable`](Self::is_solvable) before
+    /// expecting a solution. It becomes the scramble that
+    /// [`retry`](Self::retry) returns to.
+    pub fn from_permutation(values: &[u16]) -> Result<Puzzle, BoardError> {
+        let side = values.len().isqrt();
+        if side * side != values.len() || !(MIN_GRID_SIZE..=MAX_GRID_SIZE).contains(&side) {
+            return Err(BoardError::NotSquare(values.len()));
+        }
+
+        Self::from_grid(side, side, values)
+    }
+
+    /// Like [`from_permutation`](Self::from_permutation), for a board of any
+    /// supported size.
+    pub fn from_grid(rows: usize, cols: usize, values: &[u16]) -> Result<Puzzle, BoardError> {
+        if !(MIN_GRID_SIZE..=MAX_GRID_SIZE).contains(&rows)
+            || !(MIN_GRID_SIZE..=MAX_GRID_SIZE).contains(&cols)
+        {
+            return Err(BoardError::InvalidDimensions { rows, cols });
+        }
+
+        let mut puzzle = Self::new(rows, cols);
+        puzzle.tiles = tiles_from_values(rows * cols, values)?;
+        puzzle.empty = puzzle.find_empty().expect("validated boards have a blank");
+        puzzle.start_scramble();
+        Ok(puzzle)
+    }
+
+    /// Parses tile numbers separated by any mix of whitespace, commas and `/`
+    /// row separators, e.g. `"1 2 / 3 0"`. Rows of one length, on lines of
+    /// their own or between `/`s, give the board its shape; anything else
+    /// has to make a square board, built with
+    /// [`from_permutation`](Self::from_permutation).
+    ///
+    /// The blank may be written `_` as well as `0`, and anything after a `#`
+    /// on a line is a comment, so [`board_text`](Self::board_text) reads back
+    /// in as it was copied.
+    pub fn parse_permutation(input: &str) -> Result<Puzzle, BoardError> {
+        Self::parse_permutation_in(input, LabelMode::Numbers)
+    }
+
+    /// Like [`parse_permutation`](Self::parse_permutation), but also reads
+    /// tiles labelled in `mode`, so [`board_text_in`](Self::board_text_in)
+    /// reads back in the mode it was written in.
+    pub fn parse_permutation_in(input: &str, mode: LabelMode) -> Result<Puzzle, BoardError> {
+        let rows: Vec<Vec<&str>> = input
+            .lines()
+            .flat_map(|line| line.split('#').next().unwrap_or_default().split('/'))
+            .map(|row| {
+                row.split(|c: char| c.is_whitespace() || c == ',')
+                    .filter(|token| !token.is_empty())
+                    .collect::<Vec<_>>()
+            })
+            .filter(|row| !row.is_empty())
+            .collect();
+        let tokens: Vec<&str> = rows.concat();
+        let values = label_mode::parse_values(&tokens, mode)?;
+
+        match rows.as_slice() {
+            [first, rest @ ..]
+                if !rest.is_empty() && rest.iter().all(|row| row.len() == first.len()) =>
+            {
+                Self::from_grid(rows.len(), first.len(), &values)
+            }
+            _ => Self::from_permutation(&values),
+        }
+    }
+
+    /// The board as text to share: a line of numbers per row, aligned, with
+    /// `_` for the blank, then the move count as a comment.
+    ///
+    /// [`parse_permutation`](Self::parse_permutation) reads it back, whatever
+    /// the board's shape.
+    pub fn board_text(&self) -> String {
+        self.board_text_in(LabelMode::Numbers)
+    }
+
+    /// Like [`board_text`](Self::board_text), with the tiles labelled in
+    /// `mode`.
+    pub fn board_text_in(&self, mode: LabelMode) -> String {
+        let mode = mode.for_cells(self.tiles.len());
+        let width = label_for(self.tiles.len() as u16 - 1, mode).len();
+        let mut text = String::new();
+
+        for row in self.tiles.chunks(self.cols) {
+            let cells: Vec<String> = row
+                .iter()
+                .map(|tile| match tile.value() {
+                    Some(value) => format!("{:>width$}", label_for(value, mode)),
+                    None => format!("{:>width$}", "_"),
+                })
+                .collect();
+            text.push_str(&cells.join(" "));
+            text.push('\n');
+        }
+
+        text.push_str(&format!("# Moves: {}\n", self.moves));
+        text
+    }
+
+    fn solved_tiles(rows: usize, cols: usize, goal: &Goal) -> Vec<Tile> {
+        goal.layout(rows, cols)
+            .into_iter()
+            .map(|value| match value {
+                0 => Tile::empty(),
+                value => Tile::new(value),
+            })
+            .collect()
+    }
+
+    pub fn rows(&self) -> usize {
+        self.rows
+    }
+
+    pub fn cols(&self) -> usize {
+        self.cols
+    }
+
+    /// The number of moves made since the last shuffle.
+    pub fn moves(&self) -> u32 {
+        self.moves
+    }
+
+    /// The seed that reproduces the current scramble via [`Puzzle::shuffle_with_seed`].
+    pub fn seed(&self) -> Option<u64> {
+        self.seed
+    }
+
+    pub fn difficulty(&self) -> Difficulty {
+        self.difficulty
+    }
+
+    /// Sets the difficulty used by subsequent shuffles.
+    pub fn set_difficulty(&mut self, difficulty: Difficulty) {
+        self.difficulty = difficulty;
+    }
+
+    pub fn goal(&self) -> &Goal {
+        &self.goal
+    }
+
+    /// Sets the arrangement that counts as solved, leaving the tiles where
+    /// they are.
+    pub fn set_goal(&mut self, goal: Goal) {
+        self.goal = goal;
+    }
+
+    pub fn wrap(&self) -> bool {
+        self.wrap
+    }
+
+    /// Makes the board toroidal: a tile on one edge counts as next to an
+    /// empty slot on the opposite edge and slides around to it. Line slides
+    /// are disabled while wrapping. The undo history is cleared, since its
+    /// moves may not replay under the new rules.
+    pub fn set_wrap(&mut self, wrap: bool) {
+        self.wrap = wrap;
+        self.undo_stack.clear();
+        self.redo_stack.clear();
+    }
+
+    pub fn mode(&self) -> GameMode {
+        self.mode
+    }
+
+    pub fn set_mode(&mut self, mode: GameMode) {
+        self.mode = mode;
+    }
+
+    /// Takes `elapsed` off a time attack's clock, returning whether it has
+    /// run out. Casual games never run out.
+    pub fn count_down(&mut self, elapsed: Duration) -> bool {
+        if let GameMode::TimeAttack { remaining } = &mut self.mode {
+            *remaining = remaining.saturating_sub(elapsed);
+        }
+        self.is_out_of_time()
+    }
+
+    /// Whether a time attack's clock has reached zero, locking the board.
+    pub fn is_out_of_time(&self) -> bool {
+        self.mode.remaining() == Some(Duration::ZERO)
+    }
+
+    /// The moves a move-limit challenge has left before it fails.
+    pub fn moves_left(&self) -> Option<u32> {
+        self.mode
+            .budget()
+            .map(|budget| budget.saturating_sub(self.moves))
+    }
+
+    /// Whether a move-limit challenge has used up its budget short of the
+    /// goal, locking the board.
+    pub fn is_out_of_moves(&self) -> bool {
+        self.moves_left() == Some(0) && !self.is_solved()
+    }
+
+    /// Whether the board takes no moves under the current mode: a challenge
+    /// has failed, or its move budget is still being worked out.
+    pub fn is_locked(&self) -> bool {
+        self.is_out_of_time()
+            || self.is_out_of_moves()
+            || self.mode == GameMode::MoveLimit { budget: None }
+    }
+
+    pub fn is_blindfold(&self) -> bool {
+        self.blindfold
+    }
+
+    /// Turns blindfold play on or off. A blindfolded board shows its labels
+    /// for a preview period, then hides them until it is solved.
+    pub fn set_blindfold(&mut self, blindfold: bool) {
+        self.blindfold = blindfold;
+    }
+
+    pub fn preview_left(&self) -> Duration {
+        self.preview
+    }
+
+    /// Sets how long a blindfold keeps the labels visible from now.
+    pub fn set_preview(&mut self, preview: Duration) {
+        self.preview = preview;
+    }
+
+    /// Takes `elapsed` off the blindfold preview.
+    pub fn count_down_preview(&mut self, elapsed: Duration) {
+        self.preview = self.preview.saturating_sub(elapsed);
+    }
+
+    /// Whether a blindfold is still showing its preview.
+    pub fn is_previewing(&self) -> bool {
+        self.blindfold && !self.preview.is_zero()
+    }
+
+    /// Whether the tile labels should be hidden: a blindfold is on, its
+    /// preview is over, and the board is not yet solved.
+    pub fn is_hidden(&self) -> bool {
+        self.blindfold && self.preview.is_zero() && !self.is_solved()
+    }
+
+    /// Returns to the goal's solved position, forgetting the scramble and
+    /// the move history.
+    pub fn reset(&mut self) {
+        self.tiles = Self::solved_tiles(self.rows, self.cols, &self.goal);
+        self.empty = goal_position(0, (self.rows, self.cols), &self.goal);
+        self.moves = 0;
+        self.undo_stack.clear();
+        self.redo_stack.clear();
+        self.seed = None;
+        self.initial = None;
+        self.last_moved = None;
+    }
+
+    /// Row-major index of the cell where `value` belongs, `0` being the blank.
+    fn goal_index(&self, value: u16) -> usize {
+        let (row, col) = goal_position(value, (self.rows, self.cols), &self.goal);
+        row * self.cols + col
+    }
+
+    pub fn tile(&self, row: usize, col: usize) -> &Tile {
+        &self.tiles[row * self.cols + col]
+    }
+
+    /// All tiles in row-major order.
+    pub fn tiles(&self) -> &[Tile] {
+        &self.tiles
+    }
+
+    /// The `(row, col)` of the empty tile.
+    pub fn empty_position(&self) -> (usize, usize) {
+        debug_assert_eq!(self.find_empty(), Some(self.empty));
+        self.empty
+    }
+
+    /// Scans the board for the empty tile, bypassing the cached position.
+    pub fn find_empty(&self) -> Option<(usize, usize)> {
+        self.tiles
+            .iter()
+            .position(Tile::is_empty)
+            .map(|index| (index / self.cols, index % self.cols))
+    }
+
+    pub fn is_adjacent_to_empty(&self, row: usize, col: usize) -> bool {
+        let (empty_row, empty_col) = self.empty_position();
+
+        // Check if the tile is adjacent to the empty tile
+        (row == empty_row && (col as isize - empty_col as isize).abs() == 1)
+            || (col == empty_col && (row as isize - empty_row as isize).abs() == 1)
+            || self.is_across_edge(row, col)
+    }
+
+    /// Whether `(row, col)` touches the empty slot only by wrapping around
+    /// the board. Never true along a dimension of 2, where the cells on
+    /// either side of the blank are the same one.
+    fn is_across_edge(&self, row: usize, col: usize) -> bool {
+        let (empty_row, empty_col) = self.empty;
+
+        self.wrap
+            && ((row == empty_row && self.cols > 2 && col.abs_diff(empty_col) == self.cols - 1)
+                || (col == empty_col && self.rows > 2 && row.abs_diff(empty_row) == self.rows - 1))
+    }
+
+    /// Whether `(row, col)` shares a row or column with the empty slot,
+    /// without being the empty slot itself.
+    pub fn is_in_line_with_empty(&self, row: usize, col: usize) -> bool {
+        let (empty_row, empty_col) = self.empty_position();
+
+        row < self.rows && col < self.cols && (row == empty_row) != (col == empty_col)
+    }
+
+    /// Slides the tile at `(row, col)` into the empty slot if they are adjacent.
+    ///
+    /// Returns whether the tile moved.
+    pub fn move_tile(&mut self, row: usize, col: usize) -> bool {
+        if !self.is_adjacent_to_empty(row, col) {
+            return false;
+        }
+
+        self.push_step((row, col), 1);
+        true
+    }
+
+    /// Shifts every tile from `(row, col)` up to the empty slot one cell
+    /// towards it, as when pushing a run of tiles on a physical puzzle.
+    ///
+    /// The slide undoes as a single step and adds one move per tile shifted,
+    /// or just one with [`SlideCount::PerSlide`]. For a tile next to the empty
+    /// slot, and on a [wrapping](Self::set_wrap) board, this is the same as
+    /// [`move_tile`](Self::move_tile). Returns whether anything moved.
+    pub fn move_line(&mut self, row: usize, col: usize, count: SlideCount) -> bool {
+        if self.wrap {
+            return self.move_tile(row, col);
+        }
+        if !self.is_in_line_with_empty(row, col) {
+            return false;
+        }
+
+        let counted = match count {
+            SlideCount::PerTile => {
+                let (empty_row, empty_col) = self.empty;
+                (row.abs_diff(empty_row) + col.abs_diff(empty_col)) as u32
+            }
+            SlideCount::PerSlide => 1,
+        };
+        self.push_step((row, col), counted);
+        true
+    }
+
+    /// Applies and records a slide from `from`, which must be in line with the
+    /// empty slot.
+    fn push_step(&mut self, from: (usize, usize), counted: u32) {
+        let step = Step {
+            from,
+            to: self.empty,
+            counted,
+        };
+        self.slide(from.0, from.1);
+        self.moves += counted;
+        self.undo_stack.push(step);
+        self.redo_stack.clear();
+    }
+
+    pub fn can_undo(&self) -> bool {
+        !self.undo_stack.is_empty()
+    }
+
+    pub fn can_redo(&self) -> bool {
+        !self.redo_stack.is_empty()
+    }
+
+    /// Moves the most recently slid tile back, returning whether there was a
+    /// move to undo.
+    pub fn undo(&mut self) -> bool {
+        let Some(step) = self.undo_stack.pop() else {
+            return false;
+        };
+
+        self.slide(step.to.0, step.to.1);
+        self.moves = self.moves.saturating_sub(step.counted);
+        self.redo_stack.push(step);
+        true
+    }
+
+    /// Reapplies the most recently undone move, returning whether there was a
+    /// move to redo.
+    pub fn redo(&mut self) -> bool {
+        let Some(step) = self.redo_stack.pop() else {
+            return false;
+        };
+
+        self.slide(step.from.0, step.from.1);
+        self.moves += step.counted;
+        self.undo_stack.push(step);
+        true
+    }
+
+    /// Shifts the tiles from `(row, col)` up to the empty tile one cell
+    /// towards it without any bookkeeping, leaving `(row, col)` empty.
+    fn slide(&mut self, row: usize, col: usize) {
+        // The tile next to the blank fills it
+        if self.empty != (row, col) {
+            self.last_moved = Some(self.empty);
+        }
+
+        // A tile across the edge jumps straight to the other side
+        if self.is_across_edge(row, col) {
+            let (empty_row, empty_col) = self.empty;
+            self.tiles
+                .swap(row * self.cols + col, empty_row * self.cols + empty_col);
+            self.empty = (row, col);
+            return;
+        }
+
+        // Walk the blank towards the target one swap at a time
+        while self.empty != (row, col) {
+            let (empty_row, empty_col) = self.empty;
+            let step = |from: usize, to: usize| match to.cmp(&from) {
+                std::cmp::Ordering::Less => from - 1,
+                std::cmp::Ordering::Equal => from,
+                std::cmp::Ordering::Greater => from + 1,
+            };
+            let next = (step(empty_row, row), step(empty_col, col));
+
+            self.tiles.swap(
+                next.0 * self.cols + next.1,
+                empty_row * self.cols + empty_col,
+            );
+            self.empty = next;
+        }
+    }
+
+    /// Slides the tile next to the empty slot in `direction`, ignoring presses
+    /// at the grid edge or on a solved board.
+    pub fn move_direction(&mut self, direction: Direction) -> bool {
+        !self.is_solved() && self.apply_move(direction)
+    }
+
+    /// Slides the tile next to the empty slot in `direction`, solved board or
+    /// not, as when replaying a recorded sequence. Returns whether it moved.
+    pub fn apply_move(&mut self, direction: Direction) -> bool {
+        match self.tile_for_direction(direction) {
+            Some((row, col)) => self.move_tile(row, col),
+            None => false,
+        }
+    }
+
+    /// The way the tile at `(row, col)` would travel if
+    /// [`move_line`](Self::move_line) pushed it, or `None` if it cannot move.
+    pub fn line_direction(&self, row: usize, col: usize) -> Option<Direction> {
+        if self.wrap {
+            return Direction::ALL
+                .into_iter()
+                .find(|&direction| self.tile_for_direction(direction) == Some((row, col)));
+        }
+        if !self.is_in_line_with_empty(row, col) {
+            return None;
+        }
+
+        let (empty_row, empty_col) = self.empty;
+        Some(if empty_row < row {
+            Direction::Up
+        } else if empty_row > row {
+            Direction::Down
+        } else if empty_col < col {
+            Direction::Left
+        } else {
+            Direction::Right
+        })
+    }
+
+    /// The cell of the tile that would slide into the empty slot when moving
+    /// in `direction`, or `None` at the grid edge of a board that does not
+    /// wrap.
+    pub fn tile_for_direction(&self, direction: Direction) -> Option<(usize, usize)> {
+        let (empty_row, empty_col) = self.empty_position();
+        let (last_row, last_col) = (self.rows - 1, self.cols - 1);
+        let wraps = |length: usize| self.wrap && length > 2;
+
+        // The tile that slides is on the opposite side of the empty slot,
+        // or at the far edge when that side wraps around
+        match direction {
+            Direction::Up if empty_row == last_row => wraps(self.rows).then_some((0, empty_col)),
+            Direction::Up => Some((empty_row + 1, empty_col)),
+            Direction::Down if empty_row == 0 => wraps(self.rows).then_some((last_row, empty_col)),
+            Direction::Down => Some((empty_row - 1, empty_col)),
+            Direction::Left if empty_col == last_col => wraps(self.cols).then_some((empty_row, 0)),
+            Direction::Left => Some((empty_row, empty_col + 1)),
+            Direction::Right if empty_col == 0 => wraps(self.cols).then_some((empty_row, last_col)),
+            Direction::Right => Some((empty_row, empty_col - 1)),
+        }
+    }
+
+    /// Picks up the tile at `(row, col)` to drag it, which only works for a
+    /// tile right beside the empty cell on a board still being solved.
+    /// Returns whether the drag started.
+    pub fn start_drag(&mut self, row: usize, col: usize) -> bool {
+        if self.is_solved() || self.is_across_edge(row, col) {
+            return false;
+        }
+
+        self.drag = Direction::ALL
+            .into_iter()
+            .find(|&direction| self.tile_for_direction(direction) == Some((row, col)))
+            .map(|direction| Drag {
+                tile: (row, col),
+                direction,
+                progress: 0.0,
+            });
+        self.drag.is_some()
+    }
+
+    /// The tile being dragged, unless a move since has taken it out of
+    /// reach of the empty cell.
+    pub fn drag(&self) -> Option<Drag> {
+        self.drag
+            .filter(|drag| self.tile_for_direction(drag.direction) == Some(drag.tile))
+    }
+
+    /// Moves the dragged tile `progress` of a cell towards the empty cell,
+    /// clamped between its own cell and the empty one.
+    pub fn drag_to(&mut self, progress: f32) {
+        if let Some(drag) = &mut self.drag {
+            drag.progress = progress.clamp(0.0, 1.0);
+        }
+    }
+
+    /// Lets go of the dragged tile, which slides into the empty cell if it
+    /// made it at least halfway and snaps back otherwise. Returns whether
+    /// it moved.
+    pub fn end_drag(&mut self) -> bool {
+        match self.drag().filter(|drag| drag.progress >= 0.5) {
+            Some(drag) => {
+                self.cancel_drag();
+                self.move_tile(drag.tile.0, drag.tile.1)
+            }
+            None => {
+                self.cancel_drag();
+                false
+            }
+        }
+    }
+
+    /// Drops the dragged tile back into its own cell.
+    pub fn cancel_drag(&mut self) {
+        self.drag = None;
+    }
+
+    /// Scrambles the board according to the current difficulty and resets the
+    /// move counter.
+    pub fn shuffle(&mut self) {
+        let seed = self.rng.r#gen();
+        self.shuffle_with_seed(seed);
+    }
+
+    /// Scrambles the board deterministically: the same seed, dimensions and
+    /// difficulty always produce the same position.
+    ///
+    /// The result is never solved and always differs from the board before
+    /// the shuffle; unlucky scrambles are redrawn from the same RNG.
+    pub fn shuffle_with_seed(&mut self, seed: u64) {
+        let mut rng = StdRng::seed_from_u64(seed);
+        let previous = self.tiles.clone();
+
+        loop {
+            match self.difficulty {
+                Difficulty::Easy => {
+                    let steps = rng.gen_range(10..=20_u32);
+                    self.scramble_walk(steps as usize, &mut rng);
+                }
+                Difficulty::Medium => self.scramble_walk(50, &mut rng),
+                Difficulty::Hard => self.scramble_uniform(&mut rng),
+                Difficulty::Expert => self.scramble_expert(&mut rng),
+            }
+
+            if !self.is_solved() && self.tiles != previous {
+                break;
+            }
+        }
+        self.seed = Some(seed);
+    }
+
+    /// Resets to the solved position and makes `steps` random valid moves,
+    /// never immediately undoing the previous one.
+    pub fn scramble_walk(&mut self, steps: usize, rng: &mut impl Rng) {
+        self.tiles = Self::solved_tiles(self.rows, self.cols, &self.goal);
+        self.empty = goal_position(0, (self.rows, self.cols), &self.goal);
+
+        let mut previous_empty = None;
+
+        for _ in 0..steps {
+            let candidates: Vec<_> = self
+                .movable_tiles()
+                .into_iter()
+                .filter(|&cell| Some(cell) != previous_empty)
+                .collect();
+
+            previous_empty = Some(self.empty);
+            // Drawn as a `u32`, as a `usize` would be drawn differently on
+            // 32-bit platforms and a seed would give another board there
+            let (row, col) = candidates[rng.gen_range(0..candidates.len() as u32) as usize];
+            self.slide(row, col);
+        }
+
+        self.start_scramble();
+    }
+
+    /// Records the current position as a fresh scramble, clearing the move
+    /// counter, undo history and seed.
+    fn start_scramble(&mut self) {
+        self.moves = 0;
+        self.undo_stack.clear();
+        self.redo_stack.clear();
+        self.seed = None;
+        self.initial = Some(self.tiles.clone());
+        self.last_moved = None;
+    }
+
+    /// Whether the board has been scrambled since it was created.
+    pub fn is_scrambled(&self) -> bool {
+        self.initial.is_some()
+    }
+
+    /// Whether [`Puzzle::retry`] would change anything: the board has been
+    /// scrambled and has since moved away from the starting position.
+    pub fn can_retry(&self) -> bool {
+        self.initial
+            .as_ref()
+            .is_some_and(|initial| *initial != self.tiles || self.moves > 0)
+    }
+
+    /// Restores the position from right after the last scramble and clears
+    /// the move counter and undo history.
+    pub fn retry(&mut self) -> bool {
+        let Some(initial) = self.initial.clone() else {
+            return false;
+        };
+
+        self.tiles = initial;
+        self.empty = self.find_empty().expect("scrambles keep the empty tile");
+        self.moves = 0;
+        self.undo_stack.clear();
+        self.redo_stack.clear();
+        self.last_moved = None;
+        true
+    }
+
+    /// Draws uniform scrambles until one is well above the average Manhattan
+    /// distance, giving up after a fixed number of attempts and keeping the
+    /// hardest board seen.
+    fn scramble_expert(&mut self, rng: &mut impl Rng) {
+        const ATTEMPTS: usize = 1000;
+
+        // Expected distance of a tile from its goal along an axis of length n
+        // is (n² - 1) / 3n, so this is 15% above a uniform board's average
+        let axis = |n: usize| (n * n - 1) as f32 / (3 * n) as f32;
+        let tiles = (self.rows * self.cols - 1) as f32;
+        let threshold = (tiles * (axis(self.rows) + axis(self.cols)) * 1.15) as u32;
+
+        let mut hardest: Option<(u32, Vec<Tile>)> = None;
+
+        for _ in 0..ATTEMPTS {
+            self.scramble_uniform(rng);
+            let distance = self.manhattan_distance();
+
+            if distance > threshold {
+                return;
+            }
+            if hardest.as_ref().is_none_or(|(best, _)| distance > *best) {
+                hardest = Some((distance, self.tiles.clone()));
+            }
+        }
+
+        if let Some((_, tiles)) = hardest {
+            // Uniform scrambles always leave the empty tile in its goal cell
+            self.tiles = tiles;
+            self.start_scramble();
+        }
+    }
+
+    /// Sum over all tiles of the row and column distance to their goal cell.
+    pub fn manhattan_distance(&self) -> u32 {
+        self.distance_of(&self.tiles)
+    }
+
+    /// The [`manhattan_distance`](Self::manhattan_distance) of `tiles` laid
+    /// out on this board.
+    fn distance_of(&self, tiles: &[Tile]) -> u32 {
+        tiles
+            .iter()
+            .enumerate()
+            .filter_map(|(index, tile)| {
+                Some(tile_distance(
+                    (self.rows, self.cols),
+                    self.wrap,
+                    self.goal_index(tile.value()?),
+                    index,
+                ))
+            })
+            .sum()
+    }
+
+    /// Number of tiles, not counting the blank, outside their goal cell.
+    pub fn misplaced_count(&self) -> u32 {
+        self.tiles
+            .iter()
+            .enumerate()
+            .filter(|(index, tile)| {
+                tile.value()
+                    .is_some_and(|value| self.goal_index(value) != *index)
+            })
+            .count() as u32
+    }
+
+    /// The share of tiles, not counting the blank, in their goal cell, which
+    /// is `1.0` exactly when the board is solved.
+    pub fn placed_fraction(&self) -> f32 {
+        let tiles = self.tiles.len() as u32 - 1;
+        (tiles - self.misplaced_count()) as f32 / tiles as f32
+    }
+
+    /// How much of the last scramble's Manhattan distance has been made up,
+    /// from `0.0` to `1.0`, which it reaches exactly when the board is
+    /// solved. A board further from solved than its scramble stays at `0.0`,
+    /// as does any unsolved board that was never scrambled.
+    pub fn distance_progress(&self) -> f32 {
+        let distance = self.manhattan_distance();
+        let start = self
+            .initial
+            .as_deref()
+            .map_or(0, |initial| self.distance_of(initial));
+
+        if distance == 0 {
+            1.0
+        } else if start == 0 {
+            0.0
+        } else {
+            (1.0 - distance as f32 / start as f32).clamp(0.0, 1.0)
+        }
+    }
+
+    /// The moves that lead from the scramble, or the last board reset, to the
+    /// current position, oldest first.
+    ///
+    /// A line slide appears once for every tile it shifted.
+    pub fn history(&self) -> Vec<Direction> {
+        self.undo_stack
+            .iter()
+            .flat_map(|step| {
+                // Wrapping boards have no line slides, so a long step is a
+                // single move around the edge, seen from the other side
+                if self.wrap && step.len() > 1 {
+                    std::iter::repeat_n(step.direction().opposite(), 1)
+                } else {
+                    std::iter::repeat_n(step.direction(), step.len())
+                }
+            })
+            .collect()
+    }
+
+    /// The [`history`](Self::history) as a U/D/L/R notation string.
+    pub fn notation(&self) -> String {
+        self.history()
+            .into_iter()
+            .map(Direction::notation)
+            .collect()
+    }
+
+    /// Replays a U/D/L/R notation string move by move, ignoring whitespace.
+    ///
+    /// The board is left untouched if any character is invalid or any move is
+    /// impossible from the position reached before it.
+    pub fn apply_notation(&mut self, notation: &str) -> Result<(), NotationError> {
+        let mut replay = self.clone();
+
+        for (position, character) in notation.chars().enumerate() {
+            if character.is_whitespace() {
+                continue;
+            }
+
+            let direction =
+                Direction::from_notation(character).ok_or(NotationError::InvalidCharacter {
+                    position,
+                    character,
+                })?;
+            if !replay.apply_move(direction) {
+                return Err(NotationError::IllegalMove {
+                    position,
+                    character,
+                });
+            }
+        }
+
+        *self = replay;
+        Ok(())
+    }
+
+    /// The cell of the tile moved most recently, by a move, undo or redo.
+    pub fn last_moved(&self) -> Option<(usize, usize)> {
+        self.last_moved
+    }
+
+    /// Whether the tile at `(row, col)` sits in its goal cell. Always false
+    /// for the blank.
+    pub fn is_tile_in_place(&self, row: usize, col: usize) -> bool {
+        self.tile(row, col)
+            .value()
+            .is_some_and(|value| self.goal_index(value) == row * self.cols + col)
+    }
+
+    /// Cells whose tile can slide into the empty slot.
+    pub fn movable_tiles(&self) -> Vec<(usize, usize)> {
+        let (empty_row, empty_col) = self.empty;
+        let mut cells = Vec::with_capacity(4);
+
+        if self.wrap {
+            return Direction::ALL
+                .into_iter()
+                .filter_map(|direction| self.tile_for_direction(direction))
+                .collect();
+        }
+
+        if empty_row > 0 {
+            cells.push((empty_row - 1, empty_col));
+        }
+        if empty_row < self.rows - 1 {
+            cells.push((empty_row + 1, empty_col));
+        }
+        if empty_col > 0 {
+            cells.push((empty_row, empty_col - 1));
+        }
+        if empty_col < self.cols - 1 {
+            cells.push((empty_row, empty_col + 1));
+        }
+        cells
+    }
+
+    /// Replaces the board with a uniformly random solvable, unsolved position.
+    ///
+    /// The empty tile is placed in its goal cell, so the board is solvable
+    /// exactly when the tile permutation is even.
+    pub fn scramble_uniform(&mut self, rng: &mut impl Rng) {
+        let cells = self.rows * self.cols;
+        let blank = self.goal_index(0);
+
+        loop {
+            // Fisher-Yates shuffle of every tile except the empty one
+            let mut values: Vec<u16> = (1..cells).map(|value| value as u16).collect();
+            values.shuffle(rng);
+
+            self.tiles = values.into_iter().map(Tile::new).collect();
+            self.tiles.insert(blank, Tile::empty());
+            self.empty = (blank / self.cols, blank % self.cols);
+
+            // Swapping two tiles flips the permutation parity
+            if !self.is_solvable() {
+                let (first, second) = match blank {
+                    0 => (1, 2),
+                    1 => (0, 2),
+                    _ => (0, 1),
+                };
+                self.tiles.swap(first, second);
+            }
+            self.start_scramble();
+
+            if !self.is_solved() {
+                break;
+            }
+        }
+    }
+
+    /// Pairs of tiles, ignoring the blank, that appear in the opposite order
+    /// to their goal cells when reading the board row by row. For the
+    /// standard goal this is the classic count of larger numbers preceding
+    /// smaller ones.
+    pub fn inversions(&self) -> usize {
+        let goals: Vec<usize> = self
+            .tiles
+            .iter()
+            .filter_map(|tile| Some(self.goal_index(tile.value()?)))
+            .collect();
+
+        goals
+            .iter()
+            .enumerate()
+            .map(|(i, a)| goals[i + 1..].iter().filter(|b| a > b).count())
+            .sum()
+    }
+
+    /// Whether the goal can be reached from this position by sliding tiles.
+    ///
+    /// For odd widths the [inversion count](Self::inversions) must be even;
+    /// for even widths the inversion count plus the empty tile's row
+    /// distance from its goal row must be even. Horizontal moves leave the
+    /// count alone, and vertical ones pass a tile over `cols - 1` others.
+    ///
+    /// On a [wrapping](Self::set_wrap) board, moving around an edge flips
+    /// that parity when the board is more than 2 wide with an odd width, or
+    /// more than 2 tall with an odd height, and then every position is
+    /// solvable.
+    pub fn is_solvable(&self) -> bool {
+        if self.wrap
+            && ((self.cols > 2 && !self.cols.is_multiple_of(2))
+                || (self.rows > 2 && !self.rows.is_multiple_of(2)))
+        {
+            return true;
+        }
+
+        let inversions = self.inversions();
+        let (empty_row, _) = self.empty_position();
+        let goal_row = self.goal_index(0) / self.cols;
+
+        if self.cols % 2 == 1 {
+            inversions.is_multiple_of(2)
+        } else {
+            (inversions + empty_row.abs_diff(goal_row)).is_multiple_of(2)
+        }
+    }
+
+    /// Whether every tile, and the blank, sits in its goal cell.
+    pub fn is_solved(&self) -> bool {
+        self.tiles
+            .iter()
+            .enumerate()
+            .all(|(index, tile)| self.goal_index(tile.value().unwrap_or(0)) == index)
+    }
+}
+
+/// The serialized form of a [`Puzzle`], with tiles as plain numbers and `0`
+/// for the empty tile.
+#[derive(Serialize, Deserialize)]
+struct PuzzleData {
+    rows: usize,
+    cols: usize,
+    tiles: Vec<u16>,
+    moves: u32,
+    #[serde(default)]
+    seed: Option<u64>,
+    #[serde(default)]
+    initial: Option<Vec<u16>>,
+    #[serde(default)]
+    difficulty: Difficulty,
+    #[serde(default)]
+    goal: Goal,
+    #[serde(default)]
+    wrap: bool,
+    #[serde(default)]
+    mode: GameMode,
+    #[serde(default)]
+    blindfold: bool,
+    #[serde(default)]
+    preview: Duration,
+    #[serde(default)]
+    undo: Vec<Step>,
+    #[serde(default)]
+    redo: Vec<Step>,
+}
+
+impl From<Puzzle> for PuzzleData {
+    fn from(puzzle: Puzzle) -> Self {
+        let values = |tiles: &[Tile]| tiles.iter().map(|tile| tile.value().unwrap_or(0)).collect();
+
+        Self {
+            rows: puzzle.rows,
+            cols: puzzle.cols,
+            tiles: values(&puzzle.tiles),
+            moves: puzzle.moves,
+            seed: puzzle.seed,
+            initial: puzzle.initial.as_deref().map(values),
+            difficulty: puzzle.difficulty,
+            goal: puzzle.goal,
+            wrap: puzzle.wrap,
+            mode: puzzle.mode,
+            blindfold: puzzle.blindfold,
+            preview: puzzle.preview,
+            undo: puzzle.undo_stack,
+            redo: puzzle.redo_stack,
+        }
+    }
+}
+
+impl TryFrom<PuzzleData> for Puzzle {
+    type Error = BoardError;
+
+    fn try_from(data: PuzzleData) -> Result<Self, Self::Error> {
+        let (rows, cols) = (data.rows, data.cols);
+        if !(MIN_GRID_SIZE..=MAX_GRID_SIZE).contains(&rows)
+            || !(MIN_GRID_SIZE..=MAX_GRID_SIZE).contains(&cols)
+        {
+            return Err(BoardError::InvalidDimensions { rows, cols });
+        }
+
+        let tiles = tiles_from_values(rows * cols, &data.tiles)?;
+        let initial = data
+            .initial
+            .map(|values| tiles_from_values(rows * cols, &values))
+            .transpose()?;
+
+        let mut puzzle = Self::new(rows, cols);
+        puzzle.tiles = tiles;
+        puzzle.empty = puzzle.find_empty().expect("validated boards have a blank");
+        puzzle.moves = data.moves;
+        puzzle.seed = data.seed;
+        puzzle.initial = initial;
+        puzzle.difficulty = data.difficulty;
+        if let Goal::Custom(layout) = &data.goal {
+            tiles_from_values(rows * cols, layout)?;
+        }
+        puzzle.goal = data.goal;
+        puzzle.wrap = data.wrap;
+        puzzle.mode = data.mode;
+        puzzle.blindfold = data.blindfold;
+        puzzle.preview = data.preview;
+
+        // Replay the history on a copy to make sure every step is legal
+        let mut check = puzzle.clone();
+        check.undo_stack = data.undo.clone();
+        check.redo_stack = data.redo.clone();
+        while let Some(&step) = check.undo_stack.last() {
+            if step.from != check.empty || !check.is_in_line_with_empty(step.to.0, step.to.1) {
+                return Err(BoardError::InvalidHistory);
+            }
+            check.undo();
+        }
+        while let Some(&step) = check.redo_stack.last() {
+            if step.to != check.empty || !check.is_in_line_with_empty(step.from.0, step.from.1) {
+                return Err(BoardError::InvalidHistory);
+            }
+            check.redo();
+        }
+
+        puzzle.undo_stack = data.undo;
+        puzzle.redo_stack = data.redo;
+        Ok(puzzle)
+    }
+}
+
+/// Builds tiles from numbers where `0` is the empty tile, checking that every
+/// value in `0..cells` appears exactly once.
+pub(crate) fn tiles_from_values(cells: usize, values: &[u16]) -> Result<Vec<Tile>, BoardError> {
+    if values.len() != cells {
+        return Err(BoardError::WrongCellCount {
+            expected: cells,
+            found: values.len(),
+        });
+    }
+
+    let mut seen = vec![false; cells];
+    for &value in values {
+        let value = usize::from(value);
+
+        if value >= cells {
+            return Err(BoardError::ValueOutOfRange(value));
+        }
+        if std::mem::replace(&mut seen[value], true) {
+            return Err(BoardError::DuplicateValue(value));
+        }
+    }
+
+    Ok(values
+        .iter()
+        .map(|&value| match value {
+            0 => Tile::empty(),
+            value => Tile::new(value),
+        })
+        .collect())
+}
+
+/// Row plus column distance between row-major `index` and the `goal` cell on
+/// a board of `size` (rows, columns), taking the shorter way around the
+/// edges if it `wrap`s.
+pub(crate) fn tile_distance(size: (usize, usize), wrap: bool, goal: usize, index: usize) -> u32 {
+    let (rows, cols) = size;
+    let (row, col) = (index / cols, index % cols);
+    let (goal_row, goal_col) = (goal / cols, goal % cols);
+    let axis = |a: usize, b: usize, length: usize| {
+        let distance = a.abs_diff(b);
+        if wrap {
+            distance.min(length - distance)
+        } else {
+            distance
+        }
+    };
+
+    (axis(row, goal_row, rows) + axis(col, goal_col, cols)) as u32
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn new_board_is_solved() {
+        for (rows, cols) in [(2, 2), (3, 3), (4, 4), (3, 4), (5, 3)] {
+            let puzzle = Puzzle::new(rows, cols);
+
+            assert!(puzzle.is_solved());
+            assert_eq!(puzzle.empty_position(), (rows - 1, cols - 1));
+        }
+    }
+
+    #[test]
+    fn boards_beyond_255_tiles_number_every_tile() {
+        let mut puzzle = Puzzle::new(16, 16);
+
+        assert!(puzzle.is_solved());
+        assert_eq!(puzzle.tile(15, 14).value(), Some(255));
+        assert_eq!(puzzle.tile(15, 15).value(), None);
+
+        puzzle.move_tile(15, 14);
+        assert!(!puzzle.is_solved());
+        puzzle.move_tile(15, 15);
+        assert!(puzzle.is_solved());
+    }
+
+    #[test]
+    #[should_panic(expected = "more tiles than fit a u16")]
+    fn boards_too_large_for_the_tile_type_are_refused() {
+        Puzzle::new(300, 300);
+    }
+
+    #[test]
+    fn move_tile_slides_adjacent_tiles() {
+        let mut puzzle = Puzzle::new(4, 4);
+
+        assert!(puzzle.move_tile(3, 2));
+        assert_eq!(puzzle.empty_position(), (3, 2));
+        assert_eq!(puzzle.tile(3, 3).value(), Some(15));
+        assert_eq!(puzzle.moves(), 1);
+
+        assert!(puzzle.move_tile(2, 2));
+        assert_eq!(puzzle.empty_position(), (2, 2));
+        assert_eq!(puzzle.tile(3, 2).value(), Some(11));
+        assert_eq!(puzzle.moves(), 2);
+    }
+
+    #[test]
+    fn move_tile_rejects_non_adjacent_tiles() {
+        let mut puzzle = Puzzle::new(4, 4);
+
+        // Diagonal, same row but two away, and the far corner
+        assert!(!puzzle.move_tile(2, 2));
+        assert!(!puzzle.move_tile(3, 1));
+        assert!(!puzzle.move_tile(0, 0));
+        assert_eq!(puzzle.moves(), 0);
+        assert!(puzzle.is_solved());
+    }
+
+    #[test]
+    fn move_tile_rejects_the_empty_cell() {
+        let mut puzzle = Puzzle::new(4, 4);
+
+        assert!(!puzzle.move_tile(3, 3));
+        assert_eq!(puzzle.moves(), 0);
+    }
+
+    #[test]
+    fn move_tile_does_not_wrap_around_edges() {
+        let mut puzzle = Puzzle::new(3, 3);
+
+        // Walk the blank to the top-left corner
+        for (row, col) in [(2, 1), (2, 0), (1, 0), (0, 0)] {
+            assert!(puzzle.move_tile(row, col));
+        }
+        assert_eq!(puzzle.empty_position(), (0, 0));
+
+        assert!(!puzzle.move_tile(0, 2));
+        assert!(!puzzle.move_tile(2, 0));
+        assert!(puzzle.move_tile(0, 1));
+        assert_eq!(puzzle.moves(), 5);
+    }
+
+    #[test]
+    fn near_solved_boards_are_not_solved() {
+        let mut puzzle = Puzzle::new(4, 4);
+
+        puzzle.move_tile(3, 2);
+        assert!(!puzzle.is_solved());
+
+        // Moving the tile back restores the solved state
+        puzzle.move_tile(3, 3);
+        assert!(puzzle.is_solved());
+
+        puzzle.move_tile(2, 3);
+        assert!(!puzzle.is_solved());
+    }
+
+    #[test]
+    fn move_direction_ignores_edges_and_solved_boards() {
+        let mut puzzle = Puzzle::new(4, 4);
+
+        // The board starts solved, so key moves are ignored
+        assert!(!puzzle.move_direction(Direction::Right));
+
+        puzzle.move_tile(3, 2);
+        // Blank at (3, 2): nothing below it to slide up
+        assert!(!puzzle.move_direction(Direction::Up));
+        assert!(puzzle.move_direction(Direction::Right));
+        assert_eq!(puzzle.empty_position(), (3, 1));
+        assert_eq!(puzzle.moves(), 2);
+    }
+
+    #[test]
+    fn shuffle_resets_moves() {
+        let mut puzzle = Puzzle::with_seed(4, 4, 7);
+
+        puzzle.move_tile(3, 2);
+        puzzle.shuffle();
+
+        assert_eq!(puzzle.moves(), 0);
+        assert_eq!(puzzle.find_empty(), Some(puzzle.empty_position()));
+    }
+
+    #[test]
+    fn uniform_scrambles_are_solvable_and_unsolved() {
+        let mut rng = StdRng::seed_from_u64(42);
+
+        for (rows, cols) in [(4, 4), (3, 3), (3, 4), (2, 2)] {
+            let mut puzzle = Puzzle::new(rows, cols);
+
+            for _ in 0..1000 {
+                puzzle.scramble_uniform(&mut rng);
+
+                assert!(puzzle.is_solvable());
+                assert!(!puzzle.is_solved());
+                assert_eq!(puzzle.empty_position(), (rows - 1, cols - 1));
+            }
+        }
+    }
+
+    #[test]
+    fn solvability_follows_blank_row_parity() {
+        let mut puzzle = Puzzle::new(4, 4);
+        assert!(puzzle.is_solvable());
+
+        // Valid moves never change solvability
+        puzzle.move_tile(2, 3);
+        puzzle.move_tile(2, 2);
+        assert!(puzzle.is_solvable());
+
+        // Swapping two tiles does
+        puzzle.tiles.swap(0, 1);
+        assert!(!puzzle.is_solvable());
+    }
+
+    #[test]
+    fn each_goal_recognizes_its_own_layout() {
+        let standard_3x3 = [1, 2, 3, 4, 5, 6, 7, 8, 0];
+        let snake_3x3 = [1, 2, 3, 6, 5, 4, 7, 8, 0];
+        let standard_4x4 = [1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15, 0];
+        let snake_4x4 = [1, 2, 3, 4, 8, 7, 6, 5, 9, 10, 11, 12, 0, 15, 14, 13];
+        let spiral_3x3 = [1, 2, 3, 8, 0, 4, 7, 6, 5];
+        let spiral_4x4 = [1, 2, 3, 4, 12, 13, 14, 5, 11, 0, 15, 6, 10, 9, 8, 7];
+
+        for (values, goal) in [
+            (&standard_3x3[..], Goal::Standard),
+            (&snake_3x3, Goal::Snake),
+            (&standard_4x4, Goal::Standard),
+            (&snake_4x4, Goal::Snake),
+            (&spiral_3x3, Goal::Spiral),
+            (&spiral_4x4, Goal::Spiral),
+        ] {
+            let mut puzzle = Puzzle::from_permutation(values).unwrap();
+
+            puzzle.set_goal(goal.clone());
+            assert!(puzzle.is_solved(), "{goal} should accept {values:?}");
+            assert_eq!(puzzle.manhattan_distance(), 0);
+
+            for other in Goal::ALL.into_iter().filter(|other| *other != goal) {
+                puzzle.set_goal(other.clone());
+                assert!(!puzzle.is_solved(), "{other} should reject {values:?}");
+            }
+        }
+    }
+
+    #[test]
+    fn reset_lays_out_the_goal() {
+        let mut puzzle = Puzzle::new(4, 4);
+        puzzle.set_goal(Goal::Snake);
+        assert!(!puzzle.is_solved());
+
+        puzzle.reset();
+        assert!(puzzle.is_solved());
+        assert_eq!(puzzle.empty_position(), (3, 0));
+        assert!(puzzle.is_tile_in_place(1, 0));
+        assert_eq!(puzzle.tile(1, 0).value(), Some(8));
+    }
+
+    #[test]
+    fn spiral_blank_ends_near_the_centre() {
+        assert_eq!(goal_position(0, (4, 4), &Goal::Spiral), (2, 1));
+        assert_eq!(goal_position(0, (5, 5), &Goal::Spiral), (2, 2));
+        assert_eq!(goal_position(16, (5, 5), &Goal::Spiral), (1, 0));
+        assert_eq!(goal_position(0, (3, 4), &Goal::Spiral), (1, 2));
+    }
+
+    #[test]
+    fn alternative_goal_scrambles_are_solvable() {
+        let mut rng = StdRng::seed_from_u64(3);
+
+        for (goal, (rows, cols)) in [Goal::Snake, Goal::Spiral]
+            .into_iter()
+            .flat_map(|goal| [(4, 4), (3, 3), (5, 5), (2, 2)].map(|size| (goal.clone(), size)))
+            .chain([(
+                Goal::custom(vec![0, 8, 7, 6, 5, 4, 3, 2, 1]).unwrap(),
+                (3, 3),
+            )])
+        {
+            let mut puzzle = Puzzle::new(rows, cols);
+            puzzle.set_goal(goal);
+
+            for _ in 0..200 {
+                puzzle.scramble_uniform(&mut rng);
+                assert!(puzzle.is_solvable());
+                assert!(!puzzle.is_solved());
+
+                puzzle.scramble_walk(30, &mut rng);
+                assert!(puzzle.is_solvable());
+            }
+        }
+    }
+
+    #[test]
+    fn custom_goals_are_validated_and_followed() {
+        assert_eq!(
+            Goal::custom(vec![1, 2, 3, 3]).err(),
+            Some(BoardError::DuplicateValue(3))
+        );
+        assert_eq!(
+            Goal::custom(vec![1, 2, 4, 0]).err(),
+            Some(BoardError::ValueOutOfRange(4))
+        );
+
+        let mut puzzle = Puzzle::new(2, 2);
+        puzzle.set_goal(Goal::custom(vec![0, 3, 2, 1]).unwrap());
+        assert!(!puzzle.is_solved());
+        assert_eq!(puzzle.misplaced_count(), 3);
+
+        puzzle.reset();
+        assert!(puzzle.is_solved());
+        assert_eq!(puzzle.empty_position(), (0, 0));
+        assert_eq!(puzzle.tile(0, 1).value(), Some(3));
+
+        // Layouts for another board size fall back to the standard goal
+        let mut larger = Puzzle::new(3, 3);
+        larger.set_goal(puzzle.goal().clone());
+        assert!(larger.is_solved());
+    }
+
+    #[test]
+    fn solved_board_has_no_distance() {
+        let puzzle = Puzzle::new(4, 4);
+
+        assert_eq!(puzzle.manhattan_distance(), 0);
+        assert_eq!(puzzle.misplaced_count(), 0);
+    }
+
+    #[test]
+    fn distance_counts_each_displaced_tile() {
+        // Sliding a tile into the blank moves one tile by one cell
+        let mut puzzle = Puzzle::new(4, 4);
+        puzzle.move_tile(3, 2);
+        assert_eq!(puzzle.manhattan_distance(), 1);
+        assert_eq!(puzzle.misplaced_count(), 1);
+
+        // Swapping neighbours displaces both by one cell
+        let mut puzzle = Puzzle::new(4, 4);
+        puzzle.tiles.swap(0, 1);
+        assert_eq!(puzzle.manhattan_distance(), 2);
+        assert_eq!(puzzle.misplaced_count(), 2);
+
+        // Swapping opposite corners of the top row displaces both by three
+        let mut puzzle = Puzzle::new(4, 4);
+        puzzle.tiles.swap(0, 3);
+        assert_eq!(puzzle.manhattan_distance(), 6);
+        assert_eq!(puzzle.misplaced_count(), 2);
+    }
+
+    #[test]
+    fn progress_is_full_only_on_a_solved_board() {
+        let puzzle = Puzzle::new(4, 4);
+        assert_eq!(puzzle.placed_fraction(), 1.0);
+        assert_eq!(puzzle.distance_progress(), 1.0);
+
+        // Every tile of a reversed 3x3 is out of place, and it is its own
+        // scramble
+        let mut reversed = Puzzle::from_permutation(&[8, 7, 6, 5, 4, 3, 2, 1, 0]).unwrap();
+        assert_eq!(reversed.placed_fraction(), 0.0);
+        assert_eq!(reversed.distance_progress(), 0.0);
+        // Either way out of the corner only takes it further from solved
+        assert!(reversed.apply_move(Direction::Right));
+        assert_eq!(reversed.distance_progress(), 0.0);
+
+        // Two moves out, one back is halfway by distance
+        let mut puzzle = Puzzle::from_permutation(&[1, 2, 3, 4, 5, 6, 0, 7, 8]).unwrap();
+        assert!(puzzle.apply_move(Direction::Left));
+        assert_eq!(puzzle.distance_progress(), 0.5);
+        assert_eq!(puzzle.placed_fraction(), 7.0 / 8.0);
+        assert!(puzzle.apply_move(Direction::Left));
+        assert_eq!(puzzle.distance_progress(), 1.0);
+        assert_eq!(puzzle.placed_fraction(), 1.0);
+    }
+
+    #[test]
+    fn move_line_shifts_every_tile_towards_the_blank() {
+        let mut puzzle = Puzzle::new(4, 4);
+
+        // Blank at (3, 3): push the whole bottom row right
+        assert!(puzzle.move_line(3, 0, SlideCount::PerTile));
+        assert_eq!(puzzle.empty_position(), (3, 0));
+        let bottom: Vec<_> = (0..4).map(|col| puzzle.tile(3, col).value()).collect();
+        assert_eq!(bottom, [None, Some(13), Some(14), Some(15)]);
+        assert_eq!(puzzle.moves(), 3);
+
+        // Then the first column down
+        assert!(puzzle.move_line(0, 0, SlideCount::PerSlide));
+        assert_eq!(puzzle.empty_position(), (0, 0));
+        let first: Vec<_> = (0..4).map(|row| puzzle.tile(row, 0).value()).collect();
+        assert_eq!(first, [None, Some(1), Some(5), Some(9)]);
+        assert_eq!(puzzle.moves(), 4);
+    }
+
+    #[test]
+    fn move_line_ignores_the_blank_and_cells_out_of_line() {
+        let mut puzzle = Puzzle::new(4, 4);
+
+        assert!(!puzzle.move_line(3, 3, SlideCount::PerTile));
+        assert!(!puzzle.move_line(0, 0, SlideCount::PerTile));
+        assert!(!puzzle.move_line(4, 3, SlideCount::PerTile));
+        assert_eq!(puzzle.moves(), 0);
+
+        // Adjacent tiles still move a single step
+        assert!(puzzle.move_line(3, 2, SlideCount::PerTile));
+        assert_eq!(puzzle.empty_position(), (3, 2));
+        assert_eq!(puzzle.moves(), 1);
+    }
+
+    #[test]
+    fn line_direction_points_along_the_slide() {
+        let mut puzzle = Puzzle::new(4, 4);
+
+        assert_eq!(puzzle.line_direction(3, 0), Some(Direction::Right));
+        assert_eq!(puzzle.line_direction(1, 3), Some(Direction::Down));
+        assert_eq!(puzzle.line_direction(3, 3), None);
+        assert_eq!(puzzle.line_direction(0, 0), None);
+
+        puzzle.move_line(0, 3, SlideCount::PerTile);
+        assert_eq!(puzzle.line_direction(2, 3), Some(Direction::Up));
+        assert_eq!(puzzle.line_direction(0, 1), Some(Direction::Right));
+
+        // Wrapping boards only slide the blank's neighbours, across the edge
+        // included
+        puzzle.set_wrap(true);
+        assert_eq!(puzzle.line_direction(3, 3), Some(Direction::Down));
+        assert_eq!(puzzle.line_direction(2, 3), None);
+    }
+
+    #[test]
+    fn line_slides_undo_as_one_step() {
+        let mut puzzle = Puzzle::new(4, 4);
+        puzzle.move_line(0, 3, SlideCount::PerTile);
+        assert_eq!(puzzle.notation(), "DDD");
+
+        assert!(puzzle.undo());
+        assert!(puzzle.is_solved());
+        assert_eq!(puzzle.moves(), 0);
+
+        assert!(puzzle.redo());
+        assert_eq!(puzzle.empty_position(), (0, 3));
+        assert_eq!(puzzle.moves(), 3);
+    }
+
+    #[test]
+    fn last_moved_follows_moves_and_undo() {
+        let mut puzzle = Puzzle::new(4, 4);
+        assert_eq!(puzzle.last_moved(), None);
+
+        puzzle.move_line(3, 0, SlideCount::PerTile);
+        assert_eq!(puzzle.last_moved(), Some((3, 3)));
+        assert!(!puzzle.is_tile_in_place(3, 3));
+        assert!(puzzle.is_tile_in_place(0, 0));
+        assert!(!puzzle.is_tile_in_place(3, 0));
+
+        puzzle.undo();
+        assert_eq!(puzzle.last_moved(), Some((3, 0)));
+
+        puzzle.shuffle();
+        assert_eq!(puzzle.last_moved(), None);
+    }
+
+    #[test]
+    fn notation_round_trips_through_replay() {
+        let mut puzzle = Puzzle::with_seed(4, 4, 3);
+        puzzle.shuffle();
+        let mut replay = puzzle.clone();
+
+        for direction in [
+            Direction::Up,
+            Direction::Left,
+            Direction::Left,
+            Direction::Down,
+            Direction::Right,
+        ] {
+            puzzle.move_direction(direction);
+        }
+
+        let notation = puzzle.notation();
+        assert_eq!(notation.len(), puzzle.history().len());
+
+        replay.apply_notation(&notation).unwrap();
+        assert_eq!(replay.tiles(), puzzle.tiles());
+        assert_eq!(replay.history(), puzzle.history());
+    }
+
+    #[test]
+    fn notation_reflects_undo() {
+        let mut puzzle = Puzzle::new(3, 3);
+        puzzle.apply_notation("RD").unwrap();
+        assert_eq!(puzzle.notation(), "RD");
+
+        puzzle.undo();
+        assert_eq!(puzzle.notation(), "R");
+    }
+
+    #[test]
+    fn apply_notation_rejects_bad_input_atomically() {
+        let mut puzzle = Puzzle::new(3, 3);
+
+        assert_eq!(
+            puzzle.apply_notation("RX"),
+            Err(NotationError::InvalidCharacter {
+                position: 1,
+                character: 'X'
+            })
+        );
+        // The blank starts bottom-right, so no tile can slide up into it
+        assert_eq!(
+            puzzle.apply_notation("R U"),
+            Err(NotationError::IllegalMove {
+                position: 2,
+                character: 'U'
+            })
+        );
+        assert!(puzzle.is_solved());
+        assert_eq!(puzzle.moves(), 0);
+    }
+
+    #[test]
+    fn permutations_build_square_boards() {
+        let puzzle =
+            Puzzle::from_permutation(&[5, 1, 2, 4, 9, 6, 3, 8, 13, 10, 7, 12, 14, 11, 15, 0])
+                .unwrap();
+
+        assert_eq!((puzzle.rows(), puzzle.cols()), (4, 4));
+        assert_eq!(puzzle.tile(0, 0).value(), Some(5));
+        assert_eq!(puzzle.empty_position(), (3, 3));
+        assert!(puzzle.is_solvable());
+        assert!(puzzle.is_scrambled());
+    }
+
+    #[test]
+    fn permutations_are_validated() {
+        assert_eq!(
+            Puzzle::from_permutation(&[1, 2, 3]).err(),
+            Some(BoardError::NotSquare(3))
+        );
+        assert_eq!(
+            Puzzle::from_permutation(&[1, 2, 3, 4]).err(),
+            Some(BoardError::ValueOutOfRange(4))
+        );
+        assert_eq!(
+            Puzzle::from_permutation(&[1, 1, 2, 0]).err(),
+            Some(BoardError::DuplicateValue(1))
+        );
+    }
+
+    #[test]
+    fn permutation_parser_tolerates_separators() {
+        let slashes =
+            Puzzle::parse_permutation("5 1 2 4 / 9 6 3 8 / 13 10 7 12 / 14 11 15 0").unwrap();
+        let mixed =
+            Puzzle::parse_permutation("5,1,2,4\n9, 6, 3, 8\n13 10 7 12\n 14 11 15 0\n").unwrap();
+
+        assert_eq!(slashes.tiles(), mixed.tiles());
+        assert_eq!(
+            Puzzle::parse_permutation("1 2 x 0").err(),
+            Some(BoardError::InvalidNumber(String::from("x")))
+        );
+    }
+
+    #[test]
+    fn board_text_reads_back_as_the_same_board() {
+        let mut puzzle = Puzzle::with_seed(4, 4, 7);
+        puzzle.shuffle();
+        let (row, col) = puzzle.movable_tiles()[0];
+        assert!(puzzle.move_tile(row, col));
+
+        let text = puzzle.board_text();
+        assert_eq!(text.lines().count(), 5);
+        assert!(text.contains('_'));
+        assert!(text.ends_with("# Moves: 1\n"));
+        assert_eq!(
+            Puzzle::parse_permutation(&text).unwrap().tiles(),
+            puzzle.tiles()
+        );
+
+        assert_eq!(Puzzle::new(2, 2).board_text(), "1 2\n3 _\n# Moves: 0\n");
+
+        for mode in LabelMode::ALL {
+            let text = puzzle.board_text_in(mode);
+            assert_eq!(
+                Puzzle::parse_permutation_in(&text, mode).unwrap().tiles(),
+                puzzle.tiles(),
+                "{mode} board did not read back:\n{text}"
+            );
+        }
+        assert_eq!(
+            Puzzle::new(3, 3).board_text_in(LabelMode::ZeroBased),
+            "0 1 2\n3 4 5\n6 7 _\n# Moves: 0\n"
+        );
+    }
+
+    #[test]
+    fn rectangular_boards_read_back_in_their_own_shape() {
+        for (rows, cols) in [(3, 4), (2, 8), (8, 2), (5, 3)] {
+            let mut puzzle = Puzzle::with_seed(rows, cols, 3);
+            puzzle.shuffle();
+
+            let copied = Puzzle::parse_permutation(&puzzle.board_text()).unwrap();
+            assert_eq!((copied.rows(), copied.cols()), (rows, cols));
+            assert_eq!(copied.tiles(), puzzle.tiles());
+        }
+
+        // Rows between `/`s count as well as lines
+        let typed = Puzzle::parse_permutation("1 2 3 / 4 5 0").unwrap();
+        assert_eq!((typed.rows(), typed.cols()), (2, 3));
+        // Numbers in no particular rows still make a square
+        let loose = Puzzle::parse_permutation("1 2 3 4\n5 6 7 8 9 10\n11 12 13 14 15 0").unwrap();
+        assert_eq!((loose.rows(), loose.cols()), (4, 4));
+    }
+
+    #[test]
+    fn inversions_count_pairs_out_of_order() {
+        assert_eq!(Puzzle::new(4, 4).inversions(), 0);
+        assert_eq!(
+            Puzzle::parse_permutation("2 1 3 0").unwrap().inversions(),
+            1
+        );
+        assert_eq!(
+            Puzzle::parse_permutation("1 8 2 / 0 4 3 / 7 6 5")
+                .unwrap()
+                .inversions(),
+            10
+        );
+
+        // The blank does not count, wherever it is
+        let mut puzzle = Puzzle::new(3, 3);
+        puzzle.move_tile(1, 2);
+        puzzle.move_tile(1, 1);
+        assert_eq!(puzzle.inversions(), 2);
+    }
+
+    #[test]
+    fn known_positions_have_the_expected_solvability() {
+        for (board, solvable) in [
+            ("1 2 3 / 4 5 6 / 7 8 0", true),
+            ("1 2 3 / 4 5 6 / 8 7 0", false),
+            ("1 8 2 / 0 4 3 / 7 6 5", true),
+            ("8 1 2 / 0 4 3 / 7 6 5", false),
+            ("1 2 3 4 / 5 6 7 8 / 9 10 11 12 / 13 14 15 0", true),
+            ("1 2 3 4 / 5 6 7 8 / 9 10 11 12 / 13 15 14 0", false),
+            ("13 2 10 3 / 1 12 8 4 / 5 0 9 6 / 15 14 11 7", true),
+            ("6 13 7 10 / 8 9 11 0 / 15 2 12 5 / 14 3 1 4", true),
+            ("3 9 1 15 / 14 11 4 6 / 13 0 10 12 / 2 7 8 5", false),
+        ] {
+            let puzzle = Puzzle::parse_permutation(board).unwrap();
+
+            assert_eq!(puzzle.is_solvable(), solvable, "{board}");
+        }
+    }
+
+    #[test]
+    fn solvability_matches_reachability_on_small_boards() {
+        for (rows, cols, wrap) in [
+            (2, 2, false),
+            (2, 3, false),
+            (3, 2, false),
+            (2, 3, true),
+            (3, 2, true),
+            (2, 4, true),
+            (4, 2, true),
+        ] {
+            let mut start = Puzzle::new(rows, cols);
+            start.set_wrap(wrap);
+
+            // Breadth-first search over every position reachable from solved
+            let mut reachable = std::collections::HashSet::from([start.tiles.clone()]);
+            let mut queue = vec![start];
+            while let Some(puzzle) = queue.pop() {
+                for (row, col) in puzzle.movable_tiles() {
+                    let mut next = puzzle.clone();
+                    next.slide(row, col);
+                    if reachable.insert(next.tiles.clone()) {
+                        queue.push(next);
+                    }
+                }
+            }
+
+            // Every arrangement of the cells, built by permuting values
+            let cells = rows * cols;
+            let mut values: Vec<u16> = (0..cells as u16).collect();
+            let mut count = 0;
+            permute(&mut values, 0, &mut |values| {
+                let mut puzzle = Puzzle::new(rows, cols);
+                puzzle.set_wrap(wrap);
+                puzzle.tiles = tiles_from_values(cells, values).unwrap();
+                puzzle.empty = puzzle.find_empty().unwrap();
+
+                assert_eq!(
+                    puzzle.is_solvable(),
+                    reachable.contains(&puzzle.tiles),
+                    "{values:?} on {rows}x{cols}, wrap {wrap}"
+                );
+                count += 1;
+            });
+            if !wrap {
+                assert_eq!(reachable.len() * 2, count);
+            }
+        }
+    }
+
+    #[test]
+    fn wrapping_joins_opposite_edges() {
+        let mut puzzle = Puzzle::new(4, 4);
+        assert!(!puzzle.is_adjacent_to_empty(3, 0));
+        assert!(!puzzle.is_adjacent_to_empty(0, 3));
+
+        puzzle.set_wrap(true);
+        assert!(puzzle.is_solved());
+        // Blank in the bottom-right corner: both of its far edges wrap
+        assert!(puzzle.is_adjacent_to_empty(3, 0));
+        assert!(puzzle.is_adjacent_to_empty(0, 3));
+        assert!(!puzzle.is_adjacent_to_empty(0, 0));
+        assert!(!puzzle.is_adjacent_to_empty(3, 1));
+        assert_eq!(puzzle.movable_tiles(), [(0, 3), (2, 3), (3, 0), (3, 2)]);
+
+        // Blank on an edge but not a corner wraps one way only
+        assert!(puzzle.move_tile(2, 3));
+        assert_eq!(puzzle.movable_tiles().len(), 4);
+        assert!(puzzle.is_adjacent_to_empty(2, 0));
+        assert!(!puzzle.is_adjacent_to_empty(0, 3));
+    }
+
+    #[test]
+    fn two_wide_boards_gain_no_neighbours_from_wrapping() {
+        let mut puzzle = Puzzle::new(2, 3);
+        puzzle.set_wrap(true);
+
+        // Columns wrap, but the only other row is adjacent either way
+        assert_eq!(puzzle.movable_tiles(), [(0, 2), (1, 0), (1, 1)]);
+        assert_eq!(puzzle.tile_for_direction(Direction::Up), None);
+    }
+
+    #[test]
+    fn moves_around_the_edge_undo_and_replay() {
+        let mut puzzle = Puzzle::new(3, 3);
+        puzzle.set_wrap(true);
+
+        // The blank sits in the last column, so column 0 slides around to it
+        assert!(puzzle.move_tile(2, 0));
+        assert_eq!(puzzle.empty_position(), (2, 0));
+        assert_eq!(puzzle.tile(2, 2).value(), Some(7));
+        assert_eq!(puzzle.last_moved(), Some((2, 2)));
+        assert_eq!(puzzle.history(), [Direction::Left]);
+        assert!(puzzle.move_direction(Direction::Up));
+        assert_eq!(puzzle.empty_position(), (0, 0));
+
+        let notation = puzzle.notation();
+        assert_eq!(notation, "LU");
+
+        assert!(puzzle.undo());
+        assert!(puzzle.undo());
+        assert!(puzzle.is_solved());
+        assert!(puzzle.redo());
+        assert_eq!(puzzle.empty_position(), (2, 0));
+
+        puzzle.reset();
+        puzzle.apply_notation(&notation).unwrap();
+        assert_eq!(puzzle.empty_position(), (0, 0));
+        assert_eq!(puzzle.moves(), 2);
+    }
+
+    #[test]
+    fn wrapping_shortens_distances() {
+        let mut puzzle = Puzzle::new(3, 3);
+        puzzle.set_wrap(true);
+        puzzle.move_tile(2, 0);
+
+        // Tile 7 is one step from home around the edge, two the long way
+        assert_eq!(puzzle.manhattan_distance(), 1);
+        puzzle.set_wrap(false);
+        assert_eq!(puzzle.manhattan_distance(), 2);
+    }
+
+    fn permute(values: &mut [u16], start: usize, visit: &mut impl FnMut(&[u16])) {
+        if start == values.len() {
+            visit(values);
+            return;
+        }
+        for i in start..values.len() {
+            values.swap(start, i);
+            permute(values, start + 1, visit);
+            values.swap(start, i);
+        }
+    }
+
+    #[test]
+    fn unsolvable_permutations_still_load() {
+        let puzzle = Puzzle::parse_permutation("2 1 3 0").unwrap();
+
+        assert!(!puzzle.is_solvable());
+    }
+
+    #[test]
+    fn seeded_shuffles_are_reproducible() {
+        let mut first = Puzzle::new(4, 4);
+        let mut second = Puzzle::new(4, 4);
+
+        first.shuffle_with_seed(12345);
+        second.shuffle();
+        second.shuffle_with_seed(12345);
+
+        assert_eq!(first.tiles(), second.tiles());
+        assert_eq!(first.seed(), Some(12345));
+
+        second.shuffle_with_seed(54321);
+        assert_ne!(first.tiles(), second.tiles());
+    }
+
+    #[test]
+    fn a_seed_gives_the_same_board_on_every_platform() {
+        // Pinned, so a change of how scrambles are drawn, or a draw that
+        // depends on the width of `usize`, shows up here
+        for (difficulty, board) in [
+            (Difficulty::Easy, [7, 3, 6, 0, 4, 1, 2, 5, 8]),
+            (Difficulty::Medium, [4, 7, 6, 1, 0, 3, 2, 8, 5]),
+            (Difficulty::Hard, [1, 4, 2, 7, 3, 8, 6, 5, 0]),
+        ] {
+            let mut puzzle = Puzzle::new(3, 3);
+            puzzle.set_difficulty(difficulty);
+            puzzle.shuffle_with_seed(2024);
+
+            let values: Vec<u16> = puzzle
+                .tiles()
+                .iter()
+                .map(|tile| tile.value().unwrap_or(0))
+                .collect();
+            assert_eq!(values, board, "{difficulty:?}");
+        }
+    }
+
+    #[test]
+    fn difficulty_controls_scramble_depth() {
+        let mut puzzle = Puzzle::new(4, 4);
+
+        puzzle.set_difficulty(Difficulty::Easy);
+        for seed in 0..100 {
+            puzzle.shuffle_with_seed(seed);
+            // A walk of at most 20 moves cannot displace tiles further
+            assert!(puzzle.manhattan_distance() <= 20);
+            assert!(puzzle.is_solvable());
+        }
+
+        puzzle.set_difficulty(Difficulty::Expert);
+        for seed in 0..20 {
+            puzzle.shuffle_with_seed(seed);
+            assert!(puzzle.manhattan_distance() > 43);
+            assert!(puzzle.is_solvable());
+        }
+        assert_eq!(puzzle.difficulty(), Difficulty::Expert);
+    }
+
+    #[test]
+    fn shuffle_never_leaves_the_board_solved() {
+        for difficulty in Difficulty::ALL {
+            for (rows, cols) in [(2, 2), (2, 3), (3, 3), (4, 4)] {
+                let mut puzzle = Puzzle::with_seed(rows, cols, 1);
+                puzzle.set_difficulty(difficulty);
+
+                for seed in 0..1000 {
+                    let previous = puzzle.tiles().to_vec();
+                    puzzle.shuffle_with_seed(seed);
+
+                    assert!(
+                        !puzzle.is_solved(),
+                        "{difficulty} {rows}x{cols} seed {seed}"
+                    );
+                    assert_ne!(puzzle.tiles(), previous.as_slice());
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn undo_and_redo_walk_the_history() {
+        let mut puzzle = Puzzle::new(4, 4);
+        puzzle.shuffle_with_seed(3);
+        let scramble = puzzle.tiles().to_vec();
+
+        let mut made = 0;
+        for _ in 0..10 {
+            let (row, col) = puzzle.movable_tiles()[0];
+            made += u32::from(puzzle.move_tile(row, col));
+        }
+        let end = puzzle.tiles().to_vec();
+        assert_eq!(puzzle.moves(), made);
+
+        while puzzle.undo() {}
+        assert_eq!(puzzle.tiles(), scramble.as_slice());
+        assert_eq!(puzzle.moves(), 0);
+        assert!(!puzzle.can_undo());
+
+        while puzzle.redo() {}
+        assert_eq!(puzzle.tiles(), end.as_slice());
+        assert_eq!(puzzle.moves(), made);
+        assert!(!puzzle.can_redo());
+    }
+
+    #[test]
+    fn new_moves_clear_redo_and_shuffles_clear_both() {
+        let mut puzzle = Puzzle::new(3, 3);
+
+        puzzle.move_tile(2, 1);
+        puzzle.move_tile(1, 1);
+        puzzle.undo();
+        assert!(puzzle.can_redo());
+
+        puzzle.move_tile(2, 0);
+        assert!(!puzzle.can_redo());
+        assert!(puzzle.can_undo());
+
+        puzzle.shuffle();
+        assert!(!puzzle.can_undo());
+        assert!(!puzzle.can_redo());
+    }
+
+    #[test]
+    fn time_attacks_run_out() {
+        let mut puzzle = Puzzle::new(3, 3);
+        assert!(!puzzle.count_down(Duration::from_secs(1000)));
+
+        puzzle.set_mode(GameMode::TimeAttack {
+            remaining: Duration::from_secs(10),
+        });
+        assert!(!puzzle.count_down(Duration::from_secs(4)));
+        assert_eq!(puzzle.mode().remaining(), Some(Duration::from_secs(6)));
+        assert!(puzzle.count_down(Duration::from_secs(7)));
+        assert!(puzzle.is_out_of_time());
+        assert_eq!(puzzle.mode().remaining(), Some(Duration::ZERO));
+    }
+
+    #[test]
+    fn move_limits_lock_the_board_once_spent() {
+        let mut puzzle = Puzzle::new(3, 3);
+        puzzle.shuffle_with_seed(2);
+        puzzle.set_mode(GameMode::MoveLimit { budget: None });
+        assert!(puzzle.is_locked());
+        assert_eq!(puzzle.moves_left(), None);
+
+        puzzle.set_mode(GameMode::MoveLimit { budget: Some(2) });
+        assert!(!puzzle.is_locked());
+        let (row, col) = puzzle.movable_tiles()[0];
+        puzzle.move_tile(row, col);
+        assert_eq!(puzzle.moves_left(), Some(1));
+
+        // Undoing refunds the move
+        puzzle.undo();
+        assert_eq!(puzzle.moves_left(), Some(2));
+
+        for _ in 0..2 {
+            let (row, col) = puzzle.movable_tiles()[0];
+            puzzle.move_tile(row, col);
+        }
+        assert_eq!(puzzle.moves_left(), Some(0));
+        assert_eq!(puzzle.is_out_of_moves(), !puzzle.is_solved());
+    }
+
+    #[test]
+    fn blindfolds_hide_the_labels_after_the_preview() {
+        let mut puzzle = Puzzle::new(3, 3);
+        puzzle.shuffle_with_seed(12);
+        puzzle.set_blindfold(true);
+        puzzle.set_preview(Duration::from_secs(10));
+        assert!(puzzle.is_previewing());
+        assert!(!puzzle.is_hidden());
+
+        puzzle.count_down_preview(Duration::from_secs(4));
+        assert_eq!(puzzle.preview_left(), Duration::from_secs(6));
+        puzzle.count_down_preview(Duration::from_secs(20));
+        assert!(!puzzle.is_previewing());
+        assert!(puzzle.is_hidden());
+
+        // Solving takes the blindfold off
+        puzzle.reset();
+        assert!(puzzle.is_blindfold());
+        assert!(!puzzle.is_hidden());
+    }
+
+    #[test]
+    fn drags_commit_past_halfway_only() {
+        let mut puzzle = Puzzle::new(4, 4);
+        puzzle.shuffle_with_seed(5);
+        let (empty_row, empty_col) = puzzle.empty_position();
+        let (row, col) = puzzle
+            .tile_for_direction(Direction::Right)
+            .unwrap_or_else(|| {
+                puzzle
+                    .tile_for_direction(Direction::Left)
+                    .expect("a 4x4 board has room on one side")
+            });
+
+        // Tiles away from the blank stay put
+        let far = ((empty_row + 2) % 4, empty_col);
+        assert!(!puzzle.start_drag(far.0, far.1));
+        assert_eq!(puzzle.drag(), None);
+
+        assert!(puzzle.start_drag(row, col));
+        puzzle.drag_to(1.7);
+        assert_eq!(puzzle.drag().map(|drag| drag.progress()), Some(1.0));
+        puzzle.drag_to(0.4);
+        assert!(!puzzle.end_drag());
+        assert_eq!(puzzle.moves(), 0);
+        assert_eq!(puzzle.empty_position(), (empty_row, empty_col));
+
+        assert!(puzzle.start_drag(row, col));
+        puzzle.drag_to(0.6);
+        assert!(puzzle.end_drag());
+        assert_eq!(puzzle.moves(), 1);
+        assert_eq!(puzzle.empty_position(), (row, col));
+        assert_eq!(puzzle.drag(), None);
+        assert!(!puzzle.end_drag());
+        assert_eq!(puzzle.moves(), 1);
+    }
+
+    #[test]
+    fn retry_restores_the_scramble() {
+        let mut puzzle = Puzzle::new(4, 4);
+        assert!(!puzzle.can_retry());
+        assert!(!puzzle.retry());
+
+        puzzle.shuffle_with_seed(9);
+        let scramble = puzzle.tiles().to_vec();
+        assert!(!puzzle.can_retry());
+
+        let (row, col) = puzzle.movable_tiles()[0];
+        puzzle.move_tile(row, col);
+        assert!(puzzle.can_retry());
+
+        assert!(puzzle.retry());
+        assert_eq!(puzzle.tiles(), scramble.as_slice());
+        assert_eq!(puzzle.empty_position(), puzzle.find_empty().unwrap());
+        assert_eq!(puzzle.moves(), 0);
+        assert_eq!(puzzle.seed(), Some(9));
+        assert!(!puzzle.can_undo());
+        assert!(!puzzle.can_retry());
+    }
+}
@@ -0,0 +1,162 @@
+use crate::{Difficulty, Direction, Puzzle};
+
+use serde::{Deserialize, Serialize};
+
+use std::collections::VecDeque;
+use std::time::Duration;
+
+/// Who solved a race's scramble first.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Racer {
+    Player,
+    Bot,
+}
+
+/// Races won and lost against the bot at one difficulty.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct RaceStats {
+    pub won: u32,
+    pub lost: u32,
+}
+
+/// A head-to-head race against a bot solving the same scramble on a board of
+/// its own.
+///
+/// The bot plays a precomputed solution at a steady pace set by the
+/// difficulty, on the same clock as the player, so pausing holds both.
+#[derive(Debug, Clone)]
+pub struct Race {
+    bot: Puzzle,
+    solution: Option<VecDeque<Direction>>, // Moves the bot has left, once worked out
+    winner: Option<Racer>,
+}
+
+impl Race {
+    /// A race against a bot starting from `bot`, a copy of the player's
+    /// scramble.
+    pub fn new(bot: Puzzle) -> Self {
+        Self {
+            bot,
+            solution: None,
+            winner: None,
+        }
+    }
+
+    /// How long the bot takes over each move at `difficulty`.
+    pub fn move_interval(difficulty: Difficulty) -> Duration {
+        Duration::from_millis(match difficulty {
+            Difficulty::Easy => 1000,
+            Difficulty::Medium => 500,
+            Difficulty::Hard => 250,
+            Difficulty::Expert => 150,
+        })
+    }
+
+    pub fn bot(&self) -> &Puzzle {
+        &self.bot
+    }
+
+    /// Hands the bot the solution it will play.
+    pub fn set_solution(&mut self, solution: Vec<Direction>) {
+        self.solution = Some(solution.into());
+    }
+
+    /// Whether the bot knows its solution and can start playing.
+    pub fn is_ready(&self) -> bool {
+        self.solution.is_some()
+    }
+
+    pub fn winner(&self) -> Option<Racer> {
+        self.winner
+    }
+
+    /// Plays every bot move due `elapsed` into the race, returning whether
+    /// any was made. Once the race is won, the bot stops.
+    pub fn advance(&mut self, elapsed: Duration) -> bool {
+        let Some(solution) = self.solution.as_mut().filter(|_| self.winner.is_none()) else {
+            return false;
+        };
+        let interval = Self::move_interval(self.bot.difficulty());
+        let due = (elapsed.as_millis() / interval.as_millis()) as u32;
+        let mut moved = false;
+
+        while self.bot.moves() < due
+            && let Some(direction) = solution.pop_front()
+        {
+            moved |= self.bot.move_direction(direction);
+            if self.bot.is_solved() {
+                self.winner = Some(Racer::Bot);
+                break;
+            }
+        }
+        moved
+    }
+
+    /// Declares the player the winner for solving their board, unless the
+    /// race is already decided. Returns whether this won it.
+    pub fn finish(&mut self) -> bool {
+        let won = self.winner.is_none();
+        if won {
+            self.winner = Some(Racer::Player);
+        }
+        won
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::solver;
+
+    fn race(difficulty: Difficulty, seed: u64) -> (Puzzle, Race) {
+        let mut player = Puzzle::new(3, 3);
+        player.set_difficulty(difficulty);
+        player.shuffle_with_seed(seed);
+        let mut race = Race::new(player.clone());
+        race.set_solution(solver::solve(&player, None).unwrap());
+        (player, race)
+    }
+
+    #[test]
+    fn the_bot_plays_one_move_per_interval() {
+        let (_, mut race) = race(Difficulty::Easy, 1);
+        let length = race.solution.as_ref().unwrap().len() as u32;
+        assert!(length > 2);
+
+        assert!(!race.advance(Duration::from_millis(999)));
+        assert!(race.advance(Duration::from_millis(1000)));
+        assert_eq!(race.bot().moves(), 1);
+        // Catching up after a long gap plays every move due at once
+        race.advance(Duration::from_millis(2500));
+        assert_eq!(race.bot().moves(), 2);
+        assert_eq!(race.winner(), None);
+
+        race.advance(Duration::from_secs(u64::from(length)));
+        assert!(race.bot().is_solved());
+        assert_eq!(race.winner(), Some(Racer::Bot));
+        assert!(!race.finish());
+    }
+
+    #[test]
+    fn solving_first_beats_the_bot_and_stops_it() {
+        let (player, mut race) = race(Difficulty::Hard, 2);
+        assert_eq!(race.bot().tiles(), player.tiles());
+
+        race.advance(Race::move_interval(Difficulty::Hard));
+        assert!(race.finish());
+        assert!(!race.finish());
+        assert!(!race.advance(Duration::from_secs(600)));
+        assert_eq!(race.bot().moves(), 1);
+        assert_eq!(race.winner(), Some(Racer::Player));
+    }
+
+    #[test]
+    fn harder_races_face_a_faster_bot() {
+        let intervals = Difficulty::ALL.map(Race::move_interval);
+
+        assert_eq!(intervals[0], Duration::from_secs(1));
+        assert_eq!(intervals[2], Duration::from_millis(250));
+        assert!(intervals.windows(2).all(|pair| pair[0] > pair[1]));
+    }
+}
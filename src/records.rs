@@ -0,0 +1,853 @@
+use crate::{Day, Difficulty, Efficiency, RaceStats, storage::Versioned};
+
+use serde::{Deserialize, Serialize};
+
+use std::collections::{BTreeMap, BTreeSet};
+use std::fmt::Write;
+use std::time::Duration;
+
+/// The first row of the CSV export, naming its columns.
+pub const CSV_HEADER: &str = "date,board,mode,moves,time,hints,undos,efficiency";
+
+/// Personal bests and lifetime statistics, tracked separately for every grid
+/// size and variant under the keys built by [`Records::key`].
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Records {
+    #[serde(default)]
+    bests: BTreeMap<String, Best>, // Keyed by `Records::key`
+    #[serde(default)]
+    stats: BTreeMap<String, Counters>, // Keyed like `bests`
+    #[serde(default)]
+    solves: Vec<SolveRecord>, // Oldest first
+    #[serde(default)]
+    daily: Daily,
+    #[serde(default)]
+    races: BTreeMap<Difficulty, RaceStats>,
+}
+
+/// Version 0 files may hold only the aggregate counters, which load as the
+/// baseline [`Counters`] keep.
+impl Versioned for Records {
+    const VERSION: u32 = 1;
+}
+
+/// Results of the daily puzzle.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+struct Daily {
+    day: Option<Day>,                // The day `results` are for
+    results: BTreeMap<String, Best>, // First solve of the day's puzzle, keyed like `bests`
+    completed: BTreeSet<Day>,        // Every day with a daily solved
+}
+
+/// The fastest time and fewest moves for one grid size, which may come from
+/// different solves.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Best {
+    pub time: Duration,
+    pub moves: u32,
+}
+
+/// One finished solve, kept so the statistics can be worked out afresh and
+/// exported.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SolveRecord {
+    /// The board it was on, keyed like the bests.
+    pub board: String,
+    pub day: Day,
+    pub time: Duration,
+    pub moves: u32,
+    #[serde(default)]
+    pub hints: u32,
+    #[serde(default)]
+    pub undos: u32,
+    #[serde(default)]
+    pub time_attack: bool,
+    #[serde(default)]
+    pub move_limit: bool,
+    #[serde(default)]
+    pub blindfold: bool,
+    #[serde(default)]
+    pub daily: bool,
+    /// How efficient it was, once the optimal length has been worked out.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub efficiency: Option<Efficiency>,
+}
+
+impl SolveRecord {
+    /// A solve in no particular mode, without hints or undos.
+    pub fn new(board: &str, day: Day, time: Duration, moves: u32) -> Self {
+        Self {
+            board: board.to_owned(),
+            day,
+            time,
+            moves,
+            hints: 0,
+            undos: 0,
+            time_attack: false,
+            move_limit: false,
+            blindfold: false,
+            daily: false,
+            efficiency: None,
+        }
+    }
+
+    /// The modes it was played in, joined by `+`, or `normal` for none.
+    fn mode(&self) -> String {
+        let modes: Vec<&str> = [
+            (self.time_attack, "time-attack"),
+            (self.move_limit, "move-limit"),
+            (self.blindfold, "blindfold"),
+            (self.daily, "daily"),
+        ]
+        .into_iter()
+        .filter_map(|(played, name)| played.then_some(name))
+        .collect();
+
+        if modes.is_empty() {
+            String::from("normal")
+        } else {
+            modes.join("+")
+        }
+    }
+}
+
+/// The counters for one grid size that solves cannot tell, as stored.
+///
+/// The solve counts date from before every solve was kept as a
+/// [`SolveRecord`]: a file from then loads with its totals here, and they
+/// go on adding to those worked out from the records.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+struct Counters {
+    shuffled: u32,
+    current_streak: u32,
+    longest_streak: u32,
+    timed_out: u32,
+    best_time_left: Option<Duration>,
+    move_limit_losses: u32,
+    peeks: u32,
+    did_not_finish: u32,
+    unfinished: bool, // Whether the latest scramble is still unsolved
+    #[serde(skip_serializing_if = "is_zero")]
+    solved: u32,
+    #[serde(skip_serializing_if = "is_zero")]
+    total_moves: u64,
+    #[serde(skip_serializing_if = "Duration::is_zero")]
+    total_time: Duration,
+    #[serde(skip_serializing_if = "is_zero")]
+    move_limit_wins: u32,
+    #[serde(skip_serializing_if = "is_zero")]
+    blindfold_solves: u32,
+    #[serde(skip_serializing_if = "is_zero")]
+    scored_solves: u32,
+    #[serde(skip_serializing_if = "is_zero")]
+    total_efficiency: u64,
+}
+
+fn is_zero<T: Default + PartialEq>(count: &T) -> bool {
+    *count == T::default()
+}
+
+/// Lifetime counters for one grid size.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct Stats {
+    pub shuffled: u32,
+    pub solved: u32,
+    pub total_moves: u64,
+    pub total_time: Duration,
+    /// Solves in a row without abandoning a scramble.
+    pub current_streak: u32,
+    pub longest_streak: u32,
+    /// Time attacks that ran out of time.
+    pub timed_out: u32,
+    /// The most time left on the clock when finishing a time attack.
+    pub best_time_left: Option<Duration>,
+    /// Move-limit challenges solved within their budget, which the casual
+    /// counters above leave out.
+    pub move_limit_wins: u32,
+    /// Move-limit challenges that ran out of moves.
+    pub move_limit_losses: u32,
+    /// The fastest time and fewest moves of the move-limit wins.
+    pub move_limit_best: Option<Best>,
+    /// Solves made with the tile labels hidden.
+    pub blindfold_solves: u32,
+    /// Times the hidden labels were revealed for a moment.
+    pub peeks: u32,
+    /// Relays abandoned before the last stage was solved.
+    pub did_not_finish: u32,
+    /// Solves with a known optimal length, which the average efficiency
+    /// covers.
+    pub scored_solves: u32,
+    /// The efficiency of every scored solve added up, in thousandths.
+    pub total_efficiency: u64,
+}
+
+impl Stats {
+    pub fn average_moves(&self) -> Option<f64> {
+        (self.solved > 0).then(|| self.total_moves as f64 / f64::from(self.solved))
+    }
+
+    pub fn average_time(&self) -> Option<Duration> {
+        (self.solved > 0).then(|| self.total_time / self.solved)
+    }
+
+    /// The average [ratio](Efficiency::ratio) of the scored solves.
+    pub fn average_efficiency(&self) -> Option<f64> {
+        (self.scored_solves > 0)
+            .then(|| self.total_efficiency as f64 / 1000.0 / f64::from(self.scored_solves))
+    }
+}
+
+impl Records {
+    /// The key records are kept under: "COLSxROWS", with a suffix for
+    /// variants that play differently enough to need their own bests.
+    pub fn key(rows: usize, cols: usize, wrap: bool) -> String {
+        if wrap {
+            format!("{cols}x{rows} wrap")
+        } else {
+            format!("{cols}x{rows}")
+        }
+    }
+
+    pub fn best(&self, key: &str) -> Option<Best> {
+        self.bests.get(key).copied()
+    }
+
+    /// The statistics for the board `key`, with the solve totals worked out
+    /// from its solves.
+    pub fn stats(&self, key: &str) -> Stats {
+        let counters = self.stats.get(key).copied().unwrap_or_default();
+        let mut stats = Stats {
+            shuffled: counters.shuffled,
+            solved: counters.solved,
+            total_moves: counters.total_moves,
+            total_time: counters.total_time,
+            current_streak: counters.current_streak,
+            longest_streak: counters.longest_streak,
+            timed_out: counters.timed_out,
+            best_time_left: counters.best_time_left,
+            move_limit_wins: counters.move_limit_wins,
+            move_limit_losses: counters.move_limit_losses,
+            move_limit_best: None,
+            blindfold_solves: counters.blindfold_solves,
+            peeks: counters.peeks,
+            did_not_finish: counters.did_not_finish,
+            scored_solves: counters.scored_solves,
+            total_efficiency: counters.total_efficiency,
+        };
+
+        for solve in self.solves.iter().filter(|solve| solve.board == key) {
+            stats.blindfold_solves += u32::from(solve.blindfold);
+            if solve.move_limit {
+                stats.move_limit_wins += 1;
+                stats.move_limit_best = Some(match stats.move_limit_best {
+                    Some(best) => Best {
+                        time: best.time.min(solve.time),
+                        moves: best.moves.min(solve.moves),
+                    },
+                    None => Best {
+                        time: solve.time,
+                        moves: solve.moves,
+                    },
+                });
+                continue;
+            }
+            stats.solved += 1;
+            stats.total_moves += u64::from(solve.moves);
+            stats.total_time += solve.time;
+            // Solves whose optimal length is only bounded are left out
+            if let Some(efficiency) = solve.efficiency.filter(|efficiency| efficiency.is_exact()) {
+                stats.scored_solves += 1;
+                stats.total_efficiency += (efficiency.ratio() * 1000.0).round() as u64;
+            }
+        }
+        stats
+    }
+
+    /// Statistics for every size and variant played so far, by key.
+    pub fn all_stats(&self) -> impl Iterator<Item = (&str, Stats)> {
+        self.stats
+            .keys()
+            .map(|size| (size.as_str(), self.stats(size)))
+    }
+
+    /// Every solve recorded, oldest first.
+    pub fn solves(&self) -> &[SolveRecord] {
+        &self.solves
+    }
+
+    /// Every solve as a row of CSV after the [`CSV_HEADER`], quoted as
+    /// RFC 4180 has it.
+    pub fn solves_csv(&self) -> String {
+        let mut csv = format!("{CSV_HEADER}\r\n");
+
+        for solve in &self.solves {
+            let (year, month, day) = solve.day.ymd();
+            let efficiency = match solve.efficiency {
+                Some(efficiency) if efficiency.is_exact() => format!("{}%", efficiency.percent()),
+                Some(efficiency) => format!(">={}%", efficiency.percent()),
+                None => String::new(),
+            };
+            let fields = [
+                format!("{year:04}-{month:02}-{day:02}"),
+                solve.board.clone(),
+                solve.mode(),
+                solve.moves.to_string(),
+                format!("{:.3}", solve.time.as_secs_f64()),
+                solve.hints.to_string(),
+                solve.undos.to_string(),
+                efficiency,
+            ];
+
+            let row: Vec<String> = fields.iter().map(|field| csv_field(field)).collect();
+            let _ = write!(csv, "{}\r\n", row.join(","));
+        }
+        csv
+    }
+
+    /// Counts a new scramble. Abandoning the previous one ends the streak.
+    pub fn record_shuffle(&mut self, key: &str) {
+        let stats = self.stats.entry(key.to_owned()).or_default();
+
+        if stats.unfinished {
+            stats.current_streak = 0;
+        }
+        stats.shuffled += 1;
+        stats.unfinished = true;
+    }
+
+    /// The result of `today`'s daily puzzle for the board `key`, if it has
+    /// been solved. Results from earlier days no longer count.
+    pub fn daily_result(&self, key: &str, today: Day) -> Option<Best> {
+        if self.daily.day != Some(today) {
+            return None;
+        }
+        self.daily.results.get(key).copied()
+    }
+
+    /// Records a solve of `today`'s daily puzzle. Only the first solve of
+    /// each board counts, so the daily cannot be replayed for a better score.
+    pub fn submit_daily(&mut self, key: &str, today: Day, time: Duration, moves: u32) {
+        if self.daily.day != Some(today) {
+            self.daily.day = Some(today);
+            self.daily.results.clear();
+        }
+        self.daily
+            .results
+            .entry(key.to_owned())
+            .or_insert(Best { time, moves });
+        self.daily.completed.insert(today);
+    }
+
+    /// Whether a daily puzzle of any size was solved on `day`.
+    pub fn daily_completed(&self, day: Day) -> bool {
+        self.daily.completed.contains(&day)
+    }
+
+    /// Consecutive days with a daily solved, up to `today`. A streak that
+    /// reached yesterday still stands until today's daily is missed.
+    pub fn daily_streak(&self, today: Day) -> u32 {
+        let mut expected = if self.daily_completed(today) {
+            today
+        } else {
+            today.previous()
+        };
+
+        let mut streak = 0;
+        for &day in self.daily.completed.range(..=expected).rev() {
+            if day != expected {
+                break;
+            }
+            streak += 1;
+            expected = day.previous();
+        }
+        streak
+    }
+
+    /// Counts a relay abandoned part of the way through as a failure, ending
+    /// the streak.
+    pub fn record_did_not_finish(&mut self, key: &str) {
+        let stats = self.stats.entry(key.to_owned()).or_default();
+        stats.did_not_finish += 1;
+        stats.current_streak = 0;
+        stats.unfinished = false;
+    }
+
+    /// Counts a race against the bot at `difficulty` as won or lost.
+    pub fn record_race(&mut self, difficulty: Difficulty, won: bool) {
+        let races = self.races.entry(difficulty).or_default();
+        if won {
+            races.won += 1;
+        } else {
+            races.lost += 1;
+        }
+    }
+
+    /// Races against the bot for every difficulty raced at so far.
+    pub fn all_races(&self) -> impl Iterator<Item = (Difficulty, RaceStats)> {
+        self.races
+            .iter()
+            .map(|(&difficulty, &races)| (difficulty, races))
+    }
+
+    /// Counts a time attack that ran out of time as a failure, ending the
+    /// streak.
+    pub fn record_timeout(&mut self, key: &str) {
+        let stats = self.stats.entry(key.to_owned()).or_default();
+        stats.timed_out += 1;
+        stats.current_streak = 0;
+        stats.unfinished = false;
+    }
+
+    /// Counts a move-limit challenge that ran out of moves. Challenges are
+    /// kept apart from casual play, so the streak stands. A win goes through
+    /// [`submit`](Self::submit).
+    pub fn record_move_limit_loss(&mut self, key: &str) {
+        self.stats
+            .entry(key.to_owned())
+            .or_default()
+            .move_limit_losses += 1;
+    }
+
+    /// Notes how efficient the latest solve of the board `key` was, if it
+    /// is still waiting to hear.
+    pub fn record_efficiency(&mut self, key: &str, efficiency: Efficiency) {
+        let latest = self
+            .solves
+            .iter_mut()
+            .rev()
+            .find(|solve| solve.board == key);
+
+        if let Some(solve) = latest
+            && solve.efficiency.is_none()
+        {
+            solve.efficiency = Some(efficiency);
+        }
+    }
+
+    /// Counts a peek at a blindfolded board.
+    pub fn record_peek(&mut self, key: &str) {
+        self.stats.entry(key.to_owned()).or_default().peeks += 1;
+    }
+
+    /// Scores a time attack solved with `time_left` on the clock, returning
+    /// whether that beats the best so far. The solve itself still goes
+    /// through [`submit`](Self::submit).
+    pub fn submit_time_left(&mut self, key: &str, time_left: Duration) -> bool {
+        let best = &mut self.stats.entry(key.to_owned()).or_default().best_time_left;
+        let improved = best.is_none_or(|best| time_left > best);
+
+        if improved {
+            *best = Some(time_left);
+        }
+        improved
+    }
+
+    /// Clears the statistics and solves for every size and the race tally,
+    /// keeping the personal bests.
+    pub fn reset_stats(&mut self) {
+        self.stats.clear();
+        self.solves.clear();
+        self.races.clear();
+    }
+
+    /// Records a finished solve in the statistics, returning whether it set a
+    /// new best time or move count. A move-limit win is measured against the
+    /// other wins only, and leaves the casual bests and streak alone.
+    pub fn submit(&mut self, solve: SolveRecord) -> bool {
+        let (time, moves) = (solve.time, solve.moves);
+        let key = solve.board.clone();
+
+        if solve.move_limit {
+            let best = self.stats(&key).move_limit_best;
+            self.solves.push(solve);
+            self.stats.entry(key).or_default();
+            return best.is_none_or(|best| time < best.time || moves < best.moves);
+        }
+        self.solves.push(solve);
+
+        let stats = self.stats.entry(key.clone()).or_default();
+        stats.current_streak += 1;
+        stats.longest_streak = stats.longest_streak.max(stats.current_streak);
+        stats.unfinished = false;
+
+        match self.bests.get_mut(&key) {
+            Some(best) => {
+                let improved = time < best.time || moves < best.moves;
+
+                best.time = best.time.min(time);
+                best.moves = best.moves.min(moves);
+                improved
+            }
+            None => {
+                self.bests.insert(key, Best { time, moves });
+                true
+            }
+        }
+    }
+}
+
+/// `field` as one CSV field: quoted, with its quotes doubled, if it holds a
+/// comma, a quote or a line break, and as it is otherwise.
+fn csv_field(field: &str) -> String {
+    if field.contains([',', '"', '\r', '\n']) {
+        format!("\"{}\"", field.replace('"', "\"\""))
+    } else {
+        field.to_owned()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::solver::OptimalLength;
+
+    fn size(rows: usize, cols: usize) -> String {
+        Records::key(rows, cols, false)
+    }
+
+    fn solve(key: &str, seconds: u64, moves: u32) -> SolveRecord {
+        SolveRecord::new(
+            key,
+            Day::from_days_since_epoch(20_000),
+            Duration::from_secs(seconds),
+            moves,
+        )
+    }
+
+    #[test]
+    fn first_solve_sets_the_record() {
+        let mut records = Records::default();
+
+        assert!(records.submit(solve(&size(4, 4), 60, 80)));
+        assert_eq!(
+            records.best(&size(4, 4)),
+            Some(Best {
+                time: Duration::from_secs(60),
+                moves: 80
+            })
+        );
+        assert_eq!(records.best(&size(3, 3)), None);
+    }
+
+    #[test]
+    fn time_and_moves_improve_independently() {
+        let mut records = Records::default();
+        records.submit(solve(&size(4, 4), 60, 80));
+
+        assert!(records.submit(solve(&size(4, 4), 50, 90)));
+        assert!(records.submit(solve(&size(4, 4), 70, 74)));
+        assert!(!records.submit(solve(&size(4, 4), 70, 90)));
+
+        let best = records.best(&size(4, 4)).unwrap();
+        assert_eq!(best.time, Duration::from_secs(50));
+        assert_eq!(best.moves, 74);
+    }
+
+    #[test]
+    fn averages_need_a_solve() {
+        let mut records = Records::default();
+        records.record_shuffle(&size(4, 4));
+
+        let stats = records.stats(&size(4, 4));
+        assert_eq!(stats.shuffled, 1);
+        assert_eq!(stats.average_moves(), None);
+        assert_eq!(stats.average_time(), None);
+        assert_eq!(records.stats(&size(3, 3)), Stats::default());
+    }
+
+    #[test]
+    fn averages_cover_every_solve() {
+        let mut records = Records::default();
+        records.submit(solve(&size(4, 4), 60, 80));
+        records.submit(solve(&size(4, 4), 30, 75));
+
+        let stats = records.stats(&size(4, 4));
+        assert_eq!(stats.solved, 2);
+        assert_eq!(stats.total_moves, 155);
+        assert_eq!(stats.average_moves(), Some(77.5));
+        assert_eq!(stats.average_time(), Some(Duration::from_secs(45)));
+    }
+
+    #[test]
+    fn abandoning_a_scramble_ends_the_streak() {
+        let mut records = Records::default();
+        for _ in 0..3 {
+            records.record_shuffle(&size(3, 3));
+            records.submit(solve(&size(3, 3), 10, 20));
+        }
+        records.record_shuffle(&size(3, 3));
+        records.record_shuffle(&size(3, 3));
+
+        let stats = records.stats(&size(3, 3));
+        assert_eq!(stats.current_streak, 0);
+        assert_eq!(stats.longest_streak, 3);
+        assert_eq!(stats.shuffled, 5);
+
+        records.reset_stats();
+        assert_eq!(records.stats(&size(3, 3)), Stats::default());
+        assert!(records.best(&size(3, 3)).is_some());
+    }
+
+    #[test]
+    fn sizes_are_tracked_separately() {
+        let mut records = Records::default();
+        records.submit(solve(&size(3, 4), 30, 40));
+
+        assert!(records.best(&size(4, 3)).is_none());
+        assert!(records.best(&size(3, 4)).is_some());
+    }
+
+    #[test]
+    fn wrapped_boards_keep_their_own_records() {
+        let mut records = Records::default();
+        records.submit(solve(&Records::key(4, 4, true), 5, 10));
+
+        assert_eq!(Records::key(4, 4, true), "4x4 wrap");
+        assert!(records.best(&size(4, 4)).is_none());
+        assert!(records.best(&Records::key(4, 4, true)).is_some());
+    }
+
+    #[test]
+    fn only_the_first_daily_solve_of_the_day_counts() {
+        let mut records = Records::default();
+        let today = Day::from_days_since_epoch(20_000);
+
+        assert_eq!(records.daily_result(&size(4, 4), today), None);
+        records.submit_daily(&size(4, 4), today, Duration::from_secs(90), 120);
+        records.submit_daily(&size(4, 4), today, Duration::from_secs(30), 60);
+
+        let result = records.daily_result(&size(4, 4), today).unwrap();
+        assert_eq!(result.time, Duration::from_secs(90));
+        assert_eq!(result.moves, 120);
+        assert_eq!(records.daily_result(&size(3, 3), today), None);
+
+        // The next day starts afresh
+        let tomorrow = Day::from_days_since_epoch(20_001);
+        assert_eq!(records.daily_result(&size(4, 4), tomorrow), None);
+    }
+
+    #[test]
+    fn daily_streaks_count_consecutive_days() {
+        let mut records = Records::default();
+        let day = Day::from_days_since_epoch;
+        for days in [10, 12, 13, 14] {
+            records.submit_daily(&size(3, 3), day(days), Duration::from_secs(10), 20);
+        }
+
+        assert_eq!(records.daily_streak(day(14)), 3);
+        // Yesterday's streak survives until today is over
+        assert_eq!(records.daily_streak(day(15)), 3);
+        assert_eq!(records.daily_streak(day(16)), 0);
+        assert_eq!(records.daily_streak(day(10)), 1);
+    }
+
+    #[test]
+    fn time_attacks_score_the_time_left() {
+        let mut records = Records::default();
+        records.record_shuffle(&size(3, 3));
+        records.submit(solve(&size(3, 3), 100, 40));
+
+        assert!(records.submit_time_left(&size(3, 3), Duration::from_secs(80)));
+        assert!(!records.submit_time_left(&size(3, 3), Duration::from_secs(60)));
+        assert!(records.submit_time_left(&size(3, 3), Duration::from_secs(90)));
+
+        records.record_shuffle(&size(3, 3));
+        records.record_timeout(&size(3, 3));
+        // The failure already ended the streak, without counting twice
+        records.record_shuffle(&size(3, 3));
+
+        let stats = records.stats(&size(3, 3));
+        assert_eq!(stats.best_time_left, Some(Duration::from_secs(90)));
+        assert_eq!(stats.timed_out, 1);
+        assert_eq!(stats.current_streak, 0);
+        assert_eq!(stats.longest_streak, 1);
+    }
+
+    #[test]
+    fn move_limit_outcomes_are_counted_apart() {
+        let mut records = Records::default();
+        records.record_shuffle(&size(4, 4));
+        records.submit(solve(&size(4, 4), 60, 80));
+        let challenge = |seconds, moves| SolveRecord {
+            move_limit: true,
+            ..solve(&size(4, 4), seconds, moves)
+        };
+        assert!(records.submit(challenge(40, 30)));
+        assert!(!records.submit(challenge(50, 35)));
+        assert!(records.submit(challenge(45, 28)));
+        records.record_move_limit_loss(&size(4, 4));
+
+        let stats = records.stats(&size(4, 4));
+        assert_eq!(stats.move_limit_wins, 3);
+        assert_eq!(stats.move_limit_losses, 1);
+        assert_eq!(
+            stats.move_limit_best,
+            Some(Best {
+                time: Duration::from_secs(40),
+                moves: 28
+            })
+        );
+        // The casual counters only see the casual solve
+        assert_eq!(stats.solved, 1);
+        assert_eq!(stats.total_moves, 80);
+        assert_eq!(stats.current_streak, 1);
+        assert_eq!(
+            records.best(&size(4, 4)),
+            Some(Best {
+                time: Duration::from_secs(60),
+                moves: 80
+            })
+        );
+    }
+
+    #[test]
+    fn average_efficiency_leaves_out_bounded_solves() {
+        let mut records = Records::default();
+        assert_eq!(records.stats(&size(4, 4)).average_efficiency(), None);
+
+        records.submit(solve(&size(4, 4), 60, 100));
+        records.record_efficiency(&size(4, 4), Efficiency::new(OptimalLength::Exact(50), 100));
+        // Only the first result for a solve counts
+        records.record_efficiency(&size(4, 4), Efficiency::new(OptimalLength::Exact(90), 100));
+        records.submit(solve(&size(4, 4), 60, 50));
+        records.record_efficiency(&size(4, 4), Efficiency::new(OptimalLength::Exact(50), 50));
+        records.submit(solve(&size(4, 4), 60, 100));
+        records.record_efficiency(
+            &size(4, 4),
+            Efficiency::new(OptimalLength::AtLeast(10), 100),
+        );
+
+        let stats = records.stats(&size(4, 4));
+        assert_eq!(stats.solved, 3);
+        assert_eq!(stats.scored_solves, 2);
+        assert_eq!(stats.average_efficiency(), Some(0.75));
+    }
+
+    #[test]
+    fn blindfold_solves_and_peeks_are_counted() {
+        let mut records = Records::default();
+        records.record_peek(&size(3, 3));
+        records.record_peek(&size(3, 3));
+        records.submit(SolveRecord {
+            blindfold: true,
+            ..solve(&size(3, 3), 25, 30)
+        });
+
+        let stats = records.stats(&size(3, 3));
+        assert_eq!(stats.blindfold_solves, 1);
+        assert_eq!(stats.peeks, 2);
+        assert_eq!(stats.solved, 1);
+    }
+
+    #[test]
+    fn races_are_tallied_by_difficulty_and_survive_a_round_trip() {
+        let mut records = Records::default();
+        records.record_race(Difficulty::Hard, true);
+        records.record_race(Difficulty::Hard, false);
+        records.record_race(Difficulty::Easy, true);
+
+        let json = serde_json::to_string(&records).unwrap();
+        let loaded: Records = serde_json::from_str(&json).unwrap();
+        assert_eq!(
+            loaded.all_races().collect::<Vec<_>>(),
+            [
+                (Difficulty::Easy, RaceStats { won: 1, lost: 0 }),
+                (Difficulty::Hard, RaceStats { won: 1, lost: 1 }),
+            ]
+        );
+
+        records.reset_stats();
+        assert_eq!(records.all_races().next(), None);
+    }
+
+    #[test]
+    fn solves_export_as_one_csv_row_each() {
+        let mut records = Records::default();
+        records.submit(SolveRecord {
+            hints: 2,
+            undos: 5,
+            time_attack: true,
+            daily: true,
+            ..SolveRecord::new(
+                &size(4, 4),
+                Day::from_days_since_epoch(19_723),
+                Duration::from_millis(72_250),
+                87,
+            )
+        });
+        records.record_efficiency(&size(4, 4), Efficiency::new(OptimalLength::Exact(58), 87));
+        records.submit(solve(&Records::key(3, 3, true), 9, 20));
+        records.record_efficiency(
+            &Records::key(3, 3, true),
+            Efficiency::new(OptimalLength::AtLeast(15), 20),
+        );
+
+        assert_eq!(
+            records.solves_csv(),
+            "date,board,mode,moves,time,hints,undos,efficiency\r\n\
+             2024-01-01,4x4,time-attack+daily,87,72.250,2,5,67%\r\n\
+             2024-10-04,3x3 wrap,normal,20,9.000,0,0,>=75%\r\n"
+        );
+        assert_eq!(records.solves().len(), 2);
+    }
+
+    #[test]
+    fn csv_fields_are_quoted_only_when_they_need_it() {
+        assert_eq!(csv_field("Anonymous"), "Anonymous");
+        assert_eq!(csv_field(""), "");
+        assert_eq!(csv_field("Smith, Jo"), "\"Smith, Jo\"");
+        assert_eq!(csv_field("Jo \"the Slider\""), "\"Jo \"\"the Slider\"\"\"");
+        assert_eq!(csv_field("two\nlines"), "\"two\nlines\"");
+        assert_eq!(csv_field("\""), "\"\"\"\"");
+    }
+
+    #[test]
+    fn aggregate_only_stats_load_as_a_baseline_for_new_solves() {
+        // As saved before solves were kept one by one
+        let old = r#"{
+            "bests": {"4x4": {"time": {"secs": 50, "nanos": 0}, "moves": 70}},
+            "stats": {"4x4": {
+                "shuffled": 4,
+                "solved": 3,
+                "total_moves": 240,
+                "total_time": {"secs": 180, "nanos": 0},
+                "current_streak": 2,
+                "longest_streak": 3,
+                "blindfold_solves": 1,
+                "scored_solves": 1,
+                "total_efficiency": 500
+            }}
+        }"#;
+        let mut records: Records = serde_json::from_str(old).unwrap();
+
+        let stats = records.stats(&size(4, 4));
+        assert_eq!((stats.shuffled, stats.solved), (4, 3));
+        assert_eq!(stats.average_moves(), Some(80.0));
+        assert_eq!(stats.average_efficiency(), Some(0.5));
+        assert!(records.solves().is_empty());
+
+        records.record_shuffle(&size(4, 4));
+        records.submit(solve(&size(4, 4), 20, 40));
+        records.record_efficiency(&size(4, 4), Efficiency::new(OptimalLength::Exact(40), 40));
+
+        let stats = records.stats(&size(4, 4));
+        assert_eq!(stats.solved, 4);
+        assert_eq!(stats.total_moves, 280);
+        assert_eq!(stats.average_time(), Some(Duration::from_secs(50)));
+        assert_eq!(stats.average_efficiency(), Some(0.75));
+        assert_eq!(stats.blindfold_solves, 1);
+        assert_eq!(stats.current_streak, 3);
+
+        // The baseline is saved along with the solves, and nothing doubles
+        let json = serde_json::to_string(&records).unwrap();
+        let reloaded: Records = serde_json::from_str(&json).unwrap();
+        assert_eq!(reloaded, records);
+        assert_eq!(reloaded.stats(&size(4, 4)), stats);
+        assert_eq!(
+            reloaded.best(&size(4, 4)).unwrap().time,
+            Duration::from_secs(20)
+        );
+    }
+}
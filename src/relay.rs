@@ -0,0 +1,132 @@
+use serde::{Deserialize, Serialize};
+
+/// The edge length of the board at each stage of a relay.
+pub const RELAY_SIZES: [usize; 3] = [3, 4, 5];
+
+/// Progress through a relay: a 3x3, a 4x4 and a 5x5 solved back to back,
+/// counted as one solve.
+///
+/// The relay banks the moves of every stage solved; the timer simply keeps
+/// running across stages.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Relay {
+    stage: usize, // Index into `RELAY_SIZES`, one past the end once finished
+    moves: u32,   // Made on the stages already solved
+}
+
+impl Relay {
+    pub const STAGES: usize = RELAY_SIZES.len();
+
+    /// The key relay results are kept under in the records and on the
+    /// leaderboard, apart from those of single boards.
+    pub fn key(wrap: bool) -> String {
+        if wrap {
+            String::from("3x3-5x5 relay wrap")
+        } else {
+            String::from("3x3-5x5 relay")
+        }
+    }
+
+    /// The stage being played, counting from 1, or [`STAGES`](Self::STAGES)
+    /// once finished.
+    pub fn stage(self) -> usize {
+        (self.stage + 1).min(Self::STAGES)
+    }
+
+    /// The edge length of the board for the stage being played.
+    pub fn size(self) -> usize {
+        RELAY_SIZES[self.stage.min(Self::STAGES - 1)]
+    }
+
+    pub fn is_finished(self) -> bool {
+        self.stage >= Self::STAGES
+    }
+
+    /// Banks a stage solved in `moves`, returning the edge length of the next
+    /// board, or `None` if that was the last stage.
+    pub fn complete_stage(&mut self, moves: u32) -> Option<usize> {
+        if self.is_finished() {
+            return None;
+        }
+
+        self.moves += moves;
+        self.stage += 1;
+        RELAY_SIZES.get(self.stage).copied()
+    }
+
+    /// The moves made across the whole relay, with `current` made on the
+    /// stage being played.
+    pub fn total_moves(self, current: u32) -> u32 {
+        if self.is_finished() {
+            self.moves
+        } else {
+            self.moves + current
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Puzzle, solver};
+
+    use rand::{SeedableRng, rngs::StdRng};
+
+    /// Scrambles a board of the stage's size and solves it, returning the
+    /// number of moves taken.
+    fn play_stage(relay: Relay, seed: u64) -> u32 {
+        let mut puzzle = Puzzle::new(relay.size(), relay.size());
+        // A short walk keeps the larger stages quick to solve
+        puzzle.scramble_walk(12, &mut StdRng::seed_from_u64(seed));
+        for direction in solver::solve(&puzzle, None).unwrap() {
+            assert!(puzzle.move_direction(direction));
+        }
+
+        assert!(puzzle.is_solved());
+        puzzle.moves()
+    }
+
+    #[test]
+    fn stages_grow_from_3x3_to_5x5_then_finish() {
+        let mut relay = Relay::default();
+        assert_eq!((relay.stage(), relay.size()), (1, 3));
+
+        assert_eq!(relay.complete_stage(20), Some(4));
+        assert_eq!((relay.stage(), relay.size()), (2, 4));
+        assert_eq!(relay.total_moves(5), 25);
+
+        assert_eq!(relay.complete_stage(50), Some(5));
+        assert_eq!((relay.stage(), relay.size()), (3, 5));
+        assert!(!relay.is_finished());
+
+        assert_eq!(relay.complete_stage(100), None);
+        assert!(relay.is_finished());
+        assert_eq!(relay.stage(), Relay::STAGES);
+        // Nothing further counts once the relay is over
+        assert_eq!(relay.total_moves(7), 170);
+        assert_eq!(relay.complete_stage(1), None);
+        assert_eq!(relay.total_moves(0), 170);
+    }
+
+    #[test]
+    fn every_stage_builds_a_board_of_its_size() {
+        let mut relay = Relay::default();
+        let (mut sizes, mut total) = (Vec::new(), 0);
+
+        while !relay.is_finished() {
+            sizes.push(relay.size());
+            let moves = play_stage(relay, sizes.len() as u64);
+            total += moves;
+            relay.complete_stage(moves);
+        }
+        assert_eq!(sizes, RELAY_SIZES);
+        assert_eq!(relay.total_moves(0), total);
+    }
+
+    #[test]
+    fn relays_keep_their_own_key() {
+        assert_eq!(Relay::key(false), "3x3-5x5 relay");
+        assert_ne!(Relay::key(true), Relay::key(false));
+        assert_ne!(Relay::key(false), crate::Records::key(5, 5, false));
+    }
+}
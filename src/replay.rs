@@ -0,0 +1,192 @@
+use crate::{Direction, Puzzle};
+
+use serde::{Deserialize, Serialize};
+
+use std::time::Duration;
+
+/// A recorded attempt: the scramble it started from and every single-tile
+/// move made since, with when it happened.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Replay {
+    start: Puzzle,
+    moves: Vec<(Direction, Duration)>, // Time since the first move, as the timer saw it
+}
+
+impl Replay {
+    /// Starts recording from `start`, normally a fresh scramble.
+    pub fn new(start: Puzzle) -> Self {
+        Self {
+            start,
+            moves: Vec::new(),
+        }
+    }
+
+    /// The position the replay starts from.
+    pub fn start(&self) -> &Puzzle {
+        &self.start
+    }
+
+    /// Every recorded move, oldest first.
+    pub fn moves(&self) -> &[(Direction, Duration)] {
+        &self.moves
+    }
+
+    pub fn len(&self) -> usize {
+        self.moves.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.moves.is_empty()
+    }
+
+    /// How long the recorded attempt took, up to its last move.
+    pub fn duration(&self) -> Duration {
+        self.moves.last().map_or(Duration::ZERO, |&(_, at)| at)
+    }
+
+    /// Whether the replay starts from the scramble `start`, towards the same
+    /// goal.
+    pub fn is_of(&self, start: &Puzzle) -> bool {
+        self.start.rows() == start.rows()
+            && self.start.tiles() == start.tiles()
+            && self.start.goal() == start.goal()
+            && self.start.wrap() == start.wrap()
+    }
+
+    /// Appends whatever moved `board`'s empty tile away from `previous_empty`
+    /// at time `at`. A line slide, or the undo of one, is recorded as one
+    /// move per tile shifted.
+    pub fn record(&mut self, previous_empty: (usize, usize), board: &Puzzle, at: Duration) {
+        let (row, col) = board.empty_position();
+        let (row_step, col_step) = (
+            (row as isize - previous_empty.0 as isize).signum(),
+            (col as isize - previous_empty.1 as isize).signum(),
+        );
+        let length = row.abs_diff(previous_empty.0) + col.abs_diff(previous_empty.1);
+
+        // Tiles slide the opposite way to the empty tile
+        let direction = match (row_step, col_step) {
+            (1, _) => Direction::Up,
+            (-1, _) => Direction::Down,
+            (_, 1) => Direction::Left,
+            (_, -1) => Direction::Right,
+            _ => return,
+        };
+
+        // Wrapping boards have no line slides, so a long jump is a single
+        // move around the edge, seen from the other side
+        if board.wrap() && length > 1 {
+            self.moves.push((direction.opposite(), at));
+        } else {
+            self.moves
+                .extend(std::iter::repeat_n((direction, at), length));
+        }
+    }
+
+    /// The board after the first `count` recorded moves.
+    pub fn position(&self, count: usize) -> Puzzle {
+        let mut board = self.start.clone();
+        for index in 0..count.min(self.moves.len()) {
+            self.apply(index, &mut board);
+        }
+        board
+    }
+
+    /// Plays recorded move `index` on `board`, which should be the position
+    /// reached by the moves before it.
+    pub fn apply(&self, index: usize, board: &mut Puzzle) {
+        let (direction, _) = self.moves[index];
+
+        // Unlike `move_direction`, this carries on past a solved board
+        if let Some((row, col)) = board.tile_for_direction(direction) {
+            board.move_tile(row, col);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::SlideCount;
+
+    use rand::{Rng, SeedableRng, rngs::StdRng};
+
+    /// Plays random clicks, undos and redos on `puzzle`, recording each.
+    fn play_randomly(puzzle: &mut Puzzle, replay: &mut Replay, rng: &mut StdRng) {
+        for turn in 0..300 {
+            let previous_empty = puzzle.empty_position();
+            let at = Duration::from_millis(turn * 250);
+
+            match rng.gen_range(0..10) {
+                0 => {
+                    puzzle.undo();
+                }
+                1 => {
+                    puzzle.redo();
+                }
+                _ => {
+                    let row = rng.gen_range(0..puzzle.rows());
+                    let col = rng.gen_range(0..puzzle.cols());
+                    puzzle.move_line(row, col, SlideCount::default());
+                }
+            }
+            replay.record(previous_empty, puzzle, at);
+        }
+    }
+
+    #[test]
+    fn replaying_a_random_game_reproduces_the_end_state() {
+        let mut rng = StdRng::seed_from_u64(42);
+
+        for (rows, cols, wrap) in [(3, 3, false), (4, 4, false), (3, 5, false), (4, 4, true)] {
+            let mut puzzle = Puzzle::with_seed(rows, cols, 7);
+            puzzle.set_wrap(wrap);
+            puzzle.shuffle();
+            let mut replay = Replay::new(puzzle.clone());
+
+            play_randomly(&mut puzzle, &mut replay, &mut rng);
+
+            assert_eq!(replay.position(replay.len()).tiles(), puzzle.tiles());
+            assert_eq!(replay.position(0).tiles(), replay.start().tiles());
+        }
+    }
+
+    #[test]
+    fn replaying_a_solve_ends_solved() {
+        let mut puzzle = Puzzle::with_seed(3, 3, 3);
+        puzzle.shuffle();
+        let mut replay = Replay::new(puzzle.clone());
+
+        let solution = crate::solver::solve(&puzzle, None).expect("3x3 boards solve quickly");
+        for (turn, direction) in solution.into_iter().enumerate() {
+            let previous_empty = puzzle.empty_position();
+            puzzle.move_direction(direction);
+            replay.record(previous_empty, &puzzle, Duration::from_secs(turn as u64));
+        }
+
+        let end = replay.position(replay.len());
+        assert!(end.is_solved());
+        assert_eq!(end.tiles(), puzzle.tiles());
+        assert!(replay.moves().windows(2).all(|pair| pair[0].1 <= pair[1].1));
+    }
+
+    #[test]
+    fn line_slides_record_one_move_per_tile() {
+        let mut puzzle = Puzzle::new(4, 4);
+        let mut replay = Replay::new(puzzle.clone());
+        let previous_empty = puzzle.empty_position();
+
+        puzzle.move_line(3, 0, SlideCount::default());
+        replay.record(previous_empty, &puzzle, Duration::ZERO);
+
+        assert_eq!(replay.len(), 3);
+        assert!(
+            replay
+                .moves()
+                .iter()
+                .all(|&(direction, _)| direction == Direction::Right)
+        );
+        assert_eq!(replay.position(3).tiles(), puzzle.tiles());
+    }
+}
@@ -0,0 +1,241 @@
+use crate::{
+    Day, MigrateError, Puzzle, Relay,
+    storage::{self, Persistence, Versioned},
+};
+
+use serde::{Deserialize, Serialize};
+
+use std::time::Duration;
+
+/// How many save slots there are.
+pub const SAVE_SLOTS: usize = 8;
+
+/// Everything needed to resume a game: the board with its history, the
+/// time spent on it so far, the relay or daily puzzle it is part of, if any,
+/// and the help taken on the attempt.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct SavedGame {
+    pub puzzle: Puzzle,
+    pub elapsed: Duration,
+    #[serde(default)]
+    pub relay: Option<Relay>,
+    #[serde(default)]
+    pub daily: Option<Day>,
+    #[serde(default)]
+    pub hints: u32,
+    #[serde(default)]
+    pub undos: u32,
+    #[serde(default)]
+    pub peeks: u32,
+    /// Whether auto-solve has played any of it.
+    #[serde(default)]
+    pub assisted: bool,
+}
+
+impl Versioned for SavedGame {
+    const VERSION: u32 = 1;
+}
+
+impl SavedGame {
+    pub fn to_json(&self) -> String {
+        storage::to_json(self).expect("saved games always serialize")
+    }
+
+    /// Parses and validates a saved game written in any version.
+    pub fn from_json(json: &str) -> Result<Self, MigrateError> {
+        storage::parse(json).map(|loaded| loaded.document)
+    }
+}
+
+/// A game parked under a name, in one of the [`SAVE_SLOTS`].
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SaveSlot {
+    pub name: String,
+    pub game: SavedGame,
+}
+
+impl Versioned for SaveSlot {
+    const VERSION: u32 = 1;
+}
+
+/// What a save slot holds, as last read.
+#[derive(Debug, Clone)]
+pub enum SlotContents {
+    Empty,
+    Saved(Box<SaveSlot>),
+    /// A document that could not be read, and why. Saving to the slot
+    /// replaces it.
+    Unreadable(String),
+}
+
+impl SlotContents {
+    pub fn saved(&self) -> Option<&SaveSlot> {
+        match self {
+            SlotContents::Saved(slot) => Some(slot),
+            _ => None,
+        }
+    }
+}
+
+/// The document slot `index`, counting from 0, is kept in.
+pub fn slot_file(index: usize) -> String {
+    format!("slot-{}.json", index + 1)
+}
+
+/// Every slot, each in its own document so that one which cannot be read
+/// leaves the others be.
+pub fn read_slots(storage: &impl Persistence) -> Vec<SlotContents> {
+    (0..SAVE_SLOTS)
+        .map(
+            |index| match storage::load::<SaveSlot>(storage, &slot_file(index)) {
+                None => SlotContents::Empty,
+                Some(Ok(loaded)) => SlotContents::Saved(Box::new(loaded.document)),
+                Some(Err(error)) => SlotContents::Unreadable(error.to_string()),
+            },
+        )
+        .collect()
+}
+
+pub fn write_slot(storage: &impl Persistence, index: usize, slot: &SaveSlot) -> Result<(), String> {
+    storage::save(storage, &slot_file(index), slot)
+}
+
+pub fn clear_slot(storage: &impl Persistence, index: usize) -> Result<(), String> {
+    storage.remove(&slot_file(index))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::GameMode;
+
+    #[test]
+    fn scrambled_games_round_trip() {
+        let mut puzzle = Puzzle::new(4, 4);
+        puzzle.shuffle_with_seed(21);
+        let (row, col) = puzzle.movable_tiles()[0];
+        puzzle.move_tile(row, col);
+
+        let saved = SavedGame {
+            puzzle,
+            elapsed: Duration::from_millis(12_345),
+            ..SavedGame::default()
+        };
+        let loaded = SavedGame::from_json(&saved.to_json()).unwrap();
+
+        assert_eq!(loaded.elapsed, saved.elapsed);
+        assert_eq!(loaded.puzzle.tiles(), saved.puzzle.tiles());
+        assert_eq!(loaded.puzzle.moves(), 1);
+        assert_eq!(loaded.puzzle.seed(), Some(21));
+        assert_eq!(
+            loaded.puzzle.empty_position(),
+            saved.puzzle.empty_position()
+        );
+
+        // History survives too
+        let mut loaded = loaded.puzzle;
+        assert!(loaded.undo());
+        assert!(!loaded.can_retry());
+    }
+
+    #[test]
+    fn rectangular_games_round_trip() {
+        let mut puzzle = Puzzle::new(3, 5);
+        puzzle.shuffle();
+
+        let saved = SavedGame {
+            puzzle,
+            elapsed: Duration::ZERO,
+            ..SavedGame::default()
+        };
+        let loaded = SavedGame::from_json(&saved.to_json()).unwrap();
+
+        assert_eq!((loaded.puzzle.rows(), loaded.puzzle.cols()), (3, 5));
+        assert_eq!(loaded.puzzle.tiles(), saved.puzzle.tiles());
+    }
+
+    #[test]
+    fn time_attacks_keep_their_clock() {
+        let mut puzzle = Puzzle::new(3, 3);
+        puzzle.shuffle();
+        puzzle.set_mode(GameMode::TimeAttack {
+            remaining: Duration::from_secs(42),
+        });
+
+        let saved = SavedGame {
+            puzzle,
+            elapsed: Duration::from_secs(138),
+            ..SavedGame::default()
+        };
+        let loaded = SavedGame::from_json(&saved.to_json()).unwrap();
+
+        assert_eq!(loaded.puzzle.mode(), saved.puzzle.mode());
+        assert!(!loaded.puzzle.is_out_of_time());
+    }
+
+    fn game_json(tiles: &str) -> String {
+        format!(
+            r#"{{"puzzle": {{"rows": 2, "cols": 2, "tiles": {tiles}, "moves": 0}}, "elapsed": {{"secs": 0, "nanos": 0}}}}"#
+        )
+    }
+
+    #[test]
+    fn invalid_boards_are_rejected() {
+        assert!(SavedGame::from_json(&game_json("[1, 2, 3, 0]")).is_ok());
+
+        // Duplicate tile, two blanks, out of range, wrong length
+        assert!(SavedGame::from_json(&game_json("[1, 1, 3, 0]")).is_err());
+        assert!(SavedGame::from_json(&game_json("[1, 0, 3, 0]")).is_err());
+        assert!(SavedGame::from_json(&game_json("[1, 2, 4, 0]")).is_err());
+        assert!(SavedGame::from_json(&game_json("[1, 2, 0]")).is_err());
+        assert!(SavedGame::from_json("not json").is_err());
+    }
+
+    #[cfg(not(target_arch = "wasm32"))]
+    #[test]
+    fn slots_are_kept_apart() {
+        use crate::storage::FileStorage;
+
+        let dir = std::env::temp_dir().join(format!("iced_15_puzzle-slots-{}", std::process::id()));
+        let files = FileStorage::new(&dir);
+        assert!(
+            read_slots(&files)
+                .iter()
+                .all(|slot| matches!(slot, SlotContents::Empty))
+        );
+
+        let mut puzzle = Puzzle::new(5, 5);
+        puzzle.shuffle_with_seed(4);
+        let slot = SaveSlot {
+            name: String::from("Big one"),
+            game: SavedGame {
+                puzzle,
+                elapsed: Duration::from_secs(75),
+                daily: Some(Day::from_days_since_epoch(20_000)),
+                hints: 2,
+                ..SavedGame::default()
+            },
+        };
+        write_slot(&files, 0, &slot).unwrap();
+        write_slot(&files, 2, &slot).unwrap();
+        std::fs::write(files.path(&slot_file(2)), "{ not quite").unwrap();
+
+        let slots = read_slots(&files);
+        assert_eq!(slots.len(), SAVE_SLOTS);
+        let loaded = slots[0].saved().unwrap();
+        assert_eq!(loaded.name, "Big one");
+        assert_eq!(loaded.game.puzzle.tiles(), slot.game.puzzle.tiles());
+        assert_eq!(loaded.game.daily, slot.game.daily);
+        assert_eq!(loaded.game.hints, 2);
+        // The broken slot takes none of the others with it
+        assert!(matches!(slots[2], SlotContents::Unreadable(_)));
+        assert!(matches!(slots[1], SlotContents::Empty));
+
+        clear_slot(&files, 0).unwrap();
+        clear_slot(&files, 1).unwrap();
+        assert!(matches!(read_slots(&files)[0], SlotContents::Empty));
+
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+}
@@ -0,0 +1,325 @@
+//! Short codes for a position, to share a board by reading it out or typing
+//! it in on another device.
+//!
+//! A code is one number: the rank of the tiles' arrangement among all those
+//! of its size, in the order of their Lehmer codes, with the size as its
+//! lowest digit. The number is written in Crockford's base 32, most
+//! significant digit first, and followed by a check character that catches
+//! any one mistyped character and any two swapped neighbours. Codes read the
+//! same in either case, with `-` or spaces anywhere, and with `O` for `0` and
+//! `I` or `L` for `1`.
+//!
+//! A code holds only the tiles, not the goal, wrapping or any mode.
+
+use crate::{CodeError, MAX_GRID_SIZE, MIN_GRID_SIZE, Puzzle};
+
+const DIGITS: &[u8; 32] = b"0123456789ABCDEFGHJKMNPQRSTVWXYZ";
+// The check character is one of the digits or of these, for 32 to 36
+const CHECK_SYMBOLS: &[u8; 5] = b"*~$=U";
+const CHECK_MODULUS: usize = 37;
+
+// Rows and columns each come in this many sizes
+const SIZES: usize = MAX_GRID_SIZE - MIN_GRID_SIZE + 1;
+
+/// The most digits before the check character, which the last arrangement of
+/// the largest board takes.
+const MAX_DIGITS: usize = 107;
+
+/// The code for the position on `puzzle`, which
+/// [`decode_board`] reads back.
+pub fn encode_board(puzzle: &Puzzle) -> String {
+    let values: Vec<u16> = puzzle
+        .tiles()
+        .iter()
+        .map(|tile| tile.value().unwrap_or(0))
+        .collect();
+    let cells = values.len();
+
+    let mut number = Number::default();
+    for (cell, &value) in values.iter().enumerate() {
+        let smaller_after = values[cell + 1..]
+            .iter()
+            .filter(|&&later| later < value)
+            .count();
+        number.mul_add(cells - cell, smaller_after);
+    }
+    let size = (puzzle.rows() - MIN_GRID_SIZE) * SIZES + puzzle.cols() - MIN_GRID_SIZE;
+    number.mul_add(SIZES * SIZES, size);
+
+    let mut digits = Vec::new();
+    loop {
+        digits.push(number.div_rem(DIGITS.len()));
+        if number.is_zero() {
+            break;
+        }
+    }
+    digits.reverse();
+
+    let mut code: String = digits
+        .iter()
+        .map(|&digit| char::from(DIGITS[digit]))
+        .collect();
+    code.push(check_character(checksum(&digits)));
+    code
+}
+
+/// The board `code` stands for, made by [`encode_board`], as a fresh
+/// scramble in the default goal.
+pub fn decode_board(code: &str) -> Result<Puzzle, CodeError> {
+    let mut characters: Vec<char> = code
+        .chars()
+        .filter(|&character| !character.is_whitespace() && character != '-')
+        .collect();
+    let check = characters.pop().ok_or(CodeError::TooShort)?;
+    if characters.is_empty() {
+        return Err(CodeError::TooShort);
+    }
+    if characters.len() > MAX_DIGITS {
+        return Err(CodeError::TooLong);
+    }
+
+    let digits = characters
+        .iter()
+        .map(|&character| digit_value(character).ok_or(CodeError::InvalidCharacter(character)))
+        .collect::<Result<Vec<_>, _>>()?;
+    let check = check_value(check).ok_or(CodeError::InvalidCharacter(check))?;
+    if check != checksum(&digits) {
+        return Err(CodeError::ChecksumMismatch);
+    }
+
+    let mut number = Number::default();
+    for &digit in &digits {
+        number.mul_add(DIGITS.len(), digit);
+    }
+    let size = number.div_rem(SIZES * SIZES);
+    let (rows, cols) = (size / SIZES + MIN_GRID_SIZE, size % SIZES + MIN_GRID_SIZE);
+    let cells = rows * cols;
+
+    // The Lehmer code comes out last cell first
+    let mut smaller_after = vec![0; cells];
+    for cell in (0..cells).rev() {
+        smaller_after[cell] = number.div_rem(cells - cell);
+    }
+    if !number.is_zero() {
+        return Err(CodeError::NoSuchBoard);
+    }
+
+    let mut unused: Vec<u16> = (0..cells as u16).collect();
+    let values: Vec<u16> = smaller_after
+        .into_iter()
+        .map(|smaller| unused.remove(smaller))
+        .collect();
+    Puzzle::from_grid(rows, cols, &values).map_err(|_| CodeError::NoSuchBoard)
+}
+
+fn digit_value(character: char) -> Option<usize> {
+    match character.to_ascii_uppercase() {
+        'O' => Some(0),
+        'I' | 'L' => Some(1),
+        upper => DIGITS.iter().position(|&digit| char::from(digit) == upper),
+    }
+}
+
+fn check_value(character: char) -> Option<usize> {
+    digit_value(character).or_else(|| {
+        let upper = character.to_ascii_uppercase();
+        CHECK_SYMBOLS
+            .iter()
+            .position(|&symbol| char::from(symbol) == upper)
+            .map(|index| DIGITS.len() + index)
+    })
+}
+
+fn check_character(value: usize) -> char {
+    char::from(match DIGITS.get(value) {
+        Some(&digit) => digit,
+        None => CHECK_SYMBOLS[value - DIGITS.len()],
+    })
+}
+
+/// The digits weighted by their place, so that swapping two neighbours
+/// changes the sum, modulo a prime so that changing any one digit does.
+fn checksum(digits: &[usize]) -> usize {
+    digits
+        .iter()
+        .enumerate()
+        .map(|(place, &digit)| digit * (place % (CHECK_MODULUS - 1) + 1))
+        .sum::<usize>()
+        % CHECK_MODULUS
+}
+
+/// A whole number of any size, in 32-bit limbs, least significant first,
+/// without high zero limbs.
+#[derive(Debug, Default)]
+struct Number(Vec<u32>);
+
+impl Number {
+    /// Multiplies the number by `factor` and adds `digit`, less than it.
+    fn mul_add(&mut self, factor: usize, digit: usize) {
+        let mut carry = digit as u64;
+        for limb in &mut self.0 {
+            let product = u64::from(*limb) * factor as u64 + carry;
+            *limb = product as u32;
+            carry = product >> 32;
+        }
+        if carry > 0 {
+            self.0.push(carry as u32);
+        }
+    }
+
+    /// Divides the number by `divisor`, returning the remainder.
+    fn div_rem(&mut self, divisor: usize) -> usize {
+        let mut remainder = 0;
+        for limb in self.0.iter_mut().rev() {
+            let dividend = (remainder << 32) | u64::from(*limb);
+            *limb = (dividend / divisor as u64) as u32;
+            remainder = dividend % divisor as u64;
+        }
+        while self.0.last() == Some(&0) {
+            self.0.pop();
+        }
+        remainder as usize
+    }
+
+    fn is_zero(&self) -> bool {
+        self.0.is_empty()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn values(puzzle: &Puzzle) -> Vec<u16> {
+        puzzle
+            .tiles()
+            .iter()
+            .map(|tile| tile.value().unwrap_or(0))
+            .collect()
+    }
+
+    fn round_trip(rows: usize, cols: usize, board: &[u16]) -> String {
+        let puzzle = Puzzle::from_grid(rows, cols, board).unwrap();
+        let code = encode_board(&puzzle);
+        let decoded = decode_board(&code).unwrap();
+
+        assert_eq!((decoded.rows(), decoded.cols()), (rows, cols), "{code}");
+        assert_eq!(values(&decoded), board, "{code}");
+        code
+    }
+
+    /// The arrangement of `cells` tiles at `rank` in Lehmer order.
+    fn arrangement(cells: usize, mut rank: usize) -> Vec<u16> {
+        let mut unused: Vec<u16> = (0..cells as u16).collect();
+        let mut place_value: usize = (1..cells).product();
+        let mut board = Vec::with_capacity(cells);
+        for cell in 0..cells {
+            board.push(unused.remove(rank / place_value));
+            rank %= place_value;
+            place_value /= (cells - 1 - cell).max(1);
+        }
+        board
+    }
+
+    #[test]
+    fn every_3x3_and_smaller_position_comes_back_with_its_own_code() {
+        for (rows, cols) in [(2, 2), (2, 3), (3, 2), (3, 3)] {
+            let cells = rows * cols;
+            let mut codes = std::collections::HashSet::new();
+            for rank in 0..(1..=cells).product() {
+                assert!(codes.insert(round_trip(rows, cols, &arrangement(cells, rank))));
+            }
+        }
+    }
+
+    #[test]
+    fn larger_boards_come_back_whatever_their_shape() {
+        for (rows, cols) in [(4, 4), (5, 5), (3, 5), (5, 3), (10, 2), (10, 10)] {
+            let mut puzzle = Puzzle::new(rows, cols);
+            round_trip(rows, cols, &values(&puzzle));
+            for seed in 0..20 {
+                puzzle.shuffle_with_seed(seed);
+                round_trip(rows, cols, &values(&puzzle));
+            }
+            let reversed: Vec<u16> = (0..(rows * cols) as u16).rev().collect();
+            round_trip(rows, cols, &reversed);
+        }
+
+        // The highest rank of the largest board takes the most digits
+        let reversed: Vec<u16> = (0..100).rev().collect();
+        assert_eq!(round_trip(10, 10, &reversed).len(), MAX_DIGITS + 1);
+    }
+
+    #[test]
+    fn a_4x4_code_is_a_dozen_characters() {
+        let mut puzzle = Puzzle::new(4, 4);
+        for seed in 0..50 {
+            puzzle.shuffle_with_seed(seed);
+            assert!(encode_board(&puzzle).len() <= 12);
+        }
+    }
+
+    #[test]
+    fn every_single_typo_and_swap_is_caught() {
+        let mut puzzle = Puzzle::new(4, 4);
+        puzzle.shuffle_with_seed(3);
+        let code: Vec<char> = encode_board(&puzzle).chars().collect();
+
+        for place in 0..code.len() {
+            for &digit in DIGITS {
+                let mut typo = code.clone();
+                typo[place] = char::from(digit);
+                if typo != code {
+                    let typo: String = typo.into_iter().collect();
+                    assert!(decode_board(&typo).is_err(), "{typo}");
+                }
+            }
+        }
+        for place in 0..code.len() - 1 {
+            let mut swapped = code.clone();
+            swapped.swap(place, place + 1);
+            if swapped != code {
+                let swapped: String = swapped.into_iter().collect();
+                assert!(decode_board(&swapped).is_err(), "{swapped}");
+            }
+        }
+    }
+
+    #[test]
+    fn codes_forgive_case_separators_and_look_alike_letters() {
+        let mut puzzle = Puzzle::new(4, 4);
+        puzzle.shuffle_with_seed(11);
+        let code = encode_board(&puzzle);
+        let (first, second) = code.split_at(6);
+        let relaxed = format!(" {}-{} ", first.to_lowercase(), second)
+            .replace('0', "o")
+            .replace('1', "l");
+
+        assert_eq!(values(&decode_board(&relaxed).unwrap()), values(&puzzle));
+    }
+
+    #[test]
+    fn malformed_codes_say_what_is_wrong() {
+        assert_eq!(decode_board("").err(), Some(CodeError::TooShort));
+        assert_eq!(decode_board(" - ").err(), Some(CodeError::TooShort));
+        assert_eq!(decode_board("7").err(), Some(CodeError::TooShort));
+        assert_eq!(
+            decode_board("12U45").err(),
+            Some(CodeError::InvalidCharacter('U'))
+        );
+        assert_eq!(
+            decode_board("12345!").err(),
+            Some(CodeError::InvalidCharacter('!'))
+        );
+        assert_eq!(
+            decode_board(&"1".repeat(MAX_DIGITS + 2)).err(),
+            Some(CodeError::TooLong)
+        );
+
+        // Too large a number for even the largest board
+        let digits = vec![DIGITS.len() - 1; MAX_DIGITS];
+        let mut code = "Z".repeat(MAX_DIGITS);
+        code.push(check_character(checksum(&digits)));
+        assert_eq!(decode_board(&code).err(), Some(CodeError::NoSuchBoard));
+    }
+}
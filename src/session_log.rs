@@ -0,0 +1,296 @@
+//! A log of what happened in a session, for streams and bug reports.
+//!
+//! Each entry is one line: the time into the session as `HH:MM:SS`, an event
+//! name, then `key=value` fields separated by spaces, in a fixed order. The
+//! copied log starts with a [`LOG_HEADER`] naming the format's version, which
+//! changes whenever a line would read differently.
+
+use crate::{Difficulty, Efficiency, solver::OptimalLength};
+
+use std::collections::VecDeque;
+use std::fmt;
+use std::time::Duration;
+
+/// The most entries kept; older ones make way for new ones.
+pub const MAX_LOG_ENTRIES: usize = 300;
+
+/// The first line of a copied log.
+pub const LOG_HEADER: &str = "# session log v1";
+
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct SessionLog {
+    entries: VecDeque<LogEntry>, // Oldest first
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LogEntry {
+    /// How far into the session it happened.
+    pub at: Duration,
+    pub event: LogEvent,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum LogEvent {
+    /// A fresh scramble, and the seed that reproduces it if it has one.
+    Shuffle {
+        rows: usize,
+        cols: usize,
+        difficulty: Difficulty,
+        seed: Option<u64>,
+    },
+    /// A board typed or pasted in.
+    Import {
+        rows: usize,
+        cols: usize,
+    },
+    /// A solve, with its optimal length once that has been worked out.
+    Solve {
+        moves: u32,
+        time: Duration,
+        optimal: Option<OptimalLength>,
+    },
+    Hint,
+    /// A mode turned on or off for the scrambles to come.
+    Mode {
+        mode: LoggedMode,
+        on: bool,
+    },
+}
+
+/// The modes whose changes are logged.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LoggedMode {
+    TimeAttack,
+    MoveLimit,
+    Blindfold,
+    Wrap,
+}
+
+impl SessionLog {
+    pub fn push(&mut self, at: Duration, event: LogEvent) {
+        if self.entries.len() == MAX_LOG_ENTRIES {
+            self.entries.pop_front();
+        }
+        self.entries.push_back(LogEntry { at, event });
+    }
+
+    /// Fills in the optimal length of the latest solve, if it is still
+    /// waiting for one.
+    pub fn set_optimal(&mut self, length: OptimalLength) {
+        let latest = self
+            .entries
+            .iter_mut()
+            .rev()
+            .find_map(|entry| match &mut entry.event {
+                LogEvent::Solve { optimal, .. } => Some(optimal),
+                _ => None,
+            });
+
+        if let Some(optimal @ None) = latest {
+            *optimal = Some(length);
+        }
+    }
+
+    pub fn newest_first(&self) -> impl Iterator<Item = &LogEntry> {
+        self.entries.iter().rev()
+    }
+
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// The whole log as plain text, oldest first as a log file reads, after
+    /// the [`LOG_HEADER`].
+    pub fn text(&self) -> String {
+        let mut text = String::from(LOG_HEADER);
+        for entry in &self.entries {
+            text.push('\n');
+            text.push_str(&entry.to_string());
+        }
+        text
+    }
+}
+
+impl fmt::Display for LogEntry {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let seconds = self.at.as_secs();
+        write!(
+            f,
+            "{:02}:{:02}:{:02} ",
+            seconds / 3600,
+            seconds / 60 % 60,
+            seconds % 60
+        )?;
+
+        match self.event {
+            LogEvent::Shuffle {
+                rows,
+                cols,
+                difficulty,
+                seed,
+            } => {
+                write!(
+                    f,
+                    "shuffle size={cols}x{rows} difficulty={}",
+                    difficulty_name(difficulty)
+                )?;
+                match seed {
+                    Some(seed) => write!(f, " seed={seed}"),
+                    None => Ok(()),
+                }
+            }
+            LogEvent::Import { rows, cols } => write!(f, "import size={cols}x{rows}"),
+            LogEvent::Solve {
+                moves,
+                time,
+                optimal,
+            } => {
+                write!(f, "solve moves={moves} time={:.3}", time.as_secs_f64())?;
+                let Some(optimal) = optimal else {
+                    return Ok(());
+                };
+                let percent = Efficiency::new(optimal, moves).percent();
+                match optimal {
+                    OptimalLength::Exact(length) => {
+                        write!(f, " optimal={length} efficiency={percent}%")
+                    }
+                    OptimalLength::AtLeast(length) => write!(
+                        f,
+                        " optimal_at_least={length} efficiency_at_least={percent}%"
+                    ),
+                }
+            }
+            LogEvent::Hint => f.write_str("hint"),
+            LogEvent::Mode { mode, on } => write!(
+                f,
+                "mode {}={}",
+                mode_name(mode),
+                if on { "on" } else { "off" }
+            ),
+        }
+    }
+}
+
+// The names in the log stay put even if the ones shown in the app change
+
+fn difficulty_name(difficulty: Difficulty) -> &'static str {
+    match difficulty {
+        Difficulty::Easy => "easy",
+        Difficulty::Medium => "medium",
+        Difficulty::Hard => "hard",
+        Difficulty::Expert => "expert",
+    }
+}
+
+fn mode_name(mode: LoggedMode) -> &'static str {
+    match mode {
+        LoggedMode::TimeAttack => "time-attack",
+        LoggedMode::MoveLimit => "move-limit",
+        LoggedMode::Blindfold => "blindfold",
+        LoggedMode::Wrap => "wrap",
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn entries_read_one_per_line_oldest_first() {
+        let mut log = SessionLog::default();
+        log.push(
+            Duration::from_secs(3),
+            LogEvent::Shuffle {
+                rows: 4,
+                cols: 4,
+                difficulty: Difficulty::Hard,
+                seed: Some(1234),
+            },
+        );
+        log.push(Duration::from_secs(40), LogEvent::Hint);
+        log.push(
+            Duration::from_millis(3_725_500),
+            LogEvent::Solve {
+                moves: 87,
+                time: Duration::from_millis(72_250),
+                optimal: None,
+            },
+        );
+        log.push(
+            Duration::from_secs(3726),
+            LogEvent::Mode {
+                mode: LoggedMode::TimeAttack,
+                on: true,
+            },
+        );
+        log.push(
+            Duration::from_secs(3730),
+            LogEvent::Import { rows: 3, cols: 5 },
+        );
+
+        assert_eq!(
+            log.text(),
+            "# session log v1\n\
+             00:00:03 shuffle size=4x4 difficulty=hard seed=1234\n\
+             00:00:40 hint\n\
+             01:02:05 solve moves=87 time=72.250\n\
+             01:02:06 mode time-attack=on\n\
+             01:02:10 import size=5x3"
+        );
+        assert_eq!(
+            log.newest_first().next().map(|entry| &entry.event),
+            Some(&LogEvent::Import { rows: 3, cols: 5 })
+        );
+    }
+
+    #[test]
+    fn the_optimal_length_fills_in_the_latest_solve_once() {
+        let mut log = SessionLog::default();
+        let solve = LogEvent::Solve {
+            moves: 50,
+            time: Duration::from_secs(30),
+            optimal: None,
+        };
+        log.push(Duration::ZERO, solve);
+        log.push(Duration::ZERO, LogEvent::Hint);
+
+        log.set_optimal(OptimalLength::Exact(40));
+        log.set_optimal(OptimalLength::Exact(10));
+        assert!(
+            log.text()
+                .ends_with("solve moves=50 time=30.000 optimal=40 efficiency=80%\n00:00:00 hint")
+        );
+
+        log.push(
+            Duration::ZERO,
+            LogEvent::Solve {
+                moves: 60,
+                time: Duration::from_secs(30),
+                optimal: None,
+            },
+        );
+        log.set_optimal(OptimalLength::AtLeast(45));
+        assert!(
+            log.text()
+                .ends_with("optimal_at_least=45 efficiency_at_least=75%")
+        );
+    }
+
+    #[test]
+    fn old_entries_make_way_for_new_ones() {
+        let mut log = SessionLog::default();
+        for second in 0..MAX_LOG_ENTRIES as u64 + 5 {
+            log.push(Duration::from_secs(second), LogEvent::Hint);
+        }
+
+        assert_eq!(log.len(), MAX_LOG_ENTRIES);
+        assert_eq!(
+            log.newest_first().last().map(|entry| entry.at),
+            Some(Duration::from_secs(5))
+        );
+    }
+}
@@ -0,0 +1,167 @@
+use crate::{
+    BoardRenderer, ColorScheme, DEFAULT_GRID_SIZE, DEFAULT_TIME_LIMIT, Easing, KeyScheme,
+    LabelMode, Language, ProgressMetric, SlideCount, ThemeMode, TileFont,
+};
+
+use crate::storage::Versioned;
+
+use serde::{Deserialize, Serialize};
+
+/// User preferences persisted between runs.
+///
+/// Missing keys take their default value and unknown keys are ignored, so
+/// older and newer settings files both load.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Settings {
+    /// Save the game on exit and resume it on the next launch.
+    pub auto_save: bool,
+    /// Show the Manhattan distance and misplaced-tile count while playing.
+    pub show_distance: bool,
+    /// What the progress bar under the status counts.
+    pub progress_metric: ProgressMetric,
+    /// How clicking a tile several cells from the blank adds to the move count.
+    pub slide_count: SlideCount,
+    /// What the direction keys move.
+    pub key_scheme: KeyScheme,
+    /// How long a direction key is held before it starts repeating, in
+    /// milliseconds.
+    pub key_repeat_delay_ms: u64,
+    /// Time between the moves of a held direction key, in milliseconds.
+    pub key_repeat_interval_ms: u64,
+    /// How long a tile takes to slide, in milliseconds; `0` disables the
+    /// animation.
+    pub animation_ms: u64,
+    /// How a sliding tile speeds up and slows down.
+    pub easing: Easing,
+    /// Keep the board still, with no sliding tiles or colour wave; `None`
+    /// follows the system's preference.
+    pub reduce_motion: Option<bool>,
+    pub theme: ThemeMode,
+    /// The tile colours, which may be tuned for contrast or colour blindness.
+    pub color_scheme: ColorScheme,
+    pub language: Language,
+    /// Tint tiles by whether they are in their goal cell.
+    pub goal_colors: bool,
+    /// Show a miniature of the goal beside the board.
+    pub goal_preview: bool,
+    /// Race a ghost of the personal best when retrying a scramble.
+    pub ghost: bool,
+    /// How tiles are labelled, and copied boards written.
+    pub label_mode: LabelMode,
+    /// Show slices of a picture on the tiles instead of numbers.
+    pub picture_mode: bool,
+    /// Overlay tile numbers on the picture slices.
+    pub picture_numbers: bool,
+    /// What draws the board.
+    pub board_renderer: BoardRenderer,
+    /// The gap between tiles on the smallest board, in pixels, widening as
+    /// the board grows.
+    pub tile_gap: u32,
+    /// How round the tiles' corners are, in pixels; those in their goal
+    /// cell are rounder still.
+    pub tile_radius: u32,
+    /// The typeface of the tile labels.
+    pub tile_font: TileFont,
+    /// Silence sound effects.
+    pub muted: bool,
+    /// Ask before a shuffle throws away a solve in progress.
+    pub confirm_shuffle: bool,
+    /// Pause a running clock, time attacks' too, when the window loses
+    /// focus.
+    pub pause_when_away: bool,
+    /// Race a countdown on every new scramble.
+    pub time_attack: bool,
+    /// The time attack countdown, in seconds.
+    pub time_limit_secs: u64,
+    /// Allow only so many moves on every new scramble, unless racing the
+    /// clock instead.
+    pub move_limit: bool,
+    /// Hide the tile labels on every new scramble once the preview is over.
+    pub blindfold: bool,
+    /// How long a blindfolded scramble shows its labels, in seconds.
+    pub blindfold_preview_secs: u64,
+    /// Whether the tutorial has been played through, after which the menu
+    /// no longer suggests it.
+    pub tutorial_done: bool,
+    /// The rows and columns of the board last picked, which the menu starts
+    /// new games at.
+    pub grid_size: (usize, usize),
+    /// The layout last saved in the goal editor, row-major with `0` for the
+    /// blank.
+    pub custom_goal: Option<Vec<u16>>,
+    /// The name last entered for the leaderboard, offered for the next one.
+    pub player_name: String,
+    /// The last windowed size, in logical pixels, to reopen at.
+    pub window_size: Option<(u32, u32)>,
+    /// The last windowed position, in logical pixels, to reopen at.
+    pub window_position: Option<(i32, i32)>,
+}
+
+impl Versioned for Settings {
+    const VERSION: u32 = 1;
+}
+
+impl Default for Settings {
+    fn default() -> Self {
+        Self {
+            auto_save: true,
+            show_distance: false,
+            progress_metric: ProgressMetric::default(),
+            slide_count: SlideCount::default(),
+            key_scheme: KeyScheme::default(),
+            key_repeat_delay_ms: 250,
+            key_repeat_interval_ms: 100,
+            animation_ms: 120,
+            easing: Easing::default(),
+            reduce_motion: None,
+            theme: ThemeMode::default(),
+            color_scheme: ColorScheme::default(),
+            language: Language::default(),
+            goal_colors: true,
+            goal_preview: true,
+            ghost: true,
+            label_mode: LabelMode::default(),
+            picture_mode: false,
+            picture_numbers: false,
+            board_renderer: BoardRenderer::default(),
+            tile_gap: 5,
+            tile_radius: 2,
+            tile_font: TileFont::default(),
+            muted: false,
+            confirm_shuffle: true,
+            pause_when_away: true,
+            time_attack: false,
+            time_limit_secs: DEFAULT_TIME_LIMIT.as_secs(),
+            move_limit: false,
+            blindfold: false,
+            blindfold_preview_secs: 10,
+            tutorial_done: false,
+            grid_size: (DEFAULT_GRID_SIZE, DEFAULT_GRID_SIZE),
+            custom_goal: None,
+            player_name: String::new(),
+            window_size: None,
+            window_position: None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn unknown_keys_are_ignored_and_missing_ones_default() {
+        let settings: Settings =
+            serde_json::from_str(r#"{ "show_distance": true, "from_a_newer_version": 1 }"#)
+                .unwrap();
+
+        assert_eq!(
+            settings,
+            Settings {
+                show_distance: true,
+                ..Settings::default()
+            }
+        );
+    }
+}
@@ -0,0 +1,12 @@
+use serde::{Deserialize, Serialize};
+
+/// How [`Puzzle::move_line`](crate::Puzzle::move_line) adds to the move
+/// counter when it shifts several tiles at once.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum SlideCount {
+    /// One move per tile shifted, the standard way of counting.
+    #[default]
+    PerTile,
+    /// One move per slide, however many tiles it shifts.
+    PerSlide,
+}
@@ -0,0 +1,758 @@
+//! Optimal solving with iterative-deepening A* (IDA*).
+//!
+//! The heuristic is a [`PatternDatabase`] once one is installed for the
+//! board being solved, otherwise the [`WalkingDistance`] on boards up to 4x4
+//! and the Manhattan distance on larger or wrapping ones. [`solve_with`]
+//! takes any other [`Heuristic`], to compare them.
+//!
+//! Searches stop at a deadline or when their [`Cancel`] flag is raised, can
+//! report how far they have got through a [`Progress`], which passes each
+//! update on to a listener if given one, and [`solve_many`] spreads a batch
+//! of boards over the machine's threads.
+
+use crate::{
+    Direction, Puzzle,
+    heuristic::{Board, Heuristic, Manhattan},
+    pattern_database::PatternDatabase,
+    walking_distance::WalkingDistance,
+};
+
+use serde::{Deserialize, Serialize};
+use web_time::Instant;
+
+use std::fmt;
+use std::num::NonZeroUsize;
+use std::sync::atomic::{AtomicBool, AtomicU32, AtomicU64, AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex, OnceLock};
+use std::thread;
+
+static PATTERN_DATABASE: OnceLock<PatternDatabase> = OnceLock::new();
+
+/// Makes every later search of a board `database` covers use it as the
+/// heuristic. Only the first database installed takes effect.
+pub fn install_pattern_database(database: PatternDatabase) {
+    let _ = PATTERN_DATABASE.set(database);
+}
+
+/// Whether searches of `puzzle` use a pattern database.
+pub fn has_pattern_database(puzzle: &Puzzle) -> bool {
+    PATTERN_DATABASE
+        .get()
+        .is_some_and(|database| database.covers(puzzle))
+}
+
+/// How often, in expanded nodes, the search checks its deadline and its
+/// cancel flag: a few hundred microseconds apart at most.
+const DEADLINE_CHECK_INTERVAL: u64 = 4096;
+
+/// A flag that stops the searches holding a clone of it, raised from any
+/// thread.
+#[derive(Debug, Clone, Default)]
+pub struct Cancel(Arc<AtomicBool>);
+
+impl Cancel {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Stops every search holding this flag at its next check.
+    pub fn cancel(&self) {
+        self.0.store(true, Ordering::Relaxed);
+    }
+
+    pub fn is_cancelled(&self) -> bool {
+        self.0.load(Ordering::Relaxed)
+    }
+}
+
+/// How far a running search has got, updated by the search as it goes and
+/// read from any thread.
+#[derive(Default)]
+pub struct Progress {
+    bound: AtomicU32,
+    nodes: AtomicU64,
+    listener: Option<Box<dyn Fn(Snapshot) + Send + Sync>>,
+}
+
+/// How far a search had got at one moment.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct Snapshot {
+    /// The solution length being tried: every shorter one is ruled out.
+    pub bound: u32,
+    /// Boards expanded so far, over every deepening of the bound.
+    pub nodes: u64,
+}
+
+impl Progress {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Progress that also hands every update to `listener`, called on the
+    /// thread running the search, so it can be passed on as it comes.
+    pub fn reporting(listener: impl Fn(Snapshot) + Send + Sync + 'static) -> Self {
+        Self {
+            listener: Some(Box::new(listener)),
+            ..Self::default()
+        }
+    }
+
+    /// The solution length being tried: every shorter one is ruled out.
+    pub fn bound(&self) -> u32 {
+        self.bound.load(Ordering::Relaxed)
+    }
+
+    /// Boards expanded so far, over every deepening of the bound.
+    pub fn nodes(&self) -> u64 {
+        self.nodes.load(Ordering::Relaxed)
+    }
+
+    pub fn snapshot(&self) -> Snapshot {
+        Snapshot {
+            bound: self.bound(),
+            nodes: self.nodes(),
+        }
+    }
+
+    fn set_bound(&self, bound: u32) {
+        self.bound.store(bound, Ordering::Relaxed);
+        self.report();
+    }
+
+    fn set_nodes(&self, nodes: u64) {
+        self.nodes.store(nodes, Ordering::Relaxed);
+        self.report();
+    }
+
+    fn report(&self) {
+        if let Some(listener) = &self.listener {
+            listener(self.snapshot());
+        }
+    }
+}
+
+impl fmt::Debug for Progress {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Progress")
+            .field("bound", &self.bound)
+            .field("nodes", &self.nodes)
+            .field("listener", &self.listener.is_some())
+            .finish()
+    }
+}
+
+/// When a search gives up, and where it reports its progress.
+#[derive(Debug, Clone, Copy)]
+struct Limit<'a> {
+    deadline: Option<Instant>,
+    cancel: Option<&'a Cancel>,
+    progress: Option<&'a Progress>,
+}
+
+impl Limit<'_> {
+    fn deadline(deadline: Option<Instant>) -> Self {
+        Self {
+            deadline,
+            cancel: None,
+            progress: None,
+        }
+    }
+
+    fn is_reached(&self) -> bool {
+        self.cancel.is_some_and(Cancel::is_cancelled)
+            || self
+                .deadline
+                .is_some_and(|deadline| Instant::now() >= deadline)
+    }
+}
+
+/// Finds a shortest sequence of tile moves that solves `puzzle`.
+///
+/// Each [`Direction`] is the way a tile slides, as accepted by
+/// [`Puzzle::move_direction`]. Returns `None` if the board is unsolvable or
+/// the search is still running at `deadline`.
+pub fn solve(puzzle: &Puzzle, deadline: Option<Instant>) -> Option<Vec<Direction>> {
+    if !puzzle.is_solvable() {
+        return None;
+    }
+
+    search(puzzle, Limit::deadline(deadline)).ok()
+}
+
+/// Like [`solve`], but also giving up as soon as `cancel` is raised.
+pub fn solve_cancellable(
+    puzzle: &Puzzle,
+    deadline: Option<Instant>,
+    cancel: &Cancel,
+) -> Option<Vec<Direction>> {
+    solve_limited(
+        puzzle,
+        Limit {
+            deadline,
+            cancel: Some(cancel),
+            progress: None,
+        },
+    )
+}
+
+/// Like [`solve_cancellable`], also keeping `progress` up to date as the
+/// search runs.
+pub fn solve_with_progress(
+    puzzle: &Puzzle,
+    deadline: Option<Instant>,
+    cancel: &Cancel,
+    progress: &Progress,
+) -> Option<Vec<Direction>> {
+    solve_limited(
+        puzzle,
+        Limit {
+            deadline,
+            cancel: Some(cancel),
+            progress: Some(progress),
+        },
+    )
+}
+
+fn solve_limited(puzzle: &Puzzle, limit: Limit) -> Option<Vec<Direction>> {
+    // A batch that was cancelled skips the boards it had not reached
+    if !puzzle.is_solvable() || limit.cancel.is_some_and(Cancel::is_cancelled) {
+        return None;
+    }
+
+    search(puzzle, limit).ok()
+}
+
+/// Solves every board of `puzzles` as [`solve_cancellable`] would, spread
+/// over as many threads as the machine runs at once, with the solutions in
+/// the order of the boards.
+pub fn solve_many(
+    puzzles: &[Puzzle],
+    deadline: Option<Instant>,
+    cancel: &Cancel,
+) -> Vec<Option<Vec<Direction>>> {
+    let threads = thread::available_parallelism().map_or(1, NonZeroUsize::get);
+    solve_many_on(threads, puzzles, deadline, cancel)
+}
+
+/// Like [`solve_many`], on at most `threads` threads.
+pub fn solve_many_on(
+    threads: usize,
+    puzzles: &[Puzzle],
+    deadline: Option<Instant>,
+    cancel: &Cancel,
+) -> Vec<Option<Vec<Direction>>> {
+    let solve = |puzzle| solve_cancellable(puzzle, deadline, cancel);
+    // Browsers give a page no threads to spawn
+    let threads = if cfg!(target_arch = "wasm32") {
+        1
+    } else {
+        threads.clamp(1, puzzles.len().max(1))
+    };
+    if threads == 1 {
+        return puzzles.iter().map(solve).collect();
+    }
+
+    // Each thread takes the next board left as it finishes one, so a few
+    // hard boards don't hold up the rest behind them
+    let next = AtomicUsize::new(0);
+    let solutions = Mutex::new(vec![None; puzzles.len()]);
+    thread::scope(|scope| {
+        for _ in 0..threads {
+            scope.spawn(|| {
+                loop {
+                    let index = next.fetch_add(1, Ordering::Relaxed);
+                    let Some(puzzle) = puzzles.get(index) else {
+                        break;
+                    };
+                    let solution = solve(puzzle);
+                    solutions
+                        .lock()
+                        .unwrap_or_else(|poisoned| poisoned.into_inner())[index] = solution;
+                }
+            });
+        }
+    });
+
+    solutions
+        .into_inner()
+        .unwrap_or_else(|poisoned| poisoned.into_inner())
+}
+
+/// The length of a shortest solution, as far as a search got by its deadline.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum OptimalLength {
+    Exact(u32),
+    /// The search ran out of time having ruled out every shorter solution.
+    AtLeast(u32),
+}
+
+/// How many moves a shortest solution of `puzzle` takes, or a lower bound on
+/// it if the search is still running at `deadline`. Returns `None` if the
+/// board is unsolvable.
+pub fn optimal_length(puzzle: &Puzzle, deadline: Option<Instant>) -> Option<OptimalLength> {
+    if !puzzle.is_solvable() {
+        return None;
+    }
+
+    Some(match search(puzzle, Limit::deadline(deadline)) {
+        Ok(path) => OptimalLength::Exact(path.len() as u32),
+        Err(bound) => OptimalLength::AtLeast(bound),
+    })
+}
+
+/// A search guided by a chosen heuristic, and how much work it took.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SearchReport {
+    /// A shortest solution, or `None` if the board is unsolvable or the
+    /// search was still running at the deadline.
+    pub solution: Option<Vec<Direction>>,
+    /// Boards expanded, over every deepening of the bound.
+    pub nodes: u64,
+}
+
+/// Like [`solve`], but guided by `heuristic`, which has to apply to
+/// `puzzle`, and reporting how many boards the search expanded.
+pub fn solve_with(
+    puzzle: &Puzzle,
+    deadline: Option<Instant>,
+    heuristic: &impl Heuristic,
+) -> SearchReport {
+    if !puzzle.is_solvable() {
+        return SearchReport {
+            solution: None,
+            nodes: 0,
+        };
+    }
+
+    let (outcome, nodes) = search_with(puzzle, Limit::deadline(deadline), heuristic);
+    SearchReport {
+        solution: outcome.ok(),
+        nodes,
+    }
+}
+
+/// Runs IDA* on a solvable board, returning a shortest solution, or the
+/// bound that was being searched when the search gave up.
+fn search(puzzle: &Puzzle, limit: Limit) -> Result<Vec<Direction>, u32> {
+    let database = PATTERN_DATABASE
+        .get()
+        .filter(|database| database.covers(puzzle));
+
+    if let Some(database) = database {
+        return search_with(puzzle, limit, database).0;
+    }
+    match WalkingDistance::for_puzzle(puzzle) {
+        Some(walking_distance) => search_with(puzzle, limit, &*walking_distance).0,
+        None => search_with(puzzle, limit, &Manhattan).0,
+    }
+}
+
+/// Runs IDA* on a solvable board guided by `heuristic`, also returning the
+/// number of boards expanded.
+fn search_with<H: Heuristic>(
+    puzzle: &Puzzle,
+    limit: Limit,
+    heuristic: &H,
+) -> (Result<Vec<Direction>, u32>, u64) {
+    let mut search = Search::new(puzzle, limit, heuristic);
+    // The heuristic never overestimates, and each bound is only raised once
+    // nothing shorter is left
+    let mut bound = search.estimate;
+
+    let outcome = loop {
+        if let Some(progress) = limit.progress {
+            progress.set_bound(bound);
+        }
+        match search.depth_first(0, bound, None) {
+            Outcome::Found => break Ok(search.path),
+            Outcome::Exceeded(next) => bound = next,
+            Outcome::Aborted => break Err(bound),
+        }
+    };
+
+    if let Some(progress) = limit.progress {
+        progress.set_nodes(search.nodes);
+    }
+    (outcome, search.nodes)
+}
+
+/// The single move that most reduces the Manhattan distance, used when a
+/// full solve takes too long.
+pub fn greedy_move(puzzle: &Puzzle) -> Option<Direction> {
+    Direction::ALL
+        .into_iter()
+        .filter_map(|direction| {
+            let mut next = puzzle.clone();
+            let (row, col) = next.tile_for_direction(direction)?;
+            next.move_tile(row, col);
+
+            Some((next.manhattan_distance(), direction))
+        })
+        .min_by_key(|&(distance, _)| distance)
+        .map(|(_, direction)| direction)
+}
+
+enum Outcome {
+    Found,
+    Exceeded(u32), // Smallest f-cost above the bound
+    Aborted,
+}
+
+/// The mutable state of one IDA* search, on a compact copy of the board.
+struct Search<'a, H> {
+    board: Board,
+    heuristic: &'a H,
+    estimate: u32, // Heuristic for `board`, kept incrementally
+    path: Vec<Direction>,
+    nodes: u64,
+    limit: Limit<'a>,
+}
+
+impl<'a, H: Heuristic> Search<'a, H> {
+    fn new(puzzle: &Puzzle, limit: Limit<'a>, heuristic: &'a H) -> Self {
+        Self {
+            board: Board::new(puzzle),
+            heuristic,
+            estimate: heuristic.estimate(puzzle),
+            path: Vec::new(),
+            nodes: 0,
+            limit,
+        }
+    }
+
+    fn depth_first(&mut self, cost: u32, bound: u32, previous: Option<Direction>) -> Outcome {
+        let estimate = cost + self.estimate;
+        if estimate > bound {
+            return Outcome::Exceeded(estimate);
+        }
+        if self.estimate == 0 {
+            return Outcome::Found;
+        }
+
+        self.nodes += 1;
+        if self.nodes.is_multiple_of(DEADLINE_CHECK_INTERVAL) {
+            if let Some(progress) = self.limit.progress {
+                progress.set_nodes(self.nodes);
+            }
+            if self.limit.is_reached() {
+                return Outcome::Aborted;
+            }
+        }
+
+        let mut next_bound = u32::MAX;
+
+        for direction in Direction::ALL {
+            // Never undo the previous move
+            if Some(direction.opposite()) == previous {
+                continue;
+            }
+            let Some(tile) = self.tile_for(direction) else {
+                continue;
+            };
+
+            let (previous_empty, delta) = self.slide(tile);
+            self.path.push(direction);
+
+            match self.depth_first(cost + 1, bound, Some(direction)) {
+                Outcome::Found => return Outcome::Found,
+                Outcome::Exceeded(estimate) => next_bound = next_bound.min(estimate),
+                Outcome::Aborted => return Outcome::Aborted,
+            }
+
+            self.path.pop();
+            self.unslide(previous_empty, delta);
+        }
+
+        Outcome::Exceeded(next_bound)
+    }
+
+    /// Index of the tile that slides into the empty cell in `direction`,
+    /// following [`Puzzle::tile_for_direction`].
+    fn tile_for(&self, direction: Direction) -> Option<usize> {
+        let (rows, cols, empty) = (self.board.rows(), self.board.cols(), self.board.empty());
+        let (row, col) = (empty / cols, empty % cols);
+        let (last_row, last_col) = (rows - 1, cols - 1);
+        let wraps = |length: usize| self.board.wrap() && length > 2;
+
+        match direction {
+            Direction::Up if row == last_row => wraps(rows).then_some(col),
+            Direction::Up => Some(empty + cols),
+            Direction::Down if row == 0 => wraps(rows).then_some(last_row * cols + col),
+            Direction::Down => Some(empty - cols),
+            Direction::Left if col == last_col => wraps(cols).then_some(row * cols),
+            Direction::Left => Some(empty + 1),
+            Direction::Right if col == 0 => wraps(cols).then_some(row * cols + last_col),
+            Direction::Right => Some(empty - 1),
+        }
+    }
+
+    /// Moves the tile at `index` into the empty cell, returning the old empty
+    /// cell and the change in estimate so the move can be reverted.
+    fn slide(&mut self, index: usize) -> (usize, i32) {
+        let previous_empty = self.board.empty();
+        let delta = self.heuristic.slide_delta(&self.board, index);
+
+        self.board.slide(index);
+        self.estimate = self.estimate.wrapping_add_signed(delta);
+        (previous_empty, delta)
+    }
+
+    /// Reverts a [`slide`](Self::slide) that emptied `previous_empty`.
+    fn unslide(&mut self, previous_empty: usize, delta: i32) {
+        self.board.slide(previous_empty);
+        self.estimate = self.estimate.wrapping_add_signed(-delta);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        Goal,
+        heuristic::{LinearConflict, MisplacedTiles},
+    };
+    use std::time::Duration;
+
+    fn apply(puzzle: &mut Puzzle, moves: &[Direction]) {
+        for &direction in moves {
+            let (row, col) = puzzle.tile_for_direction(direction).unwrap();
+            assert!(puzzle.move_tile(row, col));
+        }
+    }
+
+    #[test]
+    fn solved_board_needs_no_moves() {
+        let puzzle = Puzzle::new(4, 4);
+
+        assert_eq!(solve(&puzzle, None), Some(Vec::new()));
+    }
+
+    #[test]
+    fn undoes_a_short_scramble_optimally() {
+        let mut puzzle = Puzzle::new(4, 4);
+        apply(
+            &mut puzzle,
+            &[
+                Direction::Right,
+                Direction::Down,
+                Direction::Right,
+                Direction::Down,
+            ],
+        );
+
+        let solution = solve(&puzzle, None).unwrap();
+
+        assert_eq!(
+            solution,
+            [
+                Direction::Up,
+                Direction::Left,
+                Direction::Up,
+                Direction::Left
+            ]
+        );
+    }
+
+    #[test]
+    fn solution_reaches_the_solved_board() {
+        for goal in Goal::ALL {
+            for seed in 0..5 {
+                let mut puzzle = Puzzle::with_seed(3, 3, seed);
+                puzzle.set_goal(goal.clone());
+                puzzle.shuffle();
+
+                let solution = solve(&puzzle, None).unwrap();
+                apply(&mut puzzle, &solution);
+
+                assert!(puzzle.is_solved(), "{goal}, seed {seed}");
+            }
+        }
+    }
+
+    #[test]
+    fn gives_up_at_the_deadline() {
+        let mut puzzle = Puzzle::with_seed(10, 10, 1);
+        puzzle.shuffle();
+
+        assert_eq!(solve(&puzzle, Some(Instant::now())), None);
+    }
+
+    #[test]
+    fn stops_soon_after_being_cancelled() {
+        let mut puzzle = Puzzle::with_seed(10, 10, 1);
+        puzzle.shuffle();
+        let cancel = Cancel::new();
+
+        let (solution, stopping) = thread::scope(|scope| {
+            let search = scope.spawn(|| solve_cancellable(&puzzle, None, &cancel));
+            thread::sleep(Duration::from_millis(20));
+            cancel.cancel();
+            let cancelled = Instant::now();
+            (search.join().unwrap(), cancelled.elapsed())
+        });
+        assert_eq!(solution, None);
+        assert!(stopping < Duration::from_millis(50), "took {stopping:?}");
+
+        // A raised flag also stops searches started afterwards
+        assert_eq!(
+            solve_cancellable(&Puzzle::with_seed(3, 3, 1), None, &cancel),
+            None
+        );
+    }
+
+    #[test]
+    fn progress_follows_the_search() {
+        let mut puzzle = Puzzle::with_seed(3, 3, 3);
+        puzzle.shuffle();
+        let progress = Progress::new();
+
+        let solution = solve_with_progress(&puzzle, None, &Cancel::new(), &progress).unwrap();
+        assert_eq!(progress.bound(), solution.len() as u32);
+        assert_eq!(
+            progress.nodes(),
+            solve_with(
+                &puzzle,
+                None,
+                &*WalkingDistance::for_puzzle(&puzzle).unwrap()
+            )
+            .nodes
+        );
+
+        // A search given up on leaves where it had got to, at its first check
+        let mut hard = Puzzle::with_seed(10, 10, 1);
+        hard.shuffle();
+        assert_eq!(
+            solve_with_progress(&hard, Some(Instant::now()), &Cancel::new(), &progress),
+            None
+        );
+        assert!(progress.bound() >= Manhattan.estimate(&hard));
+        assert_eq!(progress.nodes(), DEADLINE_CHECK_INTERVAL);
+    }
+
+    #[test]
+    fn a_listener_hears_every_update_as_it_comes() {
+        let mut puzzle = Puzzle::with_seed(3, 3, 3);
+        puzzle.shuffle();
+        let heard = Arc::new(Mutex::new(Vec::new()));
+        let progress = Progress::reporting({
+            let heard = heard.clone();
+            move |snapshot| heard.lock().unwrap().push(snapshot)
+        });
+
+        let solution = solve_with_progress(&puzzle, None, &Cancel::new(), &progress).unwrap();
+        let heard = heard.lock().unwrap();
+        // Bounds only ever rise, and the last word is where the search ended
+        assert!(heard.is_sorted_by_key(|snapshot| snapshot.bound));
+        assert!(heard.len() > 1);
+        assert_eq!(heard.last(), Some(&progress.snapshot()));
+        assert_eq!(progress.snapshot().bound, solution.len() as u32);
+    }
+
+    #[test]
+    fn batches_solve_each_board_in_order() {
+        let mut puzzles: Vec<Puzzle> = (0..12)
+            .map(|seed| {
+                let mut puzzle = Puzzle::with_seed(3, 3, seed);
+                puzzle.shuffle();
+                puzzle
+            })
+            .collect();
+        // Two tiles swapped from solved can never be put right
+        puzzles[5] = Puzzle::parse_permutation("2 1 3 4 5 6 7 8 0").unwrap();
+
+        let alone: Vec<_> = puzzles.iter().map(|puzzle| solve(puzzle, None)).collect();
+        for threads in [1, 4] {
+            assert_eq!(
+                solve_many_on(threads, &puzzles, None, &Cancel::new()),
+                alone
+            );
+        }
+        assert!(
+            alone
+                .iter()
+                .enumerate()
+                .all(|(index, solution)| solution.is_some() == (index != 5))
+        );
+        assert!(solve_many(&[], None, &Cancel::new()).is_empty());
+    }
+
+    #[test]
+    fn optimal_length_falls_back_to_a_lower_bound() {
+        let mut puzzle = Puzzle::with_seed(3, 3, 4);
+        puzzle.shuffle();
+        let length = solve(&puzzle, None).unwrap().len() as u32;
+        assert_eq!(
+            optimal_length(&puzzle, None),
+            Some(OptimalLength::Exact(length))
+        );
+
+        let mut puzzle = Puzzle::with_seed(10, 10, 1);
+        puzzle.shuffle();
+        assert!(matches!(
+            optimal_length(&puzzle, Some(Instant::now())),
+            Some(OptimalLength::AtLeast(bound)) if bound >= puzzle.manhattan_distance()
+        ));
+
+        let unsolvable = Puzzle::parse_permutation("2 1 3 0").unwrap();
+        assert_eq!(optimal_length(&unsolvable, None), None);
+    }
+
+    #[test]
+    fn a_pattern_database_keeps_solutions_optimal() {
+        let database = PatternDatabase::generate(3, 3, &[&[1, 2, 3, 4], &[5, 6, 7, 8]], |_| {});
+        // Leaving a tile out of every group falls back to its own distance
+        let partial = PatternDatabase::generate(3, 3, &[&[1, 2, 4, 5], &[3, 6]], |_| {});
+
+        for seed in 0..10 {
+            let mut puzzle = Puzzle::with_seed(3, 3, seed);
+            puzzle.shuffle();
+            assert!(database.covers(&puzzle));
+
+            let optimal = solve(&puzzle, None).unwrap().len();
+            for database in [&database, &partial] {
+                let mut solved = puzzle.clone();
+                let solution = solve_with(&puzzle, None, database).solution.unwrap();
+                apply(&mut solved, &solution);
+                assert!(solved.is_solved(), "seed {seed}");
+                assert_eq!(solution.len(), optimal, "seed {seed}");
+            }
+        }
+    }
+
+    #[test]
+    fn stronger_heuristics_expand_fewer_boards() {
+        let mut puzzle = Puzzle::with_seed(3, 3, 3);
+        puzzle.shuffle();
+        let optimal = solve(&puzzle, None).unwrap().len();
+
+        let reports = [
+            solve_with(&puzzle, None, &MisplacedTiles),
+            solve_with(&puzzle, None, &Manhattan),
+            solve_with(&puzzle, None, &LinearConflict),
+        ];
+        let walking_distance = solve_with(&puzzle, None, &WalkingDistance::new(3, 3, 8));
+        assert_eq!(
+            walking_distance.solution.map(|solution| solution.len()),
+            Some(optimal)
+        );
+        assert!(walking_distance.nodes < reports[1].nodes);
+        for report in &reports {
+            assert_eq!(report.solution.as_ref().map(Vec::len), Some(optimal));
+        }
+        assert!(reports.windows(2).all(|pair| pair[0].nodes > pair[1].nodes));
+
+        let unsolvable = Puzzle::parse_permutation("2 1 3 0").unwrap();
+        assert_eq!(
+            solve_with(&unsolvable, None, &LinearConflict).solution,
+            None
+        );
+    }
+
+    #[test]
+    fn greedy_move_reduces_the_distance() {
+        let mut puzzle = Puzzle::new(3, 3);
+        apply(&mut puzzle, &[Direction::Right]);
+
+        assert_eq!(greedy_move(&puzzle), Some(Direction::Left));
+    }
+}
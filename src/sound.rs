@@ -0,0 +1,64 @@
+//! Sound effects, played on the default output device when the `sound`
+//! feature is enabled and silently skipped otherwise.
+
+use std::fmt;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Sound {
+    Move,
+    Rejected,
+    Solved,
+}
+
+/// Plays [`Sound`]s without blocking; mixing happens on the audio thread.
+///
+/// Without an output device, or without the `sound` feature, playing does
+/// nothing.
+pub struct Player {
+    #[cfg(feature = "sound")]
+    stream: Option<rodio::OutputStream>,
+}
+
+impl Player {
+    #[cfg(feature = "sound")]
+    pub fn open() -> Self {
+        let stream = rodio::OutputStreamBuilder::open_default_stream()
+            .ok()
+            .map(|mut stream| {
+                stream.log_on_drop(false);
+                stream
+            });
+
+        Self { stream }
+    }
+
+    #[cfg(not(feature = "sound"))]
+    pub fn open() -> Self {
+        Self {}
+    }
+
+    #[cfg(feature = "sound")]
+    pub fn play(&self, sound: Sound) {
+        let Some(stream) = &self.stream else {
+            return;
+        };
+
+        let bytes: &'static [u8] = match sound {
+            Sound::Move => include_bytes!("../assets/move.wav"),
+            Sound::Rejected => include_bytes!("../assets/rejected.wav"),
+            Sound::Solved => include_bytes!("../assets/solved.wav"),
+        };
+        if let Ok(source) = rodio::Decoder::new(std::io::Cursor::new(bytes)) {
+            stream.mixer().add(source);
+        }
+    }
+
+    #[cfg(not(feature = "sound"))]
+    pub fn play(&self, _sound: Sound) {}
+}
+
+impl fmt::Debug for Player {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Player").finish_non_exhaustive()
+    }
+}
@@ -0,0 +1,479 @@
+//! Where settings, records and saved games are kept between sessions: files
+//! on the desktop, the browser's `localStorage` on the web.
+//!
+//! Every document is a JSON object with a `version` field for the version of
+//! its format, and is brought up to date by [`migrate`] as it loads, so a
+//! format can change without costing anyone their records. Documents from
+//! before there were versions count as version 0. One written by a newer
+//! build of the game loads as far as it can be understood, but is never
+//! saved over, so nothing the newer build added is lost.
+
+use crate::MigrateError;
+
+use serde::{Serialize, de::DeserializeOwned};
+use serde_json::Value;
+
+#[cfg(not(target_arch = "wasm32"))]
+use std::fs::{self, File};
+#[cfg(not(target_arch = "wasm32"))]
+use std::io::{self, Write};
+#[cfg(not(target_arch = "wasm32"))]
+use std::path::PathBuf;
+
+/// The field of a stored document that holds its version.
+const VERSION_FIELD: &str = "version";
+
+/// A kind of document kept in storage, whose format has a version.
+pub trait Versioned: Serialize + DeserializeOwned {
+    /// The version of the format written now.
+    const VERSION: u32;
+
+    /// Brings `document`, written in version `from`, up to version
+    /// `from + 1`.
+    ///
+    /// Unless overridden, every step leaves the document as it is, which
+    /// suits formats that have only gained fields with defaults.
+    fn upgrade(document: Value, from: u32) -> Result<Value, MigrateError> {
+        let _ = from;
+        Ok(document)
+    }
+}
+
+/// Brings `document`, without its version field, from version `from` up to
+/// the current version of `T` one step at a time. A document newer than that
+/// is left as it is.
+pub fn migrate<T: Versioned>(document: Value, from: u32) -> Result<Value, MigrateError> {
+    (from..T::VERSION).try_fold(document, |document, version| T::upgrade(document, version))
+}
+
+/// A document as read from storage.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Loaded<T> {
+    pub document: T,
+    /// The version it was written in.
+    pub version: u32,
+}
+
+impl<T: Versioned> Loaded<T> {
+    /// Whether a newer build of the game wrote the document, so that saving
+    /// over it could lose what that build added.
+    pub fn is_read_only(&self) -> bool {
+        self.version > T::VERSION
+    }
+}
+
+/// Reads a document written in any version, bringing it up to date.
+pub fn parse<T: Versioned>(contents: &str) -> Result<Loaded<T>, MigrateError> {
+    let mut document: Value =
+        serde_json::from_str(contents).map_err(|error| MigrateError::Syntax(error.to_string()))?;
+    let version = take_version(&mut document)?;
+
+    serde_json::from_value(migrate::<T>(document, version)?)
+        .map(|document| Loaded { document, version })
+        .map_err(|error| MigrateError::Invalid(error.to_string()))
+}
+
+/// `document` as JSON, with the version of its format.
+pub fn to_json<T: Versioned>(document: &T) -> Result<String, String> {
+    let mut value = serde_json::to_value(document).map_err(|error| error.to_string())?;
+    let Value::Object(fields) = &mut value else {
+        return Err(String::from("documents must be JSON objects"));
+    };
+    fields.insert(VERSION_FIELD.into(), T::VERSION.into());
+
+    serde_json::to_string_pretty(&value).map_err(|error| error.to_string())
+}
+
+/// Takes the version field out of `document`, which has version 0 without
+/// one.
+fn take_version(document: &mut Value) -> Result<u32, MigrateError> {
+    let Some(version) = document
+        .as_object_mut()
+        .and_then(|fields| fields.remove(VERSION_FIELD))
+    else {
+        return Ok(0);
+    };
+
+    version
+        .as_u64()
+        .and_then(|version| u32::try_from(version).ok())
+        .ok_or_else(|| MigrateError::Invalid(format!("{version} is not a version")))
+}
+
+/// A place to keep named documents between sessions.
+pub trait Persistence {
+    /// The contents of the document called `name`, or `None` if there is no
+    /// such document or it cannot be read.
+    fn load(&self, name: &str) -> Option<String>;
+
+    /// Replaces the document called `name` with `contents`.
+    fn save(&self, name: &str, contents: &str) -> Result<(), String>;
+
+    /// Deletes the document called `name`, if there is one.
+    fn remove(&self, name: &str) -> Result<(), String>;
+}
+
+/// The storage this platform keeps documents in.
+#[cfg(not(target_arch = "wasm32"))]
+pub type Storage = FileStorage;
+/// The storage this platform keeps documents in.
+#[cfg(target_arch = "wasm32")]
+pub type Storage = WebStorage;
+
+/// Documents as files in a directory, named after the document.
+#[cfg(not(target_arch = "wasm32"))]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FileStorage {
+    dir: PathBuf,
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+impl FileStorage {
+    pub fn new(dir: impl Into<PathBuf>) -> Self {
+        Self { dir: dir.into() }
+    }
+
+    /// Storage for configuration, e.g. `~/.config/iced_15_puzzle` on Linux.
+    pub fn config() -> Option<Self> {
+        directories::ProjectDirs::from("", "", "iced_15_puzzle")
+            .map(|dirs| Self::new(dirs.config_dir()))
+    }
+
+    /// Storage for user data such as saved games, e.g.
+    /// `~/.local/share/iced_15_puzzle` on Linux.
+    pub fn data() -> Option<Self> {
+        directories::ProjectDirs::from("", "", "iced_15_puzzle")
+            .map(|dirs| Self::new(dirs.data_dir()))
+    }
+
+    /// The file the document called `name` is kept in.
+    pub fn path(&self, name: &str) -> PathBuf {
+        self.dir.join(name)
+    }
+
+    /// Storage in the subdirectory `dir`, which may have several levels
+    /// separated by `/`.
+    pub fn subdirectory(&self, dir: &str) -> Self {
+        Self::new(self.dir.join(dir))
+    }
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+impl Persistence for FileStorage {
+    fn load(&self, name: &str) -> Option<String> {
+        fs::read_to_string(self.path(name)).ok()
+    }
+
+    /// Writes the file, creating its directory as needed. The contents go to
+    /// a temporary file first, renamed over the old one once complete, so a
+    /// save cut short leaves the old file whole.
+    fn save(&self, name: &str, contents: &str) -> Result<(), String> {
+        let path = self.path(name);
+        let temporary = self.path(&format!("{name}.tmp"));
+
+        fs::create_dir_all(&self.dir)
+            .and_then(|()| File::create(&temporary))
+            .and_then(|mut file| {
+                file.write_all(contents.as_bytes())?;
+                file.sync_all()
+            })
+            .and_then(|()| fs::rename(&temporary, &path))
+            .map_err(|error| format!("could not write {}: {error}", path.display()))
+    }
+
+    fn remove(&self, name: &str) -> Result<(), String> {
+        let path = self.path(name);
+
+        match fs::remove_file(&path) {
+            Err(error) if error.kind() != io::ErrorKind::NotFound => {
+                Err(format!("could not delete {}: {error}", path.display()))
+            }
+            _ => Ok(()),
+        }
+    }
+}
+
+/// Documents in the browser's `localStorage`, under keys that start with a
+/// prefix so configuration and data stay apart as they do on the desktop.
+#[cfg(target_arch = "wasm32")]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct WebStorage {
+    prefix: String,
+}
+
+#[cfg(target_arch = "wasm32")]
+impl WebStorage {
+    /// Storage for configuration, or `None` if the page has no
+    /// `localStorage`.
+    pub fn config() -> Option<Self> {
+        Self::available("iced_15_puzzle/config/")
+    }
+
+    /// Storage for user data such as saved games, or `None` if the page has
+    /// no `localStorage`.
+    pub fn data() -> Option<Self> {
+        Self::available("iced_15_puzzle/data/")
+    }
+
+    fn available(prefix: &str) -> Option<Self> {
+        local_storage().map(|_| Self {
+            prefix: String::from(prefix),
+        })
+    }
+
+    /// Storage under keys that go on to `dir` and a `/`, as though in a
+    /// subdirectory.
+    pub fn subdirectory(&self, dir: &str) -> Self {
+        Self {
+            prefix: format!("{}{dir}/", self.prefix),
+        }
+    }
+}
+
+#[cfg(target_arch = "wasm32")]
+impl Persistence for WebStorage {
+    fn load(&self, name: &str) -> Option<String> {
+        local_storage()?
+            .get_item(&format!("{}{name}", self.prefix))
+            .ok()
+            .flatten()
+    }
+
+    fn save(&self, name: &str, contents: &str) -> Result<(), String> {
+        local_storage()
+            .ok_or("localStorage is not available")?
+            .set_item(&format!("{}{name}", self.prefix), contents)
+            .map_err(|error| format!("could not store {name}: {error:?}"))
+    }
+
+    fn remove(&self, name: &str) -> Result<(), String> {
+        local_storage()
+            .ok_or("localStorage is not available")?
+            .remove_item(&format!("{}{name}", self.prefix))
+            .map_err(|error| format!("could not delete {name}: {error:?}"))
+    }
+}
+
+#[cfg(target_arch = "wasm32")]
+fn local_storage() -> Option<web_sys::Storage> {
+    web_sys::window()?.local_storage().ok().flatten()
+}
+
+/// Reads the document called `name` and brings it up to date, or `None` if
+/// there is no such document.
+pub fn load<T: Versioned>(
+    storage: &impl Persistence,
+    name: &str,
+) -> Option<Result<Loaded<T>, MigrateError>> {
+    storage.load(name).map(|contents| parse(&contents))
+}
+
+/// Reads a document, falling back to the default when it is missing or
+/// cannot be read.
+pub fn load_or_default<T: Versioned + Default>(storage: &impl Persistence, name: &str) -> T {
+    load(storage, name)
+        .and_then(Result::ok)
+        .map(|loaded| loaded.document)
+        .unwrap_or_default()
+}
+
+/// Copies the documents called `names` that `from` has to `to`, as they are,
+/// leaving the originals in place.
+pub fn copy(from: &impl Persistence, to: &impl Persistence, names: &[&str]) -> Result<(), String> {
+    for name in names {
+        if let Some(contents) = from.load(name) {
+            to.save(name, &contents)?;
+        }
+    }
+    Ok(())
+}
+
+/// Writes a document with its version, unless the one already there is from
+/// a newer build of the game.
+pub fn save<T: Versioned>(
+    storage: &impl Persistence,
+    name: &str,
+    document: &T,
+) -> Result<(), String> {
+    let stored_version = storage
+        .load(name)
+        .and_then(|contents| serde_json::from_str(&contents).ok())
+        .and_then(|mut stored| take_version(&mut stored).ok());
+    if stored_version.is_some_and(|version| version > T::VERSION) {
+        return Err(format!(
+            "{name} is from a newer version of the game, so it is left as it is"
+        ));
+    }
+
+    storage.save(name, &to_json(document)?)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Settings;
+
+    use serde::Deserialize;
+    use serde_json::json;
+
+    use std::cell::RefCell;
+    use std::collections::HashMap;
+
+    /// Keeps documents in memory, as a stand-in for either platform.
+    #[derive(Default)]
+    struct Memory(RefCell<HashMap<String, String>>);
+
+    impl Persistence for Memory {
+        fn load(&self, name: &str) -> Option<String> {
+            self.0.borrow().get(name).cloned()
+        }
+
+        fn save(&self, name: &str, contents: &str) -> Result<(), String> {
+            self.0.borrow_mut().insert(name.into(), contents.into());
+            Ok(())
+        }
+
+        fn remove(&self, name: &str) -> Result<(), String> {
+            self.0.borrow_mut().remove(name);
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn documents_read_back_and_bad_ones_fall_back_to_the_default() {
+        let memory = Memory::default();
+        assert_eq!(
+            load_or_default::<Settings>(&memory, "settings.json"),
+            Settings::default()
+        );
+
+        let settings = Settings {
+            auto_save: !Settings::default().auto_save,
+            ..Settings::default()
+        };
+        save(&memory, "settings.json", &settings).unwrap();
+        assert_eq!(
+            load_or_default::<Settings>(&memory, "settings.json"),
+            settings
+        );
+
+        Persistence::save(&memory, "settings.json", "{ not json").unwrap();
+        assert_eq!(
+            load_or_default::<Settings>(&memory, "settings.json"),
+            Settings::default()
+        );
+    }
+
+    /// A document that has been through a few formats: a bare name, then an
+    /// object with a `name`, then one with a `title`.
+    #[derive(Debug, Default, PartialEq, Serialize, Deserialize)]
+    struct Note {
+        title: String,
+    }
+
+    impl Versioned for Note {
+        const VERSION: u32 = 2;
+
+        fn upgrade(document: Value, from: u32) -> Result<Value, MigrateError> {
+            match (from, document) {
+                (0, Value::String(name)) => Ok(json!({ "name": name })),
+                (1, Value::Object(mut fields)) => Ok(json!({ "title": fields.remove("name") })),
+                (version, document) => Err(MigrateError::Unexpected {
+                    version,
+                    found: document.to_string(),
+                }),
+            }
+        }
+    }
+
+    #[test]
+    fn every_step_from_the_stored_version_is_taken_in_turn() {
+        let note = |title: &str| Note {
+            title: title.into(),
+        };
+
+        assert_eq!(parse::<Note>(r#""first""#).unwrap().document, note("first"));
+        let loaded = parse::<Note>(r#"{"version": 1, "name": "second"}"#).unwrap();
+        assert_eq!((loaded.document, loaded.version), (note("second"), 1));
+        assert_eq!(
+            parse::<Note>(r#"{"version": 2, "title": "third"}"#)
+                .unwrap()
+                .document,
+            note("third")
+        );
+        assert_eq!(
+            migrate::<Note>(json!({ "name": "fourth" }), 1),
+            Ok(json!({ "title": "fourth" }))
+        );
+
+        assert!(matches!(
+            parse::<Note>("[1, 2]"),
+            Err(MigrateError::Unexpected { version: 0, .. })
+        ));
+        assert!(matches!(
+            parse::<Note>(r#"{"version": "two"}"#),
+            Err(MigrateError::Invalid(_))
+        ));
+        assert!(matches!(
+            parse::<Note>("{ not json"),
+            Err(MigrateError::Syntax(_))
+        ));
+    }
+
+    #[test]
+    fn documents_are_saved_with_their_version() {
+        let memory = Memory::default();
+        save(&memory, "note.json", &Note::default()).unwrap();
+
+        let stored: Value = serde_json::from_str(&memory.load("note.json").unwrap()).unwrap();
+        assert_eq!(stored, json!({ "version": 2, "title": "" }));
+        assert!(
+            !load::<Note>(&memory, "note.json")
+                .unwrap()
+                .unwrap()
+                .is_read_only()
+        );
+    }
+
+    #[test]
+    fn documents_from_a_newer_version_load_but_are_left_alone() {
+        let memory = Memory::default();
+        let newer = r#"{"version": 7, "title": "kept", "added_later": true}"#;
+        Persistence::save(&memory, "note.json", newer).unwrap();
+
+        let loaded = load::<Note>(&memory, "note.json").unwrap().unwrap();
+        assert!(loaded.is_read_only());
+        assert_eq!(loaded.document.title, "kept");
+
+        assert!(save(&memory, "note.json", &loaded.document).is_err());
+        assert_eq!(memory.load("note.json").as_deref(), Some(newer));
+    }
+
+    #[test]
+    fn copies_take_only_the_documents_there_are() {
+        let (from, to) = (Memory::default(), Memory::default());
+        from.save("a.json", "1").unwrap();
+        from.save("b.json", "2").unwrap();
+
+        copy(&from, &to, &["a.json", "missing.json"]).unwrap();
+        assert_eq!(to.load("a.json").as_deref(), Some("1"));
+        assert_eq!(to.load("missing.json"), None);
+        assert_eq!(to.load("b.json"), None);
+        assert_eq!(from.load("a.json").as_deref(), Some("1"));
+    }
+
+    #[cfg(not(target_arch = "wasm32"))]
+    #[test]
+    fn files_are_replaced_whole() {
+        let dir = std::env::temp_dir().join(format!("iced_15_puzzle-{}", std::process::id()));
+        let files = FileStorage::new(&dir);
+
+        Persistence::save(&files, "note.json", "old").unwrap();
+        Persistence::save(&files, "note.json", "new").unwrap();
+        assert_eq!(files.load("note.json").as_deref(), Some("new"));
+        // Nothing is left behind on the way
+        assert_eq!(fs::read_dir(&dir).unwrap().count(), 1);
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+}
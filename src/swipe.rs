@@ -0,0 +1,463 @@
+//! A wrapper that reports drags, swipes, scrolling and right clicks over its
+//! content.
+
+use iced::advanced::layout::{self, Layout};
+use iced::advanced::widget::{Operation, Tree, Widget, tree};
+use iced::advanced::{Clipboard, Shell, overlay, renderer};
+use iced::time::{Duration, Instant};
+use iced::{Element, Event, Length, Point, Rectangle, Size, Vector, keyboard, mouse, touch};
+use iced_15_puzzle::Direction;
+
+/// How far a press must travel before it is a drag rather than a tap, and
+/// then before letting go of it counts as a swipe.
+const TAP_SLOP: f32 = 5.0;
+const SWIPE_THRESHOLD: f32 = 20.0;
+
+/// How long a finger must stay down, without dragging, to stand in for a
+/// right click.
+const LONG_PRESS: Duration = Duration::from_millis(500);
+
+/// How far scrolling by pixels goes for one step, about one notch of a
+/// wheel in a browser.
+const PIXELS_PER_SCROLL_STEP: f32 = 100.0;
+
+/// The direction of a swipe from `start` to `end`, along whichever axis it
+/// travelled further, or `None` for a tap.
+pub fn swipe_direction(start: Point, end: Point) -> Option<Direction> {
+    let (dx, dy) = (end.x - start.x, end.y - start.y);
+
+    if dx.hypot(dy) < SWIPE_THRESHOLD {
+        None
+    } else if dx.abs() >= dy.abs() {
+        Some(if dx > 0.0 {
+            Direction::Right
+        } else {
+            Direction::Left
+        })
+    } else {
+        Some(if dy > 0.0 {
+            Direction::Down
+        } else {
+            Direction::Up
+        })
+    }
+}
+
+/// Wraps `content` so that letting go of a drag across it sends
+/// `on_release` with the direction of the swipe, if it was long enough for
+/// one. Taps pass through to the content as usual.
+pub fn swipe<'a, Message, Theme, Renderer>(
+    content: impl Into<Element<'a, Message, Theme, Renderer>>,
+    on_release: impl Fn(Option<Direction>) -> Message + 'a,
+) -> Swipe<'a, Message, Theme, Renderer> {
+    Swipe {
+        content: content.into(),
+        on_release: Box::new(on_release),
+        on_drag: None,
+        on_scroll: None,
+        on_secondary: None,
+    }
+}
+
+pub struct Swipe<'a, Message, Theme, Renderer> {
+    content: Element<'a, Message, Theme, Renderer>,
+    on_release: Box<dyn Fn(Option<Direction>) -> Message + 'a>,
+    on_drag: Option<Box<dyn Fn(Point, Vector) -> Message + 'a>>,
+    on_scroll: Option<Box<dyn Fn(Point, Direction) -> Message + 'a>>,
+    on_secondary: Option<Box<dyn Fn(Point) -> Message + 'a>>,
+}
+
+impl<'a, Message, Theme, Renderer> Swipe<'a, Message, Theme, Renderer> {
+    /// Sends `on_drag` as a drag moves, with where it started within the
+    /// content and how far it has gone since.
+    pub fn on_drag(mut self, on_drag: impl Fn(Point, Vector) -> Message + 'a) -> Self {
+        self.on_drag = Some(Box::new(on_drag));
+        self
+    }
+
+    /// Sends `on_scroll` for each step the wheel turns over the content,
+    /// with where the cursor is within it and which way the step went.
+    /// Holding shift turns a vertical wheel sideways: up is left.
+    pub fn on_scroll(mut self, on_scroll: impl Fn(Point, Direction) -> Message + 'a) -> Self {
+        self.on_scroll = Some(Box::new(on_scroll));
+        self
+    }
+
+    /// Sends `on_secondary` with where within the content it was right
+    /// clicked, or touched and held still for a moment before letting go.
+    /// A long press does not also count as a tap.
+    pub fn on_secondary(mut self, on_secondary: impl Fn(Point) -> Message + 'a) -> Self {
+        self.on_secondary = Some(Box::new(on_secondary));
+        self
+    }
+}
+
+#[derive(Debug, Default)]
+struct State {
+    press: Option<Press>,
+    modifiers: keyboard::Modifiers,
+    scrolled: Vector, // Trackpad scrolling short of a whole step
+}
+
+/// The press being tracked.
+#[derive(Debug, Clone, Copy)]
+struct Press {
+    start: Point, // In window coordinates
+    finger: Option<touch::Finger>,
+    at: Instant,
+    dragging: bool,
+}
+
+impl<Message, Theme, Renderer> Widget<Message, Theme, Renderer>
+    for Swipe<'_, Message, Theme, Renderer>
+where
+    Renderer: renderer::Renderer,
+{
+    fn tag(&self) -> tree::Tag {
+        tree::Tag::of::<State>()
+    }
+
+    fn state(&self) -> tree::State {
+        tree::State::new(State::default())
+    }
+
+    fn children(&self) -> Vec<Tree> {
+        vec![Tree::new(&self.content)]
+    }
+
+    fn diff(&self, tree: &mut Tree) {
+        tree.diff_children(std::slice::from_ref(&self.content));
+    }
+
+    fn size(&self) -> Size<Length> {
+        self.content.as_widget().size()
+    }
+
+    fn layout(
+        &mut self,
+        tree: &mut Tree,
+        renderer: &Renderer,
+        limits: &layout::Limits,
+    ) -> layout::Node {
+        self.content
+            .as_widget_mut()
+            .layout(&mut tree.children[0], renderer, limits)
+    }
+
+    fn operate(
+        &mut self,
+        tree: &mut Tree,
+        layout: Layout<'_>,
+        renderer: &Renderer,
+        operation: &mut dyn Operation,
+    ) {
+        self.content
+            .as_widget_mut()
+            .operate(&mut tree.children[0], layout, renderer, operation);
+    }
+
+    fn update(
+        &mut self,
+        tree: &mut Tree,
+        event: &Event,
+        layout: Layout<'_>,
+        cursor: mouse::Cursor,
+        renderer: &Renderer,
+        clipboard: &mut dyn Clipboard,
+        shell: &mut Shell<'_, Message>,
+        viewport: &Rectangle,
+    ) {
+        let state: &mut State = tree.state.downcast_mut();
+        let mut cursor = cursor;
+
+        // Presses are only noted on the way down; the tiles beneath still
+        // see them, so a tap stays a click
+        match *event {
+            Event::Mouse(mouse::Event::ButtonPressed(mouse::Button::Left)) => {
+                state.press = cursor.position_over(layout.bounds()).map(|start| Press {
+                    start,
+                    finger: None,
+                    at: Instant::now(),
+                    dragging: false,
+                });
+            }
+            Event::Touch(touch::Event::FingerPressed { id, position })
+                if layout.bounds().contains(position) =>
+            {
+                state.press = Some(Press {
+                    start: position,
+                    finger: Some(id),
+                    at: Instant::now(),
+                    dragging: false,
+                });
+            }
+            Event::Mouse(mouse::Event::ButtonPressed(mouse::Button::Right)) => {
+                if let Some(on_secondary) = &self.on_secondary
+                    && let Some(position) = cursor.position_over(layout.bounds())
+                {
+                    shell.publish(on_secondary(within(layout, position)));
+                }
+            }
+            Event::Mouse(mouse::Event::CursorMoved { position }) => {
+                self.drag(state, position, None, layout, shell);
+            }
+            Event::Touch(touch::Event::FingerMoved { id, position }) => {
+                self.drag(state, position, Some(id), layout, shell);
+            }
+            Event::Mouse(mouse::Event::ButtonReleased(mouse::Button::Left)) => {
+                if let Some(position) = cursor.position() {
+                    cursor = self.release(state, position, None, layout, shell, cursor);
+                }
+            }
+            Event::Touch(touch::Event::FingerLifted { id, position }) => {
+                cursor = self.release(state, position, Some(id), layout, shell, cursor);
+            }
+            Event::Keyboard(keyboard::Event::ModifiersChanged(modifiers)) => {
+                state.modifiers = modifiers;
+            }
+            Event::Mouse(mouse::Event::WheelScrolled { delta }) => {
+                if let Some(position) = cursor.position_over(layout.bounds()) {
+                    self.scroll(state, position, delta, layout, shell);
+                }
+            }
+            Event::Touch(touch::Event::FingerLost { id, .. })
+                if state.press.is_some_and(|press| press.finger == Some(id)) =>
+            {
+                state.press = None;
+            }
+            _ => {}
+        }
+
+        self.content.as_widget_mut().update(
+            &mut tree.children[0],
+            event,
+            layout,
+            cursor,
+            renderer,
+            clipboard,
+            shell,
+            viewport,
+        );
+    }
+
+    fn mouse_interaction(
+        &self,
+        tree: &Tree,
+        layout: Layout<'_>,
+        cursor: mouse::Cursor,
+        viewport: &Rectangle,
+        renderer: &Renderer,
+    ) -> mouse::Interaction {
+        self.content.as_widget().mouse_interaction(
+            &tree.children[0],
+            layout,
+            cursor,
+            viewport,
+            renderer,
+        )
+    }
+
+    fn draw(
+        &self,
+        tree: &Tree,
+        renderer: &mut Renderer,
+        theme: &Theme,
+        style: &renderer::Style,
+        layout: Layout<'_>,
+        cursor: mouse::Cursor,
+        viewport: &Rectangle,
+    ) {
+        self.content.as_widget().draw(
+            &tree.children[0],
+            renderer,
+            theme,
+            style,
+            layout,
+            cursor,
+            viewport,
+        );
+    }
+
+    fn overlay<'b>(
+        &'b mut self,
+        tree: &'b mut Tree,
+        layout: Layout<'b>,
+        renderer: &Renderer,
+        viewport: &Rectangle,
+        translation: Vector,
+    ) -> Option<overlay::Element<'b, Message, Theme, Renderer>> {
+        self.content.as_widget_mut().overlay(
+            &mut tree.children[0],
+            layout,
+            renderer,
+            viewport,
+            translation,
+        )
+    }
+}
+
+impl<Message, Theme, Renderer> Swipe<'_, Message, Theme, Renderer> {
+    /// Follows the press being tracked to `position`, once it has moved far
+    /// enough not to be a tap.
+    fn drag(
+        &self,
+        state: &mut State,
+        position: Point,
+        finger: Option<touch::Finger>,
+        layout: Layout<'_>,
+        shell: &mut Shell<'_, Message>,
+    ) {
+        // Other fingers, and a mouse moving without its button held, are
+        // not this press
+        let Some(press) = state.press.as_mut().filter(|press| press.finger == finger) else {
+            return;
+        };
+
+        let offset = position - press.start;
+        press.dragging |= offset.x.hypot(offset.y) >= TAP_SLOP;
+        if let Some(on_drag) = &self.on_drag
+            && press.dragging
+        {
+            shell.publish(on_drag(within(layout, press.start), offset));
+        }
+    }
+
+    /// Turns a turn of the wheel with the cursor at `position` into steps,
+    /// keeping any part of a step a trackpad has scrolled for next time.
+    fn scroll(
+        &self,
+        state: &mut State,
+        position: Point,
+        delta: mouse::ScrollDelta,
+        layout: Layout<'_>,
+        shell: &mut Shell<'_, Message>,
+    ) {
+        let Some(on_scroll) = &self.on_scroll else {
+            return;
+        };
+
+        let mut steps = match delta {
+            mouse::ScrollDelta::Lines { x, y } => Vector::new(x, y),
+            mouse::ScrollDelta::Pixels { x, y } => {
+                state.scrolled += Vector::new(x, y) * (1.0 / PIXELS_PER_SCROLL_STEP);
+                let whole = Vector::new(state.scrolled.x.trunc(), state.scrolled.y.trunc());
+                state.scrolled -= whole;
+                whole
+            }
+        };
+        if state.modifiers.shift() && steps.x == 0.0 {
+            steps = Vector::new(-steps.y, 0.0);
+        }
+
+        // A wheel turned away from the player slides up, and with shift left
+        let direction = if steps.x.abs() > steps.y.abs() {
+            if steps.x > 0.0 {
+                Direction::Right
+            } else {
+                Direction::Left
+            }
+        } else if steps.y > 0.0 {
+            Direction::Up
+        } else if steps.y < 0.0 {
+            Direction::Down
+        } else {
+            return;
+        };
+
+        let at = within(layout, position);
+        for _ in 0..(steps.x.abs().max(steps.y.abs()) as usize).max(1) {
+            shell.publish(on_scroll(at, direction));
+        }
+    }
+
+    /// Finishes the press being tracked where it let go, at `end`, and
+    /// returns the cursor the content should see for the release.
+    fn release(
+        &self,
+        state: &mut State,
+        end: Point,
+        finger: Option<touch::Finger>,
+        layout: Layout<'_>,
+        shell: &mut Shell<'_, Message>,
+        cursor: mouse::Cursor,
+    ) -> mouse::Cursor {
+        // Another finger lifting leaves this press going
+        let Some(press) = state.press.filter(|press| press.finger == finger) else {
+            return cursor;
+        };
+        state.press = None;
+
+        if !press.dragging {
+            // Mice have a button of their own for this
+            return match &self.on_secondary {
+                Some(on_secondary) if finger.is_some() && press.at.elapsed() >= LONG_PRESS => {
+                    shell.publish(on_secondary(within(layout, press.start)));
+                    mouse::Cursor::Unavailable
+                }
+                _ => cursor,
+            };
+        }
+
+        shell.publish((self.on_release)(swipe_direction(press.start, end)));
+        // Hiding the cursor lets a tile that was pressed let go without
+        // counting a click
+        mouse::Cursor::Unavailable
+    }
+}
+
+/// `position`, in window coordinates, from the top left corner of `layout`.
+fn within(layout: Layout<'_>, position: Point) -> Point {
+    let origin = layout.bounds().position();
+    Point::new(position.x - origin.x, position.y - origin.y)
+}
+
+impl<'a, Message, Theme, Renderer> From<Swipe<'a, Message, Theme, Renderer>>
+    for Element<'a, Message, Theme, Renderer>
+where
+    Message: 'a,
+    Theme: 'a,
+    Renderer: renderer::Renderer + 'a,
+{
+    fn from(swipe: Swipe<'a, Message, Theme, Renderer>) -> Self {
+        Element::new(swipe)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn swipes_follow_the_dominant_axis() {
+        let origin = Point::new(100.0, 100.0);
+
+        assert_eq!(
+            swipe_direction(origin, Point::new(150.0, 110.0)),
+            Some(Direction::Right)
+        );
+        assert_eq!(
+            swipe_direction(origin, Point::new(90.0, 40.0)),
+            Some(Direction::Up)
+        );
+        assert_eq!(
+            swipe_direction(origin, Point::new(60.0, 130.0)),
+            Some(Direction::Left)
+        );
+        assert_eq!(
+            swipe_direction(origin, Point::new(105.0, 160.0)),
+            Some(Direction::Down)
+        );
+    }
+
+    #[test]
+    fn short_drags_are_taps() {
+        let origin = Point::new(100.0, 100.0);
+
+        assert_eq!(swipe_direction(origin, origin), None);
+        assert_eq!(swipe_direction(origin, Point::new(112.0, 112.0)), None);
+        assert_eq!(
+            swipe_direction(origin, Point::new(120.0, 100.0)),
+            Some(Direction::Right)
+        );
+    }
+}
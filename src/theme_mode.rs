@@ -0,0 +1,27 @@
+use serde::{Deserialize, Serialize};
+
+use std::fmt;
+
+/// Which colour scheme the interface uses.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum ThemeMode {
+    /// Follow the system light or dark preference.
+    #[default]
+    System,
+    Light,
+    Dark,
+}
+
+impl ThemeMode {
+    pub const ALL: [ThemeMode; 3] = [ThemeMode::System, ThemeMode::Light, ThemeMode::Dark];
+}
+
+impl fmt::Display for ThemeMode {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            ThemeMode::System => "System theme",
+            ThemeMode::Light => "Light",
+            ThemeMode::Dark => "Dark",
+        })
+    }
+}
@@ -0,0 +1,29 @@
+use serde::{Deserialize, Serialize};
+
+use std::fmt;
+
+/// The typeface of the tile labels.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum TileFont {
+    /// The one the rest of the interface is in.
+    #[default]
+    Standard,
+    /// Inter, an even sans-serif that reads well small.
+    Inter,
+    /// Fira Mono, in which every number is as wide as the next.
+    FiraMono,
+}
+
+impl TileFont {
+    pub const ALL: [TileFont; 3] = [TileFont::Standard, TileFont::Inter, TileFont::FiraMono];
+}
+
+impl fmt::Display for TileFont {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            TileFont::Standard => "Standard",
+            TileFont::Inter => "Inter",
+            TileFont::FiraMono => "Fira Mono",
+        })
+    }
+}
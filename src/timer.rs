@@ -0,0 +1,155 @@
+use std::time::Duration;
+use web_time::Instant;
+
+/// A stopwatch that accumulates running time across starts and stops.
+///
+/// Time is only ever measured between monotonic [`Instant`]s, never read off
+/// the wall clock, so neither a change of the system clock nor the machine
+/// sleeping mid-solve can make it jump.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct Timer {
+    accumulated: Duration,
+    started: Option<Instant>, // Set while running
+}
+
+impl Timer {
+    /// A stopped timer that has already accumulated `elapsed`.
+    pub fn with_elapsed(elapsed: Duration) -> Self {
+        Self {
+            accumulated: elapsed,
+            started: None,
+        }
+    }
+
+    pub fn is_running(&self) -> bool {
+        self.started.is_some()
+    }
+
+    /// Starts the timer if it isn't already running.
+    pub fn start(&mut self, now: Instant) {
+        if self.started.is_none() {
+            self.started = Some(now);
+        }
+    }
+
+    /// Stops the timer, keeping the time accumulated so far.
+    pub fn stop(&mut self, now: Instant) {
+        if let Some(started) = self.started.take() {
+            self.accumulated += now.saturating_duration_since(started);
+        }
+    }
+
+    /// Stops the timer and clears the accumulated time.
+    pub fn reset(&mut self) {
+        *self = Self::default();
+    }
+
+    /// The total running time as of `now`.
+    pub fn elapsed(&self, now: Instant) -> Duration {
+        match self.started {
+            Some(started) => self.accumulated + now.saturating_duration_since(started),
+            None => self.accumulated,
+        }
+    }
+}
+
+/// How finely [`format_duration`] shows a time.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Precision {
+    /// `m:ss.t`, for a clock that is still running.
+    Tenths,
+    /// `m:ss.ttt`, for a time that is done and may be compared.
+    Millis,
+}
+
+/// Formats `duration` as minutes and seconds to `precision`, with hours in
+/// front as `h:mm:ss.t` once there are any. Digits past the precision are
+/// dropped rather than rounded, so a time never reads as more than has
+/// passed.
+pub fn format_duration(duration: Duration, precision: Precision) -> String {
+    let (per_second, digits) = match precision {
+        Precision::Tenths => (10, 1),
+        Precision::Millis => (1000, 3),
+    };
+    let fraction = duration.subsec_millis() / (1000 / per_second);
+    let seconds = duration.as_secs();
+    let (hours, minutes) = (seconds / 3600, seconds / 60 % 60);
+
+    if hours > 0 {
+        format!(
+            "{hours}:{minutes:02}:{:02}.{fraction:0digits$}",
+            seconds % 60
+        )
+    } else {
+        format!("{minutes}:{:02}.{fraction:0digits$}", seconds % 60)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn millis(millis: u64) -> Duration {
+        Duration::from_millis(millis)
+    }
+
+    #[test]
+    fn times_under_a_second_keep_their_leading_zeros() {
+        assert_eq!(format_duration(Duration::ZERO, Precision::Tenths), "0:00.0");
+        assert_eq!(format_duration(millis(7), Precision::Millis), "0:00.007");
+        assert_eq!(format_duration(millis(85), Precision::Tenths), "0:00.0");
+        assert_eq!(format_duration(millis(850), Precision::Millis), "0:00.850");
+        assert_eq!(
+            format_duration(Duration::from_nanos(999_999_999), Precision::Millis),
+            "0:00.999"
+        );
+    }
+
+    #[test]
+    fn a_minute_only_shows_once_it_has_passed() {
+        assert_eq!(format_duration(millis(59_940), Precision::Tenths), "0:59.9");
+        assert_eq!(
+            format_duration(millis(59_999), Precision::Millis),
+            "0:59.999"
+        );
+        assert_eq!(format_duration(millis(60_000), Precision::Tenths), "1:00.0");
+        assert_eq!(
+            format_duration(millis(83_456), Precision::Millis),
+            "1:23.456"
+        );
+        assert_eq!(
+            format_duration(millis(600_000), Precision::Tenths),
+            "10:00.0"
+        );
+    }
+
+    #[test]
+    fn hour_long_times_show_their_hours() {
+        assert_eq!(
+            format_duration(millis(3_599_999), Precision::Millis),
+            "59:59.999"
+        );
+        assert_eq!(
+            format_duration(millis(3_600_000), Precision::Tenths),
+            "1:00:00.0"
+        );
+        assert_eq!(
+            format_duration(millis(37_230_045), Precision::Millis),
+            "10:20:30.045"
+        );
+    }
+
+    #[test]
+    fn the_clock_counts_only_while_running() {
+        let start = Instant::now();
+        let mut timer = Timer::default();
+        timer.start(start);
+        timer.stop(start + millis(1500));
+        assert_eq!(timer.elapsed(start + millis(9000)), millis(1500));
+
+        timer.start(start + millis(2000));
+        assert_eq!(timer.elapsed(start + millis(2250)), millis(1750));
+        // An instant before the start counts as no time at all
+        assert_eq!(timer.elapsed(start), millis(1500));
+    }
+}
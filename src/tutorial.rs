@@ -0,0 +1,295 @@
+//! The tutorial: a script of presses on small boards, from a first slide to
+//! finishing off the last two rows.
+//!
+//! Each step ends once its board meets the step's goal, however it got there,
+//! and hints at the next tile along the way the script takes. A move off the
+//! script is left for the app to take back.
+
+use crate::Puzzle;
+
+/// What a step of the tutorial teaches, in the order they come.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Lesson {
+    /// A tile beside the gap slides into it.
+    SlideIntoGap,
+    /// One more slide puts the last tile back.
+    FinishTheBoard,
+    /// A scrambled board is solved top row first.
+    TopRow,
+    /// With the top row done, the two rows below are circled into place.
+    LastTwoRows,
+}
+
+/// How a move left the tutorial.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TutorialProgress {
+    /// The move was the script's, or took the board back onto it.
+    OnTrack,
+    /// The move was off the script, for the app to take back.
+    Stray,
+    /// The step met its goal, and the next one is up.
+    StepDone,
+    /// The last step met its goal.
+    Finished,
+}
+
+struct Step {
+    lesson: Lesson,
+    board: Option<[u16; 9]>, // A 3x3 to start on, row-major, or `None` to carry on
+    presses: &'static [u16], // The tiles the script presses, by number
+    done: fn(&Puzzle) -> bool,
+}
+
+const STEPS: [Step; 4] = [
+    Step {
+        lesson: Lesson::SlideIntoGap,
+        board: Some([1, 2, 3, 4, 0, 5, 7, 8, 6]),
+        presses: &[5],
+        done: middle_filled,
+    },
+    Step {
+        lesson: Lesson::FinishTheBoard,
+        board: None,
+        presses: &[6],
+        done: Puzzle::is_solved,
+    },
+    Step {
+        lesson: Lesson::TopRow,
+        board: Some([0, 2, 8, 1, 3, 5, 4, 6, 7]),
+        presses: &[2, 3, 5, 8, 3, 2, 1],
+        done: top_row_placed,
+    },
+    Step {
+        lesson: Lesson::LastTwoRows,
+        board: None,
+        presses: &[5, 6, 7, 8, 6, 5, 4, 7, 8],
+        done: Puzzle::is_solved,
+    },
+];
+
+fn middle_filled(puzzle: &Puzzle) -> bool {
+    puzzle.is_tile_in_place(1, 1)
+}
+
+fn top_row_placed(puzzle: &Puzzle) -> bool {
+    (0..puzzle.cols()).all(|col| puzzle.is_tile_in_place(0, col))
+}
+
+impl Step {
+    fn start(&self) -> Option<Puzzle> {
+        self.board
+            .map(|board| Puzzle::from_grid(3, 3, &board).expect("the tutorial boards are valid"))
+    }
+}
+
+/// Progress through the tutorial, and the board as its script has it.
+#[derive(Debug, Clone)]
+pub struct Tutorial {
+    step: usize,      // Index into `STEPS`, one past the end once finished
+    pressed: usize,   // Of the step's presses, those made
+    expected: Puzzle, // The board with the presses made
+    waiting: bool,    // Whether the step's board has yet to be put in play
+}
+
+impl Default for Tutorial {
+    fn default() -> Self {
+        Self {
+            step: 0,
+            pressed: 0,
+            expected: STEPS[0].start().expect("the tutorial starts on a board"),
+            waiting: false,
+        }
+    }
+}
+
+impl Tutorial {
+    pub const STEPS: usize = STEPS.len();
+
+    /// The step being played, counting from 1, or [`STEPS`](Self::STEPS)
+    /// once finished.
+    pub fn step(&self) -> usize {
+        (self.step + 1).min(Self::STEPS)
+    }
+
+    /// What the step being played teaches, or `None` once finished.
+    pub fn lesson(&self) -> Option<Lesson> {
+        STEPS.get(self.step).map(|step| step.lesson)
+    }
+
+    /// The board as the script has it: the one to play the step on, with
+    /// the presses so far made.
+    pub fn board(&self) -> &Puzzle {
+        &self.expected
+    }
+
+    /// Whether the step is up on a board of its own, yet to be put in play
+    /// with [`begin_step`](Self::begin_step).
+    pub fn is_waiting(&self) -> bool {
+        self.waiting
+    }
+
+    pub fn is_finished(&self) -> bool {
+        self.step >= Self::STEPS
+    }
+
+    /// Puts the step that is up in play, on [`board`](Self::board).
+    pub fn begin_step(&mut self) {
+        self.waiting = false;
+    }
+
+    /// The cell of the tile the script presses next, while a step is in
+    /// play.
+    pub fn next_press(&self) -> Option<(usize, usize)> {
+        if self.waiting {
+            return None;
+        }
+
+        let value = *STEPS.get(self.step)?.presses.get(self.pressed)?;
+        let cols = self.expected.cols();
+        let cell = self
+            .expected
+            .tiles()
+            .iter()
+            .position(|tile| tile.value() == Some(value))?;
+        Some((cell / cols, cell % cols))
+    }
+
+    /// Follows a move made on `puzzle`, the board in play.
+    pub fn moved(&mut self, puzzle: &Puzzle) -> TutorialProgress {
+        let Some(step) = STEPS.get(self.step).filter(|_| !self.waiting) else {
+            return TutorialProgress::OnTrack;
+        };
+
+        if (step.done)(puzzle) {
+            self.step += 1;
+            self.pressed = 0;
+            return match STEPS.get(self.step) {
+                None => TutorialProgress::Finished,
+                Some(next) => {
+                    match next.start() {
+                        Some(board) => {
+                            self.expected = board;
+                            self.waiting = true;
+                        }
+                        None => self.expected = puzzle.clone(),
+                    }
+                    TutorialProgress::StepDone
+                }
+            };
+        }
+        if puzzle.tiles() == self.expected.tiles() {
+            return TutorialProgress::OnTrack;
+        }
+
+        let mut pressed = self.expected.clone();
+        match self.next_press() {
+            Some((row, col))
+                if pressed.move_tile(row, col) && pressed.tiles() == puzzle.tiles() =>
+            {
+                self.expected = pressed;
+                self.pressed += 1;
+                TutorialProgress::OnTrack
+            }
+            _ => TutorialProgress::Stray,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Plays the step in play by the script, returning how the last press
+    /// left it and how many presses it took.
+    fn follow_script(tutorial: &mut Tutorial, puzzle: &mut Puzzle) -> (TutorialProgress, usize) {
+        for presses in 1.. {
+            let (row, col) = tutorial.next_press().expect("a press for every step");
+            assert!(puzzle.move_tile(row, col));
+            match tutorial.moved(puzzle) {
+                TutorialProgress::OnTrack => {}
+                progress => return (progress, presses),
+            }
+        }
+        unreachable!()
+    }
+
+    #[test]
+    fn the_script_plays_every_step_through_to_the_end() {
+        let mut tutorial = Tutorial::default();
+        let mut puzzle = tutorial.board().clone();
+        let mut lessons = Vec::new();
+
+        loop {
+            lessons.push(tutorial.lesson().unwrap());
+            let index = tutorial.step() - 1;
+            let (progress, presses) = follow_script(&mut tutorial, &mut puzzle);
+
+            // Each step ends with its last press, not before
+            assert_eq!(presses, STEPS[index].presses.len());
+            if progress == TutorialProgress::Finished {
+                break;
+            }
+            assert_eq!(progress, TutorialProgress::StepDone);
+            if tutorial.is_waiting() {
+                assert_eq!(tutorial.next_press(), None);
+                tutorial.begin_step();
+                puzzle = tutorial.board().clone();
+            }
+        }
+
+        assert_eq!(
+            lessons,
+            [
+                Lesson::SlideIntoGap,
+                Lesson::FinishTheBoard,
+                Lesson::TopRow,
+                Lesson::LastTwoRows
+            ]
+        );
+        assert!(puzzle.is_solved() && tutorial.is_finished());
+        assert_eq!(
+            (tutorial.step(), tutorial.lesson()),
+            (Tutorial::STEPS, None)
+        );
+    }
+
+    #[test]
+    fn a_stray_move_is_caught_and_taking_it_back_is_on_track() {
+        let mut tutorial = Tutorial::default();
+        let mut puzzle = tutorial.board().clone();
+        let press = tutorial.next_press();
+
+        // The gap's other neighbours are all off the script
+        let stray = puzzle
+            .movable_tiles()
+            .into_iter()
+            .find(|&cell| Some(cell) != press)
+            .unwrap();
+        assert!(puzzle.move_tile(stray.0, stray.1));
+        assert_eq!(tutorial.moved(&puzzle), TutorialProgress::Stray);
+        assert_eq!(tutorial.next_press(), press);
+
+        assert!(puzzle.undo());
+        assert_eq!(tutorial.moved(&puzzle), TutorialProgress::OnTrack);
+        let (row, col) = press.unwrap();
+        assert!(puzzle.move_tile(row, col));
+        assert_eq!(tutorial.moved(&puzzle), TutorialProgress::StepDone);
+        assert_eq!(tutorial.lesson(), Some(Lesson::FinishTheBoard));
+    }
+
+    #[test]
+    fn a_goal_met_off_the_script_still_ends_the_step() {
+        let mut tutorial = Tutorial::default();
+        let _ = tutorial.moved(&Puzzle::from_grid(3, 3, &[1, 2, 3, 4, 5, 0, 7, 8, 6]).unwrap());
+        let _ = tutorial.moved(&Puzzle::new(3, 3));
+        assert!(tutorial.is_waiting());
+        tutorial.begin_step();
+
+        // The top row, put in place some other way
+        let placed = Puzzle::from_grid(3, 3, &[1, 2, 3, 0, 4, 5, 6, 7, 8]).unwrap();
+        assert_eq!(tutorial.moved(&placed), TutorialProgress::StepDone);
+        assert_eq!(tutorial.lesson(), Some(Lesson::LastTwoRows));
+        assert_eq!(tutorial.board().tiles(), placed.tiles());
+        assert!(!tutorial.is_waiting());
+    }
+}
@@ -0,0 +1,48 @@
+//! The record of which achievements have been reached, and when.
+//!
+//! Achievements are known here only by their ids, which stay the same across
+//! releases; what each one asks of a solve is up to the app.
+
+use crate::{Day, storage::Versioned};
+use serde::{Deserialize, Serialize};
+
+use std::collections::BTreeMap;
+
+/// The achievements reached so far, by id, with when each was first reached
+/// in seconds since 1970.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct Unlocked(BTreeMap<String, u64>);
+
+impl Versioned for Unlocked {
+    const VERSION: u32 = 1;
+}
+
+impl Unlocked {
+    /// The day the achievement `id` was unlocked, if it has been.
+    pub fn day(&self, id: &str) -> Option<Day> {
+        let seconds = *self.0.get(id)?;
+        Some(Day::from_days_since_epoch(
+            (seconds / (24 * 60 * 60)) as u32,
+        ))
+    }
+
+    /// Unlocks the achievement `id` at `now`, in seconds since 1970, unless
+    /// it already is, returning whether it was just unlocked.
+    pub fn unlock(&mut self, id: &str, now: u64) -> bool {
+        if self.0.contains_key(id) {
+            return false;
+        }
+        self.0.insert(id.to_owned(), now);
+        true
+    }
+
+    /// How many achievements have been unlocked.
+    pub fn len(&self) -> usize {
+        self.0.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+}
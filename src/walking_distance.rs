@@ -0,0 +1,283 @@
+//! The walking distance, a heuristic that counts the vertical and the
+//! horizontal moves a board needs separately.
+//!
+//! Looking only at rows, a board is a table of how many tiles in each row
+//! belong in each row, and where the blank is. The blank trades places with
+//! one tile of a neighbouring row at every vertical move, so the fewest
+//! vertical moves from each such table to the solved one can be worked out
+//! once, breadth first. Columns are the same with horizontal moves, and as
+//! no move is both, the two counts add up to a lower bound on the solution.
+//! It sees tiles of a row getting in each other's way, which makes it much
+//! stronger than the Manhattan distance on a 4x4, with tables of only a few
+//! thousand entries.
+
+use crate::{
+    Puzzle, goal_position,
+    heuristic::{Board, Heuristic},
+};
+
+use std::collections::{HashMap, VecDeque, hash_map::Entry};
+use std::hash::{BuildHasherDefault, Hasher};
+use std::sync::{Arc, Mutex};
+
+/// The most rows or columns a board can have for its tables to be worked
+/// out: on a 5x5 they run to millions of entries.
+pub const MAX_LINES: usize = 4;
+
+// Each count of tiles takes three bits of a table's key
+const COUNT_BITS: usize = 3;
+const COUNT_MASK: u64 = (1 << COUNT_BITS) - 1;
+// Where the blank's line sits in a table's key, past every count
+const BLANK_SHIFT: usize = MAX_LINES * MAX_LINES * COUNT_BITS;
+
+/// Every board size and blank goal worked out so far, kept for the session.
+static TABLES: Mutex<Vec<Arc<WalkingDistance>>> = Mutex::new(Vec::new());
+
+/// The walking distance for boards of one size whose blank belongs in one
+/// cell, without wrapping.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct WalkingDistance {
+    rows: usize,
+    cols: usize,
+    blank_goal: usize,
+    vertical: Table,   // Moves along columns, over the rows' contents
+    horizontal: Table, // Moves along rows, over the columns' contents
+}
+
+/// The fewest moves from each arrangement of tiles across lines, keyed as
+/// [`Table::key`] packs them.
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct Table {
+    lines: usize,
+    line_of: Vec<usize>, // The line each cell is in
+    costs: HashMap<u64, u8, BuildHasherDefault<KeyHasher>>,
+}
+
+/// Hashes a table's key with one multiplication: the search looks keys up
+/// at every move, and they come from the board rather than from anyone
+/// trying to collide them.
+#[derive(Debug, Clone, Copy, Default)]
+struct KeyHasher(u64);
+
+impl Hasher for KeyHasher {
+    fn finish(&self) -> u64 {
+        self.0
+    }
+
+    fn write(&mut self, bytes: &[u8]) {
+        for &byte in bytes {
+            self.write_u64(u64::from(byte));
+        }
+    }
+
+    fn write_u64(&mut self, key: u64) {
+        self.0 = (self.0 ^ key).wrapping_mul(0x9E37_79B9_7F4A_7C15);
+    }
+}
+
+impl WalkingDistance {
+    /// Works out the tables for a `rows` by `cols` board whose blank belongs
+    /// in the row-major cell `blank_goal`.
+    ///
+    /// # Panics
+    ///
+    /// If the board has more than [`MAX_LINES`] rows or columns.
+    pub fn new(rows: usize, cols: usize, blank_goal: usize) -> Self {
+        assert!(
+            rows <= MAX_LINES && cols <= MAX_LINES,
+            "walking distance tables go up to {MAX_LINES}x{MAX_LINES}"
+        );
+
+        let cells = rows * cols;
+        Self {
+            rows,
+            cols,
+            blank_goal,
+            vertical: Table::generate(rows, cols, blank_goal, (0..cells).map(|cell| cell / cols)),
+            horizontal: Table::generate(cols, rows, blank_goal, (0..cells).map(|cell| cell % cols)),
+        }
+    }
+
+    /// The walking distance for `puzzle`, worked out the first time a board
+    /// of its size and blank goal comes up, or `None` if it wraps or is too
+    /// large.
+    pub fn for_puzzle(puzzle: &Puzzle) -> Option<Arc<Self>> {
+        let (rows, cols) = (puzzle.rows(), puzzle.cols());
+        if puzzle.wrap() || rows > MAX_LINES || cols > MAX_LINES {
+            return None;
+        }
+
+        let mut tables = TABLES
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner());
+        if let Some(found) = tables.iter().find(|found| found.covers(puzzle)) {
+            return Some(Arc::clone(found));
+        }
+        let made = Arc::new(Self::new(rows, cols, blank_goal(puzzle)));
+        tables.push(Arc::clone(&made));
+        Some(made)
+    }
+
+    /// Whether the tables apply to `puzzle`: the same size, the blank
+    /// belonging in the same cell, and no wrapping.
+    pub fn covers(&self, puzzle: &Puzzle) -> bool {
+        (puzzle.rows(), puzzle.cols()) == (self.rows, self.cols)
+            && blank_goal(puzzle) == self.blank_goal
+            && !puzzle.wrap()
+    }
+}
+
+/// The row-major cell the blank of `puzzle` belongs in.
+fn blank_goal(puzzle: &Puzzle) -> usize {
+    let (row, col) = goal_position(0, (puzzle.rows(), puzzle.cols()), puzzle.goal());
+    row * puzzle.cols() + col
+}
+
+/// One count of the tiles in `line` that belong in `goal_line`, placed where
+/// a table's key keeps it.
+fn count(line: usize, goal_line: usize) -> u64 {
+    1 << (COUNT_BITS * (line * MAX_LINES + goal_line))
+}
+
+impl Table {
+    /// Works out the fewest moves to the solved arrangement from every one
+    /// reachable, on a board with `lines` lines of `length` cells, `line_of`
+    /// giving the line of each cell.
+    fn generate(
+        lines: usize,
+        length: usize,
+        blank_goal: usize,
+        line_of: impl IntoIterator<Item = usize>,
+    ) -> Self {
+        let line_of: Vec<usize> = line_of.into_iter().collect();
+        let blank_line = line_of[blank_goal];
+        let solved = (0..lines)
+            .map(|line| count(line, line) * length as u64)
+            .sum::<u64>()
+            - count(blank_line, blank_line)
+            + ((blank_line as u64) << BLANK_SHIFT);
+        let mut costs = HashMap::default();
+        costs.insert(solved, 0);
+        let mut queue = VecDeque::from([solved]);
+
+        while let Some(key) = queue.pop_front() {
+            let cost = costs[&key];
+            let blank = (key >> BLANK_SHIFT) as usize;
+            let counts = key & ((1 << BLANK_SHIFT) - 1);
+
+            // The blank trades places with any tile of a neighbouring line
+            let neighbours = [
+                blank.checked_sub(1),
+                Some(blank + 1).filter(|&line| line < lines),
+            ];
+            for line in neighbours.into_iter().flatten() {
+                for goal_line in 0..lines {
+                    if (counts / count(line, goal_line)) & COUNT_MASK == 0 {
+                        continue;
+                    }
+                    let next = counts - count(line, goal_line)
+                        + count(blank, goal_line)
+                        + ((line as u64) << BLANK_SHIFT);
+                    if let Entry::Vacant(entry) = costs.entry(next) {
+                        entry.insert(cost + 1);
+                        queue.push_back(next);
+                    }
+                }
+            }
+        }
+
+        Self {
+            lines,
+            line_of,
+            costs,
+        }
+    }
+
+    /// The key of the arrangement on `board`.
+    fn key(&self, board: &Board) -> u64 {
+        let line_of = |cell: usize| self.line_of[cell];
+        let counts: u64 = (1..board.positions().len() as u16)
+            .map(|value| {
+                let cell = board.positions()[usize::from(value)];
+                count(line_of(cell), line_of(board.goal(value)))
+            })
+            .sum();
+
+        counts + ((line_of(board.empty()) as u64) << BLANK_SHIFT)
+    }
+
+    fn cost(&self, key: u64) -> u32 {
+        u32::from(self.costs[&key])
+    }
+}
+
+impl Heuristic for WalkingDistance {
+    fn estimate(&self, board: &Puzzle) -> u32 {
+        let board = Board::new(board);
+
+        self.vertical.cost(self.vertical.key(&board))
+            + self.horizontal.cost(self.horizontal.key(&board))
+    }
+
+    fn slide_delta(&self, board: &Board, from: usize) -> i32 {
+        let to = board.empty();
+        // A vertical move only changes the rows' contents, a horizontal one
+        // only the columns'
+        let table = if from / self.cols != to / self.cols {
+            &self.vertical
+        } else {
+            &self.horizontal
+        };
+        let line_of = |cell: usize| table.line_of[cell];
+
+        let before = table.key(board);
+        let goal_line = line_of(board.goal(board.value(from)));
+        let after = before - count(line_of(from), goal_line) + count(line_of(to), goal_line)
+            - ((line_of(to) as u64) << BLANK_SHIFT)
+            + ((line_of(from) as u64) << BLANK_SHIFT);
+        table.cost(after) as i32 - table.cost(before) as i32
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Goal, heuristic::Manhattan};
+
+    #[test]
+    fn the_4x4_tables_match_the_known_counts() {
+        let tables = WalkingDistance::new(4, 4, 15);
+
+        // The figures published with the heuristic
+        assert_eq!(tables.vertical.costs.len(), 24_964);
+        assert_eq!(tables.vertical.costs.values().max(), Some(&35));
+        assert_eq!(tables.vertical.costs, tables.horizontal.costs);
+        assert_eq!(tables.vertical.lines, 4);
+    }
+
+    #[test]
+    fn boards_share_tables_by_size_and_blank_goal() {
+        let mut puzzle = Puzzle::new(4, 4);
+        let first = WalkingDistance::for_puzzle(&puzzle).unwrap();
+        assert!(Arc::ptr_eq(
+            &first,
+            &WalkingDistance::for_puzzle(&puzzle).unwrap()
+        ));
+        assert_eq!(first.estimate(&puzzle), 0);
+
+        puzzle.shuffle_with_seed(5);
+        assert!(first.estimate(&puzzle) >= Manhattan.estimate(&puzzle));
+
+        for goal in Goal::ALL {
+            puzzle.set_goal(goal);
+            let tables = WalkingDistance::for_puzzle(&puzzle).unwrap();
+            assert!(tables.covers(&puzzle));
+            assert_eq!(Arc::ptr_eq(&first, &tables), blank_goal(&puzzle) == 15);
+        }
+
+        puzzle.set_wrap(true);
+        assert!(WalkingDistance::for_puzzle(&puzzle).is_none());
+        assert!(WalkingDistance::for_puzzle(&Puzzle::new(5, 5)).is_none());
+        assert!(WalkingDistance::for_puzzle(&Puzzle::new(3, 4)).is_some());
+    }
+}
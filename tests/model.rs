@@ -0,0 +1,112 @@
+//! Invariants of the puzzle model that must hold for any board and any
+//! sequence of moves, checked on random inputs across 3x3, 4x4 and 5x5.
+
+use iced_15_puzzle::{Difficulty, Direction, Puzzle};
+use proptest::prelude::*;
+use rand::{SeedableRng, rngs::StdRng};
+
+fn size() -> impl Strategy<Value = usize> {
+    3..=5usize
+}
+
+fn moves() -> impl Strategy<Value = Vec<Direction>> {
+    prop::collection::vec(prop::sample::select(Direction::ALL.to_vec()), 0..200)
+}
+
+/// A uniformly scrambled `size` by `size` board drawn from `seed`.
+fn scrambled(size: usize, seed: u64) -> Puzzle {
+    let mut puzzle = Puzzle::new(size, size);
+    puzzle.scramble_uniform(&mut StdRng::seed_from_u64(seed));
+    puzzle
+}
+
+/// The tile values on the board in ascending order, 0 standing for the blank.
+fn sorted_values(puzzle: &Puzzle) -> Vec<u16> {
+    let mut values: Vec<u16> = puzzle
+        .tiles()
+        .iter()
+        .map(|tile| tile.value().unwrap_or(0))
+        .collect();
+    values.sort_unstable();
+    values
+}
+
+proptest! {
+    #[test]
+    fn moves_keep_every_tile_and_a_single_blank(
+        size in size(),
+        seed: u64,
+        moves in moves(),
+    ) {
+        let mut puzzle = scrambled(size, seed);
+        let expected: Vec<u16> = (0..(size * size) as u16).collect();
+
+        for direction in moves {
+            puzzle.apply_move(direction);
+
+            prop_assert_eq!(sorted_values(&puzzle), expected.clone());
+            prop_assert_eq!(puzzle.tiles().iter().filter(|tile| tile.is_empty()).count(), 1);
+            let (row, col) = puzzle.empty_position();
+            prop_assert!(puzzle.tile(row, col).is_empty());
+            prop_assert!(puzzle.is_solvable());
+        }
+    }
+
+    #[test]
+    fn scrambles_are_always_solvable(size in size(), seed: u64) {
+        let puzzle = scrambled(size, seed);
+        prop_assert!(puzzle.is_solvable());
+        prop_assert!(!puzzle.is_solved());
+
+        for difficulty in Difficulty::ALL {
+            let mut puzzle = Puzzle::new(size, size);
+            puzzle.set_difficulty(difficulty);
+            puzzle.shuffle_with_seed(seed);
+
+            prop_assert!(puzzle.is_solvable(), "{:?} scramble is unsolvable", difficulty);
+            prop_assert!(!puzzle.is_solved());
+        }
+    }
+
+    #[test]
+    fn reversing_a_sequence_returns_to_the_start(
+        size in size(),
+        seed: u64,
+        moves in moves(),
+    ) {
+        let start = scrambled(size, seed);
+        let mut puzzle = start.clone();
+
+        let made: Vec<Direction> = moves
+            .into_iter()
+            .filter(|&direction| puzzle.apply_move(direction))
+            .collect();
+        for direction in made.into_iter().rev() {
+            prop_assert!(puzzle.apply_move(direction.opposite()));
+        }
+
+        prop_assert_eq!(puzzle.tiles(), start.tiles());
+    }
+
+    #[test]
+    fn only_vertical_moves_change_the_inversion_count(
+        size in size(),
+        seed: u64,
+        direction in prop::sample::select(Direction::ALL.to_vec()),
+    ) {
+        let mut puzzle = scrambled(size, seed);
+        let before = puzzle.inversions();
+        prop_assume!(puzzle.apply_move(direction));
+        let after = puzzle.inversions();
+
+        match direction {
+            Direction::Left | Direction::Right => prop_assert_eq!(after, before),
+            // The tile passes over the `size - 1` tiles between its old and
+            // new cell, flipping the parity exactly when that is odd
+            Direction::Up | Direction::Down => {
+                prop_assert!(after.abs_diff(before) < size);
+                prop_assert_eq!((after + before) % 2, (size - 1) % 2);
+            }
+        }
+    }
+}
@@ -0,0 +1,163 @@
+//! Documents saved by every version of the game so far, under
+//! `tests/fixtures/v<version>`, must load into the current structures.
+
+use iced_15_puzzle::{
+    Day, Ghosts, Leaderboard, Puzzle, Records, SaveSlot, SavedGame, Settings, ThemeMode, Unlocked,
+    storage::{self, Loaded, Versioned},
+};
+
+use std::fs;
+use std::time::Duration;
+
+/// Versions with fixtures, oldest first; the last is the current one.
+const VERSIONS: [u32; 2] = [0, 1];
+
+fn load<T: Versioned>(version: u32, name: &str) -> Loaded<T> {
+    let path = format!(
+        "{}/tests/fixtures/v{version}/{name}",
+        env!("CARGO_MANIFEST_DIR")
+    );
+    let contents = fs::read_to_string(&path).unwrap_or_else(|error| panic!("{path}: {error}"));
+
+    let loaded = storage::parse::<T>(&contents).unwrap_or_else(|error| panic!("{path}: {error}"));
+    assert_eq!(loaded.version, version, "{path}");
+    assert!(!loaded.is_read_only(), "{path}");
+    loaded
+}
+
+#[test]
+fn the_latest_fixtures_are_of_the_current_versions() {
+    let current = *VERSIONS.last().unwrap();
+
+    assert_eq!(Settings::VERSION, current);
+    assert_eq!(Records::VERSION, current);
+    assert_eq!(Leaderboard::VERSION, current);
+    assert_eq!(Ghosts::VERSION, current);
+    assert_eq!(SavedGame::VERSION, current);
+    assert_eq!(SaveSlot::VERSION, current);
+    assert_eq!(Unlocked::VERSION, current);
+}
+
+#[test]
+fn settings_of_every_version_load() {
+    for version in VERSIONS {
+        let settings = load::<Settings>(version, "settings.json").document;
+
+        assert!(!settings.auto_save && settings.show_distance && settings.muted);
+        assert_eq!(settings.theme, ThemeMode::Dark);
+        assert_eq!(settings.time_limit_secs, 90);
+        assert_eq!(settings.custom_goal, Some(vec![1, 2, 3, 4, 5, 6, 7, 8, 0]));
+        assert_eq!(settings.player_name, "Smith, Jo");
+        // Added since, so at its default
+        assert_eq!(
+            settings.key_repeat_delay_ms,
+            Settings::default().key_repeat_delay_ms
+        );
+    }
+}
+
+#[test]
+fn records_of_every_version_load() {
+    // Version 0 kept only the aggregate counters
+    let records = load::<Records>(0, "records.json").document;
+    let stats = records.stats("4x4");
+    assert_eq!(
+        (stats.shuffled, stats.solved, stats.total_moves),
+        (4, 3, 240)
+    );
+    assert_eq!(stats.average_time(), Some(Duration::from_secs(60)));
+    assert_eq!(records.best("3x3").unwrap().moves, 22);
+    assert!(records.solves().is_empty());
+
+    // The same, with one more solve kept on its own
+    let records = load::<Records>(1, "records.json").document;
+    let stats = records.stats("4x4");
+    assert_eq!(
+        (stats.shuffled, stats.solved, stats.total_moves),
+        (5, 4, 302)
+    );
+    assert_eq!(stats.scored_solves, 1);
+    assert_eq!(records.solves()[0].undos, 3);
+    assert_eq!(records.stats("3x3").solved, 1);
+}
+
+#[test]
+fn leaderboards_of_every_version_load() {
+    for version in VERSIONS {
+        let leaderboard = load::<Leaderboard>(version, "leaderboard.json").document;
+        let names: Vec<&str> = leaderboard
+            .entries("4x4")
+            .iter()
+            .map(|entry| entry.name.as_str())
+            .collect();
+
+        assert_eq!(names, ["Smith, Jo", "Anonymous"]);
+    }
+}
+
+#[test]
+fn ghosts_of_every_version_load() {
+    // Version 0 was a bare list of replays
+    let start = Puzzle::from_grid(2, 3, &[2, 5, 1, 3, 4, 0]).unwrap();
+
+    for version in VERSIONS {
+        let ghosts = load::<Ghosts>(version, "ghosts.json").document;
+        let best = ghosts.best(&start).expect("the fixture's replay");
+
+        assert_eq!(best.duration(), Duration::from_millis(16 * 700));
+    }
+}
+
+#[test]
+fn achievements_of_every_version_load() {
+    for version in VERSIONS {
+        let unlocked = load::<Unlocked>(version, "achievements.json").document;
+
+        assert_eq!(
+            unlocked.day("no-undo"),
+            Some(Day::from_days_since_epoch(19_676))
+        );
+        // The version is not mistaken for an achievement
+        assert_eq!(unlocked.len(), 2);
+    }
+}
+
+#[test]
+fn saved_games_of_every_version_load() {
+    for version in VERSIONS {
+        let game = load::<SavedGame>(version, "game.json").document;
+
+        assert_eq!(game.elapsed, Duration::from_millis(15_250));
+        assert_eq!(
+            game.puzzle
+                .tiles()
+                .iter()
+                .map(|tile| tile.value().unwrap_or(0))
+                .collect::<Vec<_>>(),
+            [7, 5, 2, 3, 4, 0, 8, 6, 1]
+        );
+        assert_eq!((game.puzzle.moves(), game.puzzle.seed()), (1, Some(9)));
+        assert!(game.puzzle.can_undo());
+    }
+}
+
+#[test]
+fn save_slots_load() {
+    // Slots came with version 1, along with the mode state of a saved game
+    let slot = load::<SaveSlot>(1, "slot-1.json").document;
+
+    assert_eq!(slot.name, "Smith's 3x3");
+    assert_eq!(slot.game.daily, Some(Day::from_days_since_epoch(20_000)));
+    assert_eq!((slot.game.hints, slot.game.undos), (1, 2));
+    assert_eq!(slot.game.puzzle.moves(), 1);
+}
+
+#[test]
+fn documents_upgraded_from_any_version_save_as_the_current_one() {
+    for version in VERSIONS {
+        let ghosts = load::<Ghosts>(version, "ghosts.json").document;
+        let saved = storage::parse::<Ghosts>(&storage::to_json(&ghosts).unwrap()).unwrap();
+
+        assert_eq!(saved.version, Ghosts::VERSION);
+    }
+}